@@ -0,0 +1,108 @@
+//! Scanner throughput on a synthetic tree
+//!
+//! Builds projects with nested `node_modules` and `target` directories in a
+//! temp dir and measures `ParallelScanner::scan` in directories and bytes
+//! per second. The tree size comes from the environment:
+//!
+//! - `NULL_E_BENCH_PROJECTS`: number of projects (default 40)
+//! - `NULL_E_BENCH_WIDTH`: subdirectories per artifact directory (default 4)
+//! - `NULL_E_BENCH_DEPTH`: nesting depth of artifact directories (default 3)
+//!
+//! ```text
+//! cargo bench --bench scanner
+//! NULL_E_BENCH_PROJECTS=200 cargo bench --bench scanner -- --save-baseline main
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use null_e::core::{ScanConfig, Scanner};
+use null_e::plugins::PluginRegistry;
+use null_e::scanner::ParallelScanner;
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+/// Bytes in each generated file
+const FILE_SIZE: usize = 4096;
+
+fn env_or(name: &str, default: usize) -> usize {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// A synthetic tree and what it contains
+struct Fixture {
+    dir: TempDir,
+    dirs: u64,
+    bytes: u64,
+}
+
+impl Fixture {
+    fn build(projects: usize, width: usize, depth: usize) -> Self {
+        let mut fixture = Self {
+            dir: TempDir::new().expect("temp dir"),
+            dirs: 1,
+            bytes: 0,
+        };
+        let root = fixture.dir.path().to_path_buf();
+
+        for i in 0..projects {
+            let project = root.join(format!("project-{}", i));
+            if i % 2 == 0 {
+                fixture.file(&project.join("package.json"), br#"{"name": "bench"}"#);
+                fixture.tree(&project.join("node_modules"), width, depth);
+            } else {
+                fixture.file(&project.join("Cargo.toml"), b"[package]\nname = \"bench\"\nversion = \"0.1.0\"\n");
+                fixture.tree(&project.join("target/debug"), width, depth);
+            }
+            fixture.dirs += 1;
+        }
+
+        fixture
+    }
+
+    fn file(&mut self, path: &Path, contents: &[u8]) {
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        std::fs::write(path, contents).expect("write file");
+        self.bytes += contents.len() as u64;
+    }
+
+    fn tree(&mut self, dir: &Path, width: usize, depth: usize) {
+        self.dirs += 1;
+        self.file(&dir.join("index.js"), &[b'x'; FILE_SIZE]);
+        if depth == 0 {
+            return;
+        }
+        for i in 0..width {
+            self.tree(&dir.join(format!("pkg-{}", i)), width, depth - 1);
+        }
+    }
+}
+
+fn scan(root: &Path) -> u64 {
+    let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins()));
+    let result = scanner.scan(&ScanConfig::new(root)).expect("scan");
+    result.total_size
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let fixture = Fixture::build(
+        env_or("NULL_E_BENCH_PROJECTS", 40),
+        env_or("NULL_E_BENCH_WIDTH", 4),
+        env_or("NULL_E_BENCH_DEPTH", 3),
+    );
+    let root = fixture.dir.path();
+    eprintln!("fixture: {} directories, {} bytes", fixture.dirs, fixture.bytes);
+
+    let mut group = c.benchmark_group("scan");
+    group.sample_size(20);
+
+    group.throughput(Throughput::Elements(fixture.dirs));
+    group.bench_function("dirs", |b| b.iter(|| scan(root)));
+
+    group.throughput(Throughput::Bytes(fixture.bytes));
+    group.bench_function("bytes", |b| b.iter(|| scan(root)));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
@@ -0,0 +1,148 @@
+//! Age distribution of cleanable items
+//!
+//! Buckets artifacts, caches and cleaner items by how long ago they were last
+//! modified, to help tune cleanup policy (e.g. "what would a 6-month cutoff free?").
+
+/// Age bucket based on days since last modification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AgeBucket {
+    /// Modified today
+    Today,
+    /// Within the last week
+    Week,
+    /// Within the last month
+    Month,
+    /// Within the last 6 months
+    SixMonths,
+    /// Within the last year
+    Year,
+    /// A year or more ago
+    OverYear,
+    /// No modification time available
+    Unknown,
+}
+
+impl AgeBucket {
+    /// All buckets, youngest first
+    pub fn all() -> &'static [AgeBucket] {
+        &[
+            Self::Today,
+            Self::Week,
+            Self::Month,
+            Self::SixMonths,
+            Self::Year,
+            Self::OverYear,
+            Self::Unknown,
+        ]
+    }
+
+    /// Bucket for an age in days, as returned by the `age_days()` helpers
+    pub fn from_age_days(days: Option<u64>) -> Self {
+        match days {
+            Some(0) => Self::Today,
+            Some(d) if d < 7 => Self::Week,
+            Some(d) if d < 30 => Self::Month,
+            Some(d) if d < 182 => Self::SixMonths,
+            Some(d) if d < 365 => Self::Year,
+            Some(_) => Self::OverYear,
+            None => Self::Unknown,
+        }
+    }
+
+    /// Human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Today => "today",
+            Self::Week => "< 1 week",
+            Self::Month => "< 1 month",
+            Self::SixMonths => "< 6 months",
+            Self::Year => "< 1 year",
+            Self::OverYear => "1 year+",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::all().iter().position(|b| b == self).unwrap_or(0)
+    }
+}
+
+/// Count and total size for one age bucket
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgeBucketStats {
+    /// Number of items in the bucket
+    pub count: usize,
+    /// Total size in bytes
+    pub size: u64,
+}
+
+/// Histogram of items by age bucket
+#[derive(Debug, Clone, Default)]
+pub struct AgeHistogram {
+    buckets: [AgeBucketStats; 7],
+}
+
+impl AgeHistogram {
+    /// Create an empty histogram
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item with the given age in days and size
+    pub fn add(&mut self, age_days: Option<u64>, size: u64) {
+        let stats = &mut self.buckets[AgeBucket::from_age_days(age_days).index()];
+        stats.count += 1;
+        stats.size += size;
+    }
+
+    /// Stats for a single bucket
+    pub fn get(&self, bucket: AgeBucket) -> AgeBucketStats {
+        self.buckets[bucket.index()]
+    }
+
+    /// Iterate over all buckets in order, youngest first
+    pub fn iter(&self) -> impl Iterator<Item = (AgeBucket, AgeBucketStats)> + '_ {
+        AgeBucket::all().iter().map(|b| (*b, self.get(*b)))
+    }
+
+    /// Size of the largest bucket (for scaling bars)
+    pub fn max_size(&self) -> u64 {
+        self.buckets.iter().map(|b| b.size).max().unwrap_or(0)
+    }
+
+    /// Total number of items
+    pub fn total_count(&self) -> usize {
+        self.buckets.iter().map(|b| b.count).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_boundaries() {
+        assert_eq!(AgeBucket::from_age_days(Some(0)), AgeBucket::Today);
+        assert_eq!(AgeBucket::from_age_days(Some(6)), AgeBucket::Week);
+        assert_eq!(AgeBucket::from_age_days(Some(7)), AgeBucket::Month);
+        assert_eq!(AgeBucket::from_age_days(Some(30)), AgeBucket::SixMonths);
+        assert_eq!(AgeBucket::from_age_days(Some(200)), AgeBucket::Year);
+        assert_eq!(AgeBucket::from_age_days(Some(365)), AgeBucket::OverYear);
+        assert_eq!(AgeBucket::from_age_days(None), AgeBucket::Unknown);
+    }
+
+    #[test]
+    fn test_histogram_totals() {
+        let mut histogram = AgeHistogram::new();
+        histogram.add(Some(1), 100);
+        histogram.add(Some(3), 50);
+        histogram.add(Some(400), 1000);
+        histogram.add(None, 10);
+
+        assert_eq!(histogram.get(AgeBucket::Week), AgeBucketStats { count: 2, size: 150 });
+        assert_eq!(histogram.get(AgeBucket::OverYear).size, 1000);
+        assert_eq!(histogram.get(AgeBucket::Unknown).count, 1);
+        assert_eq!(histogram.total_count(), 4);
+        assert_eq!(histogram.max_size(), 1000);
+    }
+}
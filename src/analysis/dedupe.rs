@@ -0,0 +1,273 @@
+//! Reclaiming space from duplicate dependencies with hardlinks
+//!
+//! The duplicate finder reports the same dependency installed in several
+//! projects. Dependency trees are read-mostly, so identical files in them can
+//! be replaced by hardlinks to a single copy: every path keeps working and
+//! nothing is deleted, but the data is stored once. Files are only linked
+//! after a full SHA-256 of both sides matches, and only within one filesystem.
+
+use crate::error::{DevSweepError, Result, ResultExt};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{Metadata, Permissions};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Files smaller than this aren't worth linking (they barely fill a block)
+pub const MIN_LINK_SIZE: u64 = 4096;
+
+/// Regular files with the same content, permissions and filesystem
+#[derive(Debug, Clone)]
+pub struct IdenticalFiles {
+    /// Copy the others get linked to
+    pub keep: PathBuf,
+    /// Copies that aren't yet hardlinks of `keep`
+    pub copies: Vec<PathBuf>,
+    /// Size of each file
+    pub size: u64,
+    /// Bytes freed once every copy is linked (copies already linked to each
+    /// other only count once)
+    pub reclaimable: u64,
+}
+
+/// Find identical files under sets of paths that should hold the same content
+///
+/// Each set lists directories (or single files) believed to be copies of each
+/// other, e.g. one npm package version installed in several projects. Files
+/// are compared at the same relative path below each entry of a set, and
+/// grouped only when their full hashes match.
+pub fn find_identical_files(sets: &[Vec<PathBuf>], min_size: u64) -> Vec<IdenticalFiles> {
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut found = Vec::new();
+
+    for set in sets {
+        let Some(reference) = set.first() else {
+            continue;
+        };
+
+        for rel in relative_files(reference) {
+            let candidates: Vec<(PathBuf, Metadata)> = set
+                .iter()
+                .map(|base| if rel.as_os_str().is_empty() { base.clone() } else { base.join(&rel) })
+                .filter(|path| !seen.contains(path))
+                .filter_map(|path| {
+                    let meta = std::fs::symlink_metadata(&path).ok()?;
+                    (meta.is_file() && meta.len() >= min_size.max(1)).then_some((path, meta))
+                })
+                .collect();
+            if candidates.len() < 2 {
+                continue;
+            }
+
+            for group in group_identical(candidates) {
+                seen.insert(group.keep.clone());
+                seen.extend(group.copies.iter().cloned());
+                found.push(group);
+            }
+        }
+    }
+
+    found.sort_by_key(|group| std::cmp::Reverse(group.reclaimable));
+    found
+}
+
+/// Replace every copy in `files` with a hardlink to `files.keep`
+///
+/// Each copy's hash is checked against the kept file again right before it
+/// is replaced, and the link is swapped in with a rename so the path never
+/// goes missing. Returns the bytes freed and the copies that couldn't be
+/// linked.
+pub fn hardlink_identical(files: &IdenticalFiles) -> (u64, Vec<(PathBuf, DevSweepError)>) {
+    let mut freed = 0;
+    let mut failed = Vec::new();
+
+    let keep_hash = match hash_file(&files.keep) {
+        Ok(hash) => hash,
+        Err(e) => return (0, vec![(files.keep.clone(), e)]),
+    };
+
+    for copy in &files.copies {
+        match link_copy(&files.keep, &keep_hash, copy) {
+            Ok(bytes) => freed += bytes,
+            Err(e) => failed.push((copy.clone(), e)),
+        }
+    }
+
+    (freed, failed)
+}
+
+/// Link one verified copy to `keep`, returning the bytes freed
+fn link_copy(keep: &Path, keep_hash: &[u8; 32], copy: &Path) -> Result<u64> {
+    let meta = std::fs::symlink_metadata(copy).with_path(copy)?;
+    if hash_file(copy)? != *keep_hash {
+        return Err(DevSweepError::Other(format!(
+            "{} changed since it was compared, not linking it",
+            copy.display()
+        )));
+    }
+
+    let name = copy.file_name().unwrap_or_default().to_string_lossy();
+    let temp = copy.with_file_name(format!(".{}.null-e-link", name));
+    // Fails with a cross-device error when the two aren't on one filesystem
+    std::fs::hard_link(keep, &temp).with_path(copy)?;
+    if let Err(e) = std::fs::rename(&temp, copy) {
+        let _ = std::fs::remove_file(&temp);
+        return Err(e).with_path(copy);
+    }
+
+    // Only the last link to the old data actually frees it
+    Ok(if link_count(&meta) <= 1 { meta.len() } else { 0 })
+}
+
+/// Files that may be linked together: device, size and content hash
+type BucketKey = (Option<u64>, u64, [u8; 32]);
+
+/// Split same-path candidates into groups of identical content
+fn group_identical(candidates: Vec<(PathBuf, Metadata)>) -> Vec<IdenticalFiles> {
+    let mut buckets: HashMap<BucketKey, Vec<(PathBuf, Metadata)>> = HashMap::new();
+    for (path, meta) in candidates {
+        let Ok(hash) = hash_file(&path) else {
+            continue;
+        };
+        buckets
+            .entry((file_id(&meta).map(|(dev, _)| dev), meta.len(), hash))
+            .or_default()
+            .push((path, meta));
+    }
+
+    let mut groups = Vec::new();
+    for mut bucket in buckets.into_values() {
+        // Linking would give a copy the kept file's mode, so keep those apart
+        while let Some((keep, keep_meta)) = bucket.first().cloned() {
+            let permissions: Permissions = keep_meta.permissions();
+            let (same, rest): (Vec<_>, Vec<_>) =
+                bucket.into_iter().partition(|(_, meta)| meta.permissions() == permissions);
+            bucket = rest;
+
+            let keep_id = file_id(&keep_meta);
+            let mut inodes = HashSet::new();
+            let copies: Vec<PathBuf> = same
+                .into_iter()
+                .skip(1)
+                .filter(|(_, meta)| keep_id.is_none() || file_id(meta) != keep_id)
+                .enumerate()
+                .map(|(i, (path, meta))| {
+                    inodes.insert(file_id(&meta).unwrap_or((0, i as u64)));
+                    path
+                })
+                .collect();
+
+            if !copies.is_empty() {
+                groups.push(IdenticalFiles {
+                    keep,
+                    copies,
+                    size: keep_meta.len(),
+                    reclaimable: keep_meta.len() * inodes.len() as u64,
+                });
+            }
+        }
+    }
+    groups
+}
+
+/// Regular files below `base`, relative to it (an empty path when `base` is a file)
+fn relative_files(base: &Path) -> Vec<PathBuf> {
+    WalkDir::new(base)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.path().strip_prefix(base).ok().map(Path::to_path_buf))
+        .collect()
+}
+
+/// Full SHA-256 of a file's content
+fn hash_file(path: &Path) -> Result<[u8; 32]> {
+    let mut file = std::fs::File::open(path).with_path(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).with_path(path)?;
+    Ok(hasher.finalize().into())
+}
+
+/// Device and inode, where the platform exposes them
+#[cfg(unix)]
+fn file_id(meta: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((meta.dev(), meta.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_meta: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(unix)]
+fn link_count(meta: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.nlink()
+}
+
+#[cfg(not(unix))]
+fn link_count(_meta: &Metadata) -> u64 {
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hardlink_identical_package_files() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp = TempDir::new().unwrap();
+        let content = vec![7u8; 10_000];
+        let packages: Vec<PathBuf> = ["a", "b", "c"]
+            .iter()
+            .map(|p| temp.path().join(p).join("node_modules/lodash"))
+            .collect();
+        for package in &packages {
+            std::fs::create_dir_all(package).unwrap();
+            std::fs::write(package.join("lodash.js"), &content).unwrap();
+            std::fs::write(package.join("package.json"), "{}").unwrap();
+        }
+        // Same path, different content: never linked
+        std::fs::write(packages[2].join("lodash.js"), vec![8u8; 10_000]).unwrap();
+
+        let found = find_identical_files(std::slice::from_ref(&packages), MIN_LINK_SIZE);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].copies, vec![packages[1].join("lodash.js")]);
+        assert_eq!(found[0].reclaimable, 10_000);
+
+        let (freed, failed) = hardlink_identical(&found[0]);
+        assert!(failed.is_empty());
+        assert_eq!(freed, 10_000);
+
+        let ino = |p: PathBuf| std::fs::metadata(p).unwrap().ino();
+        assert_eq!(ino(packages[0].join("lodash.js")), ino(packages[1].join("lodash.js")));
+        assert_eq!(std::fs::read(packages[1].join("lodash.js")).unwrap(), content);
+
+        // Already linked, nothing left to do
+        assert!(find_identical_files(&[packages], MIN_LINK_SIZE).is_empty());
+    }
+
+    #[test]
+    fn test_changed_copy_is_not_linked() {
+        let temp = TempDir::new().unwrap();
+        let keep = temp.path().join("keep.bin");
+        let copy = temp.path().join("copy.bin");
+        std::fs::write(&keep, vec![1u8; 5_000]).unwrap();
+        std::fs::write(&copy, vec![1u8; 5_000]).unwrap();
+
+        let found = find_identical_files(&[vec![keep.clone(), copy.clone()]], MIN_LINK_SIZE);
+        assert_eq!(found.len(), 1);
+
+        std::fs::write(&copy, vec![2u8; 5_000]).unwrap();
+        let (freed, failed) = hardlink_identical(&found[0]);
+        assert_eq!(freed, 0);
+        assert_eq!(failed.len(), 1);
+        assert_eq!(std::fs::read(&copy).unwrap(), [2u8; 5_000]);
+    }
+}
@@ -2,13 +2,14 @@
 //!
 //! Finds duplicate dependencies across projects:
 //! - Same npm package in multiple node_modules
-//! - Multiple Python venvs with similar packages
+//! - Python venvs installed from the same lockfile
 //! - Duplicate cargo dependencies in different targets
 
 use super::{Recommendation, RecommendationKind, RiskLevel};
 use crate::cleaners::calculate_dir_size;
 use crate::error::Result;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -74,57 +75,43 @@ impl DuplicateFinder {
         Ok(recommendations)
     }
 
-    /// Find duplicate packages in node_modules
-    fn find_node_duplicates(&self, root: &Path, max_depth: usize) -> Result<Vec<Recommendation>> {
-        let mut package_locations: HashMap<String, Vec<(PathBuf, String, u64)>> = HashMap::new();
-
-        // Find all node_modules directories
-        for entry in WalkDir::new(root)
-            .max_depth(max_depth + 5) // Go deeper to find nested node_modules
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| {
-                let name = e.file_name().to_string_lossy();
-                name != ".git" && name != "target" && name != "venv"
-            })
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+    /// Sets of paths that should hold identical content, for hardlinking
+    ///
+    /// One set per npm package version installed more than once, and per
+    /// compiled Cargo dependency file present in more than one target dir.
+    /// See [`find_identical_files`](super::dedupe::find_identical_files).
+    pub fn dedupe_candidates(&self, root: &Path, max_depth: usize) -> Vec<Vec<PathBuf>> {
+        let mut sets: HashMap<String, Vec<PathBuf>> = HashMap::new();
+
+        for (name, locations) in node_packages(root, max_depth) {
+            for (path, version, _) in locations {
+                sets.entry(format!("npm:{}@{}", name, version)).or_default().push(path);
+            }
+        }
 
-            // Look for package.json files in node_modules
-            if path.is_file() && path.file_name().map(|n| n == "package.json").unwrap_or(false) {
-                // Check if inside node_modules
-                let path_str = path.to_string_lossy();
-                if !path_str.contains("node_modules") {
+        for (target, _) in rust_targets(root, max_depth) {
+            for (rel, profile) in cargo_profiles(&target) {
+                let Ok(entries) = std::fs::read_dir(profile.join("deps")) else {
                     continue;
-                }
-
-                // Read package.json
-                if let Ok(content) = std::fs::read_to_string(path) {
-                    if let Ok(pkg) = serde_json::from_str::<PackageJson>(&content) {
-                        if let (Some(name), Some(version)) = (pkg.name, pkg.version) {
-                            // Skip scoped packages that are likely unique
-                            if name.starts_with('@') && name.contains('/') {
-                                continue;
-                            }
-
-                            // Get package directory
-                            if let Some(pkg_dir) = path.parent() {
-                                let size = calculate_dir_size(pkg_dir)
-                                    .map(|(s, _)| s)
-                                    .unwrap_or(0);
-
-                                package_locations
-                                    .entry(name)
-                                    .or_default()
-                                    .push((pkg_dir.to_path_buf(), version, size));
-                            }
-                        }
+                };
+                for entry in entries.filter_map(|e| e.ok()) {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if cargo_unit_name(&name).is_some() {
+                        sets.entry(format!("cargo:{}/deps/{}", rel, name))
+                            .or_default()
+                            .push(entry.path());
                     }
                 }
             }
         }
 
+        sets.into_values().filter(|paths| paths.len() > 1).collect()
+    }
+
+    /// Find duplicate packages in node_modules
+    fn find_node_duplicates(&self, root: &Path, max_depth: usize) -> Result<Vec<Recommendation>> {
+        let package_locations = node_packages(root, max_depth);
+
         // Find packages that appear multiple times
         let mut recommendations = Vec::new();
 
@@ -204,129 +191,345 @@ impl DuplicateFinder {
         Ok(recommendations)
     }
 
-    /// Find duplicate Python venvs
+    /// Find Python venvs installed from the same lockfile
+    ///
+    /// Two venvs built from byte-identical `uv.lock`, `poetry.lock` or
+    /// `requirements.txt` files on the same Python version hold the same
+    /// packages, so all but one of them could go.
     fn find_python_duplicates(&self, root: &Path, max_depth: usize) -> Result<Vec<Recommendation>> {
-        let mut venvs: Vec<(PathBuf, u64)> = Vec::new();
-
-        // Find all venv/virtualenv directories
-        for entry in WalkDir::new(root)
-            .max_depth(max_depth)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| {
-                let name = e.file_name().to_string_lossy();
-                name != ".git" && name != "node_modules" && name != "target"
-            })
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
-
-            // Check for venv markers
-            if path.is_dir() && (name == "venv" || name == ".venv" || name == "env") {
-                let pyvenv_cfg = path.join("pyvenv.cfg");
-                if pyvenv_cfg.exists() {
-                    let size = calculate_dir_size(path).map(|(s, _)| s).unwrap_or(0);
-                    venvs.push((path.to_path_buf(), size));
-                }
+        let mut groups: HashMap<LockKey, Vec<(PathBuf, u64)>> = HashMap::new();
+        for (venv, size) in python_venvs(root, max_depth) {
+            if let Some(key) = venv_lock_key(&venv) {
+                groups.entry(key).or_default().push((venv, size));
             }
         }
 
         let mut recommendations = Vec::new();
 
-        if venvs.len() >= 3 {
+        for ((lockfile, _, version), mut venvs) in groups {
+            if venvs.len() < self.min_duplicate_count {
+                continue;
+            }
+
+            // Keep the largest, the rest are reclaimable
+            venvs.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
             let total_size: u64 = venvs.iter().map(|(_, s)| s).sum();
-            let avg_size = total_size / venvs.len() as u64;
-
-            // Many venvs often have duplicate packages
-            // Estimate ~40% could be shared
-            let potential_savings = (total_size as f64 * 0.4) as u64;
-
-            if total_size > 500_000_000 {
-                recommendations.push(Recommendation {
-                    kind: RecommendationKind::DuplicateDependency,
-                    title: format!(
-                        "🐍 {} Python venvs ({})",
-                        venvs.len(),
-                        format_size(total_size)
-                    ),
-                    description: format!(
-                        "Found {} virtual environments averaging {}. Consider using uv, poetry, or conda for better dependency management.",
-                        venvs.len(),
-                        format_size(avg_size)
-                    ),
-                    path: venvs.first().map(|(p, _)| p.clone()).unwrap_or_default(),
-                    potential_savings,
-                    fix_command: Some("Consider using uv or poetry with centralized cache".to_string()),
-                    risk: RiskLevel::Low,
-                });
+            let potential_savings = total_size - venvs[0].1;
+
+            if potential_savings < self.min_duplicate_size {
+                continue;
             }
+
+            let projects: Vec<String> = venvs
+                .iter()
+                .filter_map(|(p, _)| p.parent()?.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .collect();
+            let keep = &venvs[0].0;
+
+            recommendations.push(Recommendation {
+                kind: RecommendationKind::DuplicateDependency,
+                title: format!(
+                    "🐍 {} identical venvs ({})",
+                    venvs.len(),
+                    format_size(total_size)
+                ),
+                description: format!(
+                    "{} were installed from the same {} on Python {}. Sharing one environment would save {}; \
+                     uv also avoids the copies by hard-linking packages from its cache.",
+                    projects.join(", "),
+                    lockfile,
+                    version,
+                    format_size(potential_savings)
+                ),
+                path: keep.clone(),
+                potential_savings,
+                fix_command: Some(format!(
+                    "ln -s {} {}",
+                    keep.display(),
+                    keep.file_name().unwrap_or_default().to_string_lossy()
+                )),
+                risk: RiskLevel::Low,
+            });
         }
 
+        recommendations.sort_by_key(|r| std::cmp::Reverse(r.potential_savings));
+
         Ok(recommendations)
     }
 
-    /// Find duplicate Rust target directories
+    /// Find compiled Rust dependencies duplicated across target directories
+    ///
+    /// Cargo names each compiled unit `<crate>-<hash>`, where the hash covers
+    /// the package, version, features, profile and compiler. Registry
+    /// dependencies built the same way in two projects get the same name, so
+    /// the savings of a shared `CARGO_TARGET_DIR` are the units that appear
+    /// in more than one target dir, counted once.
     fn find_rust_duplicates(&self, root: &Path, max_depth: usize) -> Result<Vec<Recommendation>> {
-        let mut targets: Vec<(PathBuf, u64)> = Vec::new();
-
-        // Find all Rust target directories
-        for entry in WalkDir::new(root)
-            .max_depth(max_depth)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| {
-                let name = e.file_name().to_string_lossy();
-                name != ".git" && name != "node_modules" && name != "venv"
-            })
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-
-            // Check for target with Cargo.toml sibling
-            if path.is_dir() && path.file_name().map(|n| n == "target").unwrap_or(false) {
-                if let Some(parent) = path.parent() {
-                    if parent.join("Cargo.toml").exists() {
-                        let size = calculate_dir_size(path).map(|(s, _)| s).unwrap_or(0);
-                        if size > 50_000_000 {
-                            targets.push((path.to_path_buf(), size));
+        let targets = rust_targets(root, max_depth);
+
+        if targets.len() < self.min_duplicate_count {
+            return Ok(vec![]);
+        }
+
+        // Every copy of each compiled unit, across all target dirs
+        let mut units: HashMap<String, Vec<u64>> = HashMap::new();
+        for (target, _) in &targets {
+            for (unit, size) in cargo_units(target) {
+                units.entry(unit).or_default().push(size);
+            }
+        }
+
+        let mut shared_units = 0;
+        let mut potential_savings = 0;
+        for sizes in units.values().filter(|sizes| sizes.len() > 1) {
+            shared_units += 1;
+            potential_savings += sizes.iter().sum::<u64>() - sizes.iter().max().copied().unwrap_or(0);
+        }
+
+        if potential_savings < self.min_duplicate_size {
+            return Ok(vec![]);
+        }
+
+        let total_size: u64 = targets.iter().map(|(_, s)| s).sum();
+
+        Ok(vec![Recommendation {
+            kind: RecommendationKind::DuplicateDependency,
+            title: format!(
+                "🦀 {} Rust targets ({})",
+                targets.len(),
+                format_size(total_size)
+            ),
+            description: format!(
+                "{} compiled dependencies are built identically in more than one of {} target directories. \
+                 A shared CARGO_TARGET_DIR would keep one copy of each and save {}.",
+                shared_units,
+                targets.len(),
+                format_size(potential_savings)
+            ),
+            path: targets.first().map(|(p, _)| p.clone()).unwrap_or_default(),
+            potential_savings,
+            fix_command: Some("export CARGO_TARGET_DIR=~/.cargo/target".to_string()),
+            risk: RiskLevel::None,
+        }])
+    }
+}
+
+/// Installed npm packages by name: directory, version and size of each copy
+fn node_packages(root: &Path, max_depth: usize) -> HashMap<String, Vec<(PathBuf, String, u64)>> {
+    let mut package_locations: HashMap<String, Vec<(PathBuf, String, u64)>> = HashMap::new();
+
+    // Find all node_modules directories
+    for entry in WalkDir::new(root)
+        .max_depth(max_depth + 5) // Go deeper to find nested node_modules
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            name != ".git" && name != "target" && name != "venv"
+        })
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+
+        // Look for package.json files in node_modules
+        if path.is_file() && path.file_name().map(|n| n == "package.json").unwrap_or(false) {
+            // Check if inside node_modules
+            let path_str = path.to_string_lossy();
+            if !path_str.contains("node_modules") {
+                continue;
+            }
+
+            // Read package.json
+            if let Ok(content) = std::fs::read_to_string(path) {
+                if let Ok(pkg) = serde_json::from_str::<PackageJson>(&content) {
+                    if let (Some(name), Some(version)) = (pkg.name, pkg.version) {
+                        // Skip scoped packages that are likely unique
+                        if name.starts_with('@') && name.contains('/') {
+                            continue;
+                        }
+
+                        // Get package directory
+                        if let Some(pkg_dir) = path.parent() {
+                            let size = calculate_dir_size(pkg_dir)
+                                .map(|(s, _)| s)
+                                .unwrap_or(0);
+
+                            package_locations
+                                .entry(name)
+                                .or_default()
+                                .push((pkg_dir.to_path_buf(), version, size));
                         }
                     }
                 }
             }
         }
+    }
 
-        let mut recommendations = Vec::new();
+    package_locations
+}
+
+/// Python virtual environments (directories with a `pyvenv.cfg`) and their size
+fn python_venvs(root: &Path, max_depth: usize) -> Vec<(PathBuf, u64)> {
+    let mut venvs: Vec<(PathBuf, u64)> = Vec::new();
+
+    let mut walker = WalkDir::new(root)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            name != ".git" && name != "node_modules" && name != "target"
+        });
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+
+        if entry.file_type().is_dir() && path.join("pyvenv.cfg").is_file() {
+            walker.skip_current_dir();
+            let size = calculate_dir_size(path).map(|(s, _)| s).unwrap_or(0);
+            venvs.push((path.to_path_buf(), size));
+        }
+    }
 
-        if targets.len() >= 2 {
-            let total_size: u64 = targets.iter().map(|(_, s)| s).sum();
-
-            // Rust target directories often share compiled dependencies
-            // With a shared target directory, could save ~30-50%
-            let potential_savings = (total_size as f64 * 0.35) as u64;
-
-            if total_size > 1_000_000_000 {
-                recommendations.push(Recommendation {
-                    kind: RecommendationKind::DuplicateDependency,
-                    title: format!(
-                        "🦀 {} Rust targets ({})",
-                        targets.len(),
-                        format_size(total_size)
-                    ),
-                    description: format!(
-                        "Found {} Rust projects with separate target directories. Consider using CARGO_TARGET_DIR for shared compilation cache.",
-                        targets.len()
-                    ),
-                    path: targets.first().map(|(p, _)| p.clone()).unwrap_or_default(),
-                    potential_savings,
-                    fix_command: Some("export CARGO_TARGET_DIR=~/.cargo/target".to_string()),
-                    risk: RiskLevel::None,
-                });
+    venvs
+}
+
+/// Lockfiles that pin a project's environment, most precise first
+const PYTHON_LOCKFILES: &[&str] = &["uv.lock", "poetry.lock", "requirements.txt"];
+
+/// Lockfile name, its SHA-256 and the venv's Python version
+type LockKey = (&'static str, String, String);
+
+/// What a venv was installed from
+///
+/// `None` when the project next to the venv has no lockfile.
+fn venv_lock_key(venv: &Path) -> Option<LockKey> {
+    let project = venv.parent()?;
+    let (lockfile, contents) = PYTHON_LOCKFILES
+        .iter()
+        .find_map(|name| Some((*name, std::fs::read(project.join(name)).ok()?)))?;
+
+    let digest: String = Sha256::digest(&contents)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    let cfg = std::fs::read_to_string(venv.join("pyvenv.cfg")).unwrap_or_default();
+    let version = cfg
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| matches!(key.trim(), "version" | "version_info"))
+        .map(|(_, value)| value.trim().to_string())
+        .unwrap_or_else(|| "?".to_string());
+
+    Some((lockfile, digest, version))
+}
+
+/// Rust target directories (next to a `Cargo.toml`) over 50 MB, with their size
+fn rust_targets(root: &Path, max_depth: usize) -> Vec<(PathBuf, u64)> {
+    let mut targets: Vec<(PathBuf, u64)> = Vec::new();
+
+    // Find all Rust target directories
+    let mut walker = WalkDir::new(root)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            name != ".git" && name != "node_modules" && name != "venv"
+        });
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+
+        // Check for target with Cargo.toml sibling
+        if entry.file_type().is_dir()
+            && path.file_name().is_some_and(|n| n == "target")
+            && path.parent().is_some_and(|p| p.join("Cargo.toml").exists())
+        {
+            walker.skip_current_dir();
+            let size = calculate_dir_size(path).map(|(s, _)| s).unwrap_or(0);
+            if size > 50_000_000 {
+                targets.push((path.to_path_buf(), size));
             }
         }
+    }
 
-        Ok(recommendations)
+    targets
+}
+
+/// Compiled units in a Cargo target directory and their size on disk
+///
+/// Keys are `<profile dir>/<crate>-<hash>` (e.g. `debug/serde-1a2b3c`), so
+/// the same unit in another target dir gets the same key. Build script
+/// outputs are included under `<profile dir>/build/<crate>-<hash>`.
+fn cargo_units(target: &Path) -> HashMap<String, u64> {
+    let mut units: HashMap<String, u64> = HashMap::new();
+
+    for (rel, profile) in cargo_profiles(target) {
+        if let Ok(entries) = std::fs::read_dir(profile.join("deps")) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let Ok(meta) = entry.metadata() else {
+                    continue;
+                };
+                if !meta.is_file() {
+                    continue;
+                }
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(unit) = cargo_unit_name(&name) else {
+                    continue;
+                };
+                *units.entry(format!("{}/{}", rel, unit)).or_default() += meta.len();
+            }
+        }
+
+        if let Ok(entries) = std::fs::read_dir(profile.join("build")) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let size = calculate_dir_size(&entry.path()).map(|(s, _)| s).unwrap_or(0);
+                let name = entry.file_name().to_string_lossy().to_string();
+                units.insert(format!("{}/build/{}", rel, name), size);
+            }
+        }
     }
+
+    units
+}
+
+/// Profile dirs of a target dir, keyed by their `/`-separated relative path
+///
+/// They are `target/<profile>` or `target/<triple>/<profile>`.
+fn cargo_profiles(target: &Path) -> Vec<(String, PathBuf)> {
+    WalkDir::new(target)
+        .min_depth(1)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir() && e.path().join(".fingerprint").is_dir())
+        .filter_map(|profile| {
+            let rel = profile.path().strip_prefix(target).ok()?;
+            Some((rel.to_string_lossy().replace('\\', "/"), profile.into_path()))
+        })
+        .collect()
+}
+
+/// `<crate>-<hash>` for a file in a `deps` directory
+///
+/// `libserde-1a2b.rlib`, `libserde-1a2b.rmeta` and `serde-1a2b.d` all belong
+/// to the unit `serde-1a2b`.
+fn cargo_unit_name(file_name: &str) -> Option<&str> {
+    let (stem, ext) = file_name.split_once('.').unwrap_or((file_name, ""));
+    let stem = match ext {
+        "rlib" | "rmeta" | "so" | "dylib" | "a" => stem.strip_prefix("lib").unwrap_or(stem),
+        _ => stem,
+    };
+    stem.contains('-').then_some(stem)
 }
 
 /// Format bytes as human-readable size
@@ -344,6 +547,72 @@ mod tests {
         assert_eq!(finder.min_duplicate_count, 2);
     }
 
+    #[test]
+    fn test_cargo_unit_name() {
+        assert_eq!(cargo_unit_name("libserde-1a2b.rlib"), Some("serde-1a2b"));
+        assert_eq!(cargo_unit_name("libserde-1a2b.rmeta"), Some("serde-1a2b"));
+        assert_eq!(cargo_unit_name("serde-1a2b.d"), Some("serde-1a2b"));
+        assert_eq!(cargo_unit_name("liblibc-9f.rlib"), Some("libc-9f"));
+        assert_eq!(cargo_unit_name("app-77"), Some("app-77"));
+        assert_eq!(cargo_unit_name("README"), None);
+    }
+
+    #[test]
+    fn test_rust_duplicates_count_shared_units_once() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let write = |path: PathBuf, size: usize| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, vec![0u8; size]).unwrap();
+        };
+
+        for (project, own) in [("a", "a-11"), ("b", "b-22"), ("c", "c-33")] {
+            let root = temp.path().join(project);
+            write(root.join("Cargo.toml"), 10);
+            let debug = root.join("target/debug");
+            std::fs::create_dir_all(debug.join(".fingerprint")).unwrap();
+            // Same serde build everywhere, plus each project's own crate
+            write(debug.join("deps/libserde-1a2b.rlib"), 20_000_000);
+            write(debug.join(format!("deps/lib{}.rlib", own)), 40_000_000);
+        }
+        // Built with different features: a different unit, not a duplicate
+        write(temp.path().join("c/target/debug/deps/libserde-ffff.rlib"), 1_000);
+
+        let finder = DuplicateFinder::new();
+        let recs = finder.find_rust_duplicates(temp.path(), 5).unwrap();
+        assert_eq!(recs.len(), 1);
+        // Two of the three serde copies go away, nothing else is shared
+        assert_eq!(recs[0].potential_savings, 40_000_000);
+        assert!(recs[0].fix_command.as_deref().unwrap().contains("CARGO_TARGET_DIR"));
+    }
+
+    #[test]
+    fn test_python_duplicates_group_by_lockfile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = |name: &str, requirements: &str, version: &str, size: usize| {
+            let venv = temp.path().join(name).join(".venv");
+            std::fs::create_dir_all(venv.join("lib")).unwrap();
+            std::fs::write(temp.path().join(name).join("requirements.txt"), requirements).unwrap();
+            std::fs::write(venv.join("pyvenv.cfg"), format!("home = /usr/bin\nversion = {}\n", version)).unwrap();
+            std::fs::write(venv.join("lib/site.bin"), vec![0u8; size]).unwrap();
+        };
+
+        project("a", "requests==2.31\n", "3.12.1", 3_000_000);
+        project("b", "requests==2.31\n", "3.12.1", 2_000_000);
+        // Different pins or interpreter: not interchangeable
+        project("c", "requests==2.30\n", "3.12.1", 2_000_000);
+        project("d", "requests==2.31\n", "3.11.4", 2_000_000);
+
+        let finder = DuplicateFinder {
+            min_duplicate_size: 0,
+            ..Default::default()
+        };
+        let recs = finder.find_python_duplicates(temp.path(), 4).unwrap();
+        assert_eq!(recs.len(), 1);
+        assert!(recs[0].path.starts_with(temp.path().join("a")));
+        assert!(recs[0].potential_savings >= 2_000_000 && recs[0].potential_savings < 2_100_000);
+        assert!(recs[0].description.contains("requirements.txt"));
+    }
+
     #[test]
     fn test_duplicate_scan() {
         let finder = DuplicateFinder::new();
@@ -20,6 +20,8 @@ pub struct GitAnalyzer {
     pub min_git_size: u64,
     /// Minimum loose objects to suggest gc
     pub min_loose_objects: usize,
+    /// Minimum garbage (stale temp packs, broken files) to suggest gc
+    pub min_garbage_size: u64,
 }
 
 impl Default for GitAnalyzer {
@@ -27,10 +29,81 @@ impl Default for GitAnalyzer {
         Self {
             min_git_size: 100_000_000, // 100MB
             min_loose_objects: 1000,
+            min_garbage_size: 10_000_000, // 10MB
         }
     }
 }
 
+/// Object database statistics as reported by `git count-objects -v`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitObjectStats {
+    /// Number of loose objects
+    pub loose_count: usize,
+    /// Size of loose objects in bytes
+    pub loose_size: u64,
+    /// Number of packed objects
+    pub in_pack: usize,
+    /// Number of pack files
+    pub packs: usize,
+    /// Size of pack files in bytes
+    pub pack_size: u64,
+    /// Loose objects that are also present in a pack
+    pub prune_packable: usize,
+    /// Number of garbage files in the object database
+    pub garbage_count: usize,
+    /// Size of garbage files in bytes
+    pub garbage_size: u64,
+}
+
+impl GitObjectStats {
+    /// Parse the output of `git count-objects -v` (sizes are reported in KiB)
+    pub fn parse(output: &str) -> Option<Self> {
+        let mut stats = Self::default();
+        let mut seen_any = false;
+
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<u64>() else {
+                continue;
+            };
+            seen_any = true;
+
+            match key.trim() {
+                "count" => stats.loose_count = value as usize,
+                "size" => stats.loose_size = value * 1024,
+                "in-pack" => stats.in_pack = value as usize,
+                "packs" => stats.packs = value as usize,
+                "size-pack" => stats.pack_size = value * 1024,
+                "prune-packable" => stats.prune_packable = value as usize,
+                "garbage" => stats.garbage_count = value as usize,
+                "size-garbage" => stats.garbage_size = value * 1024,
+                _ => {}
+            }
+        }
+
+        seen_any.then_some(stats)
+    }
+
+    /// Realistic estimate of what `git gc` would reclaim.
+    ///
+    /// Garbage and loose objects that already exist in a pack are freed entirely;
+    /// the remaining loose objects usually shrink by 50-80% once delta-compressed.
+    /// Existing packs are not counted since repacking them rarely frees much.
+    pub fn reclaimable_estimate(&self) -> u64 {
+        let avg_loose = if self.loose_count > 0 {
+            self.loose_size / self.loose_count as u64
+        } else {
+            0
+        };
+        let prunable = (avg_loose * self.prune_packable as u64).min(self.loose_size);
+        let remaining_loose = self.loose_size - prunable;
+
+        self.garbage_size + prunable + (remaining_loose as f64 * 0.6) as u64
+    }
+}
+
 /// Information about a git repository
 #[derive(Debug, Clone)]
 pub struct GitRepoInfo {
@@ -40,6 +113,10 @@ pub struct GitRepoInfo {
     pub git_size: u64,
     /// Number of loose objects
     pub loose_objects: usize,
+    /// Size of loose objects
+    pub loose_size: u64,
+    /// Size of garbage in the object database
+    pub garbage_size: u64,
     /// Number of pack files
     pub pack_count: usize,
     /// Size of pack files
@@ -115,21 +192,12 @@ impl GitAnalyzer {
             return Ok(recommendations);
         }
 
-        // Count loose objects
-        let objects_dir = git_dir.join("objects");
-        let (loose_count, loose_size) = self.count_loose_objects(&objects_dir);
+        let stats = self.object_stats(repo_path, &git_dir.join("objects"));
+        let gc_recommended = self.gc_would_help(&stats);
 
-        // Count pack files
-        let pack_dir = objects_dir.join("pack");
-        let (pack_count, pack_size) = self.count_packs(&pack_dir);
-
-        // Check if gc would help
-        let gc_recommended = loose_count > self.min_loose_objects ||
-                            (loose_size > 50_000_000 && loose_count > 500);
-
-        // Estimate savings (loose objects can usually be compressed 50-80%)
+        // Report what gc would realistically free, not the total .git size
         let estimated_savings = if gc_recommended {
-            (loose_size as f64 * 0.6) as u64
+            stats.reclaimable_estimate()
         } else {
             0
         };
@@ -140,9 +208,11 @@ impl GitAnalyzer {
         let _info = GitRepoInfo {
             path: repo_path.to_path_buf(),
             git_size,
-            loose_objects: loose_count,
-            pack_count,
-            pack_size,
+            loose_objects: stats.loose_count,
+            loose_size: stats.loose_size,
+            garbage_size: stats.garbage_size,
+            pack_count: stats.packs,
+            pack_size: stats.pack_size,
             gc_recommended,
             estimated_savings,
             last_commit: last_commit.clone(),
@@ -157,16 +227,27 @@ impl GitAnalyzer {
             );
 
             let description = if gc_recommended {
-                format!(
-                    "{} loose objects ({} bytes). Running 'git gc' could save ~{}.",
-                    loose_count,
-                    format_size(loose_size),
+                let mut description = format!(
+                    "{} loose objects ({})",
+                    stats.loose_count,
+                    format_size(stats.loose_size)
+                );
+                if stats.garbage_size > 0 {
+                    description.push_str(&format!(
+                        ", {} garbage",
+                        format_size(stats.garbage_size)
+                    ));
+                }
+                description.push_str(&format!(
+                    ". Running 'git gc' could save ~{}.",
                     format_size(estimated_savings)
-                )
+                ));
+                description
             } else {
                 format!(
-                    "Large repository with {} pack files. Already well-packed.",
-                    pack_count
+                    "Large repository with {} pack files ({}). Already well-packed.",
+                    stats.packs,
+                    format_size(stats.pack_size)
                 )
             };
 
@@ -190,6 +271,45 @@ impl GitAnalyzer {
         Ok(recommendations)
     }
 
+    /// Decide whether `git gc` would free a meaningful amount of space
+    pub fn gc_would_help(&self, stats: &GitObjectStats) -> bool {
+        stats.loose_count > self.min_loose_objects
+            || (stats.loose_size > 50_000_000 && stats.loose_count > 500)
+            || stats.garbage_size > self.min_garbage_size
+    }
+
+    /// Read object statistics from `git count-objects -v`, falling back to
+    /// walking the object directory when git is unavailable or external
+    /// commands are disabled
+    fn object_stats(&self, repo_path: &Path, objects_dir: &Path) -> GitObjectStats {
+        let from_git = crate::cleaners::external_commands_enabled()
+            .then(|| {
+                Command::new("git")
+                    .args(["count-objects", "-v"])
+                    .current_dir(repo_path)
+                    .output()
+                    .ok()
+            })
+            .flatten()
+            .filter(|output| output.status.success())
+            .and_then(|output| GitObjectStats::parse(&String::from_utf8_lossy(&output.stdout)));
+
+        if let Some(stats) = from_git {
+            return stats;
+        }
+
+        let (loose_count, loose_size) = self.count_loose_objects(objects_dir);
+        let (packs, pack_size) = self.count_packs(&objects_dir.join("pack"));
+
+        GitObjectStats {
+            loose_count,
+            loose_size,
+            packs,
+            pack_size,
+            ..Default::default()
+        }
+    }
+
     /// Count loose objects in objects directory
     fn count_loose_objects(&self, objects_dir: &Path) -> (usize, u64) {
         let mut count = 0;
@@ -245,6 +365,10 @@ impl GitAnalyzer {
 
     /// Get last commit date
     fn get_last_commit_date(&self, repo_path: &Path) -> Option<String> {
+        if !crate::cleaners::external_commands_enabled() {
+            return None;
+        }
+
         let output = Command::new("git")
             .args(["log", "-1", "--format=%ci"])
             .current_dir(repo_path)
@@ -313,6 +437,65 @@ mod tests {
         assert_eq!(analyzer.min_git_size, 100_000_000);
     }
 
+    #[test]
+    fn test_parse_count_objects() {
+        let output = "count: 2000\nsize: 8000\nin-pack: 50000\npacks: 2\nsize-pack: 120000\nprune-packable: 500\ngarbage: 1\nsize-garbage: 16\n";
+        let stats = GitObjectStats::parse(output).unwrap();
+
+        assert_eq!(stats.loose_count, 2000);
+        assert_eq!(stats.loose_size, 8000 * 1024);
+        assert_eq!(stats.in_pack, 50000);
+        assert_eq!(stats.packs, 2);
+        assert_eq!(stats.pack_size, 120000 * 1024);
+        assert_eq!(stats.prune_packable, 500);
+        assert_eq!(stats.garbage_count, 1);
+        assert_eq!(stats.garbage_size, 16 * 1024);
+
+        assert!(GitObjectStats::parse("fatal: not a git repository").is_none());
+    }
+
+    #[test]
+    fn test_gc_not_recommended_for_packed_repo() {
+        let analyzer = GitAnalyzer::new();
+        let packed = GitObjectStats {
+            loose_count: 12,
+            loose_size: 48 * 1024,
+            in_pack: 400_000,
+            packs: 1,
+            pack_size: 2_000_000_000,
+            ..Default::default()
+        };
+        assert!(!analyzer.gc_would_help(&packed));
+
+        let loose = GitObjectStats {
+            loose_count: 5000,
+            loose_size: 20_000_000,
+            ..packed.clone()
+        };
+        assert!(analyzer.gc_would_help(&loose));
+
+        let garbage = GitObjectStats {
+            garbage_size: 50_000_000,
+            ..packed
+        };
+        assert!(analyzer.gc_would_help(&garbage));
+    }
+
+    #[test]
+    fn test_reclaimable_estimate_ignores_packs() {
+        let stats = GitObjectStats {
+            loose_count: 100,
+            loose_size: 1_000_000,
+            pack_size: 5_000_000_000,
+            prune_packable: 50,
+            garbage_size: 200_000,
+            ..Default::default()
+        };
+
+        // 200KB garbage + 500KB prunable + 60% of the remaining 500KB
+        assert_eq!(stats.reclaimable_estimate(), 1_000_000);
+    }
+
     #[test]
     fn test_git_scan() {
         let analyzer = GitAnalyzer::new();
@@ -3,11 +3,14 @@
 //! This module contains analysis tools that go beyond simple cleanup:
 //! - Git repository health and optimization
 //! - Stale project detection
-//! - Duplicate dependency detection
+//! - Duplicate dependency detection, and hardlinking identical copies
+//! - Age distribution of cleanable items
 
 pub mod git;
 pub mod stale;
 pub mod duplicates;
+pub mod dedupe;
+pub mod age;
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
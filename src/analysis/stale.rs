@@ -296,6 +296,10 @@ impl StaleProjectFinder {
 
     /// Get days since last git commit
     fn get_git_last_commit(&self, path: &Path) -> Option<(u64, String)> {
+        if !crate::cleaners::external_commands_enabled() {
+            return None;
+        }
+
         let output = Command::new("git")
             .args(["log", "-1", "--format=%ct"])
             .current_dir(path)
@@ -2,12 +2,17 @@
 //!
 //! This module provides intelligent caching of scan results with mtime-based invalidation.
 //! When a directory's modification time hasn't changed, we can skip rescanning it.
+//!
+//! The cache file is gzip-compressed JSON, capped at [`ScanCache::MAX_PROJECTS`]
+//! entries so it never becomes a disk-usage problem of its own.
 
-use crate::core::Project;
-use crate::error::{DevSweepError, Result};
+use crate::core::{Project, SizeMode};
+use crate::error::{DevSweepError, Result, ResultExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -18,7 +23,7 @@ pub struct CachedProject {
     pub project: Project,
     /// Modification time of the project root when cached
     pub root_mtime: u64,
-    /// When this entry was cached
+    /// When this entry was cached, i.e. last confirmed by a full scan
     pub cached_at: u64,
 }
 
@@ -31,6 +36,51 @@ pub struct CachedDirectory {
     pub project_roots: Vec<PathBuf>,
 }
 
+/// Whether a cached project entry would be reused by the next scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryValidity {
+    /// Up to date, the project is served from the cache
+    Valid,
+    /// The project root no longer exists or can't be read
+    Missing,
+    /// The project root was modified after it was cached
+    Modified {
+        /// Root mtime recorded in the cache
+        cached_mtime: u64,
+        /// Root mtime on disk now
+        current_mtime: u64,
+    },
+    /// Older than [`ScanCache::TTL_SECS`]
+    Expired {
+        /// Seconds since the entry was cached
+        age_secs: u64,
+    },
+}
+
+impl EntryValidity {
+    /// Whether the entry would be reused
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+
+    /// Plain-English reason for this state
+    pub fn reason(&self) -> String {
+        match self {
+            Self::Valid => "valid".into(),
+            Self::Missing => "project root no longer exists".into(),
+            Self::Modified { cached_mtime, current_mtime } => format!(
+                "root modified since cached (mtime {} -> {})",
+                cached_mtime, current_mtime
+            ),
+            Self::Expired { age_secs } => format!(
+                "expired ({}h old, TTL {}h)",
+                age_secs / 3600,
+                ScanCache::TTL_SECS / 3600
+            ),
+        }
+    }
+}
+
 /// The scan cache
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ScanCache {
@@ -42,6 +92,9 @@ pub struct ScanCache {
     pub projects: HashMap<PathBuf, CachedProject>,
     /// Cached directory scan info
     pub directories: HashMap<PathBuf, CachedDirectory>,
+    /// How the cached sizes were measured
+    #[serde(default)]
+    pub size_mode: SizeMode,
 }
 
 impl ScanCache {
@@ -51,6 +104,9 @@ impl ScanCache {
     /// Cache TTL - invalidate after 24 hours regardless
     pub const TTL_SECS: u64 = 24 * 60 * 60;
 
+    /// Most projects kept on save; the least recently validated go first
+    pub const MAX_PROJECTS: usize = 5_000;
+
     /// Create a new empty cache
     pub fn new() -> Self {
         Self {
@@ -58,26 +114,40 @@ impl ScanCache {
             updated_at: current_timestamp(),
             projects: HashMap::new(),
             directories: HashMap::new(),
+            size_mode: SizeMode::default(),
         }
     }
 
     /// Check if a project is still valid in cache
     pub fn get_valid_project(&self, root: &Path) -> Option<&CachedProject> {
         let cached = self.projects.get(root)?;
+        self.entry_validity(root)?.is_valid().then_some(cached)
+    }
+
+    /// Whether the cached entry for `root` would be reused, and if not why
+    ///
+    /// `None` if `root` isn't cached at all.
+    pub fn entry_validity(&self, root: &Path) -> Option<EntryValidity> {
+        let cached = self.projects.get(root)?;
 
         // Check if mtime has changed
-        let current_mtime = get_mtime(root).ok()?;
+        let Ok(current_mtime) = get_mtime(root) else {
+            return Some(EntryValidity::Missing);
+        };
         if cached.root_mtime != current_mtime {
-            return None; // Directory was modified
+            return Some(EntryValidity::Modified {
+                cached_mtime: cached.root_mtime,
+                current_mtime,
+            });
         }
 
         // Check TTL
-        let now = current_timestamp();
-        if now - cached.cached_at > Self::TTL_SECS {
-            return None; // Cache expired
+        let age_secs = current_timestamp().saturating_sub(cached.cached_at);
+        if age_secs > Self::TTL_SECS {
+            return Some(EntryValidity::Expired { age_secs });
         }
 
-        Some(cached)
+        Some(EntryValidity::Valid)
     }
 
     /// Check if a directory needs to be rescanned
@@ -128,8 +198,12 @@ impl ScanCache {
         }
 
         // TTL check
-        let now = current_timestamp();
-        now - self.updated_at < Self::TTL_SECS
+        self.age_secs() < Self::TTL_SECS
+    }
+
+    /// Seconds since the cache was last updated
+    pub fn age_secs(&self) -> u64 {
+        current_timestamp().saturating_sub(self.updated_at)
     }
 
     /// Get all valid cached projects
@@ -147,6 +221,25 @@ impl ScanCache {
         self.projects.len()
     }
 
+    /// Drop the least recently validated projects until at most
+    /// `max_projects` remain
+    ///
+    /// Entries for deleted projects are never re-cached, so they age out
+    /// first. Returns the number of projects dropped.
+    pub fn evict(&mut self, max_projects: usize) -> usize {
+        let excess = self.projects.len().saturating_sub(max_projects);
+        let mut by_age: Vec<_> = self
+            .projects
+            .iter()
+            .map(|(root, cached)| (cached.cached_at, root.clone()))
+            .collect();
+        by_age.sort();
+        for (_, root) in by_age.into_iter().take(excess) {
+            self.projects.remove(&root);
+        }
+        excess
+    }
+
     /// Clear the cache
     pub fn clear(&mut self) {
         self.projects.clear();
@@ -162,23 +255,60 @@ pub fn default_cache_path() -> Result<PathBuf> {
 
     let devsweep_cache = cache_dir.join("devsweep");
     if !devsweep_cache.exists() {
-        fs::create_dir_all(&devsweep_cache)?;
+        fs::create_dir_all(&devsweep_cache).with_path(&devsweep_cache)?;
     }
 
-    Ok(devsweep_cache.join("scan_cache.json"))
+    Ok(devsweep_cache.join("scan_cache.json.gz"))
+}
+
+/// Uncompressed cache file written by older versions, removed on the next save
+fn legacy_cache_path(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+/// `path` with `suffix` appended to its file name
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Read a cache file, gunzipping it unless it is plain JSON from an older version
+fn read_cache_text(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_path(path)?;
+    let mut content = String::new();
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        flate2::read::GzDecoder::new(bytes.as_slice())
+            .read_to_string(&mut content)
+            .context(format!("while decompressing {}", path.display()))?;
+    } else {
+        content = String::from_utf8(bytes)
+            .map_err(|e| DevSweepError::Other(e.to_string()))
+            .context(format!("while reading {}", path.display()))?;
+    }
+    Ok(content)
 }
 
 /// Load the cache from disk
 pub fn load_cache() -> Result<ScanCache> {
-    let path = default_cache_path()?;
+    load_cache_from(&default_cache_path()?)
+}
 
+/// Load the cache from a specific file
+///
+/// A missing, unreadable-as-JSON or outdated cache file yields a fresh cache,
+/// since the cache is only an optimisation and will be rewritten on save.
+pub fn load_cache_from(path: &Path) -> Result<ScanCache> {
     if !path.exists() {
         return Ok(ScanCache::new());
     }
 
-    let content = fs::read_to_string(&path)?;
-    let cache: ScanCache = serde_json::from_str(&content)
-        .map_err(|e| DevSweepError::Config(format!("Invalid cache file: {}", e)))?;
+    let Ok(content) = read_cache_text(path) else {
+        return Ok(ScanCache::new()); // Corrupt or truncated, start fresh
+    };
+    let Ok(cache) = serde_json::from_str::<ScanCache>(&content) else {
+        return Ok(ScanCache::new()); // Corrupt or truncated, start fresh
+    };
 
     // Check version
     if cache.version != ScanCache::VERSION {
@@ -188,17 +318,86 @@ pub fn load_cache() -> Result<ScanCache> {
     Ok(cache)
 }
 
+/// Read a cache file as-is, without the fallbacks of [`load_cache_from`]
+///
+/// For troubleshooting: `Ok(None)` if there is no cache file, an error if it
+/// can't be parsed, and an outdated version is returned rather than replaced.
+pub fn read_cache_file(path: &Path) -> Result<Option<ScanCache>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = read_cache_text(path)?;
+    let cache = serde_json::from_str(&content)
+        .context(format!("while parsing {}", path.display()))?;
+    Ok(Some(cache))
+}
+
 /// Save the cache to disk
-pub fn save_cache(cache: &ScanCache) -> Result<()> {
+///
+/// Returns `false` if another null-e process is saving at the same time, in
+/// which case this save is skipped rather than waiting for it.
+pub fn save_cache(cache: &ScanCache) -> Result<bool> {
     let path = default_cache_path()?;
-    let content = serde_json::to_string_pretty(cache)?;
-    fs::write(&path, content)?;
-    Ok(())
+    let saved = save_cache_to(cache, &path)?;
+    if saved {
+        let _ = fs::remove_file(legacy_cache_path(&path));
+    }
+    Ok(saved)
+}
+
+/// Save the cache to a specific file
+///
+/// Holds an advisory lock on `<path>.lock` while merging with whatever is on
+/// disk, then writes a temp file and renames it into place, so concurrent
+/// runs never see (or leave behind) a half-written cache. The merged cache is
+/// trimmed with [`ScanCache::evict`] and written gzip-compressed.
+pub fn save_cache_to(cache: &ScanCache, path: &Path) -> Result<bool> {
+    let lock_path = with_suffix(path, ".lock");
+    let lock_file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_path(&lock_path)?;
+
+    match lock_file.try_lock() {
+        Ok(()) => {}
+        Err(fs::TryLockError::WouldBlock) => return Ok(false),
+        Err(fs::TryLockError::Error(e)) => return Err(e.into()),
+    }
+
+    // Keep entries another process saved since we loaded; ours win on conflict.
+    // Entries measured in another size mode aren't comparable, so drop them.
+    let mut merged = load_cache_from(path)?;
+    if merged.size_mode != cache.size_mode {
+        merged = ScanCache::new();
+        merged.size_mode = cache.size_mode;
+    }
+    merged.projects.extend(cache.projects.clone());
+    merged.directories.extend(cache.directories.clone());
+    merged.updated_at = cache.updated_at;
+    merged.evict(ScanCache::MAX_PROJECTS);
+
+    let tmp_path = with_suffix(path, &format!(".{}.tmp", std::process::id()));
+    let content = serde_json::to_vec(&merged)?;
+    let written = fs::File::create(&tmp_path)
+        .and_then(|file| {
+            let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            encoder.write_all(&content)?;
+            encoder.finish()?.sync_all()
+        })
+        .and_then(|()| fs::rename(&tmp_path, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    written.with_path(path)?;
+
+    Ok(true)
 }
 
 /// Get modification time of a path as unix timestamp
 fn get_mtime(path: &Path) -> Result<u64> {
-    let metadata = fs::metadata(path)?;
+    let metadata = fs::metadata(path).with_path(path)?;
     let mtime = metadata.modified()?;
     Ok(mtime
         .duration_since(UNIX_EPOCH)
@@ -217,6 +416,7 @@ fn current_timestamp() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ProjectKind;
     use tempfile::TempDir;
 
     #[test]
@@ -240,4 +440,105 @@ mod tests {
         let loaded: ScanCache = serde_json::from_str(&fs::read_to_string(&cache_path).unwrap()).unwrap();
         assert_eq!(loaded.version, cache.version);
     }
+
+    #[test]
+    fn test_corrupt_cache_starts_fresh() {
+        let temp = TempDir::new().unwrap();
+        let cache_path = temp.path().join("scan_cache.json");
+        fs::write(&cache_path, "{\"version\": 1, \"projects\": {").unwrap();
+
+        let cache = load_cache_from(&cache_path).unwrap();
+        assert_eq!(cache.project_count(), 0);
+    }
+
+    #[test]
+    fn test_save_merges_with_concurrent_writer() {
+        let temp = TempDir::new().unwrap();
+        let cache_path = temp.path().join("scan_cache.json");
+
+        let mut first = ScanCache::new();
+        first.cache_project(Project::new(ProjectKind::Rust, temp.path().join("a")));
+        assert!(save_cache_to(&first, &cache_path).unwrap());
+
+        // Loaded before `first` was saved, so it doesn't know about "a"
+        let mut second = ScanCache::new();
+        second.cache_project(Project::new(ProjectKind::Go, temp.path().join("b")));
+        assert!(save_cache_to(&second, &cache_path).unwrap());
+
+        let loaded = load_cache_from(&cache_path).unwrap();
+        assert_eq!(loaded.project_count(), 2);
+        assert!(!temp.path().join(format!("scan_cache.json.{}.tmp", std::process::id())).exists());
+    }
+
+    #[test]
+    fn test_entry_validity() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("app");
+        fs::create_dir(&root).unwrap();
+
+        let mut cache = ScanCache::new();
+        assert_eq!(cache.entry_validity(&root), None);
+
+        cache.cache_project(Project::new(ProjectKind::Rust, root.clone()));
+        assert_eq!(cache.entry_validity(&root), Some(EntryValidity::Valid));
+        assert!(cache.get_valid_project(&root).is_some());
+
+        let entry = cache.projects.get_mut(&root).unwrap();
+        entry.cached_at -= ScanCache::TTL_SECS + 1;
+        assert!(matches!(cache.entry_validity(&root), Some(EntryValidity::Expired { .. })));
+
+        cache.projects.get_mut(&root).unwrap().root_mtime += 1;
+        assert!(matches!(cache.entry_validity(&root), Some(EntryValidity::Modified { .. })));
+
+        fs::remove_dir(&root).unwrap();
+        assert_eq!(cache.entry_validity(&root), Some(EntryValidity::Missing));
+        assert!(cache.get_valid_project(&root).is_none());
+    }
+
+    #[test]
+    fn test_saved_cache_is_compressed() {
+        let temp = TempDir::new().unwrap();
+        let cache_path = temp.path().join("scan_cache.json.gz");
+
+        // Plain JSON from an older version still loads
+        let mut cache = ScanCache::new();
+        cache.cache_project(Project::new(ProjectKind::Rust, temp.path().join("a")));
+        fs::write(&cache_path, serde_json::to_string_pretty(&cache).unwrap()).unwrap();
+        assert_eq!(load_cache_from(&cache_path).unwrap().project_count(), 1);
+
+        assert!(save_cache_to(&cache, &cache_path).unwrap());
+        let bytes = fs::read(&cache_path).unwrap();
+        assert!(bytes.starts_with(&[0x1f, 0x8b]));
+        assert_eq!(load_cache_from(&cache_path).unwrap().project_count(), 1);
+        assert_eq!(read_cache_file(&cache_path).unwrap().unwrap().project_count(), 1);
+        assert!(temp.path().join("scan_cache.json.gz.lock").exists());
+    }
+
+    #[test]
+    fn test_evict_least_recently_validated() {
+        let temp = TempDir::new().unwrap();
+        let mut cache = ScanCache::new();
+        for (i, name) in ["old", "mid", "new"].iter().enumerate() {
+            let root = temp.path().join(name);
+            cache.cache_project(Project::new(ProjectKind::Rust, root.clone()));
+            cache.projects.get_mut(&root).unwrap().cached_at = i as u64;
+        }
+
+        assert_eq!(cache.evict(5), 0);
+        assert_eq!(cache.evict(2), 1);
+        assert!(!cache.projects.contains_key(&temp.path().join("old")));
+        assert_eq!(cache.project_count(), 2);
+    }
+
+    #[test]
+    fn test_save_skips_on_lock_contention() {
+        let temp = TempDir::new().unwrap();
+        let cache_path = temp.path().join("scan_cache.json");
+
+        let held = fs::File::create(temp.path().join("scan_cache.json.lock")).unwrap();
+        held.lock().unwrap();
+
+        assert!(!save_cache_to(&ScanCache::new(), &cache_path).unwrap());
+        assert!(!cache_path.exists());
+    }
 }
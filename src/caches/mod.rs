@@ -3,9 +3,10 @@
 //! This module handles system-wide package manager caches like ~/.npm, ~/.cargo/registry, etc.
 //! These are separate from project-specific artifacts (node_modules, target).
 
+use crate::core::SizeMode;
 use crate::error::{DevSweepError, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 /// A global developer cache location
@@ -14,9 +15,9 @@ pub struct GlobalCache {
     /// Human-readable name
     pub name: String,
     /// Short identifier
-    pub id: &'static str,
+    pub id: String,
     /// Icon/emoji for display
-    pub icon: &'static str,
+    pub icon: String,
     /// Full path to the cache directory
     pub path: PathBuf,
     /// Size in bytes
@@ -26,9 +27,12 @@ pub struct GlobalCache {
     /// Last modification time
     pub last_modified: Option<SystemTime>,
     /// Official clean command (if available)
-    pub clean_command: Option<&'static str>,
+    pub clean_command: Option<String>,
     /// Description of what this cache contains
-    pub description: &'static str,
+    pub description: String,
+    /// Language or tool family the cache belongs to
+    #[serde(default)]
+    pub ecosystem: CacheEcosystem,
 }
 
 impl GlobalCache {
@@ -44,6 +48,13 @@ impl GlobalCache {
             .map(|d| d.as_secs() / 86400)
     }
 
+    /// Whether this was modified fewer than `min_age_days` days ago
+    ///
+    /// An unknown age never counts as recent.
+    pub fn is_recent(&self, min_age_days: u64) -> bool {
+        self.age_days().is_some_and(|days| days < min_age_days)
+    }
+
     /// Format the last used time
     pub fn last_used_display(&self) -> String {
         match self.age_days() {
@@ -58,19 +69,177 @@ impl GlobalCache {
     }
 }
 
+/// Language or tool family a cache belongs to, for grouping and selection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheEcosystem {
+    Js,
+    Python,
+    Rust,
+    Go,
+    Jvm,
+    Dotnet,
+    Ruby,
+    Php,
+    Mobile,
+    Ml,
+    #[default]
+    Other,
+}
+
+impl CacheEcosystem {
+    /// Every ecosystem, in display order
+    pub fn all() -> &'static [CacheEcosystem] {
+        &[
+            Self::Js,
+            Self::Python,
+            Self::Rust,
+            Self::Go,
+            Self::Jvm,
+            Self::Dotnet,
+            Self::Ruby,
+            Self::Php,
+            Self::Mobile,
+            Self::Ml,
+            Self::Other,
+        ]
+    }
+
+    /// Short name used in config files and selections (`js`, `python`, ...)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Js => "js",
+            Self::Python => "python",
+            Self::Rust => "rust",
+            Self::Go => "go",
+            Self::Jvm => "jvm",
+            Self::Dotnet => "dotnet",
+            Self::Ruby => "ruby",
+            Self::Php => "php",
+            Self::Mobile => "mobile",
+            Self::Ml => "ml",
+            Self::Other => "other",
+        }
+    }
+
+    /// Heading shown when caches are grouped
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Js => "JavaScript",
+            Self::Python => "Python",
+            Self::Rust => "Rust",
+            Self::Go => "Go",
+            Self::Jvm => "JVM",
+            Self::Dotnet => ".NET",
+            Self::Ruby => "Ruby",
+            Self::Php => "PHP",
+            Self::Mobile => "Mobile",
+            Self::Ml => "ML/AI",
+            Self::Other => "Other",
+        }
+    }
+
+    /// Parse a short name or a common alias, case-insensitively
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "javascript" | "node" => Some(Self::Js),
+            "py" => Some(Self::Python),
+            "java" => Some(Self::Jvm),
+            ".net" => Some(Self::Dotnet),
+            "ai" => Some(Self::Ml),
+            other => Self::all().iter().copied().find(|e| e.as_str() == other),
+        }
+    }
+}
+
 /// Definition of a known cache location
 #[derive(Debug, Clone)]
 pub struct CacheDefinition {
     pub id: &'static str,
     pub name: &'static str,
     pub icon: &'static str,
+    pub ecosystem: CacheEcosystem,
     /// Paths relative to home directory
     pub paths: &'static [&'static str],
+    /// Environment variables that relocate the cache, as `(variable, subpath)`
+    ///
+    /// Checked before `paths`. `XDG_CACHE_HOME`/`XDG_DATA_HOME` apply to any
+    /// `.cache/` or `.local/share/` path and need no entry here.
+    pub env: &'static [(&'static str, &'static str)],
     /// Official clean command
     pub clean_command: Option<&'static str>,
     pub description: &'static str,
 }
 
+/// A user-defined cache from the `[[caches.custom]]` config section
+///
+/// ```toml
+/// [[caches.custom]]
+/// id = "acme"
+/// name = "ACME build cache"
+/// paths = ["$ACME_CACHE_DIR", "~/.acme/cache"]
+/// clean_command = "acme cache purge"
+/// description = "Artifacts downloaded by the acme CLI"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomCacheDefinition {
+    pub id: String,
+    pub name: String,
+    #[serde(default = "default_custom_icon")]
+    pub icon: String,
+    /// Group the cache is listed and selected under
+    #[serde(default)]
+    pub ecosystem: CacheEcosystem,
+    /// Paths relative to home directory, `~/`-prefixed, absolute, or
+    /// starting with an environment variable (`$ACME_CACHE/objects`)
+    pub paths: Vec<String>,
+    /// Official clean command
+    #[serde(default)]
+    pub clean_command: Option<String>,
+    #[serde(default)]
+    pub description: String,
+}
+
+fn default_custom_icon() -> String {
+    "📁".to_string()
+}
+
+impl From<&CacheDefinition> for CustomCacheDefinition {
+    fn from(def: &CacheDefinition) -> Self {
+        Self {
+            id: def.id.to_string(),
+            name: def.name.to_string(),
+            icon: def.icon.to_string(),
+            ecosystem: def.ecosystem,
+            paths: env_paths(def).chain(def.paths.iter().flat_map(|p| xdg_paths(p))).collect(),
+            clean_command: def.clean_command.map(String::from),
+            description: def.description.to_string(),
+        }
+    }
+}
+
+/// `$VAR/subpath` entries for a definition's environment hints
+fn env_paths(def: &CacheDefinition) -> impl Iterator<Item = String> + '_ {
+    def.env.iter().map(|(var, sub)| match *sub {
+        "" => format!("${}", var),
+        sub => format!("${}/{}", var, sub),
+    })
+}
+
+/// A home-relative path, preceded by its XDG base directory equivalent
+fn xdg_paths(path: &str) -> Vec<String> {
+    const XDG_DIRS: [(&str, &str); 2] = [(".cache/", "XDG_CACHE_HOME"), (".local/share/", "XDG_DATA_HOME")];
+
+    let mut paths = Vec::with_capacity(2);
+    for (prefix, var) in XDG_DIRS {
+        if let Some(rest) = path.strip_prefix(prefix) {
+            paths.push(format!("${}/{}", var, rest));
+        }
+    }
+    paths.push(path.to_string());
+    paths
+}
+
 /// All known global cache locations
 pub fn known_caches() -> Vec<CacheDefinition> {
     vec![
@@ -81,7 +250,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "npm",
             name: "npm cache",
             icon: "📦",
+            ecosystem: CacheEcosystem::Js,
             paths: &[".npm/_cacache"],
+            env: &[("npm_config_cache", "_cacache"), ("NPM_CONFIG_CACHE", "_cacache")],
             clean_command: Some("npm cache clean --force"),
             description: "Cached npm packages and metadata",
         },
@@ -89,7 +260,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "yarn",
             name: "Yarn cache",
             icon: "🧶",
+            ecosystem: CacheEcosystem::Js,
             paths: &[".yarn/cache", ".cache/yarn"],
+            env: &[("YARN_CACHE_FOLDER", "")],
             clean_command: Some("yarn cache clean"),
             description: "Cached Yarn packages",
         },
@@ -97,7 +270,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "pnpm",
             name: "pnpm store",
             icon: "📦",
+            ecosystem: CacheEcosystem::Js,
             paths: &[".pnpm-store", ".local/share/pnpm/store"],
+            env: &[],
             clean_command: Some("pnpm store prune"),
             description: "Global pnpm content-addressable store",
         },
@@ -105,7 +280,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "bun",
             name: "Bun cache",
             icon: "🥟",
+            ecosystem: CacheEcosystem::Js,
             paths: &[".bun/install/cache"],
+            env: &[("BUN_INSTALL_CACHE_DIR", ""), ("BUN_INSTALL", "install/cache")],
             clean_command: None,
             description: "Cached Bun packages",
         },
@@ -113,7 +290,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "deno",
             name: "Deno cache",
             icon: "🦕",
+            ecosystem: CacheEcosystem::Js,
             paths: &[".cache/deno", ".deno"],
+            env: &[("DENO_DIR", "")],
             clean_command: Some("deno cache --reload"),
             description: "Cached Deno modules and compiled scripts",
         },
@@ -125,7 +304,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "pip",
             name: "pip cache",
             icon: "🐍",
+            ecosystem: CacheEcosystem::Python,
             paths: &[".cache/pip", "Library/Caches/pip"],
+            env: &[("PIP_CACHE_DIR", "")],
             clean_command: Some("pip cache purge"),
             description: "Cached pip wheels and HTTP responses",
         },
@@ -133,7 +314,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "uv",
             name: "uv cache",
             icon: "⚡",
+            ecosystem: CacheEcosystem::Python,
             paths: &[".cache/uv"],
+            env: &[("UV_CACHE_DIR", "")],
             clean_command: Some("uv cache clean"),
             description: "Cached uv packages (fast Python installer)",
         },
@@ -141,7 +324,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "poetry",
             name: "Poetry cache",
             icon: "📜",
+            ecosystem: CacheEcosystem::Python,
             paths: &[".cache/pypoetry", "Library/Caches/pypoetry"],
+            env: &[("POETRY_CACHE_DIR", "")],
             clean_command: Some("poetry cache clear --all ."),
             description: "Cached Poetry packages and virtualenvs",
         },
@@ -149,7 +334,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "pipenv",
             name: "Pipenv cache",
             icon: "🐍",
+            ecosystem: CacheEcosystem::Python,
             paths: &[".cache/pipenv"],
+            env: &[("PIPENV_CACHE_DIR", "")],
             clean_command: None,
             description: "Cached Pipenv packages",
         },
@@ -157,7 +344,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "conda",
             name: "Conda cache",
             icon: "🐍",
+            ecosystem: CacheEcosystem::Python,
             paths: &[".conda/pkgs", "anaconda3/pkgs", "miniconda3/pkgs"],
+            env: &[],
             clean_command: Some("conda clean --all"),
             description: "Cached Conda packages",
         },
@@ -169,7 +358,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "cargo-registry",
             name: "Cargo registry",
             icon: "🦀",
+            ecosystem: CacheEcosystem::Rust,
             paths: &[".cargo/registry"],
+            env: &[("CARGO_HOME", "registry")],
             clean_command: None, // Cargo 1.75+ has auto GC
             description: "Downloaded crate sources and indices",
         },
@@ -177,7 +368,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "cargo-git",
             name: "Cargo git",
             icon: "🦀",
+            ecosystem: CacheEcosystem::Rust,
             paths: &[".cargo/git"],
+            env: &[("CARGO_HOME", "git")],
             clean_command: None,
             description: "Git dependencies cache",
         },
@@ -189,7 +382,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "go-mod",
             name: "Go modules",
             icon: "🐹",
+            ecosystem: CacheEcosystem::Go,
             paths: &["go/pkg/mod"],
+            env: &[("GOMODCACHE", ""), ("GOPATH", "pkg/mod")],
             clean_command: Some("go clean -modcache"),
             description: "Downloaded Go module cache",
         },
@@ -197,7 +392,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "go-build",
             name: "Go build cache",
             icon: "🐹",
+            ecosystem: CacheEcosystem::Go,
             paths: &[".cache/go-build", "Library/Caches/go-build"],
+            env: &[("GOCACHE", "")],
             clean_command: Some("go clean -cache"),
             description: "Go build artifacts cache",
         },
@@ -209,15 +406,49 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "gradle",
             name: "Gradle cache",
             icon: "🐘",
+            ecosystem: CacheEcosystem::Jvm,
             paths: &[".gradle/caches"],
-            clean_command: None, // Manual or gradle --stop && rm
+            env: &[("GRADLE_USER_HOME", "caches")],
+            clean_command: None, // Daemon is stopped first, see stop_command()
             description: "Gradle dependencies and build cache",
         },
+        CacheDefinition {
+            id: "gradle-daemon",
+            name: "Gradle daemon",
+            icon: "🐘",
+            ecosystem: CacheEcosystem::Jvm,
+            paths: &[".gradle/daemon"],
+            env: &[("GRADLE_USER_HOME", "daemon")],
+            clean_command: None,
+            description: "Gradle daemon logs and registry",
+        },
+        CacheDefinition {
+            id: "konan",
+            name: "Kotlin/Native",
+            icon: "🟣",
+            ecosystem: CacheEcosystem::Jvm,
+            paths: &[".konan"],
+            env: &[("KONAN_DATA_DIR", "")],
+            clean_command: None,
+            description: "Kotlin/Native compilers, toolchains and dependency cache",
+        },
+        CacheDefinition {
+            id: "kotlin",
+            name: "Kotlin compiler cache",
+            icon: "🟣",
+            ecosystem: CacheEcosystem::Jvm,
+            paths: &[".kotlin", ".local/share/kotlin", "Library/Application Support/kotlin"],
+            env: &[],
+            clean_command: None,
+            description: "Kotlin compiler daemon files and caches",
+        },
         CacheDefinition {
             id: "maven",
             name: "Maven repository",
             icon: "🪶",
+            ecosystem: CacheEcosystem::Jvm,
             paths: &[".m2/repository"],
+            env: &[],
             clean_command: None,
             description: "Maven local repository",
         },
@@ -225,7 +456,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "sbt",
             name: "SBT cache",
             icon: "📦",
+            ecosystem: CacheEcosystem::Jvm,
             paths: &[".sbt", ".ivy2/cache"],
+            env: &[],
             clean_command: None,
             description: "SBT and Ivy dependency cache",
         },
@@ -237,7 +470,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "nuget",
             name: "NuGet cache",
             icon: "🔷",
+            ecosystem: CacheEcosystem::Dotnet,
             paths: &[".nuget/packages"],
+            env: &[("NUGET_PACKAGES", "")],
             clean_command: Some("dotnet nuget locals all --clear"),
             description: "NuGet package cache",
         },
@@ -249,7 +484,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "gem",
             name: "Ruby gems",
             icon: "💎",
+            ecosystem: CacheEcosystem::Ruby,
             paths: &[".gem", ".local/share/gem"],
+            env: &[("GEM_HOME", "")],
             clean_command: Some("gem cleanup"),
             description: "Installed Ruby gems",
         },
@@ -257,7 +494,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "bundler",
             name: "Bundler cache",
             icon: "💎",
+            ecosystem: CacheEcosystem::Ruby,
             paths: &[".bundle/cache"],
+            env: &[("BUNDLE_USER_CACHE", "")],
             clean_command: Some("bundle clean --force"),
             description: "Bundler gem cache",
         },
@@ -269,7 +508,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "composer",
             name: "Composer cache",
             icon: "🎼",
+            ecosystem: CacheEcosystem::Php,
             paths: &[".composer/cache", ".cache/composer"],
+            env: &[("COMPOSER_CACHE_DIR", ""), ("COMPOSER_HOME", "cache")],
             clean_command: Some("composer clear-cache"),
             description: "Composer package cache",
         },
@@ -281,7 +522,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "cocoapods",
             name: "CocoaPods cache",
             icon: "🍫",
+            ecosystem: CacheEcosystem::Mobile,
             paths: &["Library/Caches/CocoaPods"],
+            env: &[],
             clean_command: Some("pod cache clean --all"),
             description: "CocoaPods specs and pod cache",
         },
@@ -289,7 +532,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "pub",
             name: "Dart/Flutter pub",
             icon: "🎯",
+            ecosystem: CacheEcosystem::Mobile,
             paths: &[".pub-cache"],
+            env: &[("PUB_CACHE", "")],
             clean_command: None,
             description: "Dart and Flutter package cache",
         },
@@ -297,7 +542,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "android-gradle",
             name: "Android Gradle",
             icon: "🤖",
+            ecosystem: CacheEcosystem::Mobile,
             paths: &[".android/cache", ".android/build-cache"],
+            env: &[("ANDROID_USER_HOME", "cache")],
             clean_command: None,
             description: "Android build cache",
         },
@@ -309,7 +556,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "huggingface",
             name: "Hugging Face cache",
             icon: "🤗",
+            ecosystem: CacheEcosystem::Ml,
             paths: &[".cache/huggingface"],
+            env: &[("HF_HOME", "")],
             clean_command: None,
             description: "Downloaded ML models and datasets",
         },
@@ -317,7 +566,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "torch",
             name: "PyTorch cache",
             icon: "🔥",
+            ecosystem: CacheEcosystem::Ml,
             paths: &[".cache/torch"],
+            env: &[("TORCH_HOME", "")],
             clean_command: None,
             description: "PyTorch model hub cache",
         },
@@ -329,7 +580,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "homebrew",
             name: "Homebrew cache",
             icon: "🍺",
+            ecosystem: CacheEcosystem::Other,
             paths: &["Library/Caches/Homebrew"],
+            env: &[("HOMEBREW_CACHE", "")],
             clean_command: Some("brew cleanup --prune=all"),
             description: "Downloaded Homebrew bottles and source",
         },
@@ -337,7 +590,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "cypress",
             name: "Cypress cache",
             icon: "🌲",
+            ecosystem: CacheEcosystem::Other,
             paths: &[".cache/Cypress", "Library/Caches/Cypress"],
+            env: &[("CYPRESS_CACHE_FOLDER", "")],
             clean_command: Some("cypress cache clear"),
             description: "Cypress browser binaries",
         },
@@ -345,7 +600,9 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "playwright",
             name: "Playwright cache",
             icon: "🎭",
+            ecosystem: CacheEcosystem::Other,
             paths: &[".cache/ms-playwright", "Library/Caches/ms-playwright"],
+            env: &[("PLAYWRIGHT_BROWSERS_PATH", "")],
             clean_command: None,
             description: "Playwright browser binaries",
         },
@@ -353,38 +610,81 @@ pub fn known_caches() -> Vec<CacheDefinition> {
             id: "electron",
             name: "Electron cache",
             icon: "⚛️",
+            ecosystem: CacheEcosystem::Other,
             paths: &[".cache/electron", "Library/Caches/electron"],
+            env: &[("electron_config_cache", "")],
             clean_command: None,
             description: "Electron framework binaries",
         },
     ]
 }
 
-/// Detect all existing global caches
+/// Command that stops a cache's background process before it is deleted directly
+///
+/// The Gradle daemon keeps files under `~/.gradle` open, so deleting them
+/// while it runs can fail or leave it in a broken state.
+pub fn stop_command(cache_id: &str) -> Option<&'static str> {
+    match cache_id {
+        "gradle" | "gradle-daemon" => Some("gradle --stop"),
+        _ => None,
+    }
+}
+
+/// Detect all existing global caches, including custom ones from the config file
 pub fn detect_caches() -> Result<Vec<GlobalCache>> {
+    // A broken config shouldn't hide the builtin caches
+    let custom = crate::config::load_default_config()
+        .map(|c| c.caches.custom)
+        .unwrap_or_default();
+
+    detect_caches_with(&custom)
+}
+
+/// Detect builtin caches merged with `custom` definitions.
+///
+/// A custom definition with the same id as a builtin replaces it.
+pub fn detect_caches_with(custom: &[CustomCacheDefinition]) -> Result<Vec<GlobalCache>> {
     let home = dirs::home_dir()
         .ok_or_else(|| DevSweepError::Config("Could not find home directory".into()))?;
 
-    let definitions = known_caches();
+    Ok(detect_caches_in(&home, custom, |name| std::env::var(name).ok()))
+}
+
+/// Detect caches under `home`, looking environment variables up through `var`
+fn detect_caches_in(
+    home: &Path,
+    custom: &[CustomCacheDefinition],
+    var: impl Fn(&str) -> Option<String>,
+) -> Vec<GlobalCache> {
+    let mut definitions: Vec<CustomCacheDefinition> = known_caches()
+        .iter()
+        .filter(|def| !custom.iter().any(|c| c.id == def.id))
+        .map(CustomCacheDefinition::from)
+        .collect();
+    definitions.extend(custom.iter().cloned());
+
     let mut caches = Vec::new();
 
     for def in definitions {
         // Try each possible path for this cache
-        for rel_path in def.paths {
-            let full_path = home.join(rel_path);
+        for path in &def.paths {
+            let Some(full_path) = resolve_cache_path(home, path, &var) else {
+                continue;
+            };
 
             if full_path.exists() && full_path.is_dir() {
                 // Found this cache!
                 let mut cache = GlobalCache {
-                    name: def.name.to_string(),
-                    id: def.id,
-                    icon: def.icon,
+                    name: def.name.clone(),
+                    id: def.id.clone(),
+                    icon: def.icon.clone(),
                     path: full_path.clone(),
                     size: 0,
                     file_count: 0,
                     last_modified: None,
-                    clean_command: def.clean_command,
-                    description: def.description,
+                    clean_command: def.clean_command.clone(),
+                    description: def.description.clone(),
+                    ecosystem: def.ecosystem,
                 };
 
                 // Get last modified time
@@ -398,11 +698,36 @@ pub fn detect_caches() -> Result<Vec<GlobalCache>> {
         }
     }
 
-    Ok(caches)
+    caches
+}
+
+/// Resolve a cache path: `~/x` and relative paths are under home, absolute paths stay as-is
+///
+/// `$VAR/x` is under the variable's value (the first entry of a list like
+/// `GOPATH`); `None` when it is unset, empty or not absolute.
+fn resolve_cache_path(
+    home: &Path,
+    path: &str,
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<PathBuf> {
+    if let Some(spec) = path.strip_prefix('$') {
+        let (name, rest) = spec.split_once('/').unwrap_or((spec, ""));
+        let value = var(name).filter(|v| !v.trim().is_empty())?;
+        let base = std::env::split_paths(&value).next().filter(|p| p.is_absolute())?;
+        return Some(if rest.is_empty() { base } else { base.join(rest) });
+    }
+
+    let path = path.strip_prefix("~/").unwrap_or(path);
+    Some(home.join(path))
 }
 
 /// Calculate size for a single cache (can be slow for large caches)
 pub fn calculate_cache_size(cache: &mut GlobalCache) -> Result<()> {
+    calculate_cache_size_with_mode(cache, SizeMode::Apparent)
+}
+
+/// Calculate size for a single cache, measuring each file with `mode`
+pub fn calculate_cache_size_with_mode(cache: &mut GlobalCache, mode: SizeMode) -> Result<()> {
     use rayon::prelude::*;
     use walkdir::WalkDir;
 
@@ -421,7 +746,7 @@ pub fn calculate_cache_size(cache: &mut GlobalCache) -> Result<()> {
         .filter(|m| m.is_file())
         .fold(
             || (0u64, 0u64),
-            |(size, count), m| (size + m.len(), count + 1),
+            |(size, count), m| (size + mode.of(&m), count + 1),
         )
         .reduce(|| (0, 0), |(s1, c1), (s2, c2)| (s1 + s2, c1 + c2));
 
@@ -433,11 +758,16 @@ pub fn calculate_cache_size(cache: &mut GlobalCache) -> Result<()> {
 
 /// Calculate sizes for all caches in parallel
 pub fn calculate_all_sizes(caches: &mut [GlobalCache]) -> Result<()> {
+    calculate_all_sizes_with_mode(caches, SizeMode::Apparent)
+}
+
+/// Calculate sizes for all caches in parallel, measuring each file with `mode`
+pub fn calculate_all_sizes_with_mode(caches: &mut [GlobalCache], mode: SizeMode) -> Result<()> {
     use rayon::prelude::*;
 
     // Calculate sizes in parallel
     caches.par_iter_mut().for_each(|cache| {
-        let _ = calculate_cache_size(cache);
+        let _ = calculate_cache_size_with_mode(cache, mode);
     });
 
     Ok(())
@@ -445,6 +775,27 @@ pub fn calculate_all_sizes(caches: &mut [GlobalCache]) -> Result<()> {
 
 /// Clean a cache using the official command if available, otherwise rm -rf
 pub fn clean_cache(cache: &GlobalCache, use_official_command: bool) -> Result<CleanResult> {
+    clean_cache_with_method(
+        cache,
+        use_official_command,
+        crate::trash::DeleteMethod::Permanent,
+        SizeMode::Apparent,
+    )
+}
+
+/// Clean a cache, falling back to `method` when no official command is used
+///
+/// Safe mode passes [`DeleteMethod::Trash`](crate::trash::DeleteMethod::Trash) here
+/// and only enables the official command when it is reversible. `size_mode`
+/// must be the mode `cache.size` was measured in; what an official command
+/// left behind is measured the same way.
+pub fn clean_cache_with_method(
+    cache: &GlobalCache,
+    use_official_command: bool,
+    method: crate::trash::DeleteMethod,
+    size_mode: SizeMode,
+) -> Result<CleanResult> {
+    let _span = tracing::info_span!("clean_cache", cache = %cache.id).entered();
     if !cache.path.exists() {
         return Ok(CleanResult {
             success: true,
@@ -457,51 +808,58 @@ pub fn clean_cache(cache: &GlobalCache, use_official_command: bool) -> Result<Cl
 
     // Try official command first if requested
     if use_official_command {
-        if let Some(cmd) = cache.clean_command {
-            let result = run_clean_command(cmd);
-            if result.is_ok() {
-                return Ok(CleanResult {
-                    success: true,
-                    bytes_freed: size_before,
-                    method: CleanMethod::OfficialCommand(cmd.to_string()),
-                });
+        if let Some(cmd) = &cache.clean_command {
+            match crate::cleaners::run_clean_command(cmd) {
+                // Official commands often only clear part of the cache
+                Ok(()) => {
+                    return Ok(CleanResult {
+                        success: true,
+                        bytes_freed: size_before.saturating_sub(remaining_size(cache, size_mode)),
+                        method: CleanMethod::OfficialCommand(cmd.to_string()),
+                    });
+                }
+                // Ctrl-C stops the clean, it doesn't switch to deleting
+                Err(e) if e.is_user_interrupt() => return Err(e),
+                // Fall through to manual deletion if command fails
+                Err(e) => tracing::warn!("{}; deleting {} directly", e, cache.path.display()),
+            }
+        }
+    }
+
+    // Stopping is best effort: the tool may not be installed or running
+    if method != crate::trash::DeleteMethod::DryRun && crate::cleaners::external_commands_enabled() {
+        if let Some(cmd) = stop_command(&cache.id) {
+            match crate::cleaners::run_clean_command(cmd) {
+                Ok(()) => {}
+                Err(e) if e.is_user_interrupt() => return Err(e),
+                Err(e) => tracing::debug!("{}; deleting {} anyway", e, cache.path.display()),
             }
-            // Fall through to manual deletion if command fails
         }
     }
 
     // Manual deletion
-    match crate::trash::delete_path(&cache.path, crate::trash::DeleteMethod::Permanent) {
-        Ok(_) => Ok(CleanResult {
+    match crate::trash::delete_path_as(&cache.path, method, &cache.name, "cache") {
+        Ok(freed) => Ok(CleanResult {
             success: true,
-            bytes_freed: size_before,
+            bytes_freed: freed,
             method: CleanMethod::ManualDelete,
         }),
-        Err(e) => Err(e),
+        Err(e) => Err(e.with_context(format!(
+            "while cleaning {} cache at {}",
+            cache.name,
+            cache.path.display()
+        ))),
     }
 }
 
-/// Run an official clean command
-fn run_clean_command(cmd: &str) -> Result<()> {
-    use std::process::Command;
-
-    let parts: Vec<&str> = cmd.split_whitespace().collect();
-    if parts.is_empty() {
-        return Err(DevSweepError::Config("Empty clean command".into()));
-    }
-
-    let output = Command::new(parts[0])
-        .args(&parts[1..])
-        .output()
-        .map_err(|e| DevSweepError::Io(e))?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(DevSweepError::CleanFailed {
-            path: PathBuf::from(cmd),
-            reason: String::from_utf8_lossy(&output.stderr).to_string(),
-        })
+/// Size of what is left at a cache's path, 0 once it is gone
+fn remaining_size(cache: &GlobalCache, size_mode: SizeMode) -> u64 {
+    let mut after = cache.clone();
+    after.size = 0;
+    match calculate_cache_size_with_mode(&mut after, size_mode) {
+        Ok(()) => after.size,
+        // Can't tell what is left, so don't claim anything was freed
+        Err(_) => cache.size,
     }
 }
 
@@ -553,23 +911,177 @@ mod tests {
         assert!(caches.len() > 20); // We defined 25+ caches
     }
 
+    #[test]
+    fn test_cache_ecosystems() {
+        let ecosystem_of = |id: &str| known_caches().into_iter().find(|c| c.id == id).map(|c| c.ecosystem);
+        assert_eq!(ecosystem_of("npm"), Some(CacheEcosystem::Js));
+        assert_eq!(ecosystem_of("pip"), Some(CacheEcosystem::Python));
+        assert_eq!(ecosystem_of("cargo-registry"), Some(CacheEcosystem::Rust));
+        assert_eq!(ecosystem_of("gradle"), Some(CacheEcosystem::Jvm));
+        assert_eq!(ecosystem_of("konan"), Some(CacheEcosystem::Jvm));
+
+        assert_eq!(CacheEcosystem::from_name("JS"), Some(CacheEcosystem::Js));
+        assert_eq!(CacheEcosystem::from_name("node"), Some(CacheEcosystem::Js));
+        assert_eq!(CacheEcosystem::from_name("java"), Some(CacheEcosystem::Jvm));
+        assert_eq!(CacheEcosystem::from_name("cobol"), None);
+        for ecosystem in CacheEcosystem::all() {
+            assert_eq!(CacheEcosystem::from_name(ecosystem.as_str()), Some(*ecosystem));
+        }
+    }
+
     #[test]
     fn test_cache_age_display() {
         let mut cache = GlobalCache {
             name: "test".into(),
-            id: "test",
-            icon: "📦",
+            id: "test".into(),
+            icon: "📦".into(),
             path: PathBuf::from("/tmp/test"),
             size: 0,
             file_count: 0,
             last_modified: Some(SystemTime::now()),
             clean_command: None,
-            description: "test",
+            description: "test".into(),
+            ecosystem: CacheEcosystem::Js,
         };
 
         assert_eq!(cache.last_used_display(), "today");
     }
 
+    #[test]
+    fn test_official_command_reversibility() {
+        use crate::cleaners::is_reversible_command;
+
+        let irreversible = ["npm", "pip", "go-mod", "nuget", "homebrew", "conda"];
+        for def in known_caches().iter().filter(|d| irreversible.contains(&d.id)) {
+            let cmd = def.clean_command.unwrap();
+            assert!(!is_reversible_command(cmd), "{} should be irreversible", cmd);
+        }
+
+        assert!(!is_reversible_command("docker volume prune -f"));
+        assert!(!is_reversible_command("swift package purge-cache"));
+        // Re-fetching modules overwrites the cache but removes nothing
+        assert!(is_reversible_command("deno cache --reload"));
+    }
+
+    #[test]
+    fn test_stop_command() {
+        assert_eq!(stop_command("gradle-daemon"), Some("gradle --stop"));
+        assert_eq!(stop_command("gradle"), Some("gradle --stop"));
+        assert_eq!(stop_command("npm"), None);
+        // Stopping must never remove anything itself
+        assert!(crate::cleaners::is_reversible_command(stop_command("gradle").unwrap()));
+    }
+
+    #[test]
+    fn test_custom_cache_definitions() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let custom_dir = temp.path().join("acme-cache");
+        std::fs::create_dir(&custom_dir).unwrap();
+
+        let custom = CustomCacheDefinition {
+            id: "acme".into(),
+            name: "ACME cache".into(),
+            icon: default_custom_icon(),
+            paths: vec![custom_dir.display().to_string()],
+            ecosystem: CacheEcosystem::Other,
+            clean_command: Some("acme cache purge".into()),
+            description: "Internal tool cache".into(),
+        };
+
+        let caches = detect_caches_with(&[custom]).unwrap();
+        let acme = caches.iter().find(|c| c.id == "acme").unwrap();
+        assert_eq!(acme.path, custom_dir);
+        assert_eq!(acme.clean_command.as_deref(), Some("acme cache purge"));
+
+        // Custom entries override builtins with the same id
+        let npm = CustomCacheDefinition {
+            id: "npm".into(),
+            paths: vec![custom_dir.display().to_string()],
+            ..CustomCacheDefinition::from(&known_caches()[0])
+        };
+        let caches = detect_caches_with(&[npm]).unwrap();
+        let npm_caches: Vec<_> = caches.iter().filter(|c| c.id == "npm").collect();
+        assert_eq!(npm_caches.len(), 1);
+        assert_eq!(npm_caches[0].path, custom_dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_official_command_reports_actual_delta() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("purged.bin"), vec![0u8; 3_000]).unwrap();
+        std::fs::write(temp.path().join("kept.bin"), vec![0u8; 1_000]).unwrap();
+
+        // An official command that only clears part of the cache
+        let mut cache = GlobalCache {
+            name: "Partial".into(),
+            id: "partial".into(),
+            icon: default_custom_icon(),
+            path: temp.path().to_path_buf(),
+            size: 0,
+            file_count: 0,
+            last_modified: None,
+            clean_command: Some(format!("rm {}", temp.path().join("purged.bin").display())),
+            description: String::new(),
+            ecosystem: CacheEcosystem::Other,
+        };
+        calculate_cache_size(&mut cache).unwrap();
+        assert_eq!(cache.size, 4_000);
+
+        let result = clean_cache(&cache, true).unwrap();
+        assert!(matches!(result.method, CleanMethod::OfficialCommand(_)));
+        assert_eq!(result.bytes_freed, 3_000);
+        assert!(temp.path().join("kept.bin").exists());
+    }
+
+    #[test]
+    fn test_resolve_cache_path() {
+        let home = Path::new("/home/me");
+        let unset = |_: &str| None;
+        assert_eq!(resolve_cache_path(home, "~/.acme/cache", unset), Some(home.join(".acme/cache")));
+        assert_eq!(resolve_cache_path(home, ".acme/cache", unset), Some(home.join(".acme/cache")));
+        assert_eq!(resolve_cache_path(home, "/opt/acme", unset), Some(PathBuf::from("/opt/acme")));
+        assert_eq!(resolve_cache_path(home, "$ACME_CACHE", unset), None);
+
+        let set = |name: &str| match name {
+            "ACME_CACHE" => Some("/data/acme".to_string()),
+            "GOPATH" => Some(std::env::join_paths(["/data/go", "/opt/go"]).unwrap().into_string().unwrap()),
+            "RELATIVE" => Some("acme".to_string()),
+            _ => Some(String::new()),
+        };
+        assert_eq!(resolve_cache_path(home, "$ACME_CACHE", set), Some(PathBuf::from("/data/acme")));
+        assert_eq!(resolve_cache_path(home, "$GOPATH/pkg/mod", set), Some(PathBuf::from("/data/go/pkg/mod")));
+        assert_eq!(resolve_cache_path(home, "$RELATIVE/x", set), None);
+        assert_eq!(resolve_cache_path(home, "$EMPTY/x", set), None);
+    }
+
+    #[test]
+    fn test_relocated_caches() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        let cargo_home = temp.path().join("cargo");
+        let xdg_cache = temp.path().join("xdg");
+        std::fs::create_dir_all(home.join(".cargo/registry")).unwrap();
+        std::fs::create_dir_all(home.join(".cache/pip")).unwrap();
+        std::fs::create_dir_all(cargo_home.join("registry")).unwrap();
+        std::fs::create_dir_all(xdg_cache.join("pip")).unwrap();
+        std::fs::create_dir_all(home.join(".cargo/git")).unwrap();
+
+        let var = |name: &str| match name {
+            "CARGO_HOME" => Some(cargo_home.display().to_string()),
+            "XDG_CACHE_HOME" => Some(xdg_cache.display().to_string()),
+            _ => None,
+        };
+        let caches = detect_caches_in(&home, &[], var);
+        let path_of = |id: &str| caches.iter().find(|c| c.id == id).map(|c| c.path.clone());
+
+        // The variables win over the default locations...
+        assert_eq!(path_of("cargo-registry"), Some(cargo_home.join("registry")));
+        assert_eq!(path_of("pip"), Some(xdg_cache.join("pip")));
+        // ...which are still used when the relocated cache doesn't exist
+        assert_eq!(path_of("cargo-git"), Some(home.join(".cargo/git")));
+    }
+
     #[test]
     fn test_detect_caches() {
         // This will detect real caches on the system
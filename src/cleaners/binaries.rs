@@ -142,6 +142,22 @@ pub struct BinaryInstance {
     pub in_path: bool,
     /// Is this the active/default version?
     pub is_active: bool,
+    /// Toolchain install behind this binary (a pyenv version, a Cellar keg, …)
+    pub install_dir: Option<PathBuf>,
+    /// Size of `install_dir`, once measured
+    pub install_size: Option<u64>,
+}
+
+impl BinaryInstance {
+    /// What removing this binary actually frees: its whole install if known
+    pub fn removal_size(&self) -> u64 {
+        self.install_size.unwrap_or(self.binary_size)
+    }
+
+    /// The install this binary belongs to, or the binary itself
+    fn install_key(&self) -> &Path {
+        self.install_dir.as_deref().unwrap_or(&self.resolved_path)
+    }
 }
 
 /// Recommendation for handling duplicate binaries
@@ -161,6 +177,35 @@ pub enum DuplicateRecommendation {
     KeepAll { reason: String },
 }
 
+impl DuplicateRecommendation {
+    /// Plain-English explanation of why `instance` is listed for removal
+    pub fn explain(&self, instance: &BinaryInstance) -> String {
+        match self {
+            Self::ConflictingManagers { managers } => {
+                let names: Vec<&str> = managers.iter().map(|m| m.name()).collect();
+                format!(
+                    "Duplicate from {}. Also installed via: {}",
+                    instance.source.name(),
+                    names.join(", ")
+                )
+            }
+            Self::RemoveDuplicateSource { source } => {
+                format!("Duplicate installation from {}", source.name())
+            }
+            Self::RemoveOldVersions { versions } => {
+                format!("Old version. Newer versions available: {}", versions.join(", "))
+            }
+            Self::UnusedVersionManager { name, .. } => {
+                format!("{} is installed but none of its versions are in use", name)
+            }
+            Self::StaleConfig { file, manager } => {
+                format!("{} still sets up {}, which is no longer installed", file.display(), manager)
+            }
+            Self::KeepAll { reason } => format!("Duplicate binary ({})", reason),
+        }
+    }
+}
+
 /// A group of duplicate binaries
 #[derive(Debug, Clone)]
 pub struct DuplicateGroup {
@@ -170,6 +215,8 @@ pub struct DuplicateGroup {
     pub instances: Vec<BinaryInstance>,
     /// Total size of all instances
     pub total_size: u64,
+    /// Space freed by removing the non-active installs, each counted once
+    pub reclaimable_size: u64,
     /// Recommendation for handling
     pub recommendation: DuplicateRecommendation,
     /// Safety level for cleanup
@@ -211,6 +258,11 @@ impl BinaryAnalyzer {
     pub fn analyze(&self) -> Result<BinaryAnalysisResult> {
         let mut result = BinaryAnalysisResult::default();
 
+        // Discovery relies on `which` and `--version` probes
+        if !super::external_commands_enabled() {
+            return Ok(result);
+        }
+
         // Discover binaries for key commands
         // Build dynamic list including all Python versions
         let mut commands: Vec<&str> = vec![
@@ -351,6 +403,7 @@ impl BinaryAnalyzer {
         let commands: Vec<&str> = commands;
 
         result.binaries = self.discover_binaries(&commands);
+        self.measure_installs(&mut result.binaries);
 
         // Find duplicates
         result.duplicates = self.find_duplicates(&result.binaries);
@@ -361,21 +414,17 @@ impl BinaryAnalyzer {
         // Detect stale configs
         result.stale_configs = self.detect_stale_configs()?;
 
-        // Calculate potential savings
-        result.potential_savings = result
-            .duplicates
-            .iter()
-            .filter(|d| d.safety != SafetyLevel::Dangerous)
-            .map(|d| {
-                // Don't count active/system binaries
-                d.instances
-                    .iter()
-                    .filter(|i| !i.is_active && i.source != BinarySource::System)
-                    .map(|i| i.binary_size)
-                    .sum::<u64>()
-            })
-            .sum::<u64>()
-            + result.unused_managers.iter().map(|m| m.size).sum::<u64>();
+        // Calculate potential savings; python, python3 and python3.12 are
+        // often one install, so count installs across groups only once
+        let active = active_installs(&result.binaries);
+        result.potential_savings = reclaimable_size(
+            result
+                .duplicates
+                .iter()
+                .filter(|d| d.safety != SafetyLevel::Dangerous)
+                .flat_map(|d| &d.instances),
+            &active,
+        ) + result.unused_managers.iter().map(|m| m.size).sum::<u64>();
 
         Ok(result)
     }
@@ -412,6 +461,10 @@ impl BinaryAnalyzer {
         let binary_type = self.determine_binary_type(path);
         let in_path = self.is_in_current_path(path);
         let is_active = self.is_active_version(command, path);
+        let install_dir = self
+            .get_version_install_dir(&resolved_path)
+            .or_else(|| self.get_version_install_dir(path))
+            .or_else(|| self.shim_install_dir(command, path));
 
         Some(BinaryInstance {
             command: command.to_string(),
@@ -423,9 +476,49 @@ impl BinaryAnalyzer {
             binary_type,
             in_path,
             is_active,
+            install_dir,
+            install_size: None,
         })
     }
 
+    /// Measure each distinct install directory once
+    fn measure_installs(&self, binaries: &mut [BinaryInstance]) {
+        let mut sizes: HashMap<PathBuf, u64> = HashMap::new();
+        for binary in binaries {
+            let Some(dir) = &binary.install_dir else {
+                continue;
+            };
+            let size = *sizes
+                .entry(dir.clone())
+                .or_insert_with(|| calculate_dir_size(dir).map(|(size, _)| size).unwrap_or(0));
+            binary.install_size = Some(size);
+        }
+    }
+
+    /// Version a pyenv or rbenv shim currently dispatches to
+    ///
+    /// Shims are tiny scripts; the toolchain behind one is the globally
+    /// selected version (`PYENV_VERSION` or the root's `version` file).
+    fn shim_install_dir(&self, command: &str, path: &Path) -> Option<PathBuf> {
+        let shims = path.parent().filter(|p| p.ends_with("shims"))?;
+        let root = shims.parent()?;
+        let env = match root.file_name()?.to_str()? {
+            ".pyenv" => "PYENV_VERSION",
+            ".rbenv" => "RBENV_VERSION",
+            _ => return None,
+        };
+
+        let selected = std::env::var(env)
+            .ok()
+            .or_else(|| std::fs::read_to_string(root.join("version")).ok())?;
+        selected
+            .split([':', '\n'])
+            .map(str::trim)
+            .filter(|v| !v.is_empty() && *v != "system")
+            .map(|v| root.join("versions").join(v))
+            .find(|dir| dir.join("bin").join(command).exists())
+    }
+
     /// Resolve symlink chain to find actual binary
     fn resolve_symlink_chain(&self, path: &Path) -> PathBuf {
         let mut current = path.to_path_buf();
@@ -737,6 +830,7 @@ impl BinaryAnalyzer {
                 .push(binary);
         }
 
+        let active = active_installs(binaries);
         let mut duplicates = Vec::new();
 
         for (command, instances) in groups {
@@ -815,18 +909,25 @@ impl BinaryAnalyzer {
             };
 
             let total_size = instances.iter().map(|i| i.binary_size).sum();
+            let reclaimable_size = reclaimable_size(instances.iter().copied(), &active);
 
             duplicates.push(DuplicateGroup {
                 command,
                 instances: instances.into_iter().cloned().collect(),
                 total_size,
+                reclaimable_size,
                 recommendation,
                 safety,
             });
         }
 
         // Sort by size
-        duplicates.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+        duplicates.sort_by(|a, b| {
+            b.reclaimable_size
+                .cmp(&a.reclaimable_size)
+                .then_with(|| b.total_size.cmp(&a.total_size))
+                .then_with(|| a.command.cmp(&b.command))
+        });
 
         duplicates
     }
@@ -1368,6 +1469,8 @@ impl BinaryAnalyzer {
     /// Convert analysis results to cleanable items for TUI display
     pub fn to_cleanable_items(&self, result: &BinaryAnalysisResult) -> Vec<CleanableItem> {
         let mut items = Vec::new();
+        let active = active_installs(&result.binaries);
+        let mut listed: HashSet<&Path> = HashSet::new();
 
         // Add duplicate groups
         for group in &result.duplicates {
@@ -1388,35 +1491,15 @@ impl BinaryAnalyzer {
             }
 
             for instance in removable {
-                let description = match &group.recommendation {
-                    DuplicateRecommendation::ConflictingManagers { managers } => {
-                        let names: Vec<&str> = managers.iter().map(|m| m.name()).collect();
-                        format!(
-                            "Duplicate from {}. Also installed via: {}",
-                            instance.source.name(),
-                            names.join(", ")
-                        )
-                    }
-                    DuplicateRecommendation::RemoveDuplicateSource { source } => {
-                        format!("Duplicate installation from {}", source.name())
-                    }
-                    DuplicateRecommendation::RemoveOldVersions { versions } => {
-                        format!(
-                            "Old version. Newer versions available: {}",
-                            versions.join(", ")
-                        )
-                    }
-                    _ => "Duplicate binary".to_string(),
-                };
+                // One item per install, and never the install an active binary runs from
+                let path_to_clean = instance.install_key();
+                if active.contains(path_to_clean) || !listed.insert(path_to_clean) {
+                    continue;
+                }
 
-                // Get directory size for version manager installs
-                let (size, path_to_clean) =
-                    if let Some(version_dir) = self.get_version_install_dir(&instance.path) {
-                        let (dir_size, _) = calculate_dir_size(&version_dir).unwrap_or((0, 0));
-                        (dir_size, version_dir)
-                    } else {
-                        (instance.binary_size, instance.resolved_path.clone())
-                    };
+                let description = group.recommendation.explain(instance);
+                let size = instance.removal_size();
+                let path_to_clean = path_to_clean.to_path_buf();
 
                 let clean_command = self.get_clean_command(instance);
 
@@ -1452,7 +1535,7 @@ impl BinaryAnalyzer {
         items.extend(result.stale_configs.clone());
 
         // Sort by size
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by(CleanableItem::cmp_by_size);
 
         items
     }
@@ -1483,6 +1566,12 @@ impl BinaryAnalyzer {
             }
         }
 
+        // uv: ~/.local/share/uv/python/cpython-3.12.4-macos-aarch64-none/
+        if let Some((prefix, rest)) = path_str.split_once("/uv/python/") {
+            let name = rest.split('/').next().filter(|n| !n.is_empty())?;
+            return Some(PathBuf::from(format!("{}/uv/python/{}", prefix, name)));
+        }
+
         // pyenv: ~/.pyenv/versions/3.12.0/
         if path_str.contains(".pyenv/versions/") {
             let parts: Vec<&str> = path_str.split(".pyenv/versions/").collect();
@@ -1567,6 +1656,32 @@ impl BinaryAnalyzer {
     }
 }
 
+/// Installs that back an active binary and must be kept
+fn active_installs(binaries: &[BinaryInstance]) -> HashSet<&Path> {
+    binaries
+        .iter()
+        .filter(|b| b.is_active || b.source == BinarySource::System)
+        .map(BinaryInstance::install_key)
+        .collect()
+}
+
+/// Space freed by removing the installs behind `instances`
+///
+/// Each install is counted once however many commands it provides, and
+/// installs in `active` aren't counted at all.
+fn reclaimable_size<'a>(
+    instances: impl IntoIterator<Item = &'a BinaryInstance>,
+    active: &HashSet<&Path>,
+) -> u64 {
+    let mut counted: HashSet<&Path> = HashSet::new();
+    instances
+        .into_iter()
+        .filter(|i| !i.is_active && i.source != BinarySource::System)
+        .filter(|i| !active.contains(i.install_key()) && counted.insert(i.install_key()))
+        .map(BinaryInstance::removal_size)
+        .sum()
+}
+
 impl Default for BinaryAnalyzer {
     fn default() -> Self {
         Self::new().expect("BinaryAnalyzer requires home directory")
@@ -1616,6 +1731,73 @@ mod tests {
         );
     }
 
+    fn instance(command: &str, source: BinarySource, install: &str, is_active: bool) -> BinaryInstance {
+        BinaryInstance {
+            command: command.to_string(),
+            path: PathBuf::from(install).join("bin").join(command),
+            resolved_path: PathBuf::from(install).join("bin").join(command),
+            source,
+            version: None,
+            binary_size: 4_000,
+            binary_type: BinaryType::Binary,
+            in_path: true,
+            is_active,
+            install_dir: Some(PathBuf::from(install)),
+            install_size: Some(60_000_000),
+        }
+    }
+
+    #[test]
+    fn test_reclaimable_size_counts_each_install_once() {
+        let pyenv = "/home/u/.pyenv/versions/3.11.9";
+        let keg = "/opt/homebrew/Cellar/python@3.12/3.12.4";
+        let uv = "/home/u/.local/share/uv/python/cpython-3.13.0-linux-x86_64-gnu";
+        let binaries = vec![
+            instance("python3", BinarySource::Pyenv, pyenv, true),
+            // Same install as the active python3, so never reclaimable
+            instance("python3.11", BinarySource::Pyenv, pyenv, false),
+            instance("python3", BinarySource::Homebrew, keg, false),
+            instance("python3.12", BinarySource::Homebrew, keg, false),
+            instance("python3", BinarySource::Uv, uv, false),
+        ];
+
+        let active = active_installs(&binaries);
+        assert_eq!(reclaimable_size(&binaries, &active), 120_000_000);
+
+        let mut shim = instance("python3", BinarySource::Pip, "/home/u/.local/bin", false);
+        shim.install_dir = None;
+        shim.install_size = None;
+        assert_eq!(shim.removal_size(), 4_000);
+    }
+
+    #[test]
+    fn test_install_dirs() {
+        let analyzer = BinaryAnalyzer::new().unwrap();
+        assert_eq!(
+            analyzer.get_version_install_dir(Path::new(
+                "/home/u/.local/share/uv/python/cpython-3.13.0-linux-x86_64-gnu/bin/python3.13"
+            )),
+            Some(PathBuf::from("/home/u/.local/share/uv/python/cpython-3.13.0-linux-x86_64-gnu"))
+        );
+
+        if std::env::var("PYENV_VERSION").is_ok() {
+            return;
+        }
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join(".pyenv");
+        std::fs::create_dir_all(root.join("shims")).unwrap();
+        std::fs::create_dir_all(root.join("versions/3.11.9/bin")).unwrap();
+        std::fs::write(root.join("versions/3.11.9/bin/python3"), "").unwrap();
+        std::fs::write(root.join("version"), "system:3.11.9\n").unwrap();
+
+        let shim = root.join("shims/python3");
+        assert_eq!(
+            analyzer.shim_install_dir("python3", &shim),
+            Some(root.join("versions/3.11.9"))
+        );
+        assert_eq!(analyzer.shim_install_dir("python2", &shim), None);
+    }
+
     #[test]
     fn test_symlink_resolution() {
         let analyzer = BinaryAnalyzer::new().unwrap();
@@ -4,34 +4,70 @@
 //! - Dangling images
 //! - Stopped containers
 //! - Unused volumes
-//! - Build cache
+//! - Build cache, in full or only the records not used for a while
 
 use super::{CleanableItem, SafetyLevel};
-use crate::error::Result;
+use crate::error::{DevSweepError, Result};
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
+
+/// Build cache unused for this many days is offered for pruning on its own
+pub const DEFAULT_STALE_BUILD_CACHE_DAYS: u64 = 7;
 
 /// Docker cleaner
-pub struct DockerCleaner;
+pub struct DockerCleaner {
+    stale_build_cache_days: u64,
+    /// How long one `docker` query may take before it is killed
+    probe_timeout: Option<Duration>,
+}
 
 impl DockerCleaner {
     /// Create a new Docker cleaner
     pub fn new() -> Self {
-        Self
+        Self {
+            stale_build_cache_days: DEFAULT_STALE_BUILD_CACHE_DAYS,
+            probe_timeout: super::command_timeout(),
+        }
+    }
+
+    /// Kill a `docker` query that takes longer than `timeout`, e.g. on a wedged daemon
+    pub fn with_probe_timeout(mut self, timeout: Duration) -> Self {
+        self.probe_timeout = Some(timeout);
+        self
+    }
+
+    /// Run `docker <args>`, returning its stdout or `None` if it failed
+    ///
+    /// A timeout is an error, so a hung daemon isn't mistaken for an empty one.
+    fn probe(&self, args: &[&str]) -> Result<Option<String>> {
+        let mut command = Command::new("docker");
+        command.args(args);
+        match super::command_output(command, &format!("docker {}", args.join(" ")), self.probe_timeout) {
+            Ok(stdout) => Ok(Some(stdout)),
+            Err(e @ DevSweepError::CommandTimeout { .. }) => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Offer build cache unused for `days` days as its own item
+    pub fn with_stale_build_cache_days(mut self, days: u64) -> Self {
+        self.stale_build_cache_days = days.max(1);
+        self
     }
 
-    /// Check if Docker is available
+    /// Check if Docker is available (and allowed to be run)
     pub fn is_available(&self) -> bool {
-        Command::new("docker")
-            .arg("info")
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+        if !super::docker_enabled() {
+            return false;
+        }
+
+        self.probe(&["info"]).is_ok_and(|out| out.is_some())
     }
 
     /// Detect all Docker cleanable items
     pub fn detect(&self) -> Result<Vec<CleanableItem>> {
-        if !self.is_available() {
+        if !super::docker_enabled() || self.probe(&["info"])?.is_none() {
             return Ok(vec![]);
         }
 
@@ -53,21 +89,17 @@ impl DockerCleaner {
 
         // Build cache
         items.extend(self.detect_build_cache()?);
+        items.extend(self.detect_stale_build_cache()?);
 
         Ok(items)
     }
 
     /// Get Docker disk usage summary
     fn get_disk_usage(&self) -> Result<Vec<CleanableItem>> {
-        let output = Command::new("docker")
-            .args(["system", "df", "--format", "{{.Type}}\t{{.Size}}\t{{.Reclaimable}}"])
-            .output()?;
-
-        if !output.status.success() {
+        let Some(stdout) = self.probe(&["system", "df", "--format", "{{.Type}}\t{{.Size}}\t{{.Reclaimable}}"])? else {
             return Ok(vec![]);
-        }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
         let mut items = Vec::new();
 
         for line in stdout.lines() {
@@ -114,15 +146,10 @@ impl DockerCleaner {
 
     /// Detect dangling images
     fn detect_dangling_images(&self) -> Result<Vec<CleanableItem>> {
-        let output = Command::new("docker")
-            .args(["images", "-f", "dangling=true", "--format", "{{.ID}}\t{{.Size}}\t{{.CreatedAt}}"])
-            .output()?;
-
-        if !output.status.success() {
+        let Some(stdout) = self.probe(&["images", "-f", "dangling=true", "--format", "{{.ID}}\t{{.Size}}\t{{.CreatedAt}}"])? else {
             return Ok(vec![]);
-        }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
         let mut items = Vec::new();
 
         for line in stdout.lines() {
@@ -154,15 +181,10 @@ impl DockerCleaner {
 
     /// Detect stopped containers
     fn detect_stopped_containers(&self) -> Result<Vec<CleanableItem>> {
-        let output = Command::new("docker")
-            .args(["ps", "-a", "-f", "status=exited", "--format", "{{.ID}}\t{{.Names}}\t{{.Size}}\t{{.CreatedAt}}"])
-            .output()?;
-
-        if !output.status.success() {
+        let Some(stdout) = self.probe(&["ps", "-a", "-f", "status=exited", "--format", "{{.ID}}\t{{.Names}}\t{{.Size}}\t{{.CreatedAt}}"])? else {
             return Ok(vec![]);
-        }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
         let mut items = Vec::new();
 
         for line in stdout.lines() {
@@ -203,15 +225,10 @@ impl DockerCleaner {
     /// Detect unused volumes
     fn detect_unused_volumes(&self) -> Result<Vec<CleanableItem>> {
         // Get dangling volumes
-        let output = Command::new("docker")
-            .args(["volume", "ls", "-f", "dangling=true", "--format", "{{.Name}}"])
-            .output()?;
-
-        if !output.status.success() {
+        let Some(stdout) = self.probe(&["volume", "ls", "-f", "dangling=true", "--format", "{{.Name}}"])? else {
             return Ok(vec![]);
-        }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
         let mut items = Vec::new();
 
         for line in stdout.lines() {
@@ -221,13 +238,9 @@ impl DockerCleaner {
             }
 
             // Get volume size
-            let inspect = Command::new("docker")
-                .args(["system", "df", "-v", "--format", "{{.Name}}\t{{.Size}}"])
-                .output()
-                .ok();
+            let inspect = self.probe(&["system", "df", "-v", "--format", "{{.Name}}\t{{.Size}}"])?;
 
-            let size = inspect.and_then(|o| {
-                let out = String::from_utf8_lossy(&o.stdout);
+            let size = inspect.and_then(|out| {
                 out.lines()
                     .find(|l| l.starts_with(name))
                     .and_then(|l| l.split('\t').nth(1))
@@ -254,15 +267,10 @@ impl DockerCleaner {
 
     /// Detect build cache
     fn detect_build_cache(&self) -> Result<Vec<CleanableItem>> {
-        let output = Command::new("docker")
-            .args(["builder", "du", "--format", "{{.ID}}\t{{.Size}}\t{{.LastUsedAt}}"])
-            .output()?;
-
-        if !output.status.success() {
+        let Some(stdout) = self.probe(&["builder", "du", "--format", "{{.ID}}\t{{.Size}}\t{{.LastUsedAt}}"])? else {
             return Ok(vec![]);
-        }
+        };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
         let mut total_size = 0u64;
         let mut count = 0usize;
 
@@ -293,6 +301,48 @@ impl DockerCleaner {
         }
     }
 
+    /// Detect build cache records not used within `stale_build_cache_days`
+    ///
+    /// Pruned with an `until` filter, so recent layers that speed up active
+    /// builds survive.
+    fn detect_stale_build_cache(&self) -> Result<Vec<CleanableItem>> {
+        let Some(stdout) = self.probe(&["buildx", "du", "--verbose"])? else {
+            return Ok(vec![]);
+        };
+
+        let min_age = Duration::from_secs(self.stale_build_cache_days * 24 * 3600);
+        let stale: Vec<_> = parse_buildx_du(&stdout)
+            .into_iter()
+            .filter(|r| r.reclaimable && r.last_used.is_some_and(|age| age >= min_age))
+            .collect();
+        let size: u64 = stale.iter().map(|r| r.size).sum();
+
+        if size == 0 {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![CleanableItem {
+            name: format!(
+                "Build Cache unused for {}+ days ({} records)",
+                self.stale_build_cache_days,
+                stale.len()
+            ),
+            category: "Docker".to_string(),
+            subcategory: "Stale Build Cache".to_string(),
+            icon: "🔨",
+            path: PathBuf::from("/var/lib/docker/buildkit"),
+            size,
+            file_count: Some(stale.len() as u64),
+            last_modified: None,
+            description: "Build cache records not used recently. Recent layers are kept.",
+            safe_to_delete: SafetyLevel::Safe,
+            clean_command: Some(format!(
+                "docker builder prune -af --filter until={}h",
+                self.stale_build_cache_days * 24
+            )),
+        }])
+    }
+
     /// Clean all Docker resources
     pub fn clean_all(&self, include_volumes: bool) -> Result<u64> {
         let args = if include_volumes {
@@ -329,6 +379,68 @@ impl Default for DockerCleaner {
     }
 }
 
+/// One record from `docker buildx du --verbose`
+#[derive(Debug, Clone, PartialEq)]
+struct BuildCacheRecord {
+    size: u64,
+    reclaimable: bool,
+    /// Time since last use; `None` when Docker doesn't report it
+    last_used: Option<Duration>,
+}
+
+/// Parse the blank-line separated `Key: value` blocks of `docker buildx du --verbose`
+fn parse_buildx_du(output: &str) -> Vec<BuildCacheRecord> {
+    let mut records = Vec::new();
+    let mut current: Option<BuildCacheRecord> = None;
+
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            // Blank line or the summary footer ends a record
+            records.extend(current.take());
+            continue;
+        };
+        let value = value.trim();
+        let record = current.get_or_insert(BuildCacheRecord {
+            size: 0,
+            reclaimable: false,
+            last_used: None,
+        });
+        match key.trim() {
+            "Size" => record.size = parse_docker_size(value),
+            "Reclaimable" => record.reclaimable = value == "true",
+            "Last used" => record.last_used = parse_docker_age(value),
+            _ => {}
+        }
+    }
+    records.extend(current);
+
+    // "Reclaimable:" and "Total:" footer lines aren't records
+    records.retain(|r| r.reclaimable || r.last_used.is_some());
+    records
+}
+
+/// Parse Docker's human durations like "3 days ago" or "About an hour ago"
+fn parse_docker_age(s: &str) -> Option<Duration> {
+    let s = s.trim().strip_suffix(" ago")?;
+    let (count, unit) = match s.split_once(' ')? {
+        ("About", rest) => (1, rest.trim_start_matches("a ").trim_start_matches("an ")),
+        ("Less", _) => return Some(Duration::ZERO),
+        (count, unit) => (count.parse().ok()?, unit),
+    };
+
+    let secs: u64 = match unit.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3600,
+        "day" => 86_400,
+        "week" => 7 * 86_400,
+        "month" => 30 * 86_400,
+        "year" => 365 * 86_400,
+        _ => return None,
+    };
+    Some(Duration::from_secs(count * secs))
+}
+
 /// Parse Docker size strings like "1.5GB", "234MB", "567kB"
 fn parse_docker_size(s: &str) -> u64 {
     let s = s.trim();
@@ -364,6 +476,53 @@ mod tests {
         assert_eq!(parse_docker_size("100B"), 100);
     }
 
+    #[test]
+    fn test_parse_docker_age() {
+        assert_eq!(parse_docker_age("3 days ago"), Some(Duration::from_secs(3 * 86_400)));
+        assert_eq!(parse_docker_age("About an hour ago"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_docker_age("About a minute ago"), Some(Duration::from_secs(60)));
+        assert_eq!(parse_docker_age("2 weeks ago"), Some(Duration::from_secs(14 * 86_400)));
+        assert_eq!(parse_docker_age("Less than a second ago"), Some(Duration::ZERO));
+        assert_eq!(parse_docker_age("<nil>"), None);
+    }
+
+    #[test]
+    fn test_parse_buildx_du() {
+        let output = "\
+ID:\t\tk2ap2u1gq2v8ojdj4xsn7mntp
+Created at:\t2024-03-01 10:52:14.541624 +0000 UTC
+Mutable:\tfalse
+Reclaimable:\ttrue
+Shared:\t\tfalse
+Size:\t\t1.5GB
+Description:\t[stage-1 2/4] RUN npm ci
+Usage count:\t4
+Last used:\t2 weeks ago
+Type:\t\tregular
+
+ID:\t\tr0jq5x9xbbkdfjxx4n1hvzzaa
+Mutable:\ttrue
+Reclaimable:\ttrue
+Size:\t\t234MB
+Last used:\tAbout an hour ago
+
+ID:\t\tsh4r3d
+Reclaimable:\tfalse
+Size:\t\t1GB
+Last used:\t3 months ago
+
+Reclaimable:\t1.7GB
+Total:\t\t2.7GB
+";
+        let records = parse_buildx_du(output);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].size, parse_docker_size("1.5GB"));
+        assert!(records[0].reclaimable);
+        assert_eq!(records[0].last_used, Some(Duration::from_secs(14 * 86_400)));
+        assert_eq!(records[1].last_used, Some(Duration::from_secs(3600)));
+        assert!(!records[2].reclaimable);
+    }
+
     #[test]
     fn test_docker_cleaner() {
         let cleaner = DockerCleaner::new();
@@ -5,9 +5,10 @@
 //! - Unreal Engine (Intermediate, Saved, DerivedDataCache)
 //! - Godot (cache, .import)
 
-use super::{calculate_dir_size, CleanableItem, SafetyLevel};
+use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 /// Game Development cleaner
 pub struct GameDevCleaner {
@@ -301,47 +302,70 @@ impl GameDevCleaner {
         Ok(items)
     }
 
-    /// Scan for Unreal project folders and their cleanable directories
-    pub fn scan_unreal_projects(&self, search_path: &PathBuf) -> Result<Vec<CleanableItem>> {
+    /// Find Unreal projects under `roots` and itemize their generated folders
+    pub fn detect_unreal_projects(&self, roots: &[PathBuf], max_depth: Option<usize>) -> Result<Vec<CleanableItem>> {
         let mut items = Vec::new();
+        for root in roots {
+            for project in find_unreal_projects(root, max_depth.unwrap_or(UNREAL_SEARCH_DEPTH)) {
+                items.extend(self.scan_unreal_project(&project, UNREAL_MIN_SIZE)?);
+            }
+        }
+        Ok(items)
+    }
 
-        // Unreal projects have: Intermediate/, Saved/, DerivedDataCache/, Binaries/
-        let unreal_cleanable = ["Intermediate", "Saved", "DerivedDataCache", "Binaries"];
-
-        for dir_name in unreal_cleanable {
-            let path = search_path.join(dir_name);
-            if !path.exists() {
+    /// Itemize one Unreal project's generated folders of at least `min_size` bytes
+    pub fn scan_unreal_project(&self, project: &Path, min_size: u64) -> Result<Vec<CleanableItem>> {
+        let mut items = Vec::new();
+        let name = unreal_project_name(project).unwrap_or_else(|| {
+            project.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        });
+        // Without C++ sources, Binaries can't be rebuilt from this checkout
+        let has_source = project.join("Source").is_dir();
+
+        for dir_name in UNREAL_DIRS {
+            let path = project.join(dir_name);
+            if !path.is_dir() {
                 continue;
             }
 
             let (size, file_count) = calculate_dir_size(&path)?;
-            if size < 100_000_000 {
+            if size < min_size {
                 continue;
             }
 
-            let safety = match dir_name {
-                "Intermediate" | "DerivedDataCache" => SafetyLevel::SafeWithCost,
-                "Saved" => SafetyLevel::Caution, // May contain saves
-                "Binaries" => SafetyLevel::SafeWithCost,
-                _ => SafetyLevel::Safe,
+            let (safety, description) = match dir_name {
+                "DerivedDataCache" => (
+                    SafetyLevel::Safe,
+                    "Shader and asset cache. Rebuilt when the editor opens the project.",
+                ),
+                "Intermediate" => (
+                    SafetyLevel::SafeWithCost,
+                    "Build intermediates and generated project files. Rebuilt on the next build.",
+                ),
+                "Binaries" if has_source => (
+                    SafetyLevel::SafeWithCost,
+                    "Compiled editor and game modules. Rebuilt from Source/.",
+                ),
+                "Binaries" => (
+                    SafetyLevel::Caution,
+                    "Compiled modules, but the project has no Source/ to rebuild them from.",
+                ),
+                _ => (
+                    SafetyLevel::Caution,
+                    "Logs, crash dumps, autosaves and local config. Check before deleting.",
+                ),
             };
 
             items.push(CleanableItem {
-                name: format!("Unreal {}", dir_name),
+                name: format!("Unreal {} {}", name, dir_name),
                 category: "Game Development".to_string(),
                 subcategory: "Unreal Project".to_string(),
                 icon: "🎯",
+                last_modified: get_mtime(&path),
                 path,
                 size,
                 file_count: Some(file_count),
-                last_modified: None,
-                description: match dir_name {
-                    "Intermediate" => "Build intermediate files. Will be rebuilt.",
-                    "DerivedDataCache" => "Shader and asset cache. Will be rebuilt.",
-                    "Saved" => "Saved data including autosaves. Check before deleting.",
-                    "Binaries" => "Compiled binaries. Will be rebuilt.",
-                    _ => "Unreal project files.",
-                },
+                description,
                 safe_to_delete: safety,
                 clean_command: None,
             });
@@ -351,6 +375,54 @@ impl GameDevCleaner {
     }
 }
 
+/// Generated folders of an Unreal project, safest first
+const UNREAL_DIRS: [&str; 4] = ["DerivedDataCache", "Intermediate", "Binaries", "Saved"];
+
+/// Folders smaller than this aren't worth listing
+const UNREAL_MIN_SIZE: u64 = 100_000_000;
+
+/// How deep to look for `*.uproject` files without `--max-depth`
+const UNREAL_SEARCH_DEPTH: usize = 5;
+
+/// Directories holding a `*.uproject` file, at most `max_depth` below `root`
+pub fn find_unreal_projects(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut projects = Vec::new();
+    let walker = WalkDir::new(root)
+        .max_depth(max_depth)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            // Don't descend into generated folders or other heavy trees
+            e.depth() == 0
+                || !(UNREAL_DIRS.contains(&name.as_ref())
+                    || name.starts_with('.')
+                    || name == "node_modules"
+                    || name == "Content")
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "uproject") {
+            if let Some(dir) = entry.path().parent() {
+                if !projects.iter().any(|p: &PathBuf| p == dir) {
+                    projects.push(dir.to_path_buf());
+                }
+            }
+        }
+    }
+    projects
+}
+
+/// Project name from the `*.uproject` file in `project`
+fn unreal_project_name(project: &Path) -> Option<String> {
+    std::fs::read_dir(project)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .find(|p| p.extension().is_some_and(|ext| ext == "uproject"))
+        .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+}
+
 impl Default for GameDevCleaner {
     fn default() -> Self {
         Self::new().expect("GameDevCleaner requires home directory")
@@ -377,4 +449,49 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_unreal_project_dirs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let project = temp.path().join("games/ShooterGame");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join("ShooterGame.uproject"),
+            r#"{"FileVersion": 3, "EngineAssociation": "5.3", "Modules": []}"#,
+        )
+        .unwrap();
+        for dir in ["DerivedDataCache", "Intermediate/Build", "Binaries/Linux", "Saved/Logs", "Content"] {
+            std::fs::create_dir_all(project.join(dir)).unwrap();
+            std::fs::write(project.join(dir).join("data.bin"), vec![0u8; 1024]).unwrap();
+        }
+        // A copied .uproject inside a generated folder isn't another project
+        std::fs::write(project.join("Saved/Old.uproject"), "{}").unwrap();
+
+        assert_eq!(find_unreal_projects(temp.path(), 5), vec![project.clone()]);
+        assert!(find_unreal_projects(temp.path(), 1).is_empty());
+
+        let cleaner = GameDevCleaner::new().unwrap();
+        let items = cleaner.scan_unreal_project(&project, 0).unwrap();
+        let safety = |dir: &str| {
+            items
+                .iter()
+                .find(|i| i.path == project.join(dir))
+                .map(|i| i.safe_to_delete)
+        };
+        assert_eq!(items.len(), 4);
+        assert!(items[0].name.starts_with("Unreal ShooterGame "));
+        assert_eq!(safety("DerivedDataCache"), Some(SafetyLevel::Safe));
+        assert_eq!(safety("Intermediate"), Some(SafetyLevel::SafeWithCost));
+        assert_eq!(safety("Saved"), Some(SafetyLevel::Caution));
+        // No Source/, so the binaries can't be rebuilt
+        assert_eq!(safety("Binaries"), Some(SafetyLevel::Caution));
+
+        std::fs::create_dir(project.join("Source")).unwrap();
+        let items = cleaner.scan_unreal_project(&project, 0).unwrap();
+        let binaries = items.iter().find(|i| i.path == project.join("Binaries")).unwrap();
+        assert_eq!(binaries.safe_to_delete, SafetyLevel::SafeWithCost);
+
+        // Below the size cap nothing is listed
+        assert!(cleaner.scan_unreal_project(&project, 1_000_000).unwrap().is_empty());
+    }
 }
@@ -7,7 +7,7 @@
 
 use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Homebrew cleaner
@@ -15,6 +15,8 @@ pub struct HomebrewCleaner {
     #[allow(dead_code)]
     home: PathBuf,
     cache_path: PathBuf,
+    /// Preview `brew cleanup -s` instead of plain `brew cleanup`
+    scrub: bool,
 }
 
 impl HomebrewCleaner {
@@ -25,11 +27,25 @@ impl HomebrewCleaner {
         // Homebrew cache location
         let cache_path = home.join("Library/Caches/Homebrew");
 
-        Some(Self { home, cache_path })
+        Some(Self {
+            home,
+            cache_path,
+            scrub: false,
+        })
     }
 
-    /// Check if Homebrew is installed
+    /// Match what `clean_all(scrub)` will remove when detecting old versions
+    pub fn with_scrub(mut self, scrub: bool) -> Self {
+        self.scrub = scrub;
+        self
+    }
+
+    /// Check if Homebrew is installed (and allowed to be run)
     pub fn is_available(&self) -> bool {
+        if !super::external_commands_enabled() {
+            return false;
+        }
+
         Command::new("brew")
             .arg("--version")
             .output()
@@ -39,6 +55,10 @@ impl HomebrewCleaner {
 
     /// Detect all Homebrew cleanable items
     pub fn detect(&self) -> Result<Vec<CleanableItem>> {
+        if !super::external_commands_enabled() {
+            return Ok(vec![]);
+        }
+
         let mut items = Vec::new();
 
         // Main cache directory
@@ -136,9 +156,67 @@ impl HomebrewCleaner {
         Ok(items)
     }
 
-    /// Detect old formula versions
+    /// Detect old formula versions, one item per outdated version
     fn detect_old_versions(&self) -> Result<Vec<CleanableItem>> {
+        // Ask brew what it would remove; fall back to inspecting the Cellar
+        let old_versions = self
+            .cleanup_dry_run()
+            .unwrap_or_else(|| self.scan_cellar_old_versions());
+
         let mut items = Vec::new();
+        for version_path in old_versions {
+            let Some((formula, version)) = formula_and_version(&version_path) else {
+                continue;
+            };
+
+            // One unreadable version shouldn't hide the others
+            let Ok((size, file_count)) = calculate_dir_size(&version_path) else {
+                continue;
+            };
+            if size < 10_000_000 {
+                continue;
+            }
+
+            items.push(CleanableItem {
+                name: format!("Old version: {} {}", formula, version),
+                category: "Package Manager".to_string(),
+                subcategory: "Homebrew".to_string(),
+                icon: "🍺",
+                last_modified: get_mtime(&version_path),
+                path: version_path,
+                size,
+                file_count: Some(file_count),
+                description: "Outdated formula version. Use 'brew cleanup' to remove.",
+                safe_to_delete: SafetyLevel::Safe,
+                clean_command: Some(format!("brew cleanup {}", formula)),
+            });
+        }
+
+        // Keep versions of the same formula next to each other
+        items.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(items)
+    }
+
+    /// Old Cellar versions reported by `brew cleanup --dry-run`
+    fn cleanup_dry_run(&self) -> Option<Vec<PathBuf>> {
+        let mut cmd = Command::new("brew");
+        cmd.args(["cleanup", "--dry-run"]);
+        if self.scrub {
+            cmd.arg("-s");
+        }
+
+        let output = cmd.output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(parse_cleanup_dry_run(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Every version but the newest of each formula in the Cellar
+    fn scan_cellar_old_versions(&self) -> Vec<PathBuf> {
+        let mut old = Vec::new();
 
         // Check Cellar for old versions
         let cellar_paths = [
@@ -147,69 +225,37 @@ impl HomebrewCleaner {
         ];
 
         for cellar in cellar_paths {
-            if !cellar.exists() {
+            let Ok(formulas) = std::fs::read_dir(&cellar) else {
                 continue;
-            }
-
-            if let Ok(formulas) = std::fs::read_dir(&cellar) {
-                for formula in formulas.filter_map(|e| e.ok()) {
-                    let formula_path = formula.path();
-                    if !formula_path.is_dir() {
-                        continue;
-                    }
+            };
 
-                    // Count versions
-                    let versions: Vec<_> = std::fs::read_dir(&formula_path)
-                        .ok()
-                        .map(|entries| entries.filter_map(|e| e.ok()).collect())
-                        .unwrap_or_default();
+            for formula in formulas.filter_map(|e| e.ok()) {
+                let formula_path = formula.path();
+                if !formula_path.is_dir() {
+                    continue;
+                }
 
-                    if versions.len() <= 1 {
-                        continue;
-                    }
+                let mut versions: Vec<_> = std::fs::read_dir(&formula_path)
+                    .ok()
+                    .map(|entries| entries.filter_map(|e| e.ok()).collect())
+                    .unwrap_or_default();
 
-                    // Calculate size of old versions (all but the latest)
-                    let mut old_versions: Vec<_> = versions;
-                    old_versions.sort_by(|a, b| {
-                        let a_time = a.metadata().and_then(|m| m.modified()).ok();
-                        let b_time = b.metadata().and_then(|m| m.modified()).ok();
-                        b_time.cmp(&a_time)
-                    });
-
-                    // Skip the newest, sum the rest
-                    let mut total_size = 0u64;
-                    let mut total_files = 0u64;
-                    for old_version in old_versions.iter().skip(1) {
-                        if let Ok((size, count)) = calculate_dir_size(&old_version.path()) {
-                            total_size += size;
-                            total_files += count;
-                        }
-                    }
+                if versions.len() <= 1 {
+                    continue;
+                }
 
-                    if total_size > 50_000_000 {
-                        let formula_name = formula_path.file_name()
-                            .map(|n| n.to_string_lossy().to_string())
-                            .unwrap_or_default();
+                // Newest first, then keep everything after it
+                versions.sort_by(|a, b| {
+                    let a_time = a.metadata().and_then(|m| m.modified()).ok();
+                    let b_time = b.metadata().and_then(|m| m.modified()).ok();
+                    b_time.cmp(&a_time)
+                });
 
-                        items.push(CleanableItem {
-                            name: format!("Old versions: {}", formula_name),
-                            category: "Package Manager".to_string(),
-                            subcategory: "Homebrew".to_string(),
-                            icon: "🍺",
-                            path: formula_path,
-                            size: total_size,
-                            file_count: Some(total_files),
-                            last_modified: None,
-                            description: "Old formula versions. Use 'brew cleanup' to remove.",
-                            safe_to_delete: SafetyLevel::Safe,
-                            clean_command: Some(format!("brew cleanup {}", formula_name)),
-                        });
-                    }
-                }
+                old.extend(versions.iter().skip(1).map(|v| v.path()));
             }
         }
 
-        Ok(items)
+        old
     }
 
     /// Run brew cleanup command
@@ -237,6 +283,40 @@ impl HomebrewCleaner {
     }
 }
 
+/// Extract old Cellar version directories from `brew cleanup --dry-run` output.
+///
+/// Lines look like `Would remove: /opt/homebrew/Cellar/openssl@3/3.1.0 (6,470 files, 28.3MB)`.
+/// Cached downloads are reported too, but those are covered by the cache items.
+fn parse_cleanup_dry_run(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Would remove: "))
+        .map(|rest| {
+            // Drop the trailing "(N files, SIZE)" summary
+            match rest.rfind(" (") {
+                Some(idx) => &rest[..idx],
+                None => rest,
+            }
+        })
+        .map(PathBuf::from)
+        .filter(|path| formula_and_version(path).is_some())
+        .collect()
+}
+
+/// Split `.../Cellar/<formula>/<version>` into its formula and version
+fn formula_and_version(path: &Path) -> Option<(String, String)> {
+    let version = path.file_name()?.to_string_lossy().to_string();
+    let formula_dir = path.parent()?;
+    let cellar = formula_dir.parent()?;
+
+    if cellar.file_name()? != "Cellar" {
+        return None;
+    }
+
+    let formula = formula_dir.file_name()?.to_string_lossy().to_string();
+    Some((formula, version))
+}
+
 impl Default for HomebrewCleaner {
     fn default() -> Self {
         Self::new().expect("HomebrewCleaner requires home directory")
@@ -253,6 +333,29 @@ mod tests {
         assert!(cleaner.is_some());
     }
 
+    #[test]
+    fn test_parse_cleanup_dry_run() {
+        let output = "\
+Would remove: /opt/homebrew/Cellar/openssl@3/3.1.0 (6,470 files, 28.3MB)
+Would remove: /opt/homebrew/Cellar/node/20.1.0 (2,012 files, 61.2MB)
+Would remove: /Users/me/Library/Caches/Homebrew/node--20.1.0.bottle.tar.gz (14.1MB)
+==> This operation would free approximately 103.6MB of disk space.
+";
+        let paths = parse_cleanup_dry_run(output);
+
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/opt/homebrew/Cellar/openssl@3/3.1.0"),
+                PathBuf::from("/opt/homebrew/Cellar/node/20.1.0"),
+            ]
+        );
+        assert_eq!(
+            formula_and_version(&paths[0]),
+            Some(("openssl@3".to_string(), "3.1.0".to_string()))
+        );
+    }
+
     #[test]
     fn test_homebrew_detection() {
         if let Some(cleaner) = HomebrewCleaner::new() {
@@ -10,6 +10,10 @@ use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
 use std::collections::HashSet;
 use std::path::PathBuf;
+#[cfg(target_os = "macos")]
+use std::{path::Path, process::Command, time::SystemTime};
+#[cfg(target_os = "macos")]
+use walkdir::WalkDir;
 
 /// macOS system cleaner
 #[cfg(target_os = "macos")]
@@ -44,39 +48,46 @@ impl MacOsCleaner {
         Ok(items)
     }
 
-    /// Get list of installed applications
+    /// Lowercased names and bundle identifiers of installed applications
+    ///
+    /// Looks in the usual application folders (and one level of subfolders),
+    /// then asks Spotlight for apps installed anywhere else.
     fn get_installed_apps(&self) -> HashSet<String> {
         let mut apps = HashSet::new();
 
         let app_dirs = [
             PathBuf::from("/Applications"),
+            PathBuf::from("/System/Applications"),
             self.home.join("Applications"),
         ];
 
-        for app_dir in app_dirs {
-            if let Ok(entries) = std::fs::read_dir(&app_dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    if name.ends_with(".app") {
-                        let app_name = name.trim_end_matches(".app").to_lowercase();
-                        apps.insert(app_name);
-
-                        // Also extract bundle identifier if possible
-                        let plist_path = entry.path().join("Contents/Info.plist");
-                        if let Ok(content) = std::fs::read_to_string(&plist_path) {
-                            // Simple extraction of bundle identifier
-                            if let Some(start) = content.find("<key>CFBundleIdentifier</key>") {
-                                if let Some(value_start) = content[start..].find("<string>") {
-                                    let rest = &content[start + value_start + 8..];
-                                    if let Some(value_end) = rest.find("</string>") {
-                                        let bundle_id = rest[..value_end].to_lowercase();
-                                        apps.insert(bundle_id);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        let mut bundles: Vec<PathBuf> = app_dirs
+            .iter()
+            .flat_map(|dir| {
+                WalkDir::new(dir)
+                    .max_depth(2)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.into_path())
+                    .filter(|p| is_app_bundle(p))
+            })
+            .collect();
+
+        if super::external_commands_enabled() {
+            if let Ok(output) = Command::new("mdfind")
+                .arg("kMDItemContentType == 'com.apple.application-bundle'")
+                .output()
+            {
+                bundles.extend(String::from_utf8_lossy(&output.stdout).lines().map(PathBuf::from));
+            }
+        }
+
+        for bundle in bundles {
+            if let Some(name) = bundle.file_stem() {
+                apps.insert(name.to_string_lossy().to_lowercase());
+            }
+            if let Some(id) = bundle_identifier(&bundle) {
+                apps.insert(id.to_lowercase());
             }
         }
 
@@ -84,6 +95,9 @@ impl MacOsCleaner {
     }
 
     /// Detect orphaned containers (sandboxed app data for uninstalled apps)
+    ///
+    /// Containers are named after the app's bundle identifier, so they are
+    /// matched exactly rather than by name.
     fn detect_orphaned_containers(&self) -> Result<Vec<CleanableItem>> {
         let mut items = Vec::new();
 
@@ -110,33 +124,24 @@ impl MacOsCleaner {
                     continue;
                 }
 
-                // Check if this container belongs to an installed app
-                let is_orphaned = !installed_apps.iter().any(|app| {
-                    container_name.to_lowercase().contains(app)
-                });
-
-                if !is_orphaned {
+                if is_bundle_installed(&container_name, &installed_apps) {
                     continue;
                 }
 
                 let (size, file_count) = calculate_dir_size(&path)?;
-                if size < 50_000_000 {
+                if size < MIN_ORPHAN_SIZE {
                     continue;
                 }
 
-                items.push(CleanableItem {
-                    name: format!("Orphaned: {}", container_name),
-                    category: "macOS System".to_string(),
-                    subcategory: "Containers".to_string(),
-                    icon: "📦",
+                items.push(orphan_item(
+                    format!("Orphaned: {}", container_name),
+                    "Containers",
+                    "📦",
                     path,
                     size,
-                    file_count: Some(file_count),
-                    last_modified: get_mtime(&entry.path()),
-                    description: "Container data for possibly uninstalled app. Verify before deleting.",
-                    safe_to_delete: SafetyLevel::Caution,
-                    clean_command: None,
-                });
+                    file_count,
+                    "Container data for an app that is no longer installed. It may hold documents; verify before deleting.",
+                ));
             }
         }
 
@@ -259,33 +264,36 @@ impl MacOsCleaner {
                     continue;
                 }
 
-                // Check if app is installed
-                let is_orphaned = !installed_apps.iter().any(|app| {
-                    name.to_lowercase().contains(app) || app.contains(&name.to_lowercase())
-                });
+                // Bundle identifiers are exact; plain folder names only
+                // loosely match an app's name, so those need to be big
+                let (is_orphaned, min_size) = if looks_like_bundle_id(&name) {
+                    (!is_bundle_installed(&name, &installed_apps), MIN_ORPHAN_SIZE)
+                } else {
+                    let lower = name.to_lowercase();
+                    let installed = installed_apps
+                        .iter()
+                        .any(|app| lower.contains(app) || app.contains(&lower));
+                    (!installed, 100_000_000)
+                };
 
                 if !is_orphaned {
                     continue;
                 }
 
                 let (size, file_count) = calculate_dir_size(&path)?;
-                if size < 100_000_000 {
+                if size < min_size {
                     continue;
                 }
 
-                items.push(CleanableItem {
-                    name: format!("App Support: {}", name),
-                    category: "macOS System".to_string(),
-                    subcategory: "Application Support".to_string(),
-                    icon: "📁",
+                items.push(orphan_item(
+                    format!("App Support: {}", name),
+                    "Application Support",
+                    "📁",
                     path,
                     size,
-                    file_count: Some(file_count),
-                    last_modified: get_mtime(&entry.path()),
-                    description: "Application data for possibly uninstalled app.",
-                    safe_to_delete: SafetyLevel::Caution,
-                    clean_command: None,
-                });
+                    file_count,
+                    "Application data for possibly uninstalled app.",
+                ));
             }
         }
 
@@ -331,6 +339,94 @@ impl MacOsCleaner {
     }
 }
 
+/// Orphans found by bundle identifier are reported from this size up
+#[cfg(target_os = "macos")]
+const MIN_ORPHAN_SIZE: u64 = 1_000_000;
+
+/// An orphaned app's leftover directory, labeled with when it was last used
+#[cfg(target_os = "macos")]
+fn orphan_item(
+    name: String,
+    subcategory: &str,
+    icon: &'static str,
+    path: PathBuf,
+    size: u64,
+    file_count: u64,
+    description: &'static str,
+) -> CleanableItem {
+    let mut item = CleanableItem {
+        name,
+        category: "macOS System".to_string(),
+        subcategory: subcategory.to_string(),
+        icon,
+        last_modified: last_used(&path),
+        path,
+        size,
+        file_count: Some(file_count),
+        description,
+        safe_to_delete: SafetyLevel::Caution,
+        clean_command: None,
+    };
+    item.name = format!("{} (last used {})", item.name, item.last_used_display());
+    item
+}
+
+/// Whether `path` is an `.app` bundle
+#[cfg(target_os = "macos")]
+fn is_app_bundle(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "app") && path.is_dir()
+}
+
+/// `CFBundleIdentifier` from an app's `Info.plist` (XML or binary)
+#[cfg(target_os = "macos")]
+fn bundle_identifier(app: &Path) -> Option<String> {
+    let info = plist::Value::from_file(app.join("Contents/Info.plist")).ok()?;
+    info.as_dictionary()?
+        .get("CFBundleIdentifier")?
+        .as_string()
+        .map(str::to_string)
+}
+
+/// Most recent modification anywhere inside `path`
+#[cfg(target_os = "macos")]
+fn last_used(path: &Path) -> Option<SystemTime> {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()
+        .or_else(|| get_mtime(path))
+}
+
+/// Whether a directory name is a reverse-DNS bundle identifier (`com.vendor.App`)
+#[cfg(any(target_os = "macos", test))]
+fn looks_like_bundle_id(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() >= 3
+        && parts.iter().all(|p| !p.is_empty() && !p.contains(' '))
+        && parts[0].chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Whether `id` belongs to an installed app
+///
+/// Extensions and helpers are named after their app (`com.vendor.App.Share`),
+/// so a prefix match on a whole component counts too.
+#[cfg(any(target_os = "macos", test))]
+fn is_bundle_installed(id: &str, installed: &HashSet<String>) -> bool {
+    let id = id.to_lowercase();
+    let mut prefix = id.as_str();
+    loop {
+        if installed.contains(prefix) {
+            return true;
+        }
+        // Stop at the vendor (`com.vendor`), which other apps share
+        match prefix.rfind('.').filter(|&dot| prefix[..dot].matches('.').count() >= 2) {
+            Some(dot) => prefix = &prefix[..dot],
+            None => return false,
+        }
+    }
+}
+
 #[cfg(target_os = "macos")]
 impl Default for MacOsCleaner {
     fn default() -> Self {
@@ -370,6 +466,23 @@ mod tests {
         assert!(cleaner.is_some());
     }
 
+    #[test]
+    fn test_bundle_id_matching() {
+        assert!(looks_like_bundle_id("com.tinyspeck.slackmacgap"));
+        assert!(looks_like_bundle_id("org.mozilla.firefox"));
+        assert!(!looks_like_bundle_id("Firefox"));
+        assert!(!looks_like_bundle_id("Sublime Text 3"));
+        assert!(!looks_like_bundle_id("My.App Data.old"));
+
+        let installed: HashSet<String> = ["com.vendor.editor".to_string()].into();
+        assert!(is_bundle_installed("com.vendor.Editor", &installed));
+        assert!(is_bundle_installed("com.vendor.editor.ShareExtension", &installed));
+        assert!(!is_bundle_installed("com.vendor.editorpro", &installed));
+        assert!(!is_bundle_installed("com.other.app", &installed));
+        let vendor: HashSet<String> = ["com.vendor".to_string()].into();
+        assert!(!is_bundle_installed("com.vendor.editor", &vendor));
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     fn test_macos_detection() {
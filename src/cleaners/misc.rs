@@ -7,6 +7,7 @@
 //! - Ruby gems
 //! - NuGet packages (.NET)
 //! - Composer (PHP)
+//! - Gradle, Maven and Kotlin (JVM)
 
 use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
@@ -52,6 +53,9 @@ impl MiscCleaner {
         // Gradle (global)
         items.extend(self.detect_gradle()?);
 
+        // Kotlin compiler and Kotlin/Native
+        items.extend(self.detect_kotlin()?);
+
         // Maven
         items.extend(self.detect_maven()?);
 
@@ -543,6 +547,55 @@ impl MiscCleaner {
         Ok(items)
     }
 
+    /// Detect Kotlin/Native toolchains and Kotlin compiler daemon files
+    fn detect_kotlin(&self) -> Result<Vec<CleanableItem>> {
+        let mut items = Vec::new();
+
+        let konan_path = std::env::var("KONAN_DATA_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| self.home.join(".konan"));
+        if konan_path.exists() {
+            let (size, file_count) = calculate_dir_size(&konan_path)?;
+            if size > 500_000_000 { // 500MB
+                items.push(CleanableItem {
+                    name: "Kotlin/Native".to_string(),
+                    category: "Java/Kotlin".to_string(),
+                    subcategory: "Kotlin".to_string(),
+                    icon: "🟣",
+                    path: konan_path,
+                    size,
+                    file_count: Some(file_count),
+                    last_modified: None,
+                    description: "Kotlin/Native compilers and dependencies. Re-downloaded on next native build.",
+                    safe_to_delete: SafetyLevel::SafeWithCost,
+                    clean_command: None,
+                });
+            }
+        }
+
+        let kotlin_path = self.home.join(".kotlin");
+        if kotlin_path.exists() {
+            let (size, file_count) = calculate_dir_size(&kotlin_path)?;
+            if size > 100_000_000 {
+                items.push(CleanableItem {
+                    name: "Kotlin Compiler Daemon".to_string(),
+                    category: "Java/Kotlin".to_string(),
+                    subcategory: "Kotlin".to_string(),
+                    icon: "🟣",
+                    path: kotlin_path,
+                    size,
+                    file_count: Some(file_count),
+                    last_modified: None,
+                    description: "Kotlin daemon logs and compiler caches. Safe to delete.",
+                    safe_to_delete: SafetyLevel::Safe,
+                    clean_command: None,
+                });
+            }
+        }
+
+        Ok(items)
+    }
+
     /// Detect Maven cache
     fn detect_maven(&self) -> Result<Vec<CleanableItem>> {
         let mut items = Vec::new();
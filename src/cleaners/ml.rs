@@ -10,7 +10,12 @@
 
 use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long an unreferenced Ollama blob is left alone, in case a pull is still writing its manifest
+const OLLAMA_PULL_GRACE: Duration = Duration::from_secs(24 * 3600);
 
 /// ML/AI cleaner
 pub struct MlCleaner {
@@ -121,6 +126,10 @@ impl MlCleaner {
     }
 
     /// Detect Ollama models
+    ///
+    /// Blobs are content-addressed and shared between models, so each model
+    /// is reported with only the bytes that `ollama rm` would actually free.
+    /// Blobs no manifest references any more are reported separately.
     fn detect_ollama(&self) -> Result<Vec<CleanableItem>> {
         let ollama_path = self.home.join(".ollama/models");
 
@@ -128,85 +137,110 @@ impl MlCleaner {
             return Ok(vec![]);
         }
 
-        let mut items = Vec::new();
-
-        // Ollama stores models in blobs and manifests
         let blobs_path = ollama_path.join("blobs");
         let manifests_path = ollama_path.join("manifests");
 
-        // Get model names from manifests
-        if manifests_path.exists() {
-            self.scan_ollama_manifests(&manifests_path, &blobs_path, &mut items)?;
-        }
-
-        // If no manifests, just report total blobs size
-        if items.is_empty() && blobs_path.exists() {
+        // Without manifests we can't tell which blobs are in use
+        if !manifests_path.exists() {
+            if !blobs_path.exists() {
+                return Ok(vec![]);
+            }
             let (size, file_count) = calculate_dir_size(&blobs_path)?;
-            if size > 0 {
-                items.push(CleanableItem {
-                    name: "Ollama Models (all)".to_string(),
-                    category: "ML/AI".to_string(),
-                    subcategory: "Ollama".to_string(),
-                    icon: "🦙",
-                    path: ollama_path,
-                    size,
-                    file_count: Some(file_count),
-                    last_modified: None,
-                    description: "Local LLM models. Can be re-downloaded with 'ollama pull'.",
-                    safe_to_delete: SafetyLevel::SafeWithCost,
-                    clean_command: Some("ollama rm <model>".to_string()),
-                });
+            if size == 0 {
+                return Ok(vec![]);
             }
+            return Ok(vec![CleanableItem {
+                name: "Ollama Models (all)".to_string(),
+                category: "ML/AI".to_string(),
+                subcategory: "Ollama".to_string(),
+                icon: "🦙",
+                path: ollama_path,
+                size,
+                file_count: Some(file_count),
+                last_modified: None,
+                description: "Local LLM models. Can be re-downloaded with 'ollama pull'.",
+                safe_to_delete: SafetyLevel::SafeWithCost,
+                clean_command: Some("ollama rm <model>".to_string()),
+            }]);
         }
 
-        Ok(items)
-    }
+        let manifests = read_ollama_manifests(&manifests_path);
 
-    /// Scan Ollama manifests for model info
-    fn scan_ollama_manifests(&self, manifests_path: &PathBuf, _blobs_path: &PathBuf, items: &mut Vec<CleanableItem>) -> Result<()> {
-        // manifests/registry.ollama.ai/library/<model>/<tag>
-        let registry_path = manifests_path.join("registry.ollama.ai/library");
-        if !registry_path.exists() {
-            return Ok(());
+        // How many models use each blob; a manifest may list a digest twice
+        let mut ref_counts: HashMap<&str, usize> = HashMap::new();
+        for manifest in &manifests {
+            for digest in manifest.distinct_digests() {
+                *ref_counts.entry(digest).or_default() += 1;
+            }
         }
 
-        if let Ok(models) = std::fs::read_dir(&registry_path) {
-            for model in models.filter_map(|e| e.ok()) {
-                let model_path = model.path();
-                if !model_path.is_dir() {
-                    continue;
-                }
-
-                let model_name = model_path.file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default();
+        let mut items = Vec::new();
 
-                // Get total size for this model
-                let (size, file_count) = calculate_dir_size(&model_path)?;
+        for manifest in &manifests {
+            let unique: Vec<PathBuf> = manifest
+                .distinct_digests()
+                .into_iter()
+                .filter(|d| ref_counts.get(d) == Some(&1))
+                .map(|d| blobs_path.join(ollama_blob_name(d)))
+                .collect();
+            let size: u64 = unique
+                .iter()
+                .filter_map(|p| std::fs::metadata(p).ok())
+                .map(|m| m.len())
+                .sum();
 
-                // Estimate actual model size (manifests are small, blobs are big)
-                // This is a rough estimate - actual size requires parsing manifests
-                let estimated_size = size * 1000; // Manifests point to much larger blobs
+            items.push(CleanableItem {
+                name: format!("Ollama: {}", manifest.model),
+                category: "ML/AI".to_string(),
+                subcategory: "Ollama".to_string(),
+                icon: "🦙",
+                path: manifest.path.clone(),
+                size,
+                file_count: Some(unique.len() as u64 + 1),
+                last_modified: get_mtime(&manifest.path),
+                description: "Local LLM model (size excludes layers shared with other models). Removed with 'ollama rm'.",
+                safe_to_delete: SafetyLevel::SafeWithCost,
+                clean_command: Some(format!("ollama rm {}", manifest.model)),
+            });
+        }
 
-                if estimated_size > 100_000_000 {
-                    items.push(CleanableItem {
-                        name: format!("Ollama: {}", model_name),
-                        category: "ML/AI".to_string(),
-                        subcategory: "Ollama".to_string(),
-                        icon: "🦙",
-                        path: model_path,
-                        size: estimated_size,
-                        file_count: Some(file_count),
-                        last_modified: get_mtime(&model.path()),
-                        description: "Local LLM model. Use 'ollama rm' to remove.",
-                        safe_to_delete: SafetyLevel::SafeWithCost,
-                        clean_command: Some(format!("ollama rm {}", model_name)),
-                    });
+        if let Ok(blobs) = std::fs::read_dir(&blobs_path) {
+            for blob in blobs.filter_map(|e| e.ok()) {
+                let name = blob.file_name().to_string_lossy().to_string();
+                let digest = name.replacen('-', ":", 1);
+                // `-partial` files belong to a pull that may still be running
+                if ref_counts.contains_key(digest.as_str()) || name.contains("-partial") {
+                    continue;
+                }
+                let Ok(metadata) = blob.metadata() else {
+                    continue;
+                };
+                if !metadata.is_file() {
+                    continue;
+                }
+                // A blob written moments ago may be waiting for its manifest
+                let age = metadata.modified().ok().and_then(|t| t.elapsed().ok());
+                if age.is_none_or(|age| age < OLLAMA_PULL_GRACE) {
+                    continue;
                 }
+
+                items.push(CleanableItem {
+                    name: format!("Ollama: unreferenced blob {}", short_digest(&name)),
+                    category: "ML/AI".to_string(),
+                    subcategory: "Ollama".to_string(),
+                    icon: "🦙",
+                    path: blob.path(),
+                    size: metadata.len(),
+                    file_count: Some(1),
+                    last_modified: metadata.modified().ok(),
+                    description: "Ollama blob not used by any installed model (removed model or interrupted pull).",
+                    safe_to_delete: SafetyLevel::Safe,
+                    clean_command: None,
+                });
             }
         }
 
-        Ok(())
+        Ok(items)
     }
 
     /// Detect PyTorch cache
@@ -422,6 +456,73 @@ impl MlCleaner {
     }
 }
 
+/// An installed Ollama model tag and the blobs it references
+struct OllamaManifest {
+    /// Name as `ollama rm` expects it, e.g. `llama3:8b` or `user/model:tag`
+    model: String,
+    path: PathBuf,
+    /// Digests of the config and layer blobs, e.g. `sha256:abc...`
+    digests: Vec<String>,
+}
+
+impl OllamaManifest {
+    /// Each blob this model uses, once
+    fn distinct_digests(&self) -> Vec<&str> {
+        let mut digests: Vec<&str> = self.digests.iter().map(String::as_str).collect();
+        digests.sort_unstable();
+        digests.dedup();
+        digests
+    }
+}
+
+/// Read all manifests under `manifests/<registry>/<namespace>/<model>/<tag>`
+fn read_ollama_manifests(manifests_path: &Path) -> Vec<OllamaManifest> {
+    walkdir::WalkDir::new(manifests_path)
+        .min_depth(4)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|entry| {
+            let rel = entry.path().strip_prefix(manifests_path).ok()?;
+            let parts: Vec<String> = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            let [registry, namespace, model, tag] = parts.as_slice() else {
+                return None;
+            };
+
+            let model = match (registry.as_str(), namespace.as_str()) {
+                ("registry.ollama.ai", "library") => format!("{}:{}", model, tag),
+                ("registry.ollama.ai", _) => format!("{}/{}:{}", namespace, model, tag),
+                _ => format!("{}/{}/{}:{}", registry, namespace, model, tag),
+            };
+
+            let content = std::fs::read_to_string(entry.path()).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let digests = std::iter::once(&json["config"])
+                .chain(json["layers"].as_array().into_iter().flatten())
+                .filter_map(|layer| layer["digest"].as_str())
+                .map(String::from)
+                .collect();
+
+            Some(OllamaManifest { model, path: entry.path().to_path_buf(), digests })
+        })
+        .collect()
+}
+
+/// Blob file name for a digest (`sha256:abc` is stored as `sha256-abc`)
+fn ollama_blob_name(digest: &str) -> String {
+    digest.replacen(':', "-", 1)
+}
+
+/// Shorten `sha256-<hex>` for display
+fn short_digest(name: &str) -> &str {
+    let end = name.char_indices().nth(19).map_or(name.len(), |(i, _)| i);
+    &name[..end]
+}
+
 impl Default for MlCleaner {
     fn default() -> Self {
         Self::new().expect("MlCleaner requires home directory")
@@ -438,6 +539,53 @@ mod tests {
         assert!(cleaner.is_some());
     }
 
+    #[test]
+    fn test_ollama_shared_blobs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let models = temp.path().join(".ollama/models");
+        let blobs = models.join("blobs");
+        std::fs::create_dir_all(&blobs).unwrap();
+        for (digest, size) in [("shared", 100), ("llama", 40), ("mistral", 60), ("stale", 7), ("fresh", 3)] {
+            std::fs::write(blobs.join(format!("sha256-{}", digest)), vec![0u8; size]).unwrap();
+        }
+        std::fs::write(blobs.join("sha256-pulling-partial-0"), vec![0u8; 5]).unwrap();
+        let long_ago = std::time::SystemTime::now() - Duration::from_secs(7 * 86400);
+        for name in ["sha256-stale", "sha256-pulling-partial-0"] {
+            let file = std::fs::File::options().write(true).open(blobs.join(name)).unwrap();
+            file.set_times(std::fs::FileTimes::new().set_modified(long_ago)).unwrap();
+        }
+
+        let write_manifest = |rel: &str, layers: &[&str]| {
+            let path = models.join("manifests").join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let layers: Vec<_> = layers
+                .iter()
+                .map(|d| serde_json::json!({ "digest": format!("sha256:{}", d) }))
+                .collect();
+            let manifest = serde_json::json!({ "config": layers[0], "layers": layers[1..] });
+            std::fs::write(path, manifest.to_string()).unwrap();
+        };
+        // Listing a layer twice doesn't make it shared
+        write_manifest("registry.ollama.ai/library/llama3/8b", &["llama", "shared", "llama"]);
+        write_manifest("registry.ollama.ai/someone/mistral/latest", &["mistral", "shared"]);
+
+        let cleaner = MlCleaner { home: temp.path().to_path_buf() };
+        let items = cleaner.detect_ollama().unwrap();
+        let find = |name: &str| items.iter().find(|i| i.name == name).unwrap();
+
+        // The shared layer isn't freed by removing either model
+        let llama = find("Ollama: llama3:8b");
+        assert_eq!(llama.size, 40);
+        assert_eq!(llama.clean_command.as_deref(), Some("ollama rm llama3:8b"));
+        assert_eq!(find("Ollama: someone/mistral:latest").size, 60);
+
+        let stale = find("Ollama: unreferenced blob sha256-stale");
+        assert_eq!(stale.size, 7);
+        assert_eq!(stale.safe_to_delete, SafetyLevel::Safe);
+        // A pull in progress or just finished isn't garbage yet
+        assert_eq!(items.len(), 3);
+    }
+
     #[test]
     fn test_ml_detection() {
         if let Some(cleaner) = MlCleaner::new() {
@@ -36,10 +36,239 @@ pub mod system;
 pub mod runtimes;
 pub mod binaries;
 
-use crate::error::Result;
+use crate::error::{DevSweepError, Result};
 use serde::{Deserialize, Serialize};
+use std::io::Read;
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+static DOCKER_DISABLED: AtomicBool = AtomicBool::new(false);
+static EXTERNAL_COMMANDS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Default limit for one official clean command, in seconds
+pub const DEFAULT_COMMAND_TIMEOUT_SECS: u64 = 600;
+
+static COMMAND_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT_SECS);
+
+/// How often a running clean command is checked for timeout or Ctrl-C
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Allow or forbid detectors from running the `docker` CLI
+pub fn set_docker_enabled(enabled: bool) {
+    DOCKER_DISABLED.store(!enabled, Ordering::Relaxed);
+}
+
+/// Allow or forbid shelling out to external tools while detecting and
+/// analyzing: `docker`, `brew`, `rustup`, `fnm`, `node`, `go`, `tmutil`, the
+/// `git log`/`git count-objects` calls of the analyzers and the
+/// `which`/`--version` probes of the binary analyzer. Also covers the
+/// `gradle --stop` run before cleaning Gradle caches.
+///
+/// Git's protection checks, official clean commands and notification hooks
+/// aren't affected.
+pub fn set_external_commands_enabled(enabled: bool) {
+    EXTERNAL_COMMANDS_DISABLED.store(!enabled, Ordering::Relaxed);
+}
+
+/// Whether detectors may run external tools
+pub fn external_commands_enabled() -> bool {
+    !EXTERNAL_COMMANDS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// Whether detectors may run the `docker` CLI
+pub fn docker_enabled() -> bool {
+    external_commands_enabled() && !DOCKER_DISABLED.load(Ordering::Relaxed)
+}
+
+/// Limit how long an official clean command may run; `None` waits forever
+pub fn set_command_timeout(timeout: Option<Duration>) {
+    COMMAND_TIMEOUT_SECS.store(timeout.map_or(0, |t| t.as_secs().max(1)), Ordering::Relaxed);
+}
+
+/// How long an official clean command may run before it is killed
+pub fn command_timeout() -> Option<Duration> {
+    match COMMAND_TIMEOUT_SECS.load(Ordering::Relaxed) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Run an official clean command such as `npm cache clean --force`
+///
+/// The command is split on whitespace and run without a shell. See
+/// [`wait_for_command`] for timeout and Ctrl-C handling.
+pub fn run_clean_command(cmd: &str) -> Result<()> {
+    let parts: Vec<&str> = cmd.split_whitespace().collect();
+    let Some((program, args)) = parts.split_first() else {
+        return Err(DevSweepError::Config("Empty clean command".into()));
+    };
+
+    let mut command = Command::new(program);
+    command.args(args);
+    wait_for_command(command, cmd)
+}
+
+/// Run `command` to completion, killing it on timeout or Ctrl-C
+///
+/// `display` names the command in errors. A non-zero exit is reported with
+/// the command's stderr; running past [`command_timeout`] gives
+/// [`DevSweepError::CommandTimeout`] and Ctrl-C gives
+/// [`DevSweepError::CommandInterrupted`], after the child has been killed.
+pub fn wait_for_command(command: Command, display: &str) -> Result<()> {
+    let _guard = interrupt::Guard::arm();
+    run_killable(command, display, command_timeout(), interrupt::requested, false).map(drop)
+}
+
+/// Run a read-only probe such as `docker system df` and return its stdout
+///
+/// Like [`wait_for_command`], but killed after `timeout` rather than the
+/// clean command limit, and Ctrl-C ends the process as usual.
+pub fn command_output(command: Command, display: &str, timeout: Option<Duration>) -> Result<String> {
+    run_killable(command, display, timeout, || false, true)
+}
+
+fn run_killable(
+    mut command: Command,
+    display: &str,
+    timeout: Option<Duration>,
+    interrupted: fn() -> bool,
+    capture: bool,
+) -> Result<String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(if capture { Stdio::piped() } else { Stdio::null() })
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| DevSweepError::Io(e).with_context(format!("while running `{}`", display)))?;
+
+    // Drain both pipes on the side so a chatty command can't block on a full pipe
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        pipe.map(|mut pipe| {
+            std::thread::spawn(move || {
+                let mut buf = String::new();
+                let _ = pipe.read_to_string(&mut buf);
+                buf
+            })
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        let stop = if interrupted() {
+            Some(DevSweepError::CommandInterrupted(display.to_string()))
+        } else {
+            timeout.filter(|t| started.elapsed() >= *t).map(|limit| DevSweepError::CommandTimeout {
+                command: display.to_string(),
+                secs: limit.as_secs(),
+            })
+        };
+        if let Some(err) = stop {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(err);
+        }
+        std::thread::sleep(COMMAND_POLL_INTERVAL);
+    };
+
+    if status.success() {
+        Ok(stdout.and_then(|t| t.join().ok()).unwrap_or_default())
+    } else {
+        let stderr = stderr.and_then(|t| t.join().ok()).unwrap_or_default();
+        Err(DevSweepError::CleanFailed {
+            path: PathBuf::from(display),
+            reason: match stderr.trim() {
+                "" => format!("exited with {}", status),
+                err => err.to_string(),
+            },
+        })
+    }
+}
+
+/// Ctrl-C while a clean command runs
+///
+/// Outside of [`wait_for_command`] SIGINT keeps its default action and ends
+/// the process. While a command runs, it only raises a flag so the child can
+/// be killed and the interruption reported as an error.
+#[cfg(unix)]
+mod interrupt {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, OnceLock};
+
+    struct Flags {
+        /// No command running: SIGINT terminates as usual
+        idle: Arc<AtomicBool>,
+        /// SIGINT arrived since the current command started
+        requested: Arc<AtomicBool>,
+    }
+
+    static FLAGS: OnceLock<Option<Flags>> = OnceLock::new();
+
+    fn flags() -> Option<&'static Flags> {
+        FLAGS
+            .get_or_init(|| {
+                use signal_hook::consts::SIGINT;
+                use signal_hook::flag;
+
+                let flags = Flags {
+                    idle: Arc::new(AtomicBool::new(true)),
+                    requested: Arc::new(AtomicBool::new(false)),
+                };
+                // The default action has to be registered first to run first
+                flag::register_conditional_default(SIGINT, Arc::clone(&flags.idle)).ok()?;
+                flag::register(SIGINT, Arc::clone(&flags.requested)).ok()?;
+                Some(flags)
+            })
+            .as_ref()
+    }
+
+    /// Catches SIGINT until dropped
+    pub struct Guard;
+
+    impl Guard {
+        pub fn arm() -> Self {
+            if let Some(flags) = flags() {
+                flags.requested.store(false, Ordering::SeqCst);
+                flags.idle.store(false, Ordering::SeqCst);
+            }
+            Guard
+        }
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            if let Some(flags) = flags() {
+                flags.idle.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    pub fn requested() -> bool {
+        flags().is_some_and(|flags| flags.requested.load(Ordering::SeqCst))
+    }
+}
+
+/// Ctrl-C on Windows ends the whole console process group, children included
+#[cfg(not(unix))]
+mod interrupt {
+    pub struct Guard;
+
+    impl Guard {
+        pub fn arm() -> Self {
+            Guard
+        }
+    }
+
+    pub fn requested() -> bool {
+        false
+    }
+}
 
 /// A cleanable item found by a cleaner module
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,8 +297,9 @@ pub struct CleanableItem {
     pub clean_command: Option<String>,
 }
 
-/// Safety level for deletion
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Safety level for deletion, from least to most risky
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum SafetyLevel {
     /// Safe to delete, will be regenerated
     Safe,
@@ -82,6 +312,16 @@ pub enum SafetyLevel {
 }
 
 impl SafetyLevel {
+    /// Name used in config files and flags, e.g. `safe-with-cost`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Safe => "safe",
+            Self::SafeWithCost => "safe-with-cost",
+            Self::Caution => "caution",
+            Self::Dangerous => "dangerous",
+        }
+    }
+
     /// Get a color hint for display
     pub fn color_hint(&self) -> &'static str {
         match self {
@@ -92,6 +332,16 @@ impl SafetyLevel {
         }
     }
 
+    /// Plain-English reasoning behind this safety level
+    pub fn explain(&self) -> &'static str {
+        match self {
+            Self::Safe => "safe, it is regenerated on demand",
+            Self::SafeWithCost => "safe, but the next build or download will take longer",
+            Self::Caution => "may contain data that can't be recovered",
+            Self::Dangerous => "may break installed tools or projects",
+        }
+    }
+
     /// Get a symbol for display
     pub fn symbol(&self) -> &'static str {
         match self {
@@ -104,6 +354,14 @@ impl SafetyLevel {
 }
 
 impl CleanableItem {
+    /// Order by size (largest first), ties broken by path for stable output
+    pub fn cmp_by_size(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .size
+            .cmp(&self.size)
+            .then_with(|| self.path.cmp(&other.path))
+    }
+
     /// Check if this item exists
     pub fn exists(&self) -> bool {
         self.path.exists()
@@ -169,6 +427,60 @@ impl CleanerSummary {
     }
 }
 
+/// Categories of the catch-all system cleaners, which other cleaners refine
+const GENERIC_CATEGORIES: &[&str] = &["System", "macOS System"];
+
+/// Drop items whose path another cleaner already reported, or that lie inside
+/// another reported item, returning how many were dropped
+///
+/// Paths are compared canonicalized. Of each duplicate the riskiest safety
+/// level is kept, so a path is never shown as safer than any cleaner thinks it
+/// is; on a tie a specific cleaner wins over the catch-all system ones.
+pub fn dedupe_items(items: &mut Vec<CleanableItem>) -> usize {
+    let rank = |item: &CleanableItem| {
+        (
+            item.safe_to_delete,
+            !GENERIC_CATEGORIES.contains(&item.category.as_str()),
+            std::cmp::Reverse(item.category.clone()),
+        )
+    };
+
+    let before = items.len();
+    let mut kept: Vec<CleanableItem> = Vec::with_capacity(before);
+    let mut seen: std::collections::HashMap<PathBuf, usize> = std::collections::HashMap::new();
+    for item in items.drain(..) {
+        let key = item.path.canonicalize().unwrap_or_else(|_| item.path.clone());
+        match seen.get(&key) {
+            Some(&i) => {
+                if rank(&item) > rank(&kept[i]) {
+                    kept[i] = item;
+                }
+            }
+            None => {
+                seen.insert(key, kept.len());
+                kept.push(item);
+            }
+        }
+    }
+
+    // An item inside another one goes with it, so only the outer one counts;
+    // it takes on the riskier level of the two
+    let mut nested = vec![false; kept.len()];
+    for (key, &i) in &seen {
+        if let Some(&outer) = key.ancestors().skip(1).filter_map(|dir| seen.get(dir)).last() {
+            nested[i] = true;
+            kept[outer].safe_to_delete = kept[outer].safe_to_delete.max(kept[i].safe_to_delete);
+        }
+    }
+
+    *items = kept
+        .into_iter()
+        .zip(nested)
+        .filter_map(|(item, nested)| (!nested).then_some(item))
+        .collect();
+    before - items.len()
+}
+
 /// Calculate directory size recursively
 pub fn calculate_dir_size(path: &std::path::Path) -> Result<(u64, u64)> {
     use rayon::prelude::*;
@@ -196,7 +508,171 @@ pub fn calculate_dir_size(path: &std::path::Path) -> Result<(u64, u64)> {
     Ok((size, count))
 }
 
+/// Official commands known not to remove anything themselves
+///
+/// Compared after collapsing whitespace; a command that merely starts with
+/// one of these (e.g. `gradle --stop && rm -rf ...`) doesn't match.
+const REVERSIBLE_COMMANDS: &[&str] = &[
+    // Stops the daemon, the caches stay where they are
+    "gradle --stop",
+    // Re-fetching modules overwrites the cache but removes nothing
+    "deno cache --reload",
+];
+
+/// Check whether an official clean command can be undone
+///
+/// Official commands delete data themselves, bypassing the trash. Only the
+/// commands on a short allow-list are known to be harmless; everything else is
+/// treated as irreversible, and safe mode refuses to run it and trashes the
+/// item's path instead.
+pub fn is_reversible_command(cmd: &str) -> bool {
+    let normalized = cmd.split_whitespace().collect::<Vec<_>>().join(" ");
+    REVERSIBLE_COMMANDS.contains(&normalized.as_str())
+}
+
 /// Get last modification time of a path
 pub fn get_mtime(path: &std::path::Path) -> Option<SystemTime> {
     std::fs::metadata(path).ok()?.modified().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(category: &str, path: PathBuf, safety: SafetyLevel) -> CleanableItem {
+        CleanableItem {
+            name: format!("{} item", category),
+            category: category.to_string(),
+            subcategory: "Caches".to_string(),
+            icon: "📁",
+            path,
+            size: 1_000,
+            file_count: None,
+            last_modified: None,
+            description: "test",
+            safe_to_delete: safety,
+            clean_command: None,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_items_across_cleaners() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let shared = temp.path().join("shared");
+        let other = temp.path().join("other");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::create_dir_all(&other).unwrap();
+
+        // The system cleaner and a specific one both report `shared`, spelled differently
+        let mut items = vec![
+            item("macOS System", shared.clone(), SafetyLevel::SafeWithCost),
+            item("Xcode", other.join("../shared"), SafetyLevel::SafeWithCost),
+            item("Xcode", other.clone(), SafetyLevel::Safe),
+        ];
+        assert_eq!(dedupe_items(&mut items), 1);
+        assert_eq!(items.len(), 2);
+        assert_eq!(CleanerSummary::from_items(&items).total_size, 2_000);
+
+        let kept: Vec<_> = items.iter().filter(|i| i.path.ends_with("shared")).collect();
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].category, "Xcode");
+
+        // The riskier classification wins over the more specific cleaner
+        let mut items = vec![
+            item("Xcode", shared.clone(), SafetyLevel::Safe),
+            item("System", shared.clone(), SafetyLevel::Caution),
+        ];
+        assert_eq!(dedupe_items(&mut items), 1);
+        assert_eq!(items[0].safe_to_delete, SafetyLevel::Caution);
+    }
+
+    #[test]
+    fn test_dedupe_items_nested_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let outer = temp.path().join("Caches");
+        let inner = outer.join("com.apple.dt.Xcode");
+        let deeper = inner.join("SwiftPM");
+        std::fs::create_dir_all(&deeper).unwrap();
+        let sibling = temp.path().join("Caches-old");
+        std::fs::create_dir_all(&sibling).unwrap();
+
+        let mut items = vec![
+            item("Xcode", inner.clone(), SafetyLevel::Caution),
+            item("macOS System", outer.clone(), SafetyLevel::SafeWithCost),
+            item("Xcode", deeper, SafetyLevel::Safe),
+            // Shares a name prefix but isn't inside `outer`
+            item("System", sibling.clone(), SafetyLevel::Safe),
+        ];
+        assert_eq!(dedupe_items(&mut items), 2);
+        assert_eq!(items.len(), 2);
+        assert_eq!(CleanerSummary::from_items(&items).total_size, 2_000);
+
+        let kept = items.iter().find(|i| i.path == outer).unwrap();
+        // Deleting the outer directory deletes the riskier inner item too
+        assert_eq!(kept.safe_to_delete, SafetyLevel::Caution);
+        assert!(items.iter().any(|i| i.path == sibling));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_output_is_killed_on_timeout() {
+        let mut echo = Command::new("echo");
+        echo.arg("hello");
+        assert_eq!(command_output(echo, "echo hello", None).unwrap(), "hello\n");
+
+        let started = Instant::now();
+        let mut sleep = Command::new("sleep");
+        sleep.arg("30");
+        let result = command_output(sleep, "sleep 30", Some(Duration::from_millis(200)));
+        assert!(matches!(result, Err(DevSweepError::CommandTimeout { .. })));
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        assert!(command_output(Command::new("false"), "false", None).is_err());
+    }
+
+    #[test]
+    fn test_every_clean_command_is_classified() {
+        // Every official command the cleaners and cache definitions can emit,
+        // read from the source so a new one can't slip past unclassified
+        let sources = [
+            include_str!("android.rs"),
+            include_str!("binaries.rs"),
+            include_str!("browsers_test.rs"),
+            include_str!("cloud.rs"),
+            include_str!("docker.rs"),
+            include_str!("electron.rs"),
+            include_str!("gamedev.rs"),
+            include_str!("homebrew.rs"),
+            include_str!("ide.rs"),
+            include_str!("ios_deps.rs"),
+            include_str!("logs.rs"),
+            include_str!("macos.rs"),
+            include_str!("misc.rs"),
+            include_str!("ml.rs"),
+            include_str!("runtimes.rs"),
+            include_str!("system.rs"),
+            include_str!("xcode.rs"),
+            include_str!("../caches/mod.rs"),
+        ];
+        let literal = regex::Regex::new(r#"clean_command: Some\((?:format!\()?\s*"([^"]+)""#).unwrap();
+        let commands: Vec<&str> = sources
+            .iter()
+            .flat_map(|source| literal.captures_iter(source))
+            .map(|c| c.get(1).unwrap().as_str())
+            .collect();
+        assert!(commands.len() > 50, "only found {} commands", commands.len());
+
+        for cmd in &commands {
+            let expected = REVERSIBLE_COMMANDS.contains(cmd);
+            assert_eq!(is_reversible_command(cmd), expected, "{} misclassified", cmd);
+        }
+        // Both remove data without saying so in a verb
+        assert!(commands.contains(&"trash-empty"));
+        assert!(!is_reversible_command("trash-empty"));
+        assert!(commands.contains(&"sudo journalctl --vacuum-size=500M"));
+        assert!(!is_reversible_command("sudo journalctl --vacuum-size=500M"));
+        assert!(commands.contains(&"gradle --stop"));
+        assert!(is_reversible_command("gradle  --stop"));
+        assert!(!is_reversible_command("gradle --stop && rm -rf ~/.gradle/caches"));
+    }
+}
@@ -10,7 +10,7 @@
 
 use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::SystemTime;
 
@@ -181,6 +181,11 @@ impl RuntimesCleaner {
 
     /// Detect fnm installed Node versions
     fn detect_fnm(&self) -> Result<Vec<CleanableItem>> {
+        // Without `fnm current` the active version can't be told apart
+        if !super::external_commands_enabled() {
+            return Ok(vec![]);
+        }
+
         let mut items = Vec::new();
 
         // fnm paths vary by OS
@@ -338,6 +343,11 @@ impl RuntimesCleaner {
 
     /// Detect n installed Node versions
     fn detect_n(&self) -> Result<Vec<CleanableItem>> {
+        // Without `node --version` the active version can't be told apart
+        if !super::external_commands_enabled() {
+            return Ok(vec![]);
+        }
+
         let mut items = Vec::new();
 
         // n installs to /usr/local/n/versions/node by default
@@ -882,58 +892,105 @@ impl RuntimesCleaner {
 
     /// Detect Rust toolchains from rustup
     fn detect_rust_toolchains(&self) -> Result<Vec<CleanableItem>> {
-        let mut items = Vec::new();
+        // Without `rustup default` the active toolchain can't be told apart
+        if !super::external_commands_enabled() {
+            return Ok(vec![]);
+        }
 
         let rustup_home = std::env::var("RUSTUP_HOME")
             .map(PathBuf::from)
             .unwrap_or_else(|_| self.home.join(".rustup"));
 
-        let toolchains_path = rustup_home.join("toolchains");
-        if !toolchains_path.exists() {
+        if !rustup_home.join("toolchains").exists() {
             return Ok(vec![]);
         }
 
-        // Detect active toolchain
-        let active_toolchain = self.detect_rustup_active();
+        let mut in_use = rustup_toolchains_in_use(&rustup_home);
+        if in_use.is_empty() {
+            in_use.extend(self.detect_rustup_active());
+        }
+        in_use.extend(std::env::var("RUSTUP_TOOLCHAIN"));
 
-        if let Ok(entries) = std::fs::read_dir(&toolchains_path) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if !path.is_dir() {
-                    continue;
-                }
+        // Rust toolchains are usually 400MB+
+        self.rust_toolchains(&rustup_home, &in_use, 100_000_000)
+    }
 
-                let name = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "Unknown".to_string());
+    /// List installed rustup toolchains with their size, components and last use
+    ///
+    /// Toolchains named in `in_use` (the default and directory overrides) are
+    /// marked dangerous. Other nightlies older than [`OLD_NIGHTLY_DAYS`] are
+    /// flagged `SafeWithCost`, unless their binaries ran recently, which usually
+    /// means a project pins them.
+    fn rust_toolchains(
+        &self,
+        rustup_home: &Path,
+        in_use: &[String],
+        min_size: u64,
+    ) -> Result<Vec<CleanableItem>> {
+        let mut items = Vec::new();
 
-                let (size, file_count) = calculate_dir_size(&path)?;
-                if size < 100_000_000 {
-                    // Rust toolchains are usually 400MB+
-                    continue;
-                }
+        let Ok(entries) = std::fs::read_dir(rustup_home.join("toolchains")) else {
+            return Ok(items);
+        };
 
-                let last_modified = get_mtime(&path);
-                let is_active = active_toolchain
-                    .as_ref()
-                    .map(|v| name.starts_with(v) || v.starts_with(&name))
-                    .unwrap_or(false);
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
 
-                items.push(CleanableItem {
-                    name: format!("Rust {}", name),
-                    category: "Rust".to_string(),
-                    subcategory: "rustup".to_string(),
-                    icon: "🦀",
-                    path,
-                    size,
-                    file_count: Some(file_count),
-                    last_modified,
-                    description: self.get_description(is_active, last_modified),
-                    safe_to_delete: self.determine_safety(is_active, last_modified),
-                    clean_command: Some(format!("rustup toolchain remove {}", name)),
-                });
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (size, file_count) = calculate_dir_size(&path)?;
+            if size < min_size {
+                continue;
             }
+
+            let is_active = in_use.iter().any(|wanted| is_toolchain(&name, wanted));
+            let last_used = toolchain_last_used(&path).or_else(|| get_mtime(&path));
+            let release_age = toolchain_release_date(&name, &path)
+                .map(|date| (chrono::Utc::now().date_naive() - date).num_days());
+            let used_recently = last_used
+                .and_then(|t| t.elapsed().ok())
+                .is_some_and(|d| d.as_secs() / 86400 < OLD_NIGHTLY_DAYS as u64);
+
+            let old_nightly = !is_active
+                && name.starts_with("nightly")
+                && release_age.is_some_and(|days| days > OLD_NIGHTLY_DAYS);
+            let (description, safe_to_delete) = match (old_nightly, used_recently) {
+                (true, false) => (
+                    "Old nightly toolchain. Reinstall with rustup if a project needs it",
+                    SafetyLevel::SafeWithCost,
+                ),
+                (true, true) => (
+                    "Old nightly, but used recently. A project may pin it",
+                    SafetyLevel::Caution,
+                ),
+                (false, _) => (
+                    self.get_description(is_active, last_used),
+                    self.determine_safety(is_active, last_used),
+                ),
+            };
+
+            let components = toolchain_components(&path);
+            let label = if components == 0 {
+                format!("Rust {}", name)
+            } else {
+                format!("Rust {} ({} components)", name, components)
+            };
+
+            items.push(CleanableItem {
+                name: label,
+                category: "Rust".to_string(),
+                subcategory: "rustup".to_string(),
+                icon: "🦀",
+                path,
+                size,
+                file_count: Some(file_count),
+                last_modified: last_used,
+                description,
+                safe_to_delete,
+                clean_command: Some(format!("rustup toolchain uninstall {}", name)),
+            });
         }
 
         Ok(items)
@@ -1049,6 +1106,11 @@ impl RuntimesCleaner {
 
     /// Detect Go SDK downloads (golang.org/dl)
     fn detect_go_sdk(&self) -> Result<Vec<CleanableItem>> {
+        // Without `go version` the active version can't be told apart
+        if !super::external_commands_enabled() {
+            return Ok(vec![]);
+        }
+
         let mut items = Vec::new();
 
         let sdk_path = self.home.join("sdk");
@@ -1127,6 +1189,73 @@ impl RuntimesCleaner {
     }
 }
 
+// ==================== rustup helpers ====================
+
+/// Nightlies released longer ago than this are flagged for removal
+const OLD_NIGHTLY_DAYS: i64 = 30;
+
+/// Default toolchain and directory overrides from rustup's `settings.toml`
+fn rustup_toolchains_in_use(rustup_home: &Path) -> Vec<String> {
+    let mut in_use = Vec::new();
+    let Some(settings) = std::fs::read_to_string(rustup_home.join("settings.toml"))
+        .ok()
+        .and_then(|s| s.parse::<toml::Table>().ok())
+    else {
+        return in_use;
+    };
+
+    if let Some(default) = settings.get("default_toolchain").and_then(|v| v.as_str()) {
+        in_use.push(default.to_string());
+    }
+    if let Some(overrides) = settings.get("overrides").and_then(|v| v.as_table()) {
+        in_use.extend(overrides.values().filter_map(|v| v.as_str()).map(String::from));
+    }
+    in_use
+}
+
+/// Whether an installed toolchain directory is the toolchain `wanted` names
+///
+/// `wanted` may leave out the host triple ("stable", "nightly-2024-03-01"),
+/// but a plain channel never matches a dated toolchain of that channel.
+fn is_toolchain(installed: &str, wanted: &str) -> bool {
+    installed == wanted
+        || installed
+            .strip_prefix(wanted)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|host| !host.starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Release date from a dated name ("nightly-2024-03-01-…") or the channel manifest
+fn toolchain_release_date(name: &str, path: &Path) -> Option<chrono::NaiveDate> {
+    let parse = |s: &str| chrono::NaiveDate::parse_from_str(s.get(..10)?, "%Y-%m-%d").ok();
+
+    if let Some(date) = name.split_once('-').and_then(|(_, rest)| parse(rest)) {
+        return Some(date);
+    }
+
+    let manifest = std::fs::read_to_string(path.join("lib/rustlib/multirust-channel-manifest.toml")).ok()?;
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix("date = "))
+        .and_then(|date| parse(date.trim_matches('"')))
+}
+
+/// Number of components (rustc, cargo, clippy, rust-docs, …) installed
+fn toolchain_components(path: &Path) -> usize {
+    std::fs::read_to_string(path.join("lib/rustlib/components"))
+        .map(|s| s.lines().filter(|l| !l.trim().is_empty()).count())
+        .unwrap_or(0)
+}
+
+/// Newest access time of the toolchain's binaries, the best hint of last use
+fn toolchain_last_used(path: &Path) -> Option<SystemTime> {
+    std::fs::read_dir(path.join("bin"))
+        .ok()?
+        .filter_map(|e| e.ok()?.metadata().ok())
+        .filter_map(|m| m.accessed().or_else(|_| m.modified()).ok())
+        .max()
+}
+
 impl Default for RuntimesCleaner {
     fn default() -> Self {
         Self::new().expect("RuntimesCleaner requires home directory")
@@ -1181,4 +1310,65 @@ mod tests {
             SafetyLevel::Caution
         );
     }
+
+    #[test]
+    fn test_is_toolchain() {
+        let host = "stable-x86_64-unknown-linux-gnu";
+        assert!(is_toolchain(host, host));
+        assert!(is_toolchain(host, "stable"));
+        assert!(!is_toolchain("nightly-2024-03-01-x86_64-unknown-linux-gnu", "nightly"));
+        assert!(is_toolchain("nightly-2024-03-01-x86_64-unknown-linux-gnu", "nightly-2024-03-01"));
+        assert!(!is_toolchain("stable-x86_64-unknown-linux-gnu", "stab"));
+    }
+
+    #[test]
+    fn test_old_nightly_toolchains_flagged() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let rustup = temp.path();
+        let host = "x86_64-unknown-linux-gnu";
+        std::fs::write(
+            rustup.join("settings.toml"),
+            "default_toolchain = \"stable\"\n[overrides]\n\"/work/pinned\" = \"nightly-2024-02-01\"\n",
+        )
+        .unwrap();
+
+        let long_ago = SystemTime::now() - std::time::Duration::from_secs(200 * 86400);
+        for (name, last_used) in [
+            ("stable", SystemTime::now()),
+            ("nightly-2024-01-15", long_ago),
+            ("nightly-2024-02-01", long_ago),
+            ("nightly-2024-03-01", SystemTime::now()),
+        ] {
+            let toolchain = rustup.join("toolchains").join(format!("{}-{}", name, host));
+            std::fs::create_dir_all(toolchain.join("bin")).unwrap();
+            std::fs::create_dir_all(toolchain.join("lib/rustlib")).unwrap();
+            std::fs::write(toolchain.join("lib/rustlib/components"), "rustc\ncargo\n").unwrap();
+            let rustc = std::fs::File::create(toolchain.join("bin/rustc")).unwrap();
+            let times = std::fs::FileTimes::new().set_accessed(last_used).set_modified(last_used);
+            rustc.set_times(times).unwrap();
+        }
+
+        let in_use = rustup_toolchains_in_use(rustup);
+        let cleaner = RuntimesCleaner::new().unwrap();
+        let items = cleaner.rust_toolchains(rustup, &in_use, 0).unwrap();
+        assert_eq!(items.len(), 4);
+        let safety = |name: &str| {
+            let item = items.iter().find(|i| i.name.contains(name)).unwrap();
+            assert!(item.name.ends_with("(2 components)"));
+            item.safe_to_delete
+        };
+
+        assert_eq!(safety("stable"), SafetyLevel::Dangerous);
+        // Directory override
+        assert_eq!(safety("nightly-2024-02-01"), SafetyLevel::Dangerous);
+        assert_eq!(safety("nightly-2024-01-15"), SafetyLevel::SafeWithCost);
+        // Old, but its binaries ran recently
+        assert_eq!(safety("nightly-2024-03-01"), SafetyLevel::Caution);
+
+        let old = items.iter().find(|i| i.name.contains("2024-01-15")).unwrap();
+        assert_eq!(
+            old.clean_command.as_deref(),
+            Some("rustup toolchain uninstall nightly-2024-01-15-x86_64-unknown-linux-gnu")
+        );
+    }
 }
@@ -6,8 +6,9 @@
 //! - Temporary files
 //! - Time Machine local snapshots (macOS)
 //! - Windows temp files
+//! - `.DS_Store`/`Thumbs.db`/`desktop.ini` files littering scanned directories
 
-use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
+use super::{calculate_dir_size, CleanableItem, SafetyLevel};
 use crate::error::Result;
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime};
@@ -267,6 +268,10 @@ impl SystemCleaner {
     /// Detect Time Machine local snapshots (macOS only)
     #[cfg(target_os = "macos")]
     fn detect_time_machine(&self) -> Result<Vec<CleanableItem>> {
+        if !super::external_commands_enabled() {
+            return Ok(vec![]);
+        }
+
         let mut items = Vec::new();
 
         // Check if tmutil is available and get snapshot info
@@ -457,67 +462,194 @@ impl SystemCleaner {
     }
 }
 
-/// Find big files in a directory
-pub fn find_big_files(min_size_mb: u64) -> Result<Vec<CleanableItem>> {
-    let mut items = Vec::new();
-    let min_size = min_size_mb * 1_000_000;
+/// Directory names never descended into when looking for big files
+///
+/// Their contents are covered by the project and cache scanners instead.
+const BIG_FILES_SKIP_DIRS: &[&str] = &[
+    ".git", "node_modules", "target", ".cargo", ".npm",
+    ".gradle", "venv", ".venv", "__pycache__", ".cache",
+];
 
+/// Nested cache locations never descended into when looking for big files
+const BIG_FILES_SKIP_PATHS: &[&str] = &["Library/Caches", "AppData/Local"];
+
+/// Find big files in the home directory
+pub fn find_big_files(min_size_mb: u64) -> Result<Vec<CleanableItem>> {
     let home = dirs::home_dir().ok_or_else(|| {
         crate::error::DevSweepError::Config("Could not find home directory".into())
     })?;
 
-    // Walk home directory, but skip certain paths
-    let skip_paths = [
-        ".git", "node_modules", "target", ".cargo", ".npm",
-        ".gradle", "venv", ".venv", "__pycache__",
-        "Library/Caches", "AppData/Local", ".cache",
-    ];
-
-    let walker = walkdir::WalkDir::new(&home)
-        .max_depth(5) // Don't go too deep
-        .into_iter()
-        .filter_entry(|e| {
-            let path = e.path();
-            // Skip hidden system directories and known cache locations
-            !skip_paths.iter().any(|skip| path.to_string_lossy().contains(skip))
-        });
-
-    for entry in walker.filter_map(|e| e.ok()) {
-        let path = entry.path();
-
-        if !path.is_file() {
-            continue;
-        }
+    // Don't go too deep, and only report the top 50
+    Ok(find_big_files_in(&[home], min_size_mb * 1_000_000, Some(5), 50))
+}
+
+/// Find individual files of at least `min_size` bytes under `roots`
+///
+/// Symlinks are not followed. Results are sorted largest first and cut to
+/// `limit` entries (0 for no limit).
+pub fn find_big_files_in(
+    roots: &[PathBuf],
+    min_size: u64,
+    max_depth: Option<usize>,
+    limit: usize,
+) -> Vec<CleanableItem> {
+    let mut items = Vec::new();
 
-        if let Ok(metadata) = path.metadata() {
+    for root in roots {
+        let walker = walkdir::WalkDir::new(root)
+            .max_depth(max_depth.unwrap_or(usize::MAX))
+            .into_iter()
+            .filter_entry(|e| {
+                if e.depth() == 0 || !e.file_type().is_dir() {
+                    return true;
+                }
+                let name = e.file_name().to_string_lossy();
+                !BIG_FILES_SKIP_DIRS.contains(&name.as_ref())
+                    && !BIG_FILES_SKIP_PATHS.iter().any(|skip| e.path().ends_with(skip))
+            });
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
             let size = metadata.len();
-            if size >= min_size {
-                let name = path.file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "Unknown".to_string());
-
-                items.push(CleanableItem {
-                    name,
-                    category: "Big Files".to_string(),
-                    subcategory: "Files".to_string(),
-                    icon: "📄",
-                    path: path.to_path_buf(),
-                    size,
-                    file_count: Some(1),
-                    last_modified: get_mtime(path),
-                    description: "Large file found in home directory.",
-                    safe_to_delete: SafetyLevel::Caution,
-                    clean_command: None,
-                });
+            if size < min_size {
+                continue;
             }
+
+            items.push(CleanableItem {
+                name: entry.file_name().to_string_lossy().to_string(),
+                category: "Big Files".to_string(),
+                subcategory: "Files".to_string(),
+                icon: "📄",
+                path: entry.path().to_path_buf(),
+                size,
+                file_count: Some(1),
+                last_modified: metadata.modified().ok(),
+                description: "Large file outside any recognized artifact.",
+                safe_to_delete: SafetyLevel::Caution,
+                clean_command: None,
+            });
         }
     }
 
     // Sort by size (largest first)
-    items.sort_by(|a, b| b.size.cmp(&a.size));
+    items.sort_by(CleanableItem::cmp_by_size);
+    if limit > 0 {
+        items.truncate(limit);
+    }
 
-    // Return top 50
-    items.truncate(50);
+    items
+}
+
+/// File names the OS leaves in browsed folders, regenerated whenever they're needed
+pub const JUNK_FILE_NAMES: &[&str] = &[".DS_Store", "Thumbs.db", "ehthumbs.db", "desktop.ini"];
+
+/// Find OS metadata files (`.DS_Store`, `Thumbs.db`, ...) under `roots`
+///
+/// Names match case-insensitively, as Windows writes them in either case.
+/// Directories the big file search skips are skipped here too; their
+/// contents go away with the artifact or cache they belong to.
+pub fn find_junk_files_in(roots: &[PathBuf], max_depth: Option<usize>) -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+
+    for root in roots {
+        let walker = walkdir::WalkDir::new(root)
+            .max_depth(max_depth.unwrap_or(usize::MAX))
+            .into_iter()
+            .filter_entry(|e| {
+                if e.depth() == 0 || !e.file_type().is_dir() {
+                    return true;
+                }
+                let name = e.file_name().to_string_lossy();
+                !BIG_FILES_SKIP_DIRS.contains(&name.as_ref())
+            });
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy();
+            let Some(junk) = JUNK_FILE_NAMES.iter().find(|j| j.eq_ignore_ascii_case(&name)) else {
+                continue;
+            };
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
 
-    Ok(items)
+            items.push(CleanableItem {
+                name: name.to_string(),
+                category: "Junk Files".to_string(),
+                subcategory: junk.to_string(),
+                icon: "🗑️",
+                path: entry.path().to_path_buf(),
+                size: metadata.len(),
+                file_count: Some(1),
+                last_modified: metadata.modified().ok(),
+                description: "Folder metadata written by Finder or Explorer. Recreated when needed.",
+                safe_to_delete: SafetyLevel::Safe,
+                clean_command: None,
+            });
+        }
+    }
+
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_big_files_in() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("videos")).unwrap();
+        std::fs::create_dir_all(root.join("app/node_modules")).unwrap();
+        std::fs::write(root.join("videos/old.mkv"), vec![0u8; 3000]).unwrap();
+        std::fs::write(root.join("disk.iso"), vec![0u8; 2000]).unwrap();
+        std::fs::write(root.join("notes.txt"), vec![0u8; 10]).unwrap();
+        // Covered by the project scanner, not reported again
+        std::fs::write(root.join("app/node_modules/huge.bin"), vec![0u8; 5000]).unwrap();
+
+        let items = find_big_files_in(&[root.to_path_buf()], 1000, None, 0);
+        let names: Vec<_> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["old.mkv", "disk.iso"]);
+        assert!(items[0].last_modified.is_some());
+
+        assert_eq!(find_big_files_in(&[root.to_path_buf()], 1000, None, 1).len(), 1);
+    }
+
+    #[test]
+    fn test_find_junk_files_in() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("photos/2024")).unwrap();
+        std::fs::create_dir_all(root.join("app/node_modules")).unwrap();
+        std::fs::write(root.join(".DS_Store"), vec![0u8; 6148]).unwrap();
+        std::fs::write(root.join("photos/2024/.DS_Store"), vec![0u8; 8196]).unwrap();
+        std::fs::write(root.join("photos/thumbs.db"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("photos/desktop.ini"), vec![0u8; 10]).unwrap();
+        std::fs::write(root.join("photos/notes.txt"), vec![0u8; 10]).unwrap();
+        // Goes away with node_modules
+        std::fs::write(root.join("app/node_modules/.DS_Store"), vec![0u8; 10]).unwrap();
+
+        let items = find_junk_files_in(&[root.to_path_buf()], None);
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().all(|i| i.safe_to_delete == SafetyLevel::Safe));
+        assert_eq!(items.iter().map(|i| i.size).sum::<u64>(), 6148 + 8196 + 100 + 10);
+
+        // Windows writes thumbs.db in either case; it's grouped under one name
+        let thumbs = items.iter().find(|i| i.name == "thumbs.db").unwrap();
+        assert_eq!(thumbs.subcategory, "Thumbs.db");
+        assert_eq!(items.iter().filter(|i| i.subcategory == ".DS_Store").count(), 2);
+
+        assert_eq!(find_junk_files_in(&[root.to_path_buf()], Some(1)).len(), 1);
+    }
 }
@@ -9,7 +9,7 @@
 
 use super::{calculate_dir_size, get_mtime, CleanableItem, SafetyLevel};
 use crate::error::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Xcode cleaner
 pub struct XcodeCleaner {
@@ -84,8 +84,16 @@ impl XcodeCleaner {
                         continue;
                     }
 
+                    // A Swift package opened in Xcode builds here, apart
+                    // from the .build/ that `swift build` uses
+                    let package = derived_data_workspace(&path).is_some_and(|w| is_swift_package(&w));
+                    let project = name.split('-').next().unwrap_or(&name);
                     items.push(CleanableItem {
-                        name: format!("DerivedData: {}", name.split('-').next().unwrap_or(&name)),
+                        name: if package {
+                            format!("DerivedData: {} (Swift package)", project)
+                        } else {
+                            format!("DerivedData: {}", project)
+                        },
                         category: "Xcode".to_string(),
                         subcategory: "DerivedData".to_string(),
                         icon: "🔨",
@@ -93,7 +101,11 @@ impl XcodeCleaner {
                         size,
                         file_count: Some(file_count),
                         last_modified: get_mtime(&entry.path()),
-                        description: "Build artifacts, indexes, logs. Safe to delete.",
+                        description: if package {
+                            "Xcode's build of a Swift package; its .build/ is separate. Safe to delete."
+                        } else {
+                            "Build artifacts, indexes, logs. Safe to delete."
+                        },
                         safe_to_delete: SafetyLevel::Safe,
                         clean_command: None,
                     });
@@ -346,6 +358,20 @@ impl XcodeCleaner {
     }
 }
 
+/// The workspace or package a DerivedData folder was built from, per its info.plist
+fn derived_data_workspace(dir: &Path) -> Option<PathBuf> {
+    let plist = std::fs::read_to_string(dir.join("info.plist")).ok()?;
+    let (_, rest) = plist.split_once("<key>WorkspacePath</key>")?;
+    let value = rest.trim_start().strip_prefix("<string>")?;
+    let (path, _) = value.split_once("</string>")?;
+    Some(PathBuf::from(path))
+}
+
+/// Whether Xcode opened a Swift package here rather than an Xcode project
+fn is_swift_package(workspace: &Path) -> bool {
+    workspace.join("Package.swift").is_file() || workspace.ends_with(".swiftpm/xcode/package.xcworkspace")
+}
+
 impl Default for XcodeCleaner {
     fn default() -> Self {
         Self::new().expect("XcodeCleaner requires home directory")
@@ -356,6 +382,30 @@ impl Default for XcodeCleaner {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_derived_data_of_swift_package() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let package = temp.path().join("Server");
+        std::fs::create_dir(&package).unwrap();
+        std::fs::write(package.join("Package.swift"), "").unwrap();
+        let derived = temp.path().join("Server-abcdef");
+        std::fs::create_dir(&derived).unwrap();
+        std::fs::write(
+            derived.join("info.plist"),
+            format!(
+                "<plist version=\"1.0\">\n<dict>\n\t<key>LastAccessedDate</key>\n\t<date>2026-01-01T00:00:00Z</date>\n\t<key>WorkspacePath</key>\n\t<string>{}</string>\n</dict>\n</plist>\n",
+                package.display()
+            ),
+        )
+        .unwrap();
+
+        let workspace = derived_data_workspace(&derived).unwrap();
+        assert_eq!(workspace, package);
+        assert!(is_swift_package(&workspace));
+        assert!(!is_swift_package(&temp.path().join("App/App.xcodeproj")));
+        assert!(derived_data_workspace(temp.path()).is_none());
+    }
+
     #[test]
     #[cfg(target_os = "macos")]
     fn test_xcode_cleaner_creation() {
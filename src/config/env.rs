@@ -0,0 +1,143 @@
+//! `NULL_E_*` environment variable overrides
+//!
+//! Lets a shell session or CI job change key settings without a config file.
+//! Precedence, lowest first: built-in defaults, config file, environment,
+//! command-line flags.
+
+use super::Config;
+use crate::error::{DevSweepError, Result};
+use crate::git::ProtectionLevel;
+use crate::trash::DeleteMethod;
+
+/// Minimum artifact size, e.g. `500MB` (`scan.min_size`)
+pub const ENV_MIN_SIZE: &str = "NULL_E_MIN_SIZE";
+/// `trash`, `permanent` or `dry-run` (`clean.delete_method`)
+pub const ENV_DELETE_METHOD: &str = "NULL_E_DELETE_METHOD";
+/// `none`, `warn`, `block` or `paranoid` (`clean.protection_level`)
+pub const ENV_PROTECTION: &str = "NULL_E_PROTECTION";
+/// Skip the scan cache when true (`scan.use_cache`)
+pub const ENV_NO_CACHE: &str = "NULL_E_NO_CACHE";
+/// Never delete permanently when true (`clean.safe_mode`)
+pub const ENV_SAFE_MODE: &str = "NULL_E_SAFE_MODE";
+
+impl Config {
+    /// Apply `NULL_E_*` overrides from the process environment
+    pub fn apply_env(&mut self) -> Result<()> {
+        self.apply_env_from(|name| std::env::var(name).ok())
+    }
+
+    /// Apply `NULL_E_*` overrides looked up through `var`
+    ///
+    /// Unset or empty variables leave the setting alone; invalid values are
+    /// a [`DevSweepError::Config`] naming the variable.
+    pub fn apply_env_from(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<()> {
+        let get = |name: &str| var(name).filter(|v| !v.trim().is_empty());
+
+        if let Some(value) = get(ENV_MIN_SIZE) {
+            let size = parse_size(&value).ok_or_else(|| invalid(ENV_MIN_SIZE, &value))?;
+            self.scan.min_size = Some(size);
+        }
+        if let Some(value) = get(ENV_DELETE_METHOD) {
+            self.clean.delete_method = DeleteMethod::from_str(value.trim())
+                .ok_or_else(|| invalid(ENV_DELETE_METHOD, &value))?;
+        }
+        if let Some(value) = get(ENV_PROTECTION) {
+            self.clean.protection_level = ProtectionLevel::from_str(value.trim())
+                .ok_or_else(|| invalid(ENV_PROTECTION, &value))?;
+        }
+        if let Some(value) = get(ENV_NO_CACHE) {
+            let no_cache = parse_bool(&value).ok_or_else(|| invalid(ENV_NO_CACHE, &value))?;
+            self.scan.use_cache = !no_cache;
+        }
+        if let Some(value) = get(ENV_SAFE_MODE) {
+            self.clean.safe_mode = parse_bool(&value).ok_or_else(|| invalid(ENV_SAFE_MODE, &value))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid(name: &str, value: &str) -> DevSweepError {
+    DevSweepError::Config(format!("Invalid value for {}: {:?}", name, value))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse a human-readable size such as `1GB`, `500MB`, `100K` or `1000`
+///
+/// Units are decimal (1 MB = 1,000,000 bytes).
+pub fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim().to_uppercase();
+
+    let (num_str, multiplier) = if s.ends_with("GB") {
+        (&s[..s.len() - 2], 1_000_000_000u64)
+    } else if s.ends_with("MB") {
+        (&s[..s.len() - 2], 1_000_000u64)
+    } else if s.ends_with("KB") {
+        (&s[..s.len() - 2], 1_000u64)
+    } else if s.ends_with('G') {
+        (&s[..s.len() - 1], 1_000_000_000u64)
+    } else if s.ends_with('M') {
+        (&s[..s.len() - 1], 1_000_000u64)
+    } else if s.ends_with('K') {
+        (&s[..s.len() - 1], 1_000u64)
+    } else {
+        (s.as_str(), 1u64)
+    };
+
+    num_str.trim().parse::<u64>().ok().map(|n| n * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn apply(vars: &[(&str, &str)]) -> Result<Config> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let mut config = Config::default();
+        config.apply_env_from(|name| vars.get(name).cloned())?;
+        Ok(config)
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let config = apply(&[
+            (ENV_MIN_SIZE, "500MB"),
+            (ENV_DELETE_METHOD, "permanent"),
+            (ENV_PROTECTION, "block"),
+            (ENV_NO_CACHE, "1"),
+            (ENV_SAFE_MODE, "yes"),
+        ])
+        .unwrap();
+        assert_eq!(config.scan.min_size, Some(500_000_000));
+        assert_eq!(config.clean.delete_method, DeleteMethod::Permanent);
+        assert_eq!(config.clean.protection_level, ProtectionLevel::Block);
+        assert!(!config.scan.use_cache);
+        assert!(config.clean.safe_mode);
+
+        // Empty values are ignored
+        let config = apply(&[(ENV_DELETE_METHOD, "")]).unwrap();
+        assert_eq!(config.clean.delete_method, DeleteMethod::Trash);
+    }
+
+    #[test]
+    fn test_env_overrides_reject_bad_values() {
+        for (name, value) in [
+            (ENV_MIN_SIZE, "huge"),
+            (ENV_DELETE_METHOD, "shred"),
+            (ENV_PROTECTION, "maximum"),
+            (ENV_NO_CACHE, "maybe"),
+        ] {
+            let err = apply(&[(name, value)]).unwrap_err();
+            assert!(matches!(&err, DevSweepError::Config(msg) if msg.contains(name)), "{}", err);
+        }
+    }
+}
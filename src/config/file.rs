@@ -1,7 +1,7 @@
 //! Configuration file loading and saving
 
 use super::Config;
-use crate::error::{DevSweepError, Result};
+use crate::error::{DevSweepError, Result, ResultExt};
 use std::path::{Path, PathBuf};
 
 /// Get the default config file path
@@ -36,21 +36,23 @@ pub fn load_config(path: &Path) -> Result<Config> {
     Ok(config)
 }
 
-/// Load configuration from default location
+/// Load configuration from default location, with `NULL_E_*` overrides applied
 pub fn load_default_config() -> Result<Config> {
     let path = default_config_path()?;
-    load_config(&path)
+    let mut config = load_config(&path)?;
+    config.apply_env()?;
+    Ok(config)
 }
 
 /// Save configuration to file
 pub fn save_config(config: &Config, path: &Path) -> Result<()> {
     // Ensure parent directory exists
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        std::fs::create_dir_all(parent).with_path(parent)?;
     }
 
     let content = toml::to_string_pretty(config)?;
-    std::fs::write(path, content)?;
+    std::fs::write(path, content).with_path(path)?;
 
     Ok(())
 }
@@ -106,6 +108,15 @@ ignore_patterns = []
 # Check git status for each project
 check_git_status = true
 
+# I/O priority: normal, low (be gentle on shared machines or on battery)
+io_priority = "normal"
+
+# Maximum directories visited per second (null = unthrottled)
+# throttle = 200
+
+# Reuse cached scan results (NULL_E_NO_CACHE=1 or --no-cache skip them)
+use_cache = true
+
 [clean]
 # Delete method: trash, permanent, dry-run
 delete_method = "trash"
@@ -122,6 +133,23 @@ auto_confirm = false
 # Dry run by default
 dry_run = false
 
+# Safe mode: never delete permanently. Irreversible official clean commands
+# (npm cache clean, docker prune, ...) are skipped and the cache directory is
+# moved to the trash instead.
+safe_mode = false
+
+# Skip caches and artifacts modified less than this many days ago when
+# cleaning everything at once (clean, caches --clean-all). Picking them by
+# hand or --force still cleans them; 0 turns this off. --min-age overrides.
+min_age_days = 1
+
+# Clean these without asking, prompting only for the rest. Entries are safety
+# levels (safe, safe-with-cost, caution, dangerous), "Category" or
+# "Category/Subcategory" names; global caches count as "Caches/<id>" at the
+# safe-with-cost level. --force still skips every prompt.
+# no_confirm = ["safe", "Caches/npm"]
+no_confirm = []
+
 [ui]
 # Color theme: dark, light, auto
 theme = "auto"
@@ -132,7 +160,7 @@ show_file_counts = true
 # Show last modified dates
 show_dates = true
 
-# Sort results by: size, name, date, kind
+# Sort results by: size, name, date, kind, score, files (--sort overrides)
 sort_by = "size"
 
 # Reverse sort order
@@ -141,12 +169,57 @@ sort_reverse = false
 # Use icons/emojis
 use_icons = true
 
+# Sizes: "apparent" (file lengths, like ls) or "disk" (allocated blocks, like
+# du/df - closer to what a clean actually frees with sparse or compressed files,
+# and blocks shared via APFS clones, reflinks or hard links aren't counted as freed)
+size_mode = "apparent"
+
+# Pick items for caches --clean, sweep --clean and the cleaner commands with
+# the arrow keys and Space instead of typing numbers (--picker). Falls back to
+# the numbered prompt when not in a terminal.
+picker = false
+
 [plugins]
 # Enabled plugins (empty = all)
 enabled = []
 
 # Disabled plugins
 disabled = []
+
+# Extra global caches shown by `caches` alongside the builtin ones.
+# Paths are relative to your home directory unless absolute; "$VAR/sub" starts
+# from an environment variable and is skipped when it isn't set.
+# [[caches.custom]]
+# id = "acme"
+# name = "ACME build cache"
+# paths = ["~/.acme/cache"]
+# clean_command = "acme cache purge"   # optional
+# ecosystem = "js"                      # optional: js, python, rust, go, jvm, ... (default: other)
+# description = "Artifacts downloaded by the acme CLI"
+
+# Override the safety level of cleaner items (sweep, docker, ide, ...) by
+# "Category" or "Category/Subcategory": safe, safe-with-cost, caution, dangerous
+# [safety.overrides]
+# "Docker/Volumes" = "safe"
+# "IDE" = "caution"
+
+# Named cleanup policies, applied with --profile NAME. Flags still override them.
+# [profiles.gentle]
+# protection_level = "block"
+# min_age_days = 30
+# max_safety = "safe"              # safe, safe-with-cost, caution, dangerous
+# categories = ["xcode", "docker"] # sweep categories; empty for all
+
+[notify]
+# Tell you when a clean finishes (e.g. from a nightly cron job). Failures to
+# notify never fail the clean.
+# Shell command; gets the JSON summary on stdin and NULL_E_BYTES_FREED,
+# NULL_E_CLEANED, NULL_E_FAILED, ... in its environment
+# command = "logger -t null-e"
+# URL the JSON summary is POSTed to (uses curl)
+# webhook = "https://hooks.example.com/null-e"
+# Desktop notification via notify-send, osascript or a Windows toast
+desktop = false
 "#.to_string()
 }
 
@@ -163,12 +236,12 @@ pub fn init_config() -> Result<PathBuf> {
 
     // Create parent directory
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent)?;
+        std::fs::create_dir_all(parent).with_path(parent)?;
     }
 
     // Write sample config
     let sample = generate_sample_config();
-    std::fs::write(&path, sample)?;
+    std::fs::write(&path, sample).with_path(&path)?;
 
     Ok(path)
 }
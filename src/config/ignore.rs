@@ -0,0 +1,228 @@
+//! Persistent ignore list
+//!
+//! Artifacts the user always wants to keep (a cached dataset, a pre-built
+//! dependency) are stored one path per line in `ignored.txt` next to the
+//! config file, so `clean` skips them on every run without re-filtering.
+
+use crate::core::Project;
+use crate::error::{DevSweepError, Result, ResultExt};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Set of ignored artifact paths
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IgnoreList {
+    paths: BTreeSet<PathBuf>,
+}
+
+impl IgnoreList {
+    /// Create an empty ignore list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse an ignore file (one path per line, `#` comments allowed)
+    pub fn parse(content: &str) -> Self {
+        let paths = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(PathBuf::from)
+            .collect();
+        Self { paths }
+    }
+
+    /// Load the ignore list from a file, empty if it doesn't exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| DevSweepError::ConfigParse {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+        Ok(Self::parse(&content))
+    }
+
+    /// Load the ignore list from the default location
+    pub fn load_default() -> Result<Self> {
+        Self::load(&default_ignore_path()?)
+    }
+
+    /// Save the ignore list to a file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_path(parent)?;
+        }
+
+        let mut content = String::from("# Artifacts null-e never cleans (null-e ignore <path>)\n");
+        for p in &self.paths {
+            content.push_str(&p.to_string_lossy());
+            content.push('\n');
+        }
+        std::fs::write(path, content).with_path(path)?;
+
+        Ok(())
+    }
+
+    /// Save the ignore list to the default location
+    pub fn save_default(&self) -> Result<()> {
+        self.save(&default_ignore_path()?)
+    }
+
+    /// Add a path, returning false if it was already ignored
+    pub fn add(&mut self, path: impl Into<PathBuf>) -> bool {
+        self.paths.insert(path.into())
+    }
+
+    /// Remove a path, returning false if it wasn't ignored
+    pub fn remove(&mut self, path: &Path) -> bool {
+        self.paths.remove(path)
+    }
+
+    /// Check whether a path is ignored, either directly or via an ignored parent
+    pub fn contains(&self, path: &Path) -> bool {
+        self.paths.iter().any(|ignored| path.starts_with(ignored))
+    }
+
+    /// Iterate over the ignored paths
+    pub fn iter(&self) -> impl Iterator<Item = &PathBuf> {
+        self.paths.iter()
+    }
+
+    /// Number of ignored paths
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Check if nothing is ignored
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Drop ignored artifacts from scanned projects, or just mark them when `show` is set
+    ///
+    /// Ignored artifacts never count towards a project's cleanable size, and
+    /// projects left with nothing are dropped. Returns how many artifacts
+    /// were ignored.
+    pub fn apply(&self, projects: &mut Vec<Project>, show: bool) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+
+        let mut count = 0;
+        for project in projects.iter_mut() {
+            for artifact in &mut project.artifacts {
+                let path = artifact.path.canonicalize().unwrap_or_else(|_| artifact.path.clone());
+                if self.contains(&path) {
+                    artifact.metadata.extra.insert("ignored".into(), "true".into());
+                    count += 1;
+                }
+            }
+
+            if !show {
+                project.artifacts.retain(|a| !a.is_ignored());
+            }
+            project.calculate_totals();
+            project.cleanable_size = project
+                .artifacts
+                .iter()
+                .filter(|a| !a.is_ignored())
+                .map(|a| a.size)
+                .sum();
+        }
+
+        if !show {
+            projects.retain(|p| !p.artifacts.is_empty());
+        }
+
+        count
+    }
+}
+
+/// Get the default ignore file path (next to the config file)
+pub fn default_ignore_path() -> Result<PathBuf> {
+    let config_path = super::default_config_path()?;
+    Ok(config_path.with_file_name("ignored.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ignore_list_contains() {
+        let list = IgnoreList::parse("# keep these\n/data/models\n\n  /work/app/node_modules  \n");
+        assert_eq!(list.len(), 2);
+        assert!(list.contains(Path::new("/data/models")));
+        assert!(list.contains(Path::new("/data/models/llama")));
+        assert!(list.contains(Path::new("/work/app/node_modules")));
+        assert!(!list.contains(Path::new("/data/models-old")));
+        assert!(!list.contains(Path::new("/work/app")));
+    }
+
+    #[test]
+    fn test_ignore_list_apply() {
+        use crate::core::{Artifact, ArtifactKind, ProjectKind};
+
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let project = |name: &str, artifacts: &[&str]| {
+            let mut project = Project::new(ProjectKind::NodeNpm, root.join(name));
+            for artifact in artifacts {
+                let path = root.join(name).join(artifact);
+                std::fs::create_dir_all(&path).unwrap();
+                let mut artifact = Artifact::new(path, ArtifactKind::Dependencies);
+                artifact.size = 100;
+                project.artifacts.push(artifact);
+            }
+            project.calculate_totals();
+            project
+        };
+        let scanned = vec![project("web", &["node_modules", "dist"]), project("kept", &["node_modules"])];
+        let mut ignore = IgnoreList::new();
+        ignore.add(root.join("web/dist"));
+        ignore.add(root.join("kept"));
+
+        let mut projects = scanned.clone();
+        assert_eq!(ignore.apply(&mut projects, false), 2);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].artifacts.len(), 1);
+        assert_eq!(projects[0].cleanable_size, 100);
+
+        // Shown, but still not counted as cleanable
+        let mut projects = scanned.clone();
+        assert_eq!(ignore.apply(&mut projects, true), 2);
+        assert_eq!(projects.len(), 2);
+        assert!(projects[0].artifacts[1].is_ignored());
+        assert_eq!(projects[0].cleanable_size, 100);
+        assert_eq!(projects[1].cleanable_size, 0);
+
+        let mut projects = scanned.clone();
+        assert_eq!(IgnoreList::new().apply(&mut projects, false), 0);
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].cleanable_size, 200);
+    }
+
+    #[test]
+    fn test_ignore_list_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("devsweep/ignored.txt");
+
+        let mut list = IgnoreList::load(&path).unwrap();
+        assert!(list.is_empty());
+        assert!(list.add("/a/target"));
+        assert!(!list.add("/a/target"));
+        assert!(list.add("/b/.venv"));
+        list.save(&path).unwrap();
+
+        let mut loaded = IgnoreList::load(&path).unwrap();
+        assert_eq!(loaded, list);
+        assert!(loaded.remove(Path::new("/a/target")));
+        assert!(!loaded.remove(Path::new("/a/target")));
+        assert_eq!(loaded.len(), 1);
+    }
+}
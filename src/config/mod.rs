@@ -3,16 +3,24 @@
 //! Handles loading, saving, and merging configuration from multiple sources:
 //! - Default values
 //! - Config file (~/.config/devsweep/config.toml)
-//! - Environment variables
+//! - Environment variables (`NULL_E_*`, see [`env`])
 //! - Command line arguments
 
+pub mod env;
 mod file;
+mod ignore;
 
+pub use env::parse_size;
 pub use file::*;
+pub use ignore::*;
 
+use crate::caches::CustomCacheDefinition;
+use crate::cleaners::{CleanableItem, SafetyLevel};
+use crate::core::{IoPriority, SizeMode};
 use crate::git::ProtectionLevel;
 use crate::trash::DeleteMethod;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Main configuration structure
@@ -29,6 +37,16 @@ pub struct Config {
     pub ui: UiSettings,
     /// Plugin settings
     pub plugins: PluginSettings,
+    /// Global cache settings
+    pub caches: CacheSettings,
+    /// Safety level overrides for cleaner items
+    pub safety: SafetySettings,
+    /// Completion notifications
+    pub notify: NotifySettings,
+    /// Local record of what cleans have freed
+    pub stats: StatsSettings,
+    /// Named cleanup policies, selected with `--profile <name>`
+    pub profiles: BTreeMap<String, ProfileSettings>,
 }
 
 impl Default for Config {
@@ -39,6 +57,11 @@ impl Default for Config {
             clean: CleanSettings::default(),
             ui: UiSettings::default(),
             plugins: PluginSettings::default(),
+            caches: CacheSettings::default(),
+            safety: SafetySettings::default(),
+            notify: NotifySettings::default(),
+            stats: StatsSettings::default(),
+            profiles: BTreeMap::new(),
         }
     }
 }
@@ -100,6 +123,12 @@ pub struct ScanSettings {
     pub parallelism: Option<usize>,
     /// Check git status for each project
     pub check_git_status: bool,
+    /// I/O priority: "normal" or "low" (idle I/O class plus throttle)
+    pub io_priority: IoPriority,
+    /// Maximum directories visited per second (None = unthrottled)
+    pub throttle: Option<u32>,
+    /// Reuse cached scan results when they are still valid
+    pub use_cache: bool,
 }
 
 impl Default for ScanSettings {
@@ -112,6 +141,9 @@ impl Default for ScanSettings {
             ignore_patterns: vec![],
             parallelism: None,
             check_git_status: true,
+            io_priority: IoPriority::Normal,
+            throttle: None,
+            use_cache: true,
         }
     }
 }
@@ -132,6 +164,14 @@ pub struct CleanSettings {
     pub auto_confirm: bool,
     /// Dry run by default
     pub dry_run: bool,
+    /// Never delete permanently: skip irreversible official commands and trash instead
+    pub safe_mode: bool,
+    /// Leave caches and artifacts modified fewer than this many days ago out
+    /// of unattended cleaning (0 turns the guard off)
+    pub min_age_days: u64,
+    /// Safety levels, `"Category"` or `"Category/Subcategory"` names cleaned
+    /// without a confirmation prompt, e.g. `["safe", "Docker/Build Cache"]`
+    pub no_confirm: Vec<String>,
 }
 
 impl Default for CleanSettings {
@@ -142,10 +182,38 @@ impl Default for CleanSettings {
             continue_on_error: true,
             auto_confirm: false,
             dry_run: false,
+            safe_mode: false,
+            min_age_days: 1,
+            no_confirm: vec![],
         }
     }
 }
 
+impl CleanSettings {
+    /// Whether an item can be cleaned without asking first
+    ///
+    /// An entry matches the item's safety level (`"safe"`, `"safe-with-cost"`,
+    /// ...), its category or its `Category/Subcategory`, case-insensitively.
+    /// A dangerous item only matches its exact `Category/Subcategory`.
+    pub fn skips_confirmation(&self, category: &str, subcategory: &str, level: SafetyLevel) -> bool {
+        use serde::de::IntoDeserializer;
+
+        let qualified = format!("{}/{}", category, subcategory);
+        self.no_confirm.iter().any(|entry| {
+            let entry = entry.trim();
+            if level == SafetyLevel::Dangerous {
+                return entry.eq_ignore_ascii_case(&qualified);
+            }
+            let lowered = entry.to_ascii_lowercase();
+            let de: serde::de::value::StrDeserializer<'_, serde::de::value::Error> =
+                lowered.as_str().into_deserializer();
+            SafetyLevel::deserialize(de).is_ok_and(|l| l == level)
+                || entry.eq_ignore_ascii_case(category)
+                || entry.eq_ignore_ascii_case(&qualified)
+        })
+    }
+}
+
 /// UI settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -156,12 +224,16 @@ pub struct UiSettings {
     pub show_file_counts: bool,
     /// Show last modified dates
     pub show_dates: bool,
-    /// Sort by (size, name, date, kind)
+    /// Sort by (size, name, date, kind, score, files)
     pub sort_by: String,
     /// Reverse sort order
     pub sort_reverse: bool,
     /// Use icons/emojis
     pub use_icons: bool,
+    /// Report apparent file sizes or the space allocated on disk
+    pub size_mode: SizeMode,
+    /// Pick items to clean with the arrow keys instead of typing numbers
+    pub picker: bool,
 }
 
 impl Default for UiSettings {
@@ -173,6 +245,8 @@ impl Default for UiSettings {
             sort_by: "size".into(),
             sort_reverse: false,
             use_icons: true,
+            size_mode: SizeMode::Apparent,
+            picker: false,
         }
     }
 }
@@ -185,6 +259,11 @@ pub struct PluginSettings {
     pub enabled: Vec<String>,
     /// Disabled plugins
     pub disabled: Vec<String>,
+    /// Rebuild commands for `clean --rebuild` by plugin id, replacing the plugin's own
+    ///
+    /// `{root}` stands for the project directory; an empty command turns
+    /// rebuilding off for that plugin.
+    pub rebuild: BTreeMap<String, String>,
 }
 
 impl Default for PluginSettings {
@@ -192,10 +271,129 @@ impl Default for PluginSettings {
         Self {
             enabled: vec![],
             disabled: vec![],
+            rebuild: BTreeMap::new(),
+        }
+    }
+}
+
+impl PluginSettings {
+    /// The configured rebuild command for `plugin_id`, falling back to the plugin's `default`
+    pub fn rebuild_command(&self, plugin_id: &str, default: Option<String>) -> Option<String> {
+        match self.rebuild.get(plugin_id) {
+            Some(command) if command.trim().is_empty() => None,
+            Some(command) => Some(command.clone()),
+            None => default,
+        }
+    }
+}
+
+/// Global cache settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    /// Additional caches merged with the builtin list
+    pub custom: Vec<CustomCacheDefinition>,
+}
+
+/// Safety level overrides for cleaner items
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetySettings {
+    /// Level per `"Category"` or `"Category/Subcategory"`, e.g. `"Docker/Volumes" = "safe"`
+    pub overrides: BTreeMap<String, SafetyLevel>,
+}
+
+impl SafetySettings {
+    /// Override for an item, if any
+    ///
+    /// Names match case-insensitively, and a `Category/Subcategory` entry
+    /// wins over one for the whole category.
+    pub fn level_for(&self, category: &str, subcategory: &str) -> Option<SafetyLevel> {
+        let find = |key: &str| {
+            self.overrides
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, level)| *level)
+        };
+        find(&format!("{}/{}", category, subcategory)).or_else(|| find(category))
+    }
+
+    /// Replace the detected safety level of `items` with the configured policy
+    pub fn apply(&self, items: &mut [CleanableItem]) {
+        if self.overrides.is_empty() {
+            return;
+        }
+        for item in items {
+            if let Some(level) = self.level_for(&item.category, &item.subcategory) {
+                item.safe_to_delete = level;
+            }
         }
     }
 }
 
+/// A named cleanup policy, e.g. `[profiles.gentle]`
+///
+/// Each setting left out falls back to the usual default; flags given on
+/// the command line still win over the profile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    /// Protection level for git repos
+    #[serde(with = "optional_protection_level_serde", skip_serializing_if = "Option::is_none")]
+    pub protection_level: Option<ProtectionLevel>,
+    /// Leave out items modified fewer than this many days ago
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_age_days: Option<u64>,
+    /// Riskiest safety level that gets cleaned; anything above it is left out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_safety: Option<SafetyLevel>,
+    /// Sweep categories to look at (`xcode`, `docker`, ...); empty for all
+    pub categories: Vec<String>,
+}
+
+impl Config {
+    /// The profile called `name`, or an error listing the defined ones
+    pub fn profile(&self, name: &str) -> crate::error::Result<&ProfileSettings> {
+        self.profiles.get(name).ok_or_else(|| {
+            let known = if self.profiles.is_empty() {
+                "none are defined under [profiles] in the config file".to_string()
+            } else {
+                format!("defined: {}", self.profiles.keys().cloned().collect::<Vec<_>>().join(", "))
+            };
+            crate::error::DevSweepError::Config(format!("Unknown profile '{}' ({})", name, known))
+        })
+    }
+}
+
+/// Completion notification settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifySettings {
+    /// Shell command run after a clean, with the JSON summary on stdin
+    pub command: Option<String>,
+    /// URL the JSON summary is POSTed to
+    pub webhook: Option<String>,
+    /// Show a desktop notification
+    pub desktop: bool,
+}
+
+impl NotifySettings {
+    /// Whether any notification target is configured
+    pub fn is_enabled(&self) -> bool {
+        self.desktop
+            || self.command.as_deref().is_some_and(|c| !c.trim().is_empty())
+            || self.webhook.as_deref().is_some_and(|u| !u.trim().is_empty())
+    }
+}
+
+/// Local usage statistics settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsSettings {
+    /// Append each clean's freed space to a local log read by `null-e stats`
+    pub enabled: bool,
+}
+
 // Custom serde implementations for enums
 
 mod delete_method_serde {
@@ -247,6 +445,28 @@ mod protection_level_serde {
     }
 }
 
+mod optional_protection_level_serde {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S>(level: &Option<ProtectionLevel>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match level {
+            Some(level) => super::protection_level_serde::serialize(level, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<ProtectionLevel>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::protection_level_serde::deserialize(deserializer).map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,6 +479,26 @@ mod tests {
         assert_eq!(config.clean.delete_method, DeleteMethod::Trash);
     }
 
+    #[test]
+    fn test_rebuild_command_overrides() {
+        let config: Config = toml::from_str(
+            r#"
+            [plugins.rebuild]
+            rust = "cargo build --release"
+            node = ""
+            "#,
+        )
+        .unwrap();
+        let plugins = &config.plugins;
+        assert_eq!(
+            plugins.rebuild_command("rust", Some("cargo build".into())).as_deref(),
+            Some("cargo build --release")
+        );
+        assert_eq!(plugins.rebuild_command("node", Some("npm install".into())), None);
+        assert_eq!(plugins.rebuild_command("go", Some("go build ./...".into())).as_deref(), Some("go build ./..."));
+        assert_eq!(plugins.rebuild_command("terraform", None), None);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
@@ -282,6 +522,14 @@ skip_hidden = false
 [clean]
 delete_method = "permanent"
 protection_level = "block"
+safe_mode = true
+min_age_days = 7
+
+[[caches.custom]]
+id = "acme"
+name = "ACME build cache"
+paths = ["~/.acme/cache"]
+clean_command = "acme cache purge"
 "#;
 
         let config: Config = toml::from_str(toml_str).unwrap();
@@ -291,5 +539,105 @@ protection_level = "block"
         assert!(!config.scan.skip_hidden);
         assert_eq!(config.clean.delete_method, DeleteMethod::Permanent);
         assert_eq!(config.clean.protection_level, ProtectionLevel::Block);
+        assert!(config.clean.safe_mode);
+        assert_eq!(config.clean.min_age_days, 7);
+        assert_eq!(config.caches.custom.len(), 1);
+        assert_eq!(config.caches.custom[0].paths, vec!["~/.acme/cache"]);
+        assert_eq!(config.caches.custom[0].icon, "📁");
+    }
+
+    #[test]
+    fn test_safety_overrides() {
+        let toml_str = r#"
+[safety.overrides]
+"Docker/Volumes" = "safe"
+"docker" = "caution"
+"IDE" = "dangerous"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let safety = &config.safety;
+        assert_eq!(safety.level_for("Docker", "Volumes"), Some(SafetyLevel::Safe));
+        assert_eq!(safety.level_for("Docker", "Build Cache"), Some(SafetyLevel::Caution));
+        assert_eq!(safety.level_for("Xcode", "DerivedData"), None);
+
+        let mut items = vec![CleanableItem {
+            name: "JetBrains caches".into(),
+            category: "IDE".into(),
+            subcategory: "JetBrains".into(),
+            icon: "💻",
+            path: PathBuf::from("/tmp/idea"),
+            size: 0,
+            file_count: None,
+            last_modified: None,
+            description: "",
+            safe_to_delete: SafetyLevel::Safe,
+            clean_command: None,
+        }];
+        safety.apply(&mut items);
+        assert_eq!(items[0].safe_to_delete, SafetyLevel::Dangerous);
+    }
+
+
+    #[test]
+    fn test_no_confirm_allowlist() {
+        let toml_str = r#"
+[clean]
+no_confirm = ["safe", "docker/build cache", "Caches"]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let clean = &config.clean;
+        assert!(clean.skips_confirmation("Xcode", "DerivedData", SafetyLevel::Safe));
+        assert!(clean.skips_confirmation("Docker", "Build Cache", SafetyLevel::Caution));
+        assert!(clean.skips_confirmation("Caches", "npm", SafetyLevel::SafeWithCost));
+        assert!(!clean.skips_confirmation("Docker", "Volumes", SafetyLevel::Dangerous));
+        assert!(!clean.skips_confirmation("Caches", "npm", SafetyLevel::Dangerous));
+        assert!(!clean.skips_confirmation("Xcode", "Archives", SafetyLevel::SafeWithCost));
+        assert!(!CleanSettings::default().skips_confirmation("Xcode", "DerivedData", SafetyLevel::Safe));
+
+        // Dangerous items need their exact name; a level or category isn't enough
+        let toml_str = r#"
+[clean]
+no_confirm = ["dangerous", "Docker", "Xcode/Archives"]
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let clean = &config.clean;
+        assert!(!clean.skips_confirmation("Docker", "Volumes", SafetyLevel::Dangerous));
+        assert!(!clean.skips_confirmation("System", "Trash", SafetyLevel::Dangerous));
+        assert!(clean.skips_confirmation("Docker", "Volumes", SafetyLevel::Caution));
+        assert!(clean.skips_confirmation("xcode", "archives", SafetyLevel::Dangerous));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let toml_str = r#"
+[profiles.gentle]
+protection_level = "block"
+min_age_days = 30
+max_safety = "safe"
+categories = ["xcode", "docker"]
+
+[profiles.aggressive]
+max_safety = "caution"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let gentle = config.profile("gentle").unwrap();
+        assert_eq!(gentle.protection_level, Some(ProtectionLevel::Block));
+        assert_eq!(gentle.min_age_days, Some(30));
+        assert_eq!(gentle.max_safety, Some(SafetyLevel::Safe));
+        assert_eq!(gentle.categories, ["xcode", "docker"]);
+
+        let aggressive = config.profile("aggressive").unwrap();
+        assert_eq!(aggressive.protection_level, None);
+        assert_eq!(aggressive.max_safety, Some(SafetyLevel::Caution));
+        assert!(aggressive.categories.is_empty());
+
+        let err = config.profile("nightly").unwrap_err().to_string();
+        assert!(err.contains("aggressive, gentle"), "{}", err);
+        assert!(SafetyLevel::Safe < SafetyLevel::SafeWithCost && SafetyLevel::Caution < SafetyLevel::Dangerous);
+
+        // Profiles survive a round trip through the file format
+        let reparsed: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(reparsed.profile("gentle").unwrap().protection_level, Some(ProtectionLevel::Block));
+        assert_eq!(reparsed.profile("aggressive").unwrap().protection_level, None);
     }
 }
@@ -7,8 +7,8 @@
 //! - Virtual environments (.venv)
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// A cleanable artifact within a project
@@ -49,9 +49,98 @@ impl Artifact {
             .unwrap_or("unknown")
     }
 
+    /// Get age in days since last modification
+    pub fn age_days(&self) -> Option<u64> {
+        self.age.map(|d| d.as_secs() / 86400)
+    }
+
+    /// Whether this was modified fewer than `min_age_days` days ago
+    ///
+    /// An unknown age never counts as recent.
+    pub fn is_recent(&self, min_age_days: u64) -> bool {
+        self.age_days().is_some_and(|days| days < min_age_days)
+    }
+
+    /// What deleting this artifact removes: its files, or else its path
+    pub fn paths(&self) -> Vec<&Path> {
+        if self.metadata.files.is_empty() {
+            vec![&self.path]
+        } else {
+            self.metadata.files.iter().map(PathBuf::as_path).collect()
+        }
+    }
+
+    /// Whether the project that produced this artifact no longer exists
+    pub fn is_orphaned(&self) -> bool {
+        self.metadata.extra.contains_key("orphaned")
+    }
+
+    /// Whether this is an in-repo `vendor/` directory, which some projects commit on purpose
+    pub fn is_vendored(&self) -> bool {
+        self.metadata.extra.contains_key("vendored")
+    }
+
+    /// Whether this is a `dist/`-style directory that package.json publishes from
+    pub fn is_publish_output(&self) -> bool {
+        self.metadata.extra.contains_key("publish_output")
+    }
+
+    /// Whether this artifact is on the user's ignore list (see [`IgnoreList::apply`](crate::config::IgnoreList::apply))
+    pub fn is_ignored(&self) -> bool {
+        self.metadata.extra.contains_key("ignored")
+    }
+
+    /// Whether sizing was cut off by `--timeout` or Ctrl-C, so the size is a lower bound
+    pub fn is_partial_size(&self) -> bool {
+        self.metadata.extra.contains_key("partial_size")
+    }
+
+    /// Why this artifact no longer works (e.g. a venv whose interpreter is gone)
+    pub fn broken_reason(&self) -> Option<&str> {
+        self.metadata.extra.get("broken").map(String::as_str)
+    }
+
+    /// Safety level of this artifact
+    ///
+    /// Orphaned and broken artifacts have nothing left to rebuild them for
+    /// or are unusable anyway, so they are always safe regardless of kind.
+    /// A publish output may be all there is of a release, so it needs confirmation.
+    pub fn safety(&self) -> ArtifactSafety {
+        if self.is_orphaned() || self.broken_reason().is_some() {
+            ArtifactSafety::AlwaysSafe
+        } else if self.is_publish_output() {
+            ArtifactSafety::RequiresConfirmation
+        } else {
+            self.kind.default_safety()
+        }
+    }
+
+    /// One-line, plain-English reason why this artifact is considered cleanable
+    ///
+    /// Covers what it is, how it comes back and why its safety level applies.
+    pub fn explain(&self) -> String {
+        let what = if self.is_orphaned() {
+            format!("{} left behind by a project that no longer exists", self.kind.description())
+        } else if let Some(reason) = self.broken_reason() {
+            format!("{} that no longer works ({})", self.kind.description(), reason)
+        } else if self.is_publish_output() {
+            format!("{} that the package publishes", self.kind.description())
+        } else {
+            self.kind.description().to_string()
+        };
+
+        let regenerate = match &self.metadata.restore_command {
+            Some(command) => format!("regenerated by `{}`", command),
+            None if self.metadata.restorable => "regenerated automatically".to_string(),
+            None => "not regenerated automatically".to_string(),
+        };
+
+        format!("{}: {}; {}; {}", self.name(), what, regenerate, self.safety().explain())
+    }
+
     /// Check if this artifact can be safely deleted based on its kind
     pub fn is_safe_to_clean(&self) -> bool {
-        match self.kind.default_safety() {
+        match self.safety() {
             ArtifactSafety::AlwaysSafe => true,
             ArtifactSafety::SafeIfGitClean => true, // Caller should check git
             ArtifactSafety::SafeWithLockfile => self.metadata.lockfile.is_some(),
@@ -201,6 +290,19 @@ pub enum ArtifactSafety {
     NeverAuto,
 }
 
+impl ArtifactSafety {
+    /// Plain-English reasoning behind this safety level
+    pub fn explain(&self) -> &'static str {
+        match self {
+            Self::AlwaysSafe => "always safe, nothing in it is needed",
+            Self::SafeIfGitClean => "safe as long as the project has no uncommitted changes",
+            Self::SafeWithLockfile => "safe when a lockfile pins what gets reinstalled",
+            Self::RequiresConfirmation => "may hold local state, so it needs confirmation",
+            Self::NeverAuto => "never cleaned automatically",
+        }
+    }
+}
+
 /// Additional metadata about an artifact
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ArtifactMetadata {
@@ -215,6 +317,14 @@ pub struct ArtifactMetadata {
     /// Custom properties from plugins
     #[serde(default)]
     pub extra: HashMap<String, String>,
+    /// Bytes of `size` shared with files outside the artifact (clones,
+    /// reflinks, snapshots, hard links), which deleting it won't free
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_size: Option<u64>,
+    /// Files making up an artifact that is scattered across a project (e.g.
+    /// build logs); `path` then only names the group and doesn't exist
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<PathBuf>,
 }
 
 impl ArtifactMetadata {
@@ -240,6 +350,49 @@ impl ArtifactMetadata {
     }
 }
 
+/// Restore commands for cleaned artifacts, grouped by command
+///
+/// Each command maps to the project roots it should be run in, so the clean
+/// summary can tell the user exactly how to get back what was deleted.
+#[derive(Debug, Clone, Default)]
+pub struct RestoreCommands {
+    by_command: BTreeMap<String, Vec<PathBuf>>,
+}
+
+impl RestoreCommands {
+    /// Create an empty set of restore commands
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a cleaned artifact, ignoring it unless it is restorable with a known command
+    pub fn add(&mut self, project_root: &Path, artifact: &Artifact) {
+        if !artifact.metadata.restorable {
+            return;
+        }
+        let Some(command) = &artifact.metadata.restore_command else {
+            return;
+        };
+
+        let roots = self.by_command.entry(command.clone()).or_default();
+        if !roots.iter().any(|r| r == project_root) {
+            roots.push(project_root.to_path_buf());
+        }
+    }
+
+    /// Check if no restore commands were recorded
+    pub fn is_empty(&self) -> bool {
+        self.by_command.is_empty()
+    }
+
+    /// Iterate over commands and the project roots they apply to
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[PathBuf])> {
+        self.by_command
+            .iter()
+            .map(|(command, roots)| (command.as_str(), roots.as_slice()))
+    }
+}
+
 /// Statistics about artifacts found during scan
 #[derive(Debug, Clone, Default)]
 pub struct ArtifactStats {
@@ -251,21 +404,48 @@ pub struct ArtifactStats {
     pub total_artifacts: usize,
     /// Stats by artifact kind
     pub by_kind: HashMap<ArtifactKind, KindStats>,
+    /// Path and size of the single largest artifact
+    pub largest: Option<(PathBuf, u64)>,
 }
 
 impl ArtifactStats {
+    /// Compute stats over a set of artifacts
+    pub fn from_artifacts<'a>(artifacts: impl IntoIterator<Item = &'a Artifact>) -> Self {
+        let mut stats = Self::default();
+        for artifact in artifacts {
+            stats.add(artifact);
+        }
+        stats
+    }
+
     /// Add an artifact to the stats
     pub fn add(&mut self, artifact: &Artifact) {
         self.total_size += artifact.size;
         self.total_files += artifact.file_count;
         self.total_artifacts += 1;
 
+        if self.largest.as_ref().is_none_or(|(_, size)| artifact.size > *size) {
+            self.largest = Some((artifact.path.clone(), artifact.size));
+        }
+
         let entry = self.by_kind.entry(artifact.kind).or_default();
         entry.count += 1;
         entry.total_size += artifact.size;
         entry.file_count += artifact.file_count;
     }
 
+    /// Mean artifact size in bytes (0 when there are no artifacts)
+    pub fn mean_size(&self) -> u64 {
+        self.total_size
+            .checked_div(self.total_artifacts as u64)
+            .unwrap_or(0)
+    }
+
+    /// Number of artifacts of a given kind
+    pub fn count_of(&self, kind: ArtifactKind) -> usize {
+        self.by_kind.get(&kind).map(|k| k.count).unwrap_or(0)
+    }
+
     /// Get the largest artifact kind by size
     pub fn largest_kind(&self) -> Option<(ArtifactKind, u64)> {
         self.by_kind
@@ -314,6 +494,12 @@ impl CleanResult {
         }
     }
 
+    /// Set the bytes that were actually freed, as verified after deletion
+    pub fn with_bytes_freed(mut self, bytes: u64) -> Self {
+        self.bytes_freed = bytes;
+        self
+    }
+
     /// Create a failed clean result
     pub fn failure(artifact: Artifact, error: impl Into<String>) -> Self {
         Self {
@@ -324,12 +510,110 @@ impl CleanResult {
             trashed: false,
         }
     }
+
+    /// The `--output ndjson` record for this result
+    pub fn record(&self, method: &str) -> CleanRecord {
+        if self.success {
+            CleanRecord::succeeded(&self.artifact.path, method, self.bytes_freed)
+        } else {
+            CleanRecord {
+                bytes_freed: self.bytes_freed,
+                ..CleanRecord::failed(&self.artifact.path, method, self.error.clone().unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// Outcome of cleaning one item, one line of `--output ndjson`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanRecord {
+    /// What was (or would have been) cleaned
+    pub path: PathBuf,
+    /// "trash", "permanent", "dry-run" or "command"
+    pub method: String,
+    /// Official clean command that ran, for the "command" method
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Whether the item was cleaned
+    pub success: bool,
+    /// Bytes actually freed
+    pub bytes_freed: u64,
+    /// Why cleaning failed or was skipped
+    pub error: Option<String>,
+}
+
+impl CleanRecord {
+    /// Record an item that was cleaned
+    pub fn succeeded(path: impl Into<PathBuf>, method: &str, bytes_freed: u64) -> Self {
+        Self {
+            path: path.into(),
+            method: method.to_string(),
+            command: None,
+            success: true,
+            bytes_freed,
+            error: None,
+        }
+    }
+
+    /// Record an item that couldn't be cleaned
+    pub fn failed(path: impl Into<PathBuf>, method: &str, error: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            method: method.to_string(),
+            command: None,
+            success: false,
+            bytes_freed: 0,
+            error: Some(error.into()),
+        }
+    }
+
+    /// Note the official clean command used for this item
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.method = "command".to_string();
+        self.command = Some(command.into());
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_explain() {
+        let mut artifact = Artifact::new(PathBuf::from("/p/node_modules"), ArtifactKind::Dependencies);
+        artifact.metadata = ArtifactMetadata::restorable("npm install");
+        assert_eq!(
+            artifact.explain(),
+            "node_modules: dependencies; regenerated by `npm install`; \
+             safe when a lockfile pins what gets reinstalled"
+        );
+
+        artifact.metadata.extra.insert("orphaned".into(), "true".into());
+        assert!(artifact.explain().contains("no longer exists"));
+        assert!(artifact.explain().ends_with("always safe, nothing in it is needed"));
+    }
+
+    #[test]
+    fn test_clean_record_json() {
+        let artifact = Artifact::new(PathBuf::from("/p/target"), ArtifactKind::BuildOutput);
+        let failed = CleanResult::failure(artifact.clone(), "Permission denied").record("trash");
+        assert_eq!(
+            serde_json::to_string(&failed).unwrap(),
+            r#"{"path":"/p/target","method":"trash","success":false,"bytes_freed":0,"error":"Permission denied"}"#
+        );
+
+        let cleaned = CleanResult::success(artifact, false).with_bytes_freed(42).record("permanent");
+        assert!(cleaned.success);
+        assert_eq!(cleaned.bytes_freed, 42);
+        assert_eq!(cleaned.error, None);
+
+        let command = CleanRecord::succeeded("/c/go-build", "permanent", 7).with_command("go clean -cache");
+        let json: serde_json::Value = serde_json::to_value(&command).unwrap();
+        assert_eq!(json["method"], "command");
+        assert_eq!(json["command"], "go clean -cache");
+    }
+
     #[test]
     fn test_artifact_kind_safety() {
         assert_eq!(
@@ -375,4 +659,35 @@ mod tests {
         assert_eq!(stats.total_files, 150);
         assert_eq!(stats.total_artifacts, 2);
     }
+
+    #[test]
+    fn test_restore_commands_grouping() {
+        let artifact = |path: &str, metadata: ArtifactMetadata| Artifact {
+            path: PathBuf::from(path),
+            kind: ArtifactKind::BuildOutput,
+            size: 0,
+            file_count: 0,
+            age: None,
+            metadata,
+        };
+
+        let cargo = ArtifactMetadata::restorable("cargo build");
+        let docker = ArtifactMetadata {
+            restorable: false,
+            restore_command: Some("docker pull".into()),
+            ..Default::default()
+        };
+
+        let mut commands = RestoreCommands::new();
+        commands.add(Path::new("/a"), &artifact("/a/target", cargo.clone()));
+        commands.add(Path::new("/b"), &artifact("/b/target", cargo.clone()));
+        commands.add(Path::new("/b"), &artifact("/b/target/x", cargo));
+        commands.add(Path::new("/c"), &artifact("/c/.cache", ArtifactMetadata::default()));
+        commands.add(Path::new("/d"), &artifact("/d/img", docker));
+
+        let grouped: Vec<_> = commands.iter().collect();
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(grouped[0].0, "cargo build");
+        assert_eq!(grouped[0].1, &[PathBuf::from("/a"), PathBuf::from("/b")]);
+    }
 }
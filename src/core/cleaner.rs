@@ -82,7 +82,7 @@ impl CleanConfig {
 #[derive(Debug, Clone)]
 pub enum CleanTarget {
     /// Clean all artifacts in a project
-    Project(Project),
+    Project(Box<Project>),
     /// Clean specific artifacts
     Artifacts(Vec<Artifact>),
     /// Clean specific paths
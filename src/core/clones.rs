@@ -0,0 +1,195 @@
+//! Blocks shared between files
+//!
+//! Copy-on-write filesystems let files share blocks: APFS `clonefile`,
+//! Btrfs/XFS reflinks and snapshots all do. Deleting such a file only frees
+//! its private blocks, so summing file sizes overstates what a clean reclaims.
+
+use std::fs::Metadata;
+use std::path::Path;
+
+/// Bytes of `path`'s allocation that are also used by another file or snapshot
+///
+/// Asks the filesystem: the private size on APFS, extent flags (FIEMAP) on
+/// Linux. `None` where the platform or filesystem can't tell, and for
+/// anything but regular files.
+pub fn cloned_bytes(path: &Path, metadata: &Metadata) -> Option<u64> {
+    if !metadata.is_file() || metadata.len() == 0 {
+        return None;
+    }
+    platform::cloned_bytes(path, metadata)
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::fs::Metadata;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    const FS_IOC_FIEMAP: libc::Ioctl = 0xC020_660B_u32 as libc::Ioctl;
+    const FIEMAP_EXTENT_LAST: u32 = 0x0000_0001;
+    const FIEMAP_EXTENT_SHARED: u32 = 0x0000_2000;
+    /// Extents fetched per ioctl
+    const BATCH: usize = 64;
+
+    #[repr(C)]
+    #[derive(Default, Clone, Copy)]
+    struct FiemapExtent {
+        fe_logical: u64,
+        fe_physical: u64,
+        fe_length: u64,
+        fe_reserved64: [u64; 2],
+        fe_flags: u32,
+        fe_reserved: [u32; 3],
+    }
+
+    #[repr(C)]
+    struct Fiemap {
+        fm_start: u64,
+        fm_length: u64,
+        fm_flags: u32,
+        fm_mapped_extents: u32,
+        fm_extent_count: u32,
+        fm_reserved: u32,
+        fm_extents: [FiemapExtent; BATCH],
+    }
+
+    pub fn cloned_bytes(path: &Path, metadata: &Metadata) -> Option<u64> {
+        let file = std::fs::File::open(path).ok()?;
+        let mut shared = 0u64;
+        let mut start = 0u64;
+
+        loop {
+            let mut map = Fiemap {
+                fm_start: start,
+                fm_length: u64::MAX - start,
+                fm_flags: 0,
+                fm_mapped_extents: 0,
+                fm_extent_count: BATCH as u32,
+                fm_reserved: 0,
+                fm_extents: [FiemapExtent::default(); BATCH],
+            };
+            // SAFETY: `map` is a properly sized, writable fiemap with room for
+            // `fm_extent_count` extents, and the fd stays open for the call
+            let rc = unsafe { libc::ioctl(file.as_raw_fd(), FS_IOC_FIEMAP, &mut map) };
+            if rc != 0 {
+                // EOPNOTSUPP on tmpfs and friends: no way to tell
+                return None;
+            }
+
+            let mapped = &map.fm_extents[..map.fm_mapped_extents as usize];
+            for extent in mapped {
+                if extent.fe_flags & FIEMAP_EXTENT_SHARED != 0 {
+                    shared += extent.fe_length;
+                }
+            }
+            match mapped.last() {
+                Some(last) if last.fe_flags & FIEMAP_EXTENT_LAST == 0 => {
+                    start = last.fe_logical + last.fe_length;
+                }
+                _ => break,
+            }
+        }
+
+        // Extents are block-rounded; never claim more than the file occupies
+        use std::os::unix::fs::MetadataExt;
+        Some(shared.min(metadata.blocks() * 512))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::ffi::CString;
+    use std::fs::Metadata;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    /// `getattrlist` reply: total length, then the private size
+    #[repr(C, packed(4))]
+    struct PrivateSizeReply {
+        length: u32,
+        private_size: libc::off_t,
+    }
+
+    pub fn cloned_bytes(path: &Path, metadata: &Metadata) -> Option<u64> {
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut attrs = libc::attrlist {
+            bitmapcount: libc::ATTR_BIT_MAP_COUNT,
+            reserved: 0,
+            commonattr: 0,
+            volattr: 0,
+            dirattr: 0,
+            fileattr: 0,
+            forkattr: libc::ATTR_CMNEXT_PRIVATESIZE,
+        };
+        let mut reply = PrivateSizeReply { length: 0, private_size: 0 };
+        // SAFETY: both buffers are valid for their sizes and outlive the call
+        let rc = unsafe {
+            libc::getattrlist(
+                c_path.as_ptr(),
+                &mut attrs as *mut _ as *mut libc::c_void,
+                &mut reply as *mut _ as *mut libc::c_void,
+                std::mem::size_of::<PrivateSizeReply>(),
+                libc::FSOPT_ATTR_CMN_EXTENDED | libc::FSOPT_NOFOLLOW,
+            )
+        };
+        if rc != 0 {
+            return None;
+        }
+
+        let private = u64::try_from(reply.private_size).ok()?;
+        Some((metadata.blocks() * 512).saturating_sub(private))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod platform {
+    use std::fs::Metadata;
+    use std::path::Path;
+
+    pub fn cloned_bytes(_path: &Path, _metadata: &Metadata) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_plain_file_shares_nothing() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("a.bin");
+        std::fs::write(&file, vec![7u8; 256 * 1024]).unwrap();
+        let meta = std::fs::metadata(&file).unwrap();
+
+        // Some filesystems (tmpfs) can't say; those that can find no sharing
+        assert!(matches!(cloned_bytes(&file, &meta), None | Some(0)));
+        assert_eq!(cloned_bytes(temp.path(), &std::fs::metadata(temp.path()).unwrap()), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_reflinked_copy_is_shared() {
+        let temp = TempDir::new().unwrap();
+        let original = temp.path().join("a.bin");
+        let copy = temp.path().join("b.bin");
+        std::fs::write(&original, vec![7u8; 256 * 1024]).unwrap();
+
+        // Only Btrfs/XFS and the like can clone; skip elsewhere
+        let cloned = std::process::Command::new("cp")
+            .arg("--reflink=always")
+            .arg(&original)
+            .arg(&copy)
+            .status()
+            .is_ok_and(|s| s.success());
+        if !cloned {
+            return;
+        }
+
+        let meta = std::fs::metadata(&copy).unwrap();
+        let allocated = crate::core::SizeMode::Disk.of(&meta);
+        assert_eq!(cloned_bytes(&copy, &meta), Some(allocated));
+    }
+}
@@ -0,0 +1,105 @@
+//! Long paths and reserved names on Windows
+//!
+//! Deeply nested `node_modules` routinely exceed the 260 characters the Win32
+//! path APIs accept, and packages now and then ship files named `nul` or
+//! `aux.js`, which the same APIs open as devices. Both work once a path
+//! carries the `\\?\` prefix, which hands it to the filesystem verbatim.
+
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// Longest path the Win32 APIs accept without the `\\?\` prefix
+pub const MAX_PATH: usize = 260;
+
+/// Names Windows reserves for devices, with or without an extension
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// `path` in a form the filesystem accepts at any length, for filesystem calls
+///
+/// On Windows this is the absolute path with the `\\?\` (or `\\?\UNC\`)
+/// prefix, so it may exceed [`MAX_PATH`] and end in a reserved name. Elsewhere,
+/// or when it can't be made absolute, it is `path` unchanged. Errors and
+/// output should still show the original path.
+pub fn extended_path(path: &Path) -> Cow<'_, Path> {
+    #[cfg(windows)]
+    {
+        // `C:\x\nul` resolves to the device `\\.\nul`, so only resolve the parent
+        let absolute = match (path.parent(), path.file_name()) {
+            (Some(parent), Some(name)) if is_reserved_name(name) => {
+                let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+                std::path::absolute(parent).map(|p| p.join(name))
+            }
+            _ => std::path::absolute(path),
+        };
+        if let Some(verbatim) = absolute.ok().as_deref().and_then(Path::to_str).and_then(to_verbatim) {
+            return Cow::Owned(verbatim.into());
+        }
+    }
+
+    Cow::Borrowed(path)
+}
+
+/// Prefix an absolute Windows path with `\\?\`
+///
+/// `None` for paths that already are verbatim or device paths, and for
+/// anything that isn't absolute.
+#[cfg(any(windows, test))]
+fn to_verbatim(absolute: &str) -> Option<String> {
+    if absolute.starts_with(r"\\?\") || absolute.starts_with(r"\\.\") {
+        return None;
+    }
+    if let Some(unc) = absolute.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{}", unc));
+    }
+    let bytes = absolute.as_bytes();
+    let drive_absolute = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\';
+    drive_absolute.then(|| format!(r"\\?\{}", absolute))
+}
+
+/// Whether Windows treats a file name as a device (`nul`, `CON.txt`, `com1 `)
+///
+/// The check ignores case, anything after the first dot, and trailing dots
+/// and spaces, like Windows does.
+pub fn is_reserved_name(name: &OsStr) -> bool {
+    let Some(name) = name.to_str() else {
+        return false;
+    };
+    let stem = name.split('.').next().unwrap_or_default().trim_end_matches([' ', '.']);
+    RESERVED_NAMES.iter().any(|reserved| stem.eq_ignore_ascii_case(reserved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_verbatim() {
+        assert_eq!(to_verbatim(r"C:\code\app\node_modules").as_deref(), Some(r"\\?\C:\code\app\node_modules"));
+        assert_eq!(to_verbatim(r"\\nas\share\app").as_deref(), Some(r"\\?\UNC\nas\share\app"));
+        assert_eq!(to_verbatim(r"\\?\C:\already"), None);
+        assert_eq!(to_verbatim(r"\\.\nul"), None);
+        assert_eq!(to_verbatim(r"code\app"), None);
+        assert_eq!(to_verbatim("/home/me/app"), None);
+    }
+
+    #[test]
+    fn test_is_reserved_name() {
+        for name in ["nul", "NUL", "con.txt", "aux.js", "Com1", "lpt9.tar.gz", "prn ", "nul."] {
+            assert!(is_reserved_name(OsStr::new(name)), "{}", name);
+        }
+        for name in ["null", "console", "com", "com10", "lpt0", "auxiliary.js", "node_modules"] {
+            assert!(!is_reserved_name(OsStr::new(name)), "{}", name);
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_extended_path_is_unchanged_elsewhere() {
+        let path = Path::new("relative/node_modules");
+        assert_eq!(extended_path(path), Cow::Borrowed(path));
+    }
+}
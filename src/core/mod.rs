@@ -4,13 +4,19 @@
 //! - Project detection and representation
 //! - Artifact types and metadata
 //! - Scanner and cleaner traits
+//! - Detection of blocks shared between files (clones, reflinks)
+//! - Long paths and reserved names on Windows
 
 mod project;
 mod artifact;
 mod scanner;
 mod cleaner;
+mod clones;
+mod long_paths;
 
 pub use project::*;
 pub use artifact::*;
 pub use scanner::*;
 pub use cleaner::*;
+pub use clones::*;
+pub use long_paths::*;
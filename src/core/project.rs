@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 use super::Artifact;
 
@@ -94,6 +94,7 @@ pub enum ProjectKind {
     SwiftSpm,
     SwiftXcode,
     Flutter,
+    Dart,
     ReactNative,
     Android,
 
@@ -156,6 +157,7 @@ impl ProjectKind {
             Self::SwiftSpm => "Swift (SPM)",
             Self::SwiftXcode => "Swift (Xcode)",
             Self::Flutter => "Flutter",
+            Self::Dart => "Dart",
             Self::ReactNative => "React Native",
             Self::Android => "Android",
             Self::Elixir => "Elixir",
@@ -187,6 +189,7 @@ impl ProjectKind {
             Self::PhpComposer | Self::PhpLaravel => "🐘",
             Self::SwiftSpm | Self::SwiftXcode => "🍎",
             Self::Flutter => "🦋",
+            Self::Dart => "🎯",
             Self::ReactNative => "⚛️",
             Self::Android => "🤖",
             Self::Elixir => "💧",
@@ -294,6 +297,18 @@ impl MarkerKind {
     }
 }
 
+impl std::fmt::Display for MarkerKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(name) => write!(f, "{}", name),
+            Self::Directory(name) => write!(f, "{}/", name),
+            Self::Extension(ext) => write!(f, "*.{}", ext),
+            Self::AllOf(files) => write!(f, "{}", files.join(" + ")),
+            Self::AnyOf(files) => write!(f, "{}", files.join(" or ")),
+        }
+    }
+}
+
 /// Git repository status for safety checks
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GitStatus {
@@ -382,6 +397,9 @@ pub struct Project {
     pub total_size: u64,
     /// Size that can be cleaned
     pub cleanable_size: u64,
+    /// ID of the plugin that claimed this project
+    #[serde(default)]
+    pub detected_by: Option<String>,
 }
 
 impl Project {
@@ -403,6 +421,7 @@ impl Project {
             artifacts: Vec::new(),
             total_size: 0,
             cleanable_size: 0,
+            detected_by: None,
         }
     }
 
@@ -445,7 +464,43 @@ impl Project {
     /// Calculate totals from artifacts
     pub fn calculate_totals(&mut self) {
         self.total_size = self.artifacts.iter().map(|a| a.size).sum();
-        self.cleanable_size = self.total_size; // Can apply rules later
+        // Shared blocks stay allocated after a clean
+        let shared: u64 = self.artifacts.iter().filter_map(|a| a.metadata.shared_size).sum();
+        self.cleanable_size = self.total_size.saturating_sub(shared);
+    }
+
+    /// Number of files across all artifacts
+    pub fn file_count(&self) -> u64 {
+        self.artifacts.iter().map(|a| a.file_count).sum()
+    }
+
+    /// Time since the most recently modified artifact was touched
+    pub fn artifact_age(&self) -> Option<Duration> {
+        self.artifacts.iter().filter_map(|a| a.age).min()
+    }
+
+    /// How worth cleaning this project is: cleanable size times months idle
+    ///
+    /// A huge `target/` built this morning scores near zero, while a
+    /// forgotten `node_modules` from last year scores high. Projects whose
+    /// artifact age is unknown are treated as freshly used.
+    pub fn cleanup_score(&self) -> f64 {
+        let months_idle = self
+            .artifact_age()
+            .map(|age| age.as_secs_f64() / (30.0 * 86400.0))
+            .unwrap_or(0.0);
+        self.cleanable_size as f64 * months_idle
+    }
+
+    /// Order by cleanable size (largest first), ties broken by root path
+    ///
+    /// Scan threads finish in arbitrary order, so the path keeps equal-sized
+    /// projects in the same place from one run to the next.
+    pub fn cmp_by_size(&self, other: &Self) -> std::cmp::Ordering {
+        other
+            .cleanable_size
+            .cmp(&self.cleanable_size)
+            .then_with(|| self.root.cmp(&other.root))
     }
 }
 
@@ -480,6 +535,40 @@ mod tests {
         assert_ne!(id1, id2);
     }
 
+    #[test]
+    fn test_cmp_by_size_breaks_ties_by_path() {
+        let project = |root: &str, size| {
+            let mut project = Project::new(ProjectKind::Rust, PathBuf::from(root));
+            project.cleanable_size = size;
+            project
+        };
+
+        let mut projects = [project("/b", 10), project("/c", 20), project("/a", 10)];
+        projects.sort_by(Project::cmp_by_size);
+
+        let roots: Vec<_> = projects.iter().map(|p| p.root.to_str().unwrap()).collect();
+        assert_eq!(roots, vec!["/c", "/a", "/b"]);
+    }
+
+    #[test]
+    fn test_cleanup_score_weights_by_age() {
+        let project = |size, age_days: Option<u64>| {
+            let mut artifact = Artifact::new(PathBuf::from("/p/target"), crate::core::ArtifactKind::BuildOutput);
+            artifact.size = size;
+            artifact.age = age_days.map(|d| Duration::from_secs(d * 86400));
+            let mut project = Project::new(ProjectKind::Rust, PathBuf::from("/p"));
+            project.artifacts = vec![artifact];
+            project.calculate_totals();
+            project
+        };
+
+        let fresh_and_huge = project(10_000_000_000, Some(0));
+        let old_and_small = project(500_000_000, Some(365));
+        assert!(old_and_small.cleanup_score() > fresh_and_huge.cleanup_score());
+        assert_eq!(project(1_000, Some(60)).cleanup_score(), 2_000.0);
+        assert_eq!(project(1_000, None).cleanup_score(), 0.0);
+    }
+
     #[test]
     fn test_marker_kind_matches() {
         let temp = tempfile::tempdir().unwrap();
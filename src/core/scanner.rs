@@ -3,12 +3,59 @@
 //! Defines the interface for scanning filesystems for projects and artifacts.
 
 use super::{Project, ArtifactStats};
-use crate::error::Result;
-use std::path::PathBuf;
+use crate::error::{DevSweepError, Result};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Directories per second a low-priority scan visits when no rate is given
+pub const DEFAULT_NICE_RATE: u32 = 200;
+
+/// I/O priority for scanning
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IoPriority {
+    /// Walk as fast as the disk allows
+    #[default]
+    Normal,
+    /// Idle I/O class where supported, plus a cooperative throttle
+    Low,
+}
+
+/// How file sizes are measured
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeMode {
+    /// File length as reported by `ls` (same on every platform)
+    #[default]
+    Apparent,
+    /// Space actually allocated on disk, like `du` and `df` report
+    ///
+    /// Accounts for block rounding, sparse files and filesystem compression.
+    /// Uses `st_blocks` on Unix; other platforms fall back to the apparent size.
+    /// Scans in this mode also find blocks shared with other files, see
+    /// [`disk_usage`](crate::plugins::disk_usage).
+    Disk,
+}
+
+impl SizeMode {
+    /// Size of a file with this mode
+    pub fn of(self, metadata: &std::fs::Metadata) -> u64 {
+        match self {
+            Self::Apparent => metadata.len(),
+            #[cfg(unix)]
+            Self::Disk => {
+                use std::os::unix::fs::MetadataExt;
+                metadata.blocks() * 512
+            }
+            #[cfg(not(unix))]
+            Self::Disk => metadata.len(),
+        }
+    }
+}
 
 /// Configuration for scanning operations
 #[derive(Debug, Clone)]
@@ -27,12 +74,26 @@ pub struct ScanConfig {
     pub ignore_patterns: Vec<String>,
     /// Minimum artifact size to report (bytes)
     pub min_size: Option<u64>,
+    /// Minimum number of files an artifact must hold to be reported
+    pub min_files: Option<u64>,
     /// Maximum number of projects to return
     pub limit: Option<usize>,
     /// Include git status check for each project
     pub check_git_status: bool,
     /// Timeout for the entire scan operation
     pub timeout: Option<Duration>,
+    /// Follow symbolic links to directories while walking
+    pub follow_symlinks: bool,
+    /// I/O priority of the scanner threads
+    pub io_priority: IoPriority,
+    /// Maximum directories visited per second by each walker (None = unthrottled)
+    pub throttle: Option<u32>,
+    /// How artifact sizes are measured
+    pub size_mode: SizeMode,
+    /// Estimate artifact sizes from their top-level entries instead of walking them
+    pub fast: bool,
+    /// Also collect log files and core dumps inside projects as a `Logs` artifact
+    pub build_logs: bool,
 }
 
 impl Default for ScanConfig {
@@ -45,9 +106,16 @@ impl Default for ScanConfig {
             respect_gitignore: true,
             ignore_patterns: vec![],
             min_size: None,
+            min_files: None,
             limit: None,
             check_git_status: true,
             timeout: None,
+            follow_symlinks: false,
+            io_priority: IoPriority::Normal,
+            throttle: None,
+            size_mode: SizeMode::Apparent,
+            fast: false,
+            build_logs: false,
         }
     }
 }
@@ -67,6 +135,12 @@ impl ScanConfig {
         self
     }
 
+    /// Add several root directories
+    pub fn with_roots<P: Into<PathBuf>>(mut self, roots: impl IntoIterator<Item = P>) -> Self {
+        self.roots.extend(roots.into_iter().map(Into::into));
+        self
+    }
+
     /// Set maximum depth
     pub fn with_max_depth(mut self, depth: usize) -> Self {
         self.max_depth = Some(depth);
@@ -91,6 +165,15 @@ impl ScanConfig {
         self
     }
 
+    /// Set minimum file count filter
+    ///
+    /// With [`fast`](Self::fast) the count is only the artifact's top-level
+    /// entries, so this filters on that estimate.
+    pub fn with_min_files(mut self, count: u64) -> Self {
+        self.min_files = Some(count);
+        self
+    }
+
     /// Set result limit
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
@@ -103,11 +186,72 @@ impl ScanConfig {
         self
     }
 
+    /// Also walk hidden files and directories
+    pub fn with_hidden(mut self) -> Self {
+        self.skip_hidden = false;
+        self
+    }
+
+    /// Walk paths listed in .gitignore files too
+    pub fn without_gitignore(mut self) -> Self {
+        self.respect_gitignore = false;
+        self
+    }
+
     /// Set timeout
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
     }
+
+    /// Follow symbolic links to directories
+    pub fn with_follow_symlinks(mut self) -> Self {
+        self.follow_symlinks = true;
+        self
+    }
+
+    /// Set I/O priority
+    pub fn with_io_priority(mut self, priority: IoPriority) -> Self {
+        self.io_priority = priority;
+        self
+    }
+
+    /// Limit the walk to `dirs_per_sec` directories per second
+    pub fn with_throttle(mut self, dirs_per_sec: u32) -> Self {
+        self.throttle = Some(dirs_per_sec);
+        self
+    }
+
+    /// Measure artifact sizes with `mode`
+    pub fn with_size_mode(mut self, mode: SizeMode) -> Self {
+        self.size_mode = mode;
+        self
+    }
+
+    /// Estimate artifact sizes instead of measuring them (see [`ScanConfig::fast`])
+    pub fn with_fast(mut self) -> Self {
+        self.fast = true;
+        self
+    }
+
+    /// Collect build logs inside projects (see [`ScanConfig::build_logs`])
+    pub fn with_build_logs(mut self) -> Self {
+        self.build_logs = true;
+        self
+    }
+
+    /// Directory rate to throttle to, if any
+    ///
+    /// An explicit `throttle` wins; a low I/O priority without one falls
+    /// back to [`DEFAULT_NICE_RATE`].
+    pub fn effective_throttle(&self) -> Option<u32> {
+        match (self.throttle, self.io_priority) {
+            (Some(rate), _) => Some(rate),
+            (None, IoPriority::Low) => Some(DEFAULT_NICE_RATE),
+            (None, IoPriority::Normal) => None,
+        }
+        .filter(|&rate| rate > 0)
+    }
 }
 
 /// Real-time scan progress information
@@ -127,6 +271,8 @@ pub struct ScanProgress {
     pub is_complete: std::sync::atomic::AtomicBool,
     /// Whether scan was cancelled
     pub is_cancelled: std::sync::atomic::AtomicBool,
+    /// Whether scan stopped early because its timeout elapsed
+    pub is_timed_out: std::sync::atomic::AtomicBool,
 }
 
 impl ScanProgress {
@@ -175,6 +321,16 @@ impl ScanProgress {
         self.is_cancelled.load(Ordering::Acquire)
     }
 
+    /// Record that the scan deadline was reached
+    pub fn mark_timed_out(&self) {
+        self.is_timed_out.store(true, Ordering::Release);
+    }
+
+    /// Check if the scan deadline was reached
+    pub fn is_timed_out(&self) -> bool {
+        self.is_timed_out.load(Ordering::Acquire)
+    }
+
     /// Get snapshot of current progress
     pub fn snapshot(&self) -> ProgressSnapshot {
         ProgressSnapshot {
@@ -200,7 +356,7 @@ pub struct ProgressSnapshot {
 }
 
 /// Error encountered during scan
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanError {
     /// Path where error occurred
     pub path: PathBuf,
@@ -208,6 +364,9 @@ pub struct ScanError {
     pub message: String,
     /// Whether this error is recoverable
     pub recoverable: bool,
+    /// Whether the path could not be read, leaving its contents out of the scan
+    #[serde(default)]
+    pub unreadable: bool,
 }
 
 impl ScanError {
@@ -217,6 +376,15 @@ impl ScanError {
             path,
             message: message.into(),
             recoverable: true,
+            unreadable: false,
+        }
+    }
+
+    /// Create an error for a directory that could not be read
+    pub fn unreadable(path: PathBuf, message: impl Into<String>) -> Self {
+        Self {
+            unreadable: true,
+            ..Self::new(path, message)
         }
     }
 
@@ -226,11 +394,51 @@ impl ScanError {
             path,
             message: message.into(),
             recoverable: false,
+            unreadable: false,
         }
     }
 }
 
+/// Where a scan's time went
+///
+/// Phases run on several threads at once, so each is the time summed over
+/// threads, and together they can exceed the scan's wall-clock duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhaseTimings {
+    /// Walking directories
+    pub traversal: Duration,
+    /// Recognizing projects and listing their artifacts
+    pub detection: Duration,
+    /// Measuring artifact sizes
+    pub sizing: Duration,
+    /// Reading each project's git status, if that ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<Duration>,
+}
+
+impl PhaseTimings {
+    /// One line, e.g. `traversal 1.20s, detection 0.31s, sizing 4.50s`
+    pub fn summary(&self) -> String {
+        let mut phases = vec![
+            ("traversal", self.traversal),
+            ("detection", self.detection),
+            ("sizing", self.sizing),
+        ];
+        phases.extend(self.git.map(|git| ("git", git)));
+        phases
+            .iter()
+            .map(|(name, time)| format!("{} {:.2}s", name, time.as_secs_f64()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 /// Result of a complete scan
+///
+/// Serializes with a top-level `schema_version` (see
+/// [`ScanResult::SCHEMA_VERSION`]); `stats` is not serialized and is
+/// recomputed from `projects` when reading.
 #[derive(Debug)]
 pub struct ScanResult {
     /// Projects found
@@ -247,9 +455,46 @@ pub struct ScanResult {
     pub errors: Vec<ScanError>,
     /// Statistics by artifact kind
     pub stats: ArtifactStats,
+    /// Whether the scan stopped early (timeout) and results are incomplete
+    pub is_partial: bool,
+    /// Time spent in each phase of the scan
+    pub phase_timings: PhaseTimings,
 }
 
 impl ScanResult {
+    /// Version of the serialized format - bump this when fields change meaning or go away
+    ///
+    /// Version 0 is the unversioned layout: the same fields without
+    /// `schema_version`, or a bare array of projects.
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    /// Parse a serialized scan result, including ones written by older versions
+    ///
+    /// Accepts every schema up to [`Self::SCHEMA_VERSION`]; a newer one is a
+    /// [`DevSweepError::UnsupportedSchema`] rather than a best-effort guess.
+    pub fn from_json_compat(json: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let wire = if value.is_array() {
+            ScanResultWire {
+                projects: serde_json::from_value(value)?,
+                ..Default::default()
+            }
+        } else {
+            let found = value
+                .get("schema_version")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(0);
+            if found > u64::from(Self::SCHEMA_VERSION) {
+                return Err(DevSweepError::UnsupportedSchema {
+                    found: u32::try_from(found).unwrap_or(u32::MAX),
+                    supported: Self::SCHEMA_VERSION,
+                });
+            }
+            serde_json::from_value(value)?
+        };
+        Ok(wire.into())
+    }
+
     /// Get the number of projects
     pub fn project_count(&self) -> usize {
         self.projects.len()
@@ -260,25 +505,108 @@ impl ScanResult {
         self.projects.iter().map(|p| p.artifacts.len()).sum()
     }
 
+    /// Directories whose contents were skipped because they couldn't be read
+    pub fn unreadable_paths(&self) -> Vec<&Path> {
+        self.errors
+            .iter()
+            .filter(|e| e.unreadable)
+            .map(|e| e.path.as_path())
+            .collect()
+    }
+
+    /// Aggregate statistics over the artifacts of the returned projects
+    ///
+    /// Unlike the `stats` field, which the scanner fills in once, this is
+    /// recomputed from `projects` and so reflects any filtering done since.
+    pub fn stats(&self) -> ArtifactStats {
+        ArtifactStats::from_artifacts(self.projects.iter().flat_map(|p| &p.artifacts))
+    }
+
     /// Get projects sorted by cleanable size (descending)
     pub fn projects_by_size(&self) -> Vec<&Project> {
         let mut projects: Vec<_> = self.projects.iter().collect();
-        projects.sort_by(|a, b| b.cleanable_size.cmp(&a.cleanable_size));
+        projects.sort_by(|a, b| a.cmp_by_size(b));
         projects
     }
 
     /// Get human-readable summary
     pub fn summary(&self) -> String {
         format!(
-            "Found {} projects with {} cleanable across {} artifacts in {:.2}s",
+            "Found {} projects with {} cleanable across {} artifacts in {:.2}s{}",
             self.projects.len(),
             humansize::format_size(self.total_cleanable, humansize::BINARY),
             self.artifact_count(),
-            self.duration.as_secs_f64()
+            self.duration.as_secs_f64(),
+            if self.is_partial { " (partial: scan timed out)" } else { "" }
         )
     }
 }
 
+impl Serialize for ScanResult {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ScanResult", 9)?;
+        state.serialize_field("schema_version", &Self::SCHEMA_VERSION)?;
+        state.serialize_field("projects", &self.projects)?;
+        state.serialize_field("total_size", &self.total_size)?;
+        state.serialize_field("total_cleanable", &self.total_cleanable)?;
+        state.serialize_field("duration", &self.duration)?;
+        state.serialize_field("directories_scanned", &self.directories_scanned)?;
+        state.serialize_field("errors", &self.errors)?;
+        state.serialize_field("is_partial", &self.is_partial)?;
+        state.serialize_field("phase_timings", &self.phase_timings)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ScanResult {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let wire = ScanResultWire::deserialize(deserializer)?;
+        if wire.schema_version > Self::SCHEMA_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported scan result schema version {} (supported up to {})",
+                wire.schema_version,
+                Self::SCHEMA_VERSION
+            )));
+        }
+        Ok(wire.into())
+    }
+}
+
+/// Serialized form of [`ScanResult`]; every field but `projects` may be
+/// missing in version 0 documents
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ScanResultWire {
+    schema_version: u32,
+    projects: Vec<Project>,
+    total_size: u64,
+    total_cleanable: u64,
+    duration: Duration,
+    directories_scanned: usize,
+    errors: Vec<ScanError>,
+    is_partial: bool,
+    phase_timings: PhaseTimings,
+}
+
+impl From<ScanResultWire> for ScanResult {
+    fn from(wire: ScanResultWire) -> Self {
+        let stats = ArtifactStats::from_artifacts(wire.projects.iter().flat_map(|p| &p.artifacts));
+        Self {
+            projects: wire.projects,
+            total_size: wire.total_size,
+            total_cleanable: wire.total_cleanable,
+            duration: wire.duration,
+            directories_scanned: wire.directories_scanned,
+            errors: wire.errors,
+            stats,
+            is_partial: wire.is_partial,
+            phase_timings: wire.phase_timings,
+        }
+    }
+}
+
 /// Trait for implementing scanners
 pub trait Scanner: Send + Sync {
     /// Run the scan with the given configuration
@@ -311,6 +639,44 @@ mod tests {
         assert_eq!(config.ignore_patterns.len(), 1);
     }
 
+    #[test]
+    fn test_scan_config_setters() {
+        let default = ScanConfig::default();
+        let config = ScanConfig::default()
+            .with_roots(["/a", "/b"])
+            .with_parallelism(4)
+            .with_limit(10)
+            .without_git_check()
+            .with_hidden()
+            .without_gitignore()
+            .with_timeout(Duration::from_secs(30))
+            .with_follow_symlinks()
+            .with_io_priority(IoPriority::Low)
+            .with_throttle(100)
+            .with_size_mode(SizeMode::Disk)
+            .with_fast()
+            .with_build_logs();
+
+        assert_eq!(config.roots, vec![PathBuf::from("/a"), PathBuf::from("/b")]);
+        assert_eq!(config.parallelism, Some(4));
+        assert_eq!(config.limit, Some(10));
+        assert!(default.check_git_status && !config.check_git_status);
+        assert!(default.skip_hidden && !config.skip_hidden);
+        assert!(default.respect_gitignore && !config.respect_gitignore);
+        assert_eq!(config.timeout, Some(Duration::from_secs(30)));
+        assert!(!default.follow_symlinks && config.follow_symlinks);
+        assert_eq!(config.io_priority, IoPriority::Low);
+        assert_eq!(config.throttle, Some(100));
+        assert_eq!(config.size_mode, SizeMode::Disk);
+        assert!(!default.fast && config.fast);
+        assert!(!default.build_logs && config.build_logs);
+
+        // Untouched settings keep their defaults
+        assert_eq!(config.max_depth, None);
+        assert_eq!(config.min_size, None);
+        assert!(config.ignore_patterns.is_empty());
+    }
+
     #[test]
     fn test_scan_progress() {
         let progress = ScanProgress::new();
@@ -334,4 +700,152 @@ mod tests {
         progress.cancel();
         assert!(progress.is_cancelled());
     }
+
+    #[test]
+    fn test_partial_result_summary() {
+        let result = ScanResult {
+            projects: vec![],
+            total_size: 0,
+            total_cleanable: 0,
+            duration: Duration::from_secs(5),
+            directories_scanned: 10,
+            errors: vec![],
+            stats: ArtifactStats::default(),
+            is_partial: true,
+            phase_timings: PhaseTimings::default(),
+        };
+
+        assert!(result.summary().contains("partial"));
+    }
+
+    #[test]
+    fn test_phase_timings_summary() {
+        let mut timings = PhaseTimings {
+            traversal: Duration::from_millis(1200),
+            detection: Duration::from_millis(310),
+            sizing: Duration::from_millis(4500),
+            git: None,
+        };
+        assert_eq!(timings.summary(), "traversal 1.20s, detection 0.31s, sizing 4.50s");
+        timings.git = Some(Duration::from_secs(2));
+        assert!(timings.summary().ends_with(", git 2.00s"));
+    }
+
+    #[test]
+    fn test_effective_throttle() {
+        let config = ScanConfig::new("/code");
+        assert_eq!(config.effective_throttle(), None);
+
+        let config = config.with_io_priority(IoPriority::Low);
+        assert_eq!(config.effective_throttle(), Some(DEFAULT_NICE_RATE));
+
+        let config = config.with_throttle(50);
+        assert_eq!(config.effective_throttle(), Some(50));
+
+        // A zero rate means "no throttle", not "never make progress"
+        assert_eq!(ScanConfig::new("/code").with_throttle(0).effective_throttle(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_size_mode_sparse_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("sparse.img");
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_len(64 * 1024 * 1024).unwrap();
+
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert_eq!(SizeMode::Apparent.of(&metadata), 64 * 1024 * 1024);
+        // Nothing was written, so (almost) nothing is allocated
+        assert!(SizeMode::Disk.of(&metadata) < 1024 * 1024);
+    }
+
+    #[test]
+    fn test_scan_result_stats() {
+        use crate::core::{Artifact, ArtifactKind, Project, ProjectKind};
+
+        let artifact = |path: &str, kind, size| {
+            let mut artifact = Artifact::new(PathBuf::from(path), kind);
+            artifact.size = size;
+            artifact
+        };
+
+        let mut web = Project::new(ProjectKind::NodeNpm, PathBuf::from("/p/web"));
+        web.artifacts = vec![
+            artifact("/p/web/node_modules", ArtifactKind::Dependencies, 600),
+            artifact("/p/web/.cache", ArtifactKind::Cache, 100),
+        ];
+        let mut api = Project::new(ProjectKind::Rust, PathBuf::from("/p/api"));
+        api.artifacts = vec![artifact("/p/api/target", ArtifactKind::BuildOutput, 800)];
+
+        let result = ScanResult {
+            projects: vec![web, api],
+            total_size: 1500,
+            total_cleanable: 1500,
+            duration: Duration::from_secs(1),
+            directories_scanned: 3,
+            errors: vec![],
+            stats: ArtifactStats::default(),
+            is_partial: false,
+            phase_timings: PhaseTimings::default(),
+        };
+
+        let stats = result.stats();
+        assert_eq!(stats.total_artifacts, 3);
+        assert_eq!(stats.total_size, 1500);
+        assert_eq!(stats.mean_size(), 500);
+        assert_eq!(stats.count_of(ArtifactKind::Dependencies), 1);
+        assert_eq!(stats.count_of(ArtifactKind::Logs), 0);
+        assert_eq!(stats.largest, Some((PathBuf::from("/p/api/target"), 800)));
+        assert_eq!(stats.largest_kind(), Some((ArtifactKind::BuildOutput, 800)));
+        assert_eq!(ArtifactStats::default().mean_size(), 0);
+    }
+
+    #[test]
+    fn test_scan_result_schema_compat() {
+        use crate::core::{Artifact, ArtifactKind, Project, ProjectKind};
+
+        let mut project = Project::new(ProjectKind::Rust, PathBuf::from("/p/api"));
+        let mut target = Artifact::new(PathBuf::from("/p/api/target"), ArtifactKind::BuildOutput);
+        target.size = 800;
+        project.artifacts.push(target);
+        let result = ScanResult {
+            projects: vec![project.clone()],
+            total_size: 800,
+            total_cleanable: 800,
+            duration: Duration::from_millis(1500),
+            directories_scanned: 3,
+            errors: vec![ScanError::new(PathBuf::from("/p/locked"), "permission denied")],
+            stats: ArtifactStats::default(),
+            is_partial: true,
+            phase_timings: PhaseTimings {
+                sizing: Duration::from_millis(900),
+                git: Some(Duration::from_millis(200)),
+                ..Default::default()
+            },
+        };
+
+        // Current schema round-trips, with stats recomputed on read
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.starts_with(r#"{"schema_version":1,"#));
+        let read = ScanResult::from_json_compat(&json).unwrap();
+        assert_eq!(read.duration, result.duration);
+        assert_eq!(read.errors[0].path, PathBuf::from("/p/locked"));
+        assert!(read.is_partial);
+        assert_eq!(read.stats.total_size, 800);
+        assert_eq!(read.phase_timings, result.phase_timings);
+
+        // Version 0: unversioned object, or just the projects
+        let v0 = json.replacen(r#""schema_version":1,"#, "", 1);
+        assert_eq!(ScanResult::from_json_compat(&v0).unwrap().directories_scanned, 3);
+        let projects = serde_json::to_string(&vec![project]).unwrap();
+        assert_eq!(ScanResult::from_json_compat(&projects).unwrap().artifact_count(), 1);
+
+        let future = json.replacen(r#""schema_version":1"#, r#""schema_version":2"#, 1);
+        assert!(matches!(
+            ScanResult::from_json_compat(&future),
+            Err(DevSweepError::UnsupportedSchema { found: 2, supported: 1 })
+        ));
+        assert!(serde_json::from_str::<ScanResult>(&future).is_err());
+    }
 }
@@ -50,8 +50,12 @@ impl DockerArtifactType {
     }
 }
 
-/// Check if Docker is available
+/// Check if Docker is available (and allowed to be run)
 pub fn is_docker_available() -> bool {
+    if !crate::cleaners::docker_enabled() {
+        return false;
+    }
+
     Command::new("docker")
         .arg("version")
         .output()
@@ -93,9 +93,18 @@ pub enum DevSweepError {
     #[error("Failed to clean {}: {}", .path.display(), .reason)]
     CleanFailed { path: PathBuf, reason: String },
 
+    #[error("{} is in use: {}", .path.display(), .reason)]
+    InUse { path: PathBuf, reason: String },
+
     #[error("Partial clean failure: {succeeded} succeeded, {failed} failed")]
     PartialCleanFailure { succeeded: usize, failed: usize },
 
+    #[error("`{0}` was interrupted")]
+    CommandInterrupted(String),
+
+    #[error("`{command}` timed out after {secs} seconds")]
+    CommandTimeout { command: String, secs: u64 },
+
     // ═══════════════════════════════════════════════════════════════
     // Config Errors
     // ═══════════════════════════════════════════════════════════════
@@ -129,6 +138,9 @@ pub enum DevSweepError {
     #[error("TOML serialize error: {0}")]
     TomlSerialize(#[from] toml::ser::Error),
 
+    #[error("Unsupported schema version {found} (this version of null-e reads up to {supported})")]
+    UnsupportedSchema { found: u32, supported: u32 },
+
     // ═══════════════════════════════════════════════════════════════
     // Generic
     // ═══════════════════════════════════════════════════════════════
@@ -160,10 +172,18 @@ impl DevSweepError {
         }
     }
 
+    /// The underlying error with any added context stripped
+    pub fn root_cause(&self) -> &DevSweepError {
+        match self {
+            Self::WithContext { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+
     /// Check if this error is recoverable (operation can continue)
     pub fn is_recoverable(&self) -> bool {
         matches!(
-            self,
+            self.root_cause(),
             Self::PathNotFound(_)
                 | Self::PermissionDenied(_)
                 | Self::ScanInterrupted
@@ -175,27 +195,30 @@ impl DevSweepError {
 
     /// Check if this error is a user-caused interruption
     pub fn is_user_interrupt(&self) -> bool {
-        matches!(self, Self::ScanInterrupted)
+        matches!(self.root_cause(), Self::ScanInterrupted | Self::CommandInterrupted(_))
     }
 
     /// Get a suggested action for the user
     pub fn suggested_action(&self) -> Option<&'static str> {
-        match self {
+        match self.root_cause() {
             Self::PermissionDenied(_) => Some("Try running with elevated permissions (sudo)"),
             Self::UncommittedChanges(_) => Some("Commit or stash your changes first, or use --force"),
+            Self::InUse { .. } => Some("Stop the running process first, or use --force"),
             Self::DockerNotAvailable => Some("Start Docker Desktop or the Docker daemon"),
             Self::RestoreFailed(_, _) => Some("Check the trash directory or restore manually"),
             Self::NotAGitRepo(_) => Some("Initialize a git repository or use --no-git-check"),
             Self::ConfigParse { .. } => Some("Check your config file syntax"),
             Self::InvalidPattern(_) => Some("Check glob pattern syntax"),
+            Self::UnsupportedSchema { .. } => Some("Upgrade null-e to read this file"),
+            Self::CommandTimeout { .. } => Some("Raise --command-timeout or run the command yourself"),
             _ => None,
         }
     }
 
     /// Get the error code for CLI exit status
     pub fn exit_code(&self) -> i32 {
-        match self {
-            Self::ScanInterrupted => 130, // Standard SIGINT exit code
+        match self.root_cause() {
+            Self::ScanInterrupted | Self::CommandInterrupted(_) => 130, // Standard SIGINT exit code
             Self::PermissionDenied(_) => 126,
             Self::PathNotFound(_) | Self::NotADirectory(_) => 127,
             Self::Config(_) | Self::ConfigParse { .. } => 78, // EX_CONFIG
@@ -230,7 +253,7 @@ impl<T, E: Into<DevSweepError>> ResultExt<T> for std::result::Result<T, E> {
                 DevSweepError::Io(io_err) => match io_err.kind() {
                     std::io::ErrorKind::NotFound => DevSweepError::PathNotFound(path),
                     std::io::ErrorKind::PermissionDenied => DevSweepError::PermissionDenied(path),
-                    _ => err,
+                    _ => err.with_context(path.display().to_string()),
                 },
                 _ => err,
             }
@@ -280,4 +303,45 @@ mod tests {
 
         assert!(matches!(with_ctx, DevSweepError::WithContext { .. }));
     }
+
+    #[test]
+    fn test_command_interrupt_is_user_interrupt() {
+        let err = DevSweepError::CommandInterrupted("conda clean --all".into())
+            .with_context("while cleaning Conda cache");
+        assert!(err.is_user_interrupt());
+        assert_eq!(err.exit_code(), 130);
+
+        let err = DevSweepError::CommandTimeout { command: "conda clean --all".into(), secs: 600 };
+        assert!(!err.is_user_interrupt());
+        assert!(err.suggested_action().is_some());
+        assert_eq!(err.to_string(), "`conda clean --all` timed out after 600 seconds");
+    }
+
+    #[test]
+    fn test_root_cause_sees_through_context() {
+        let err = DevSweepError::PermissionDenied(PathBuf::from("/test"))
+            .with_context("while scanning /test")
+            .with_context("scan");
+
+        assert!(matches!(err.root_cause(), DevSweepError::PermissionDenied(_)));
+        assert!(err.is_recoverable());
+        assert_eq!(err.exit_code(), 126);
+        assert!(err.suggested_action().is_some());
+        assert_eq!(
+            err.to_string(),
+            "scan: while scanning /test: Permission denied: /test"
+        );
+    }
+
+    #[test]
+    fn test_with_path_keeps_path_for_other_io_errors() {
+        let res: std::result::Result<(), std::io::Error> = Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "bad bytes",
+        ));
+        let err = res.with_path("/data/file").unwrap_err();
+
+        assert!(err.to_string().starts_with("/data/file: "));
+        assert!(matches!(err.root_cause(), DevSweepError::Io(_)));
+    }
 }
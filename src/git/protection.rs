@@ -1,7 +1,9 @@
 //! Git protection - safety guards for cleaning operations
 
-use crate::core::{Artifact, Project};
+use crate::core::{Artifact, GitStatus, Project};
 use crate::error::Result;
+use std::path::Path;
+use std::time::SystemTime;
 
 /// Protection level for cleaning operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -13,7 +15,9 @@ pub enum ProtectionLevel {
     Warn,
     /// Block cleaning projects with uncommitted changes
     Block,
-    /// Paranoid: require explicit confirmation for everything
+    /// Block anything that might hold unsaved work: everything `Block` does,
+    /// plus untracked files, stashes, projects outside git, recently
+    /// modified projects and artifacts that need confirmation
     Paranoid,
 }
 
@@ -96,6 +100,50 @@ impl ProtectionResult {
 pub fn check_project_protection(
     project: &Project,
     level: ProtectionLevel,
+) -> ProtectionResult {
+    check_protection(
+        &project.name,
+        project.git_status.as_ref(),
+        project.last_modified,
+        level,
+    )
+}
+
+/// Check if it's safe to delete a path a cleaner found
+///
+/// Cleaners report paths without a [`Project`], so this looks up the git
+/// repository containing `path` and applies the same rules as
+/// [`check_project_protection`] to it. Paths outside any repository, like
+/// global caches, are always allowed, and so is a repository at the home
+/// directory (a dotfiles repo would otherwise cover every cache in it).
+pub fn check_path_protection(path: &Path, level: ProtectionLevel) -> ProtectionResult {
+    if level == ProtectionLevel::None {
+        return ProtectionResult::allowed();
+    }
+
+    let dir = if path.is_dir() { Some(path) } else { path.parent() };
+    let Some(root) = dir.and_then(|d| super::find_repo_root(d).ok().flatten()) else {
+        return ProtectionResult::allowed();
+    };
+    if dirs::home_dir().is_some_and(|home| home == root) {
+        return ProtectionResult::allowed();
+    }
+
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| root.display().to_string());
+    let status = super::get_git_status(&root).ok().flatten();
+    let modified = std::fs::metadata(&root).and_then(|m| m.modified()).ok();
+    check_protection(&name, status.as_ref(), modified, level)
+}
+
+/// Protection rules shared by projects and cleaner paths
+fn check_protection(
+    name: &str,
+    git_status: Option<&GitStatus>,
+    last_modified: Option<SystemTime>,
+    level: ProtectionLevel,
 ) -> ProtectionResult {
     if level == ProtectionLevel::None {
         return ProtectionResult::allowed();
@@ -104,34 +152,50 @@ pub fn check_project_protection(
     let mut result = ProtectionResult::allowed();
 
     // Check git status
-    match &project.git_status {
-        Some(status) if status.has_uncommitted => {
-            let msg = format!(
-                "Project '{}' has uncommitted changes ({} files)",
-                project.name,
-                status.dirty_paths.len()
-            );
+    match git_status {
+        Some(status) => {
+            if status.has_uncommitted {
+                let msg = format!(
+                    "Project '{}' has uncommitted changes ({} files)",
+                    name,
+                    status.dirty_paths.len()
+                );
 
-            match level {
-                ProtectionLevel::Warn => {
-                    result = result.with_warning(msg);
-                    result = result.with_suggestion("Commit or stash changes first");
+                match level {
+                    ProtectionLevel::Warn => {
+                        result = result.with_warning(msg);
+                        result = result.with_suggestion("Commit or stash changes first");
+                    }
+                    ProtectionLevel::Block | ProtectionLevel::Paranoid => {
+                        return ProtectionResult::blocked(msg)
+                            .with_suggestion("Use --force to override or commit changes first");
+                    }
+                    _ => {}
                 }
-                ProtectionLevel::Block | ProtectionLevel::Paranoid => {
+            }
+
+            // Untracked files may be work that exists nowhere else
+            if status.has_untracked {
+                let msg = format!("Project '{}' has untracked files", name);
+                if level == ProtectionLevel::Paranoid {
                     return ProtectionResult::blocked(msg)
-                        .with_suggestion("Use --force to override or commit changes first");
+                        .with_suggestion("Commit, ignore or remove them, or use --force");
                 }
-                _ => {}
+                result = result.with_warning(msg);
+            }
+
+            if status.has_stashed && level == ProtectionLevel::Paranoid {
+                return ProtectionResult::blocked(format!(
+                    "Project '{}' has stashed changes",
+                    name
+                ))
+                .with_suggestion("Apply or drop the stash, or use --force");
             }
-        }
-        Some(status) if status.has_untracked => {
-            let msg = format!("Project '{}' has untracked files", project.name);
-            result = result.with_warning(msg);
         }
         None => {
             let msg = format!(
                 "Project '{}' is not a git repository - cannot verify safety",
-                project.name
+                name
             );
 
             match level {
@@ -144,17 +208,16 @@ pub fn check_project_protection(
                 }
             }
         }
-        _ => {}
     }
 
     // Check if recently modified
-    if let Some(modified) = project.last_modified {
+    if let Some(modified) = last_modified {
         if let Ok(age) = modified.elapsed() {
             let days = age.as_secs() / 86400;
             if days < 7 {
                 let msg = format!(
                     "Project '{}' was modified recently ({} days ago)",
-                    project.name, days
+                    name, days
                 );
 
                 match level {
@@ -184,11 +247,25 @@ pub fn check_artifact_protection(
 
     let mut result = ProtectionResult::allowed();
 
+    // vendor/ is sometimes committed on purpose (common with Go); deleting it
+    // then edits the repository instead of cleaning something regenerable
+    if artifact.is_vendored()
+        && project.git_status.is_some()
+        && super::is_git_tracked(&project.root, &artifact.path).unwrap_or(false)
+    {
+        return ProtectionResult::blocked(format!(
+            "Artifact '{}' is committed to git",
+            artifact.path.display()
+        ))
+        .with_suggestion("Use --protection none or --force to delete it anyway");
+    }
+
     // Check if artifact path contains uncommitted changes
     if let Some(status) = &project.git_status {
         for dirty_path in &status.dirty_paths {
             // Check if dirty path is inside artifact path
-            if dirty_path.starts_with(&artifact.path) || artifact.path.starts_with(dirty_path) {
+            let touches = |path: &Path| dirty_path.starts_with(path) || path.starts_with(dirty_path);
+            if artifact.paths().into_iter().any(touches) {
                 let msg = format!(
                     "Artifact '{}' contains uncommitted changes",
                     artifact.path.display()
@@ -208,7 +285,7 @@ pub fn check_artifact_protection(
     }
 
     // Check artifact safety level
-    match artifact.kind.default_safety() {
+    match artifact.safety() {
         crate::core::ArtifactSafety::NeverAuto => {
             return ProtectionResult::blocked(format!(
                 "Artifact '{}' should never be auto-deleted",
@@ -319,6 +396,130 @@ mod tests {
         assert!(!result.allowed);
     }
 
+    #[test]
+    fn test_protection_paranoid_blocks_untracked_files() {
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .expect("git failed");
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(temp.path().join("package.json"), "{}").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "initial"]);
+        std::fs::write(temp.path().join("notes.txt"), "not committed yet").unwrap();
+
+        let mut project = Project::new(ProjectKind::NodeNpm, temp.path().to_path_buf());
+        project.git_status = crate::git::get_git_status(temp.path()).unwrap();
+        let status = project.git_status.as_ref().unwrap();
+        assert!(!status.has_uncommitted && status.has_untracked);
+
+        let block = check_project_protection(&project, ProtectionLevel::Block);
+        assert!(block.allowed);
+        assert!(block.has_warnings());
+
+        let paranoid = check_project_protection(&project, ProtectionLevel::Paranoid);
+        assert!(!paranoid.allowed);
+        assert!(paranoid.blocked_reason.unwrap().contains("untracked"));
+    }
+
+    #[test]
+    fn test_path_protection_uses_containing_repo() {
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let outside = temp.path().join("cache");
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::create_dir_all(repo.join("build")).unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .expect("git failed");
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(repo.join("main.c"), "int main() {}").unwrap();
+        git(&["add", "main.c"]);
+        git(&["commit", "-m", "initial"]);
+        std::fs::write(repo.join("main.c"), "int main() { return 1; }").unwrap();
+
+        let build = repo.join("build");
+        assert!(check_path_protection(&build, ProtectionLevel::Warn).has_warnings());
+        let blocked = check_path_protection(&build, ProtectionLevel::Block);
+        assert!(!blocked.allowed);
+        assert!(blocked.blocked_reason.unwrap().contains("uncommitted"));
+        assert!(check_path_protection(&build, ProtectionLevel::None).allowed);
+
+        // Outside any repository there is nothing to protect, even for paranoid
+        if crate::git::find_repo_root(&outside).unwrap().is_none() {
+            assert!(check_path_protection(&outside, ProtectionLevel::Paranoid).allowed);
+        }
+    }
+
+    #[test]
+    fn test_committed_vendor_is_protected() {
+        use crate::plugins::{GoPlugin, Plugin};
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(temp.path())
+                .output()
+                .expect("git failed");
+        };
+        git(&["init"]);
+        git(&["config", "user.email", "test@test.com"]);
+        git(&["config", "user.name", "Test"]);
+        std::fs::write(temp.path().join("go.mod"), "module example.com/app").unwrap();
+        std::fs::create_dir_all(temp.path().join("vendor/example.com/dep")).unwrap();
+        std::fs::write(temp.path().join("vendor/example.com/dep/dep.go"), "package dep").unwrap();
+        std::fs::write(temp.path().join("vendor/modules.txt"), "").unwrap();
+
+        let mut project = Project::new(ProjectKind::Go, temp.path().to_path_buf());
+        let vendor = GoPlugin.find_artifacts(temp.path()).unwrap().remove(0);
+
+        // Ignored vendor/: an ordinary, regenerable artifact
+        std::fs::write(temp.path().join(".gitignore"), "vendor/\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "initial"]);
+        project.git_status = crate::git::get_git_status(temp.path()).unwrap();
+        assert!(check_artifact_protection(&vendor, &project, ProtectionLevel::Block).allowed);
+
+        // Committed on purpose: kept even at the default level
+        std::fs::remove_file(temp.path().join(".gitignore")).unwrap();
+        git(&["add", "-A"]);
+        git(&["commit", "-m", "vendor dependencies"]);
+        project.git_status = crate::git::get_git_status(temp.path()).unwrap();
+        let warn = check_artifact_protection(&vendor, &project, ProtectionLevel::Warn);
+        assert!(!warn.allowed);
+        assert!(warn.blocked_reason.unwrap().contains("committed"));
+        assert!(check_artifact_protection(&vendor, &project, ProtectionLevel::None).allowed);
+    }
+
+    #[test]
+    fn test_protection_paranoid_blocks_stashes() {
+        let mut project = create_test_project(false);
+        project.git_status.as_mut().unwrap().has_stashed = true;
+
+        assert!(check_project_protection(&project, ProtectionLevel::Block).allowed);
+        let paranoid = check_project_protection(&project, ProtectionLevel::Paranoid);
+        assert!(!paranoid.allowed);
+        assert!(paranoid.blocked_reason.unwrap().contains("stashed"));
+    }
+
     #[test]
     fn test_protection_level_from_str() {
         assert_eq!(ProtectionLevel::from_str("none"), Some(ProtectionLevel::None));
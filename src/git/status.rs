@@ -151,6 +151,41 @@ pub fn find_repo_root(path: &Path) -> Result<Option<PathBuf>> {
     }
 }
 
+/// Files changed since `git_ref` in the repository containing `path`
+///
+/// Covers everything `git diff <ref>` sees (commits since the ref plus
+/// staged and unstaged edits) and untracked files, so a project started
+/// since the ref counts as changed too. Paths are absolute. Fails if `path`
+/// isn't in a repository or the ref doesn't exist.
+pub fn changed_since(path: &Path, git_ref: &str) -> Result<Vec<PathBuf>> {
+    let repo_root = find_repo_root(path)?
+        .ok_or_else(|| DevSweepError::Git(format!("{} is not in a git repository", path.display())))?;
+
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&repo_root)
+            .output()
+            .map_err(|e| DevSweepError::Git(e.to_string()))?;
+        if !output.status.success() {
+            return Err(DevSweepError::Git(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let diff = git(&["diff", "--name-only", git_ref, "--"])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard"])?;
+
+    Ok(diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .map(|line| repo_root.join(line))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,4 +297,39 @@ mod tests {
 
         assert!(has_uncommitted_changes(temp.path()).unwrap());
     }
+
+    #[test]
+    fn test_changed_since() {
+        let temp = TempDir::new().unwrap();
+        init_git_repo(temp.path());
+        let git = |args: &[&str]| {
+            Command::new("git").args(args).current_dir(temp.path()).output().unwrap();
+        };
+
+        for dir in ["api", "web", "docs"] {
+            std::fs::create_dir(temp.path().join(dir)).unwrap();
+            std::fs::write(temp.path().join(dir).join("main.txt"), "v1").unwrap();
+        }
+        git(&["add", "."]);
+        git(&["commit", "-m", "initial"]);
+        git(&["tag", "base"]);
+
+        std::fs::write(temp.path().join("api/main.txt"), "v2").unwrap();
+        git(&["commit", "-am", "api change"]);
+        std::fs::write(temp.path().join("web/main.txt"), "wip").unwrap();
+        std::fs::create_dir(temp.path().join("new")).unwrap();
+        std::fs::write(temp.path().join("new/main.txt"), "new").unwrap();
+
+        let root = find_repo_root(temp.path()).unwrap().unwrap();
+        let mut changed = changed_since(temp.path(), "base").unwrap();
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![root.join("api/main.txt"), root.join("new/main.txt"), root.join("web/main.txt")]
+        );
+
+        assert!(changed_since(temp.path(), "no-such-ref").is_err());
+        let outside = TempDir::new().unwrap();
+        assert!(changed_since(outside.path(), "base").is_err());
+    }
 }
@@ -37,7 +37,9 @@
 //!
 //! null-e uses a plugin-based architecture where each language/framework
 //! is handled by a dedicated plugin. Plugins implement the `Plugin` trait
-//! and register themselves with the `PluginRegistry`.
+//! and are registered with the `PluginRegistry`: all builtins with
+//! `with_builtins()`, or any mix of builtins and your own plugins with
+//! `from_plugins()` and `register()`.
 //!
 //! ```text
 //!      .---.
@@ -74,8 +76,11 @@ pub mod core;
 pub mod docker;
 pub mod error;
 pub mod git;
+pub mod logging;
+pub mod notify;
 pub mod plugins;
 pub mod scanner;
+pub mod stats;
 pub mod trash;
 pub mod tui;
 
@@ -83,9 +88,9 @@ pub mod tui;
 pub mod prelude {
     pub use crate::core::{
         Artifact, ArtifactKind, ArtifactMetadata, ArtifactStats,
-        CleanConfig, CleanProgress, CleanResult, CleanSafety, CleanSummary, CleanTarget, Cleaner,
+        CleanConfig, CleanProgress, CleanRecord, CleanResult, CleanSafety, CleanSummary, CleanTarget, Cleaner,
         Project, ProjectId, ProjectKind,
-        ScanConfig, ScanProgress, ScanResult, Scanner,
+        RestoreCommands, ScanConfig, ScanProgress, ScanResult, Scanner,
     };
     pub use crate::config::Config;
     pub use crate::error::{DevSweepError, Result, ResultExt};
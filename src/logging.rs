@@ -0,0 +1,242 @@
+//! Tracing setup
+//!
+//! `--verbose` prints human-readable diagnostics to stderr; `--log-file`
+//! appends one JSON object per line to a file, including a record for every
+//! closed span with its wall-clock duration. Scans, detection and cleaning
+//! run inside spans, so a log from a slow machine shows where time went.
+
+use crate::error::{DevSweepError, Result};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+/// Where diagnostics go and how detailed they are
+#[derive(Debug, Clone)]
+pub struct LogOptions<'a> {
+    /// `general.log_level`: error, warn, info, debug or trace
+    pub level: &'a str,
+    /// Print to stderr, at debug level or more detailed
+    pub verbose: bool,
+    /// Append JSON lines to this file
+    pub file: Option<&'a Path>,
+}
+
+/// Install the global tracing subscriber
+///
+/// Does nothing unless `verbose` or `file` is set. `RUST_LOG`, when present,
+/// overrides `level` for both outputs.
+pub fn init(options: &LogOptions) -> Result<()> {
+    if !options.verbose && options.file.is_none() {
+        return Ok(());
+    }
+
+    let level = parse_level(options.level)?;
+    let filter = |level: LevelFilter| {
+        EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new(format!("null_e={}", level)))
+    };
+
+    let stderr = options.verbose.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(filter(level.max(LevelFilter::DEBUG)))
+    });
+    let file = match options.file {
+        Some(path) => Some(JsonLayer::open(path)?.with_filter(filter(level))),
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(file)
+        .try_init()
+        .map_err(|e| DevSweepError::Other(format!("Failed to set up logging: {}", e)))
+}
+
+/// Parse a `general.log_level` value
+pub fn parse_level(level: &str) -> Result<LevelFilter> {
+    level
+        .trim()
+        .parse()
+        .map_err(|_| DevSweepError::Config(format!("Invalid log level: {:?}", level)))
+}
+
+/// Writes events and closed spans as JSON lines
+struct JsonLayer {
+    file: parking_lot::Mutex<File>,
+}
+
+/// Per-span state kept in the span's extensions
+struct SpanTiming {
+    started: Instant,
+    fields: Map<String, Value>,
+}
+
+impl JsonLayer {
+    fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| DevSweepError::Other(format!("Can't open log file {}: {}", path.display(), e)))?;
+        Ok(Self {
+            file: parking_lot::Mutex::new(file),
+        })
+    }
+
+    fn write(&self, mut record: Map<String, Value>) {
+        record.insert("timestamp".into(), chrono::Utc::now().to_rfc3339().into());
+        let mut line = Value::Object(record).to_string();
+        line.push('\n');
+        // Logging must never break the command
+        let _ = self.file.lock().write_all(line.as_bytes());
+    }
+}
+
+impl<S> Layer<S> for JsonLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = JsonFields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(SpanTiming {
+            started: Instant::now(),
+            fields: fields.0,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(timing) = extensions.get_mut::<SpanTiming>() {
+            let mut fields = JsonFields(std::mem::take(&mut timing.fields));
+            values.record(&mut fields);
+            timing.fields = fields.0;
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+
+        let mut record = Map::new();
+        record.insert("level".into(), meta.level().as_str().into());
+        record.insert("target".into(), meta.target().into());
+        record.insert("fields".into(), Value::Object(fields.0));
+        if let Some(scope) = ctx.event_scope(event) {
+            let spans: Vec<Value> = scope.from_root().map(|s| s.name().into()).collect();
+            record.insert("spans".into(), spans.into());
+        }
+        self.write(record);
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(timing) = span.extensions_mut().remove::<SpanTiming>() else {
+            return;
+        };
+
+        let meta = span.metadata();
+        let mut record = Map::new();
+        record.insert("level".into(), meta.level().as_str().into());
+        record.insert("target".into(), meta.target().into());
+        record.insert("span".into(), span.name().into());
+        record.insert("fields".into(), Value::Object(timing.fields));
+        record.insert(
+            "elapsed_ms".into(),
+            (timing.started.elapsed().as_secs_f64() * 1000.0).into(),
+        );
+        if let Some(parent) = span.parent() {
+            record.insert("parent".into(), parent.name().into());
+        }
+        self.write(record);
+    }
+}
+
+/// Collects span or event fields into a JSON object
+#[derive(Default)]
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().into(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug").unwrap(), LevelFilter::DEBUG);
+        assert_eq!(parse_level(" WARN ").unwrap(), LevelFilter::WARN);
+        assert!(parse_level("chatty").is_err());
+    }
+
+    #[test]
+    fn test_json_layer_writes_events_and_span_timings() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("null-e.log");
+        let subscriber = tracing_subscriber::registry().with(JsonLayer::open(&path).unwrap());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("scan", roots = 2);
+            let _guard = span.enter();
+            tracing::info!(projects = 3, "found projects");
+        });
+
+        let lines: Vec<Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["fields"]["message"], "found projects");
+        assert_eq!(lines[0]["fields"]["projects"], 3);
+        assert_eq!(lines[0]["spans"][0], "scan");
+        assert_eq!(lines[1]["span"], "scan");
+        assert_eq!(lines[1]["fields"]["roots"], 2);
+        assert!(lines[1]["elapsed_ms"].is_number());
+    }
+}
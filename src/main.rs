@@ -2,14 +2,19 @@
 //!
 //! 🤖 Send your dev cruft to /dev/null with style!
 
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use null_e::prelude::*;
+use null_e::cleaners::SafetyLevel;
+use null_e::config::parse_size;
+use null_e::analysis::age::AgeHistogram;
+use null_e::scanner::{RemoteScanner, RemoteTarget};
 use indicatif::{ProgressBar, ProgressStyle};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// ASCII art robot mascot for null-e
 const ROBOT_BANNER: &str = r#"
@@ -24,6 +29,9 @@ const ROBOT_BANNER: &str = r#"
 /// Small robot for inline display
 const ROBOT_SMALL: &str = "🤖";
 
+/// Biggest projects shown under the scan spinner while scanning
+const LIVE_TOP_PROJECTS: usize = 5;
+
 /// 🤖 null-e - The Friendly Disk Cleanup Robot
 ///
 /// Find and clean node_modules, target, .venv, and 30+ more artifact types.
@@ -48,7 +56,11 @@ struct Cli {
     #[arg(short = 's', long, global = true)]
     min_size: Option<String>,
 
-    /// Show top N largest projects [default: 25, use 0 for all]
+    /// Only show artifacts holding at least N files (e.g. 100000)
+    #[arg(long, global = true, value_name = "N")]
+    min_files: Option<u64>,
+
+    /// Show only the N largest rows of each table [default: 25 projects, 30 sweep items, all caches; 0 for all]
     #[arg(short = 't', long, global = true)]
     top: Option<usize>,
 
@@ -83,6 +95,94 @@ struct Cli {
     /// Skip cache, force full rescan
     #[arg(long, global = true)]
     no_cache: bool,
+
+    /// Safe mode: never delete permanently, skip irreversible official commands
+    #[arg(long, global = true)]
+    safe_mode: bool,
+
+    /// When to color output: auto (terminal only, honoring NO_COLOR/CLICOLOR), always or never
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value = "auto")]
+    color: ColorChoice,
+
+    /// Append structured (JSON lines) logs with per-phase timings to this file
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Leave out items modified fewer than N days ago when cleaning everything (default: 1, 0 = off)
+    #[arg(long, global = true, value_name = "DAYS")]
+    min_age: Option<u64>,
+
+    /// Stop scanning after N seconds and show partial results
+    #[arg(long, global = true, value_name = "SECS", alias = "max-runtime")]
+    timeout: Option<u64>,
+
+    /// Follow symlinked directories while scanning
+    #[arg(long, global = true)]
+    follow_symlinks: bool,
+
+    /// Also find build/test logs and core dumps inside projects
+    #[arg(long, global = true)]
+    include_build_logs: bool,
+
+    /// Show a histogram of found items by last-modified age
+    #[arg(long, global = true)]
+    age_report: bool,
+
+    /// Never run the docker CLI (Docker detectors report nothing)
+    #[arg(long, global = true)]
+    no_docker: bool,
+
+    /// Never shell out to docker, brew, go or other tools while detecting (git protection checks still run)
+    #[arg(long, global = true)]
+    no_external_commands: bool,
+
+    /// Kill an official clean command still running after N seconds (default: 600, 0 = no limit)
+    #[arg(long, global = true, value_name = "SECS")]
+    command_timeout: Option<u64>,
+
+    /// Show artifacts on the ignore list (dimmed) instead of hiding them
+    #[arg(long, global = true)]
+    show_ignored: bool,
+
+    /// Print only the totals (scan and sweep), without the list of items
+    #[arg(long, global = true)]
+    summary_only: bool,
+
+    /// Scan gently: idle I/O priority and at most RATE directories per second (default 200)
+    #[arg(long, global = true, value_name = "RATE", num_args = 0..=1, require_equals = true)]
+    nice: Option<Option<u32>>,
+
+    /// Read additional newline-separated paths to scan from FILE ("-" for stdin)
+    #[arg(long, global = true, value_name = "FILE")]
+    paths_from: Option<PathBuf>,
+
+    /// Explain why each item is considered cleanable (scan and sweep)
+    #[arg(long, global = true)]
+    explain: bool,
+
+    /// Ask before cleaning each item: y cleans it, n skips it, q stops
+    #[arg(long, global = true, conflicts_with = "force")]
+    confirm_each: bool,
+
+    /// Pick items to clean with the arrow keys and Space instead of typing numbers
+    #[arg(long, global = true)]
+    picker: bool,
+
+    /// Apply a cleanup policy from `[profiles.NAME]` in the config file
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Leave out anything riskier than LEVEL when cleaning
+    #[arg(long, global = true, value_enum, value_name = "LEVEL")]
+    max_safety: Option<SafetyArg>,
+
+    /// Sweep categories from the profile; empty for all
+    #[arg(skip)]
+    categories: Vec<String>,
+
+    /// The config file, loaded once at startup
+    #[arg(skip)]
+    config: null_e::config::Config,
 }
 
 #[derive(Subcommand)]
@@ -96,6 +196,26 @@ enum Commands {
         /// Show detailed artifact information
         #[arg(long)]
         detailed: bool,
+
+        /// Nest projects under their top-level directory with subtotals
+        #[arg(long, value_enum, default_value = "none")]
+        group_by: GroupBy,
+
+        /// Order projects by size, name, date, kind, cleanup score or file count [default: ui.sort_by or size]
+        #[arg(long, value_enum)]
+        sort: Option<SortKey>,
+
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+
+        /// Estimate sizes from each artifact's top-level entries instead of walking it
+        #[arg(long)]
+        fast: bool,
+
+        /// Also write the full scan result as JSON to FILE, for `clean --from`
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
     },
 
     /// Clean (delete) artifacts
@@ -107,6 +227,22 @@ enum Commands {
         /// Exclude specific artifact types
         #[arg(long)]
         exclude: Vec<String>,
+
+        /// Pick which projects to clean by number or range
+        #[arg(short = 'i', long)]
+        interactive: bool,
+
+        /// Leave out projects with files changed since this git ref (e.g. origin/main)
+        #[arg(long, value_name = "REF")]
+        since: Option<String>,
+
+        /// Clean what a `scan --export` file lists instead of scanning again
+        #[arg(long, value_name = "FILE", conflicts_with = "since")]
+        from: Option<PathBuf>,
+
+        /// Offer to rebuild each cleaned project afterwards (cargo build, npm install, ...)
+        #[arg(long)]
+        rebuild: bool,
     },
 
     /// Show configuration
@@ -120,6 +256,13 @@ enum Commands {
         path: bool,
     },
 
+    /// Show the scan cache: location, version, age and entry count
+    Cache {
+        /// List every cached project and whether the next scan would reuse it
+        #[arg(long)]
+        inspect: bool,
+    },
+
     /// List supported project types
     List,
 
@@ -136,6 +279,10 @@ enum Commands {
         /// Use official clean commands when available
         #[arg(long, default_value = "true")]
         official: bool,
+
+        /// List caches under their ecosystem (JS, Python, Rust, ...) with subtotals
+        #[arg(long)]
+        group: bool,
     },
 
     /// Deep sweep: find ALL cleanable items (Xcode, Android, Docker, ML, IDEs, logs)
@@ -147,6 +294,10 @@ enum Commands {
         /// Filter by category (xcode, android, docker, ml, ide, logs)
         #[arg(long)]
         category: Option<String>,
+
+        /// Also find .DS_Store, Thumbs.db and desktop.ini files under the scanned paths
+        #[arg(long)]
+        junk_files: bool,
     },
 
     /// Clean Xcode artifacts (DerivedData, Archives, Simulators)
@@ -172,6 +323,10 @@ enum Commands {
         /// Include volumes (data may be lost!)
         #[arg(long)]
         volumes: bool,
+
+        /// List build cache unused for this many days as its own, separately prunable item
+        #[arg(long, value_name = "DAYS", default_value_t = null_e::cleaners::docker::DEFAULT_STALE_BUILD_CACHE_DAYS)]
+        build_cache_days: u64,
     },
 
     /// Clean ML/AI caches (Huggingface, Ollama, PyTorch)
@@ -213,7 +368,7 @@ enum Commands {
         clean: bool,
     },
 
-    /// Clean game development caches (Unity, Unreal, Godot)
+    /// Clean game development caches (Unity, Unreal, Godot) and Unreal project folders
     Gamedev {
         /// Clean game dev caches
         #[arg(long)]
@@ -254,7 +409,28 @@ enum Commands {
     },
 
     /// Find duplicate dependencies across projects
-    Duplicates,
+    Duplicates {
+        /// Replace identical copies of duplicated files with hardlinks (same filesystem only)
+        #[arg(long)]
+        dedupe_hardlink: bool,
+    },
+
+    /// Find the largest individual files, whatever project they belong to (default --min-size 1GB)
+    #[command(alias = "big-files")]
+    Bigfiles,
+
+    /// Never clean the given artifact paths (no paths: list ignored artifacts)
+    Ignore {
+        /// Remove the paths from the ignore list instead
+        #[arg(long)]
+        remove: bool,
+    },
+
+    /// Check what null-e can see and use here (home, cache, trash, tools, cleaners)
+    Doctor,
+
+    /// Show how much space cleaning has freed over time (needs `[stats] enabled = true`)
+    Stats,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
@@ -283,6 +459,17 @@ enum DeleteMethodArg {
     DryRun,
 }
 
+impl From<ProtectionLevel> for ProtectionArg {
+    fn from(level: ProtectionLevel) -> Self {
+        match level {
+            ProtectionLevel::None => ProtectionArg::None,
+            ProtectionLevel::Warn => ProtectionArg::Warn,
+            ProtectionLevel::Block => ProtectionArg::Block,
+            ProtectionLevel::Paranoid => ProtectionArg::Paranoid,
+        }
+    }
+}
+
 impl From<DeleteMethodArg> for DeleteMethod {
     fn from(arg: DeleteMethodArg) -> Self {
         match arg {
@@ -293,39 +480,164 @@ impl From<DeleteMethodArg> for DeleteMethod {
     }
 }
 
+impl From<DeleteMethod> for DeleteMethodArg {
+    fn from(method: DeleteMethod) -> Self {
+        match method {
+            DeleteMethod::Trash => DeleteMethodArg::Trash,
+            DeleteMethod::Permanent => DeleteMethodArg::Permanent,
+            DeleteMethod::DryRun => DeleteMethodArg::DryRun,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum SafetyArg {
+    Safe,
+    SafeWithCost,
+    Caution,
+    Dangerous,
+}
+
+impl From<SafetyArg> for SafetyLevel {
+    fn from(arg: SafetyArg) -> Self {
+        match arg {
+            SafetyArg::Safe => SafetyLevel::Safe,
+            SafetyArg::SafeWithCost => SafetyLevel::SafeWithCost,
+            SafetyArg::Caution => SafetyLevel::Caution,
+            SafetyArg::Dangerous => SafetyLevel::Dangerous,
+        }
+    }
+}
+
+impl From<SafetyLevel> for SafetyArg {
+    fn from(level: SafetyLevel) -> Self {
+        match level {
+            SafetyLevel::Safe => SafetyArg::Safe,
+            SafetyLevel::SafeWithCost => SafetyArg::SafeWithCost,
+            SafetyLevel::Caution => SafetyArg::Caution,
+            SafetyLevel::Dangerous => SafetyArg::Dangerous,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum GroupBy {
+    /// Flat list sorted by size
+    #[default]
+    None,
+    /// Group by top-level directory under the scan root
+    Dir,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum SortKey {
+    /// Largest cleanable size first
+    #[default]
+    Size,
+    /// Project name, A to Z
+    Name,
+    /// Least recently touched artifacts first
+    Date,
+    /// Grouped by project type, largest first within each
+    Kind,
+    /// Worth cleaning: size weighted by how long the artifacts sat unused
+    Score,
+    /// Most files first; slow to delete and to back up even when small
+    Files,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
 enum OutputFormat {
     Pretty,
     Json,
     Compact,
+    /// One JSON object per line on stdout (a project for scan, a cleaned item for clean),
+    /// everything else on stderr
+    Ndjson,
+    /// Scan results nested by directory, with sizes rolled up at each level
+    Tree,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ColorChoice {
+    Auto,
+    Always,
+    Never,
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    if cli.output == OutputFormat::Ndjson {
+        init_record_stream();
+    }
+
+    // Decided after the ndjson redirect, so "auto" looks at where text really goes
+    use std::io::IsTerminal;
+    let color = colors_enabled(cli.color, |var| std::env::var_os(var), std::io::stdout().is_terminal());
+    colored::control::set_override(color);
+    if cli.color != ColorChoice::Auto {
+        console::set_colors_enabled(color);
+        console::set_colors_enabled_stderr(color);
+    }
+
+    // Commands that show or repair the config still run when it's malformed
+    let config = match null_e::config::load_default_config() {
+        Ok(config) => config,
+        Err(e) if matches!(cli.command, Some(Commands::Config { .. } | Commands::List | Commands::Doctor)) => {
+            eprintln!("{} {}; using defaults", "⚠".yellow(), e);
+            null_e::config::Config::default()
+        }
+        Err(e) => exit_with_error(e),
+    };
 
     // Set up logging
-    if cli.verbose {
-        tracing_subscriber::fmt()
-            .with_env_filter("null_e=debug")
-            .init();
+    let log_options = null_e::logging::LogOptions {
+        level: &config.general.log_level,
+        verbose: cli.verbose,
+        file: cli.log_file.as_deref(),
+    };
+    if let Err(e) = null_e::logging::init(&log_options) {
+        exit_with_error(e);
+    }
+
+    null_e::cleaners::set_docker_enabled(!cli.no_docker);
+    null_e::cleaners::set_external_commands_enabled(!cli.no_external_commands);
+    if let Some(secs) = cli.command_timeout {
+        null_e::cleaners::set_command_timeout((secs > 0).then(|| std::time::Duration::from_secs(secs)));
+    }
+
+    if let Err(e) = add_paths_from(&mut cli).and_then(|()| apply_config_defaults(&mut cli, &matches, config)) {
+        exit_with_error(e);
     }
 
     // Run the appropriate command
     let result = match &cli.command {
         Some(Commands::Tui) => cmd_tui(&cli),
-        Some(Commands::Scan { detailed }) => cmd_scan(&cli, *detailed),
-        Some(Commands::Clean { only, exclude }) => cmd_clean(&cli, only, exclude),
-        Some(Commands::Config { init, path }) => cmd_config(*init, *path),
+        Some(Commands::Scan { detailed, group_by, sort, reverse, fast, export }) => cmd_scan(
+            &cli,
+            ScanView::new(*detailed, *group_by, *sort, *reverse, cli.explain, &cli.config.ui),
+            *fast,
+            export.as_deref(),
+        ),
+        Some(Commands::Clean { only, exclude, interactive, since, from, rebuild }) => {
+            cmd_clean(&cli, only, exclude, *interactive, since.as_deref(), from.as_deref(), *rebuild)
+        }
+        Some(Commands::Config { init, path }) => cmd_config(&cli.config, *init, *path),
+        Some(Commands::Cache { inspect }) => cmd_cache(*inspect),
         Some(Commands::List) => cmd_list(),
-        Some(Commands::Caches { clean, clean_all, official }) => {
-            cmd_caches(&cli, *clean, *clean_all, *official)
+        Some(Commands::Caches { clean, clean_all, official, group }) => {
+            cmd_caches(&cli, *clean, *clean_all, *official, *group)
         }
-        Some(Commands::Sweep { clean, category }) => {
-            cmd_sweep(&cli, *clean, category.as_deref())
+        Some(Commands::Sweep { clean, category, junk_files }) => {
+            cmd_sweep(&cli, *clean, category.as_deref(), *junk_files)
         }
         Some(Commands::Xcode { clean }) => cmd_xcode(&cli, *clean),
         Some(Commands::Android { clean }) => cmd_android(&cli, *clean),
-        Some(Commands::Docker { clean, volumes }) => cmd_docker(&cli, *clean, *volumes),
+        Some(Commands::Docker { clean, volumes, build_cache_days }) => {
+            cmd_docker(&cli, *clean, *volumes, *build_cache_days)
+        }
         Some(Commands::Ml { clean }) => cmd_ml(&cli, *clean),
         Some(Commands::Ide { clean }) => cmd_ide(&cli, *clean),
         Some(Commands::Homebrew { clean, scrub }) => cmd_homebrew(&cli, *clean, *scrub),
@@ -337,18 +649,113 @@ fn main() {
         Some(Commands::Macos { clean }) => cmd_macos(&cli, *clean),
         Some(Commands::GitAnalyze { fix }) => cmd_git_analyze(&cli, *fix),
         Some(Commands::Stale { days, clean }) => cmd_stale(&cli, *days, *clean),
-        Some(Commands::Duplicates) => cmd_duplicates(&cli),
-        None => cmd_scan(&cli, false), // Default to scan
+        Some(Commands::Duplicates { dedupe_hardlink }) => cmd_duplicates(&cli, *dedupe_hardlink),
+        Some(Commands::Bigfiles) => cmd_bigfiles(&cli),
+        Some(Commands::Ignore { remove }) => cmd_ignore(&cli, *remove),
+        Some(Commands::Doctor) => cmd_doctor(),
+        Some(Commands::Stats) => cmd_stats(&cli.config),
+        None => cmd_scan(&cli, ScanView::new(false, GroupBy::None, None, false, cli.explain, &cli.config.ui), false, None), // Default to scan
     };
 
     // Handle errors
     if let Err(e) = result {
-        eprintln!("{} {}", "Error:".red().bold(), e);
-        if let Some(suggestion) = e.suggested_action() {
-            eprintln!("{} {}", "Hint:".yellow(), suggestion);
+        exit_with_error(e);
+    }
+}
+
+/// Whether to print ANSI colors
+///
+/// `auto` follows the usual conventions: `CLICOLOR_FORCE` forces colors on,
+/// a non-empty `NO_COLOR` or `CLICOLOR=0` turns them off, and otherwise
+/// only a terminal gets colors.
+fn colors_enabled(
+    choice: ColorChoice,
+    env: impl Fn(&str) -> Option<std::ffi::OsString>,
+    is_terminal: bool,
+) -> bool {
+    let set = |var: &str| env(var).is_some_and(|v| !v.is_empty() && v != "0");
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if set("CLICOLOR_FORCE") => true,
+        ColorChoice::Auto if env("NO_COLOR").is_some_and(|v| !v.is_empty()) => false,
+        ColorChoice::Auto if env("CLICOLOR").is_some_and(|v| v == "0") => false,
+        ColorChoice::Auto => is_terminal,
+    }
+}
+
+fn exit_with_error(e: DevSweepError) -> ! {
+    eprintln!("{} {}", "Error:".red().bold(), e);
+    if let Some(suggestion) = e.suggested_action() {
+        eprintln!("{} {}", "Hint:".yellow(), suggestion);
+    }
+    std::process::exit(e.exit_code());
+}
+
+/// Fill settings not given on the command line from the config file and `NULL_E_*` variables
+///
+/// Precedence, lowest first: built-in defaults, config file, environment
+/// variables, command-line flags.
+fn apply_config_defaults(cli: &mut Cli, matches: &ArgMatches, config: null_e::config::Config) -> Result<()> {
+    let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+    if !from_cli("method") {
+        cli.method = config.clean.delete_method.into();
+    }
+    if !from_cli("protection") {
+        cli.protection = config.clean.protection_level.into();
+    }
+    if cli.min_size.is_none() {
+        cli.min_size = config.scan.min_size.map(|bytes| bytes.to_string());
+    }
+    cli.no_cache |= !config.scan.use_cache;
+
+    // A profile stands in for the flags it sets; flags given explicitly still win
+    if let Some(name) = cli.profile.clone() {
+        let profile = config.profile(&name)?;
+        if let Some(category) = profile.categories.iter().find(|c| sweep_detector(c).is_none()) {
+            return Err(DevSweepError::Config(format!(
+                "Unknown sweep category '{}' in profile '{}'",
+                category, name
+            )));
         }
-        std::process::exit(e.exit_code());
+        if let Some(level) = profile.protection_level.filter(|_| !from_cli("protection")) {
+            cli.protection = level.into();
+        }
+        cli.min_age = cli.min_age.or(profile.min_age_days);
+        cli.max_safety = cli.max_safety.or(profile.max_safety.map(Into::into));
+        cli.categories = profile.categories.clone();
     }
+
+    cli.config = config;
+    Ok(())
+}
+
+/// Append the paths listed by `--paths-from` to the positional paths
+///
+/// They are validated later by [`get_scan_paths`], like any other path.
+fn add_paths_from(cli: &mut Cli) -> Result<()> {
+    let Some(source) = cli.paths_from.as_ref() else {
+        return Ok(());
+    };
+
+    let text = if source.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(source).with_path(source)?
+    };
+
+    cli.paths.extend(parse_path_list(&text));
+    Ok(())
+}
+
+/// Parse a newline-separated path list, skipping blank lines
+fn parse_path_list(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -365,26 +772,42 @@ fn cmd_tui(cli: &Cli) -> Result<()> {
 // Scan Command
 // ═══════════════════════════════════════════════════════════════════════════
 
-fn cmd_scan(cli: &Cli, detailed: bool) -> Result<()> {
+fn cmd_scan(cli: &Cli, view: ScanView, fast: bool, export: Option<&Path>) -> Result<()> {
+    if cli.paths.iter().any(|p| RemoteTarget::is_remote(p)) {
+        if export.is_some() {
+            return Err(DevSweepError::Config("--export only works for local paths".into()));
+        }
+        return cmd_scan_remote(cli, view);
+    }
+
     let paths = get_scan_paths(cli)?;
-    let use_cache = !cli.no_cache;
+    if cli.output == OutputFormat::Ndjson {
+        return cmd_scan_stream(cli, scan_command_config(cli, paths, configured_size_mode(cli), fast));
+    }
+    // Cached projects were scanned without logs
+    let use_cache = !cli.no_cache && !cli.include_build_logs;
 
-    println!("{}", ROBOT_BANNER.green());
-    println!(
-        "{} {}",
-        format!("{} null-e", ROBOT_SMALL).green().bold(),
-        format!("v{}", null_e::VERSION).dimmed()
-    );
+    if !cli.summary_only {
+        println!("{}", ROBOT_BANNER.green());
+        println!(
+            "{} {}",
+            format!("{} null-e", ROBOT_SMALL).green().bold(),
+            format!("v{}", null_e::VERSION).dimmed()
+        );
+    }
 
-    // Try to load cache
-    let mut cache = if use_cache {
-        null_e::cache::load_cache().unwrap_or_default()
-    } else {
-        null_e::cache::ScanCache::new()
+    // Try to load cache; sizes measured in another size mode don't count
+    let size_mode = configured_size_mode(cli);
+    let mut cache = match null_e::cache::load_cache() {
+        Ok(cache) if use_cache && cache.size_mode == size_mode => cache,
+        _ => null_e::cache::ScanCache {
+            size_mode,
+            ..null_e::cache::ScanCache::new()
+        },
     };
 
-    // Check if we can use cached results
-    let cache_valid = use_cache && cache.is_valid() && cache.project_count() > 0;
+    // Check if we can use cached results; an export needs a full scan result
+    let cache_valid = use_cache && export.is_none() && cache.is_valid() && cache.project_count() > 0;
 
     // If cache is valid and no specific paths requested, use cached data
     if cache_valid && paths.len() == 1 {
@@ -398,8 +821,10 @@ fn cmd_scan(cli: &Cli, detailed: bool) -> Result<()> {
                 .collect();
 
             if !projects.is_empty() {
-                println!("{} {}", "⚡".yellow(), "Using cached results (use --no-cache to rescan)".dimmed());
-                println!();
+                if !cli.summary_only {
+                    println!("{} {}", "⚡".yellow(), "Using cached results (use --no-cache to rescan)".dimmed());
+                    println!();
+                }
 
                 // Apply min_size filter
                 let min_size = if let Some(ref size_str) = cli.min_size {
@@ -410,36 +835,35 @@ fn cmd_scan(cli: &Cli, detailed: bool) -> Result<()> {
                     0
                 };
 
+                if let Some(min_files) = cli.min_files {
+                    for project in &mut projects {
+                        project.artifacts.retain(|a| a.file_count >= min_files);
+                        project.calculate_totals();
+                    }
+                    projects.retain(|p| !p.artifacts.is_empty());
+                }
                 projects.retain(|p| p.cleanable_size >= min_size);
-                projects.sort_by(|a, b| b.cleanable_size.cmp(&a.cleanable_size));
+                projects.sort_by(Project::cmp_by_size);
 
                 // Create a minimal config for display
-                let mut cached_config = ScanConfig::default();
-                cached_config.roots = paths.clone();
+                let cached_config = ScanConfig::default().with_roots(paths.clone());
 
-                return display_scan_results(cli, &cached_config, projects, 0, Duration::from_millis(1), detailed);
+                return display_scan_results(cli, &cached_config, projects, &ScanCoverage::default(), view);
             }
         }
     }
 
-    // Create scanner
+    // Create scanner, streaming projects to the progress display as they're found.
+    // Without the live list nothing drains the channel, so don't attach it.
+    let show_live_top = !cli.summary_only;
     let registry = Arc::new(PluginRegistry::with_builtins());
-    let scanner = ParallelScanner::new(registry);
-
-    // Build config with smart defaults
-    let mut config = ScanConfig::default();
-    config.roots = paths.clone();
-
-    if let Some(depth) = cli.max_depth {
-        config.max_depth = Some(depth);
+    let (found_tx, found_rx) = crossbeam_channel::unbounded();
+    let mut scanner = ParallelScanner::new(registry);
+    if show_live_top {
+        scanner = scanner.with_project_sender(found_tx);
     }
 
-    // Default min_size to 1MB unless specified or verbose mode
-    if let Some(ref size_str) = cli.min_size {
-        config.min_size = parse_size(size_str);
-    } else if !cli.verbose && !cli.all {
-        config.min_size = Some(1_000_000); // 1MB default
-    }
+    let config = scan_command_config(cli, paths.clone(), size_mode, fast);
 
     // Get progress handle for real-time updates
     let progress = scanner.progress();
@@ -457,21 +881,35 @@ fn cmd_scan(cli: &Cli, detailed: bool) -> Result<()> {
     let progress_clone = Arc::clone(&progress);
     let pb_clone = pb.clone();
 
-    // Spawn thread to update progress bar
+    // Spawn thread to update progress bar, along with the biggest projects so far
     let progress_thread = thread::spawn(move || {
+        let mut top: Vec<Project> = Vec::new();
         loop {
             let snapshot = progress_clone.snapshot();
             if snapshot.is_complete {
                 break;
             }
 
+            if show_live_top {
+                top.extend(found_rx.try_iter());
+                top.sort_by(Project::cmp_by_size);
+                top.truncate(LIVE_TOP_PROJECTS);
+            }
+
             let size_str = format_size(snapshot.total_size_found);
-            let msg = format!(
+            let mut msg = format!(
                 "Scanning... {} dirs | {} projects | {} found",
                 snapshot.directories_scanned,
                 snapshot.projects_found,
                 size_str.yellow()
             );
+            for project in &top {
+                msg.push_str(&format!(
+                    "\n    {} {}",
+                    format!("{:>10}", format_size(project.cleanable_size)).yellow(),
+                    project.name
+                ));
+            }
             pb_clone.set_message(msg);
 
             thread::sleep(Duration::from_millis(50));
@@ -485,10 +923,22 @@ fn cmd_scan(cli: &Cli, detailed: bool) -> Result<()> {
     let _ = progress_thread.join();
     pb.finish_and_clear();
 
+    if let Some(file) = export {
+        std::fs::write(file, serde_json::to_string_pretty(&scan_result)?).with_path(file)?;
+    }
+
+    let coverage = ScanCoverage {
+        dirs_scanned: scan_result.directories_scanned,
+        duration: scan_result.duration,
+        partial: scan_result.is_partial,
+        unreadable: scan_result.unreadable_paths().into_iter().map(Path::to_path_buf).collect(),
+        phases: Some(scan_result.phase_timings),
+    };
     let mut projects = scan_result.projects;
 
-    // Update cache with new results
-    if use_cache {
+    // Update cache with new results (partial scans would leave gaps,
+    // estimates aren't sizes and --min-files drops most projects, so skip them)
+    if use_cache && !scan_result.is_partial && !fast && cli.min_files.is_none() {
         for project in &projects {
             cache.cache_project(project.clone());
         }
@@ -497,138 +947,757 @@ fn cmd_scan(cli: &Cli, detailed: bool) -> Result<()> {
     }
 
     // Sort by size (largest first)
-    projects.sort_by(|a, b| b.cleanable_size.cmp(&a.cleanable_size));
+    projects.sort_by(Project::cmp_by_size);
 
-    display_scan_results(cli, &config, projects, scan_result.directories_scanned, scan_result.duration, detailed)
+    display_scan_results(cli, &config, projects, &coverage, view)
 }
 
-fn display_scan_results(
-    cli: &Cli,
-    config: &ScanConfig,
-    projects: Vec<Project>,
-    dirs_scanned: usize,
-    duration: Duration,
-    detailed: bool,
-) -> Result<()> {
-    // Determine display limit
-    let display_limit = if cli.all || cli.verbose {
-        projects.len()
-    } else {
-        cli.top.unwrap_or(25)
-    };
+/// Scan config for the `scan` command, with its smart defaults
+fn scan_command_config(cli: &Cli, paths: Vec<PathBuf>, size_mode: null_e::core::SizeMode, fast: bool) -> ScanConfig {
+    let mut config = cli_scan_config(cli, paths, size_mode);
+    if fast {
+        config = config.with_fast();
+    }
 
-    let total_projects = projects.len();
-    let total_size: u64 = projects.iter().map(|p| p.cleanable_size).sum();
+    // Default min_size to 1MB unless specified or verbose mode; estimates
+    // undercount, so --fast keeps everything it finds
+    if let Some(ref size_str) = cli.min_size {
+        if let Some(bytes) = parse_size(size_str) {
+            config = config.with_min_size(bytes);
+        }
+    } else if !cli.verbose && !cli.all && !fast {
+        config = config.with_min_size(1_000_000); // 1MB default
+    }
+    config
+}
 
-    // Split into displayed and hidden
-    let (displayed, hidden): (Vec<_>, Vec<_>) = if display_limit > 0 && display_limit < projects.len() {
-        let (d, h) = projects.split_at(display_limit);
-        (d.to_vec(), h.to_vec())
-    } else {
-        (projects, vec![])
-    };
+/// `scan --output ndjson`: write each project as it is found, without
+/// holding the whole result in memory
+fn cmd_scan_stream(cli: &Cli, config: ScanConfig) -> Result<()> {
+    let (found_tx, found_rx) = null_e::scanner::stream_channel(&config);
+    let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins())).with_project_stream(found_tx);
+    let writer = thread::spawn(move || {
+        for project in found_rx {
+            emit_record(&project);
+        }
+    });
 
-    // Display header
-    println!(
-        "{} Found {} projects with {} cleanable",
-        "✓".green(),
-        total_projects.to_string().cyan(),
-        format_size(total_size).yellow().bold()
-    );
+    let result = scanner.scan(&config);
+    drop(scanner);
+    let _ = writer.join();
+    let result = result?;
 
-    if dirs_scanned > 0 {
+    if !cli.summary_only {
         println!(
-            "  {} Scanned {} directories in {:.2}s",
-            "│".dimmed(),
-            dirs_scanned.to_string().dimmed(),
-            duration.as_secs_f64()
+            "{} {} cleanable in {} dirs ({:.1}s)",
+            "✓".green(),
+            format_size(result.total_cleanable),
+            result.directories_scanned,
+            result.duration.as_secs_f64()
         );
     }
-
-    println!();
-
-    if displayed.is_empty() {
-        println!("  No cleanable artifacts found (min size: 1MB).");
-        println!("  {}", "Use --min-size 0 or -v to see smaller items".dimmed());
-        return Ok(());
+    if result.is_partial {
+        println!("{} {}", "⚠".yellow(), "Scan timed out - results are incomplete".yellow());
     }
+    Ok(())
+}
 
-    // Display top projects
-    if !hidden.is_empty() {
+/// Scan `ssh://` targets by running discovery on the remote host (detection only)
+fn cmd_scan_remote(cli: &Cli, view: ScanView) -> Result<()> {
+    if !cli.summary_only {
+        println!("{}", ROBOT_BANNER.green());
         println!(
-            "{} {} (use {} to see all)",
-            "Top".bold(),
-            format!("{}", display_limit).cyan(),
-            "-a".cyan()
+            "{} {}",
+            format!("{} null-e", ROBOT_SMALL).green().bold(),
+            format!("v{}", null_e::VERSION).dimmed()
         );
-        println!();
     }
 
-    // Display projects with better formatting
-    for (i, project) in displayed.iter().enumerate() {
-        // Format size with padding for alignment
-        let size_str = format_size(project.cleanable_size);
-        let padded_size = format!("{:>10}", size_str);
+    let mut targets = Vec::new();
+    for path in &cli.paths {
+        let url = path.to_string_lossy();
+        match RemoteTarget::parse(&url) {
+            Some(target) => targets.push(target),
+            None => {
+                return Err(DevSweepError::Config(format!(
+                    "Cannot mix local and remote paths, or invalid remote path: {}",
+                    url
+                )))
+            }
+        }
+    }
 
-        // Get relative path if possible
-        let display_path = if let Some(first_root) = config.roots.first() {
-            project.root.strip_prefix(first_root)
-                .map(|p| p.display().to_string())
-                .unwrap_or_else(|_| project.root.display().to_string())
-        } else {
-            project.root.display().to_string()
-        };
+    let min_size = match cli.min_size {
+        Some(ref size_str) => parse_size(size_str),
+        None if !cli.verbose && !cli.all => Some(1_000_000), // 1MB default
+        None => None,
+    };
+    let config = ScanConfig {
+        roots: targets.iter().map(|t| PathBuf::from(&t.path)).collect(),
+        max_depth: cli.max_depth,
+        timeout: cli.timeout.map(Duration::from_secs),
+        min_size,
+        ..Default::default()
+    };
 
-        // Main line
-        println!(
-            "{} {} {} {} {}",
-            "•".cyan(),
-            project.kind.icon(),
-            padded_size.yellow(),
-            project.name.bold(),
-            format!("({})", display_path).dimmed()
+    let registry = Arc::new(PluginRegistry::with_builtins());
+    let start = Instant::now();
+    let mut projects = Vec::new();
+    let mut coverage = ScanCoverage::default();
+
+    for target in targets {
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.green} {msg}")
+                .unwrap(),
         );
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb.set_message(format!("Scanning {} over SSH...", target));
 
-        // Show artifacts in detailed mode
-        if detailed {
-            for artifact in &project.artifacts {
-                println!(
-                    "       {} {} {}",
-                    "├──".dimmed(),
-                    artifact.name(),
-                    format_size(artifact.size).dimmed()
-                );
-            }
-        }
+        let scanner = RemoteScanner::new(Arc::clone(&registry), target);
+        let result = scanner.scan(&config);
+        pb.finish_and_clear();
 
-        // Add spacing between entries for readability
-        if i < displayed.len() - 1 && (i + 1) % 10 == 0 {
-            println!();
-        }
+        let result = result?;
+        coverage.dirs_scanned += result.directories_scanned;
+        coverage.unreadable.extend(result.unreadable_paths().into_iter().map(Path::to_path_buf));
+        projects.extend(result.projects);
     }
 
-    // Show summary of hidden projects
-    if !hidden.is_empty() {
-        let hidden_size: u64 = hidden.iter().map(|p| p.cleanable_size).sum();
-        println!();
+    projects.sort_by(Project::cmp_by_size);
+
+    coverage.duration = start.elapsed();
+    display_scan_results(cli, &config, projects, &coverage, view)?;
+    if !cli.summary_only {
         println!(
-            "{} {} more projects ({}) - use {} or {} to see all",
-            "...".dimmed(),
-            hidden.len().to_string().dimmed(),
-            format_size(hidden_size).dimmed(),
-            "-a".cyan(),
-            "-v".cyan()
+            "{}",
+            "Remote results are detection-only; clean them on the host itself.".dimmed()
         );
     }
+    Ok(())
+}
 
-    println!();
+/// How `scan` lays out its results
+#[derive(Debug, Clone, Copy)]
+struct ScanView {
+    /// Show each project's artifacts
+    detailed: bool,
+    /// Nest projects under a heading
+    group_by: GroupBy,
+    /// Project order
+    sort: SortKey,
+    /// Reverse the project order
+    reverse: bool,
+    /// Print why each artifact is considered cleanable
+    explain: bool,
+    /// Show artifact file counts (`ui.show_file_counts`)
+    file_counts: bool,
+}
 
-    // Quick actions hint
-    if total_size > 100_000_000 { // > 100MB
-        println!(
-            "{} {} {} {}",
-            "💡".dimmed(),
-            "Quick clean:".dimmed(),
+impl ScanView {
+    /// Build the view from CLI flags, falling back to the `[ui]` config for ordering
+    ///
+    /// An explicit `--sort` replaces the configured order entirely, so the
+    /// config's `sort_reverse` only applies to the configured `sort_by`.
+    fn new(
+        detailed: bool,
+        group_by: GroupBy,
+        sort: Option<SortKey>,
+        reverse: bool,
+        explain: bool,
+        ui: &null_e::config::UiSettings,
+    ) -> Self {
+        let (sort, reverse) = match sort {
+            Some(sort) => (sort, reverse),
+            None => {
+                let sort = SortKey::from_str(&ui.sort_by, true).unwrap_or_default();
+                (sort, reverse || ui.sort_reverse)
+            }
+        };
+
+        Self { detailed, group_by, sort, reverse, explain, file_counts: ui.show_file_counts }
+    }
+}
+
+/// Sort projects for display; every key falls back to size, then path
+fn sort_projects(projects: &mut [Project], key: SortKey, reverse: bool) {
+    match key {
+        SortKey::Size => projects.sort_by(Project::cmp_by_size),
+        SortKey::Name => projects.sort_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.cmp_by_size(b))
+        }),
+        SortKey::Date => projects.sort_by(|a, b| {
+            b.artifact_age()
+                .cmp(&a.artifact_age())
+                .then_with(|| a.cmp_by_size(b))
+        }),
+        SortKey::Kind => projects.sort_by(|a, b| {
+            a.kind
+                .display_name()
+                .cmp(b.kind.display_name())
+                .then_with(|| a.cmp_by_size(b))
+        }),
+        SortKey::Score => projects.sort_by(|a, b| {
+            b.cleanup_score()
+                .total_cmp(&a.cleanup_score())
+                .then_with(|| a.cmp_by_size(b))
+        }),
+        SortKey::Files => projects.sort_by(|a, b| {
+            b.file_count()
+                .cmp(&a.file_count())
+                .then_with(|| a.cmp_by_size(b))
+        }),
+    }
+
+    if reverse {
+        projects.reverse();
+    }
+}
+
+/// Print one project (and its artifacts when detailed), prefixed by `indent`
+fn print_project_line(project: &Project, config: &ScanConfig, view: &ScanView, indent: &str) {
+    // Format size with padding for alignment
+    let size_str = format_size(project.cleanable_size);
+    let padded_size = format!("{:>10}", size_str);
+
+    // Get relative path if possible
+    let display_path = if let Some(first_root) = config.roots.first() {
+        project.root.strip_prefix(first_root)
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|_| project.root.display().to_string())
+    } else {
+        project.root.display().to_string()
+    };
+
+    // Flag projects that live behind a symlink
+    let link_marker = project
+        .artifacts
+        .iter()
+        .find_map(|a| a.metadata.extra.get("via_symlink"))
+        .map(|link| format!(" ↪ via {}", link).magenta().to_string())
+        .unwrap_or_default();
+
+    // Flag projects with artifacts on the ignore list (only kept with --show-ignored)
+    let ignored_in_project = project.artifacts.iter().filter(|a| a.is_ignored()).count();
+    let ignored_marker = if ignored_in_project > 0 {
+        format!(" [{} ignored]", ignored_in_project).dimmed().to_string()
+    } else {
+        String::new()
+    };
+
+    // Flag leftovers whose project was deleted, or that can no longer run
+    let orphan_marker = if project.artifacts.iter().any(|a| a.is_orphaned()) {
+        " [orphaned]".red().to_string()
+    } else if project.artifacts.iter().any(|a| a.broken_reason().is_some()) {
+        " [broken]".red().to_string()
+    } else {
+        String::new()
+    };
+
+    // Sorting by file count shows the counts being sorted on
+    let files_marker = if view.sort == SortKey::Files {
+        format!(" {}", format_file_count(project.file_count())).cyan().to_string()
+    } else {
+        String::new()
+    };
+
+    // Main line
+    println!(
+        "{}{} {} {} {} {}{}{}{}{}",
+        indent,
+        "•".cyan(),
+        project.kind.icon(),
+        padded_size.yellow(),
+        project.name.bold(),
+        format!("({})", display_path).dimmed(),
+        files_marker,
+        link_marker,
+        ignored_marker,
+        orphan_marker
+    );
+
+    // Show artifacts in detailed mode
+    if view.detailed {
+        if let Some(plugin) = &project.detected_by {
+            println!("{}       {}", indent, format!("detected by: {}", plugin).dimmed());
+        }
+        for artifact in &project.artifacts {
+            if artifact.is_ignored() {
+                println!(
+                    "{}       {} {}",
+                    indent,
+                    "├──".dimmed(),
+                    format!("{} {} (ignored)", artifact.name(), format_size(artifact.size))
+                        .dimmed()
+                );
+                continue;
+            }
+            let mut note = artifact
+                .metadata
+                .shared_size
+                .map(|shared| format!(" ({} shared)", format_size(shared)).dimmed().to_string())
+                .unwrap_or_default();
+            if let Some(reason) = artifact.broken_reason() {
+                note.push_str(&format!(" - non-functional: {}", reason).red().to_string());
+            }
+            if artifact.is_partial_size() {
+                note.push_str(&" (partial, scan stopped)".yellow().to_string());
+            }
+            let size = if view.file_counts && artifact.file_count > 0 {
+                format!("{} · {}", format_size(artifact.size), format_file_count(artifact.file_count))
+            } else {
+                format_size(artifact.size)
+            };
+            println!(
+                "{}       {} {} {}{}",
+                indent,
+                "├──".dimmed(),
+                artifact.name(),
+                size.dimmed(),
+                note
+            );
+        }
+    }
+
+    if view.explain {
+        print_project_explanation(project, indent);
+    }
+}
+
+/// Print which marker identified a project and why each artifact can go
+fn print_project_explanation(project: &Project, indent: &str) {
+    static REGISTRY: std::sync::OnceLock<PluginRegistry> = std::sync::OnceLock::new();
+
+    if let Some(plugin) = &project.detected_by {
+        let registry = REGISTRY.get_or_init(PluginRegistry::with_builtins);
+        let kind = project.kind.display_name();
+        let why = match registry.matching_marker(plugin, &project.root) {
+            Some(marker) => {
+                format!("why: {} project ({} matched, {} plugin)", kind, marker.indicator, plugin)
+            }
+            None => format!("why: {} project ({} plugin)", kind, plugin),
+        };
+        println!("{}       {}", indent, why.dimmed());
+    }
+
+    for artifact in project.artifacts.iter().filter(|a| !a.is_ignored()) {
+        println!("{}       {}", indent, format!("why: {}", artifact.explain()).dimmed());
+    }
+}
+
+/// Top-level directory of a project below whichever scan root contains it
+fn top_level_dir(config: &ScanConfig, project: &Project) -> String {
+    config
+        .roots
+        .iter()
+        .find_map(|root| project.root.strip_prefix(root).ok())
+        .and_then(|rel| rel.components().next())
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// Cleanable size and project count per scan root, biggest first
+///
+/// A project counts toward the deepest root it lives under, so overlapping
+/// roots aren't counted twice. Projects outside every root (e.g. a workspace
+/// root above the scanned path) are grouped under `None`.
+fn root_subtotals(roots: &[PathBuf], projects: &[Project]) -> Vec<(Option<PathBuf>, u64, usize)> {
+    let mut totals: Vec<(Option<PathBuf>, u64, usize)> =
+        roots.iter().map(|root| (Some(root.clone()), 0, 0)).collect();
+    let mut elsewhere = (None, 0, 0);
+
+    for project in projects {
+        let entry = totals
+            .iter_mut()
+            .filter(|(root, _, _)| root.as_ref().is_some_and(|r| project.root.starts_with(r)))
+            .max_by_key(|(root, _, _)| root.as_ref().map_or(0, |r| r.components().count()))
+            .unwrap_or(&mut elsewhere);
+        entry.1 += project.cleanable_size;
+        entry.2 += 1;
+    }
+
+    if elsewhere.2 > 0 {
+        totals.push(elsewhere);
+    }
+    totals.sort_by_key(|(_, size, _)| std::cmp::Reverse(*size));
+    totals
+}
+
+/// Print projects nested under their top-level directory, biggest directory first.
+///
+/// Subtotals include projects cut by `--top`, so a dominant folder stays visible.
+fn print_grouped_by_dir(config: &ScanConfig, displayed: &[Project], hidden: &[Project], view: &ScanView) {
+    let mut groups: std::collections::HashMap<String, (u64, usize, Vec<&Project>)> =
+        std::collections::HashMap::new();
+    for project in displayed {
+        let group = groups.entry(top_level_dir(config, project)).or_default();
+        group.0 += project.cleanable_size;
+        group.1 += 1;
+        group.2.push(project);
+    }
+    for project in hidden {
+        let group = groups.entry(top_level_dir(config, project)).or_default();
+        group.0 += project.cleanable_size;
+        group.1 += 1;
+    }
+
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(name, (size, _, _))| (std::cmp::Reverse(*size), name.clone()));
+
+    for (i, (name, (size, count, projects))) in groups.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "📁 {} {} {}",
+            format!("{:>10}", format_size(*size)).yellow().bold(),
+            format!("{}/", name).bold(),
+            format!("({} projects)", count).dimmed()
+        );
+        for project in projects {
+            print_project_line(project, config, view, "  ");
+        }
+        if *count > projects.len() {
+            println!("  {}", format!("... {} more", count - projects.len()).dimmed());
+        }
+    }
+}
+
+/// A directory in `--output tree`, with the cleanable size of everything under it
+#[derive(Default)]
+struct DirNode<'a> {
+    size: u64,
+    /// Project rooted at exactly this directory
+    project: Option<&'a Project>,
+    children: std::collections::BTreeMap<String, DirNode<'a>>,
+}
+
+impl<'a> DirNode<'a> {
+    /// Add `project`, found `components` below this directory
+    fn insert(&mut self, components: &[String], project: &'a Project) {
+        self.size += project.cleanable_size;
+        match components.split_first() {
+            Some((first, rest)) => self.children.entry(first.clone()).or_default().insert(rest, project),
+            None => self.project = Some(project),
+        }
+    }
+
+    /// Follow chains of directories holding nothing but one subdirectory, joining their names
+    fn collapsed(&self, name: &str) -> (String, &DirNode<'a>) {
+        let mut name = name.to_string();
+        let mut node = self;
+        while node.project.is_none() && node.children.len() == 1 {
+            let (child_name, child) = node.children.iter().next().unwrap();
+            name = format!("{}/{}", name, child_name);
+            node = child;
+        }
+        (name, node)
+    }
+
+    /// Children, largest first
+    fn sorted_children(&self) -> Vec<(&String, &DirNode<'a>)> {
+        let mut children: Vec<_> = self.children.iter().collect();
+        children.sort_by_key(|(name, child)| (std::cmp::Reverse(child.size), *name));
+        children
+    }
+}
+
+/// Build one directory tree per scan root, largest first
+///
+/// A project belongs to the deepest root it lives under, like in
+/// [`root_subtotals`]; projects outside every root hang off `/`.
+fn build_scan_tree<'a>(roots: &[PathBuf], projects: &'a [Project]) -> Vec<(PathBuf, DirNode<'a>)> {
+    let mut trees: Vec<(PathBuf, DirNode<'a>)> = Vec::new();
+    for project in projects {
+        let root = roots
+            .iter()
+            .filter(|r| project.root.starts_with(r))
+            .max_by_key(|r| r.components().count())
+            .cloned()
+            .unwrap_or_else(|| PathBuf::from("/"));
+        let components: Vec<String> = project
+            .root
+            .strip_prefix(&root)
+            .unwrap_or(&project.root)
+            .iter()
+            .map(|c| c.to_string_lossy().to_string())
+            .filter(|c| c != "/")
+            .collect();
+
+        let index = match trees.iter().position(|(r, _)| *r == root) {
+            Some(index) => index,
+            None => {
+                trees.push((root, DirNode::default()));
+                trees.len() - 1
+            }
+        };
+        trees[index].1.insert(&components, project);
+    }
+    trees.sort_by_key(|(root, node)| (std::cmp::Reverse(node.size), root.clone()));
+    trees
+}
+
+/// Print projects nested under their directories with rolled-up sizes (`--output tree`)
+fn print_scan_tree(config: &ScanConfig, projects: &[Project]) {
+    for (i, (root, node)) in build_scan_tree(&config.roots, projects).iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "📁 {} {}",
+            format!("{:>10}", format_size(node.size)).yellow().bold(),
+            root.display().to_string().bold()
+        );
+        print_dir_node(node, "");
+    }
+}
+
+fn print_dir_node(node: &DirNode, prefix: &str) {
+    let children = node.sorted_children();
+    for (i, (name, child)) in children.iter().enumerate() {
+        let (branch, indent) = if i + 1 == children.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let (name, child) = child.collapsed(name);
+        let label = match child.project {
+            Some(project) => format!("{} {}", project.kind.icon(), name.bold()),
+            None => format!("{}/", name),
+        };
+        println!(
+            "{}{}{} {}",
+            prefix,
+            branch.dimmed(),
+            format!("{:>10}", format_size(child.size)).yellow(),
+            label
+        );
+        print_dir_node(child, &format!("{}{}", prefix, indent.dimmed()));
+    }
+}
+
+/// Warn that directories were skipped as unreadable, listing them when verbose
+fn print_unreadable(paths: &[PathBuf], verbose: bool) {
+    if paths.is_empty() {
+        return;
+    }
+    let hint = if verbose { "" } else { ", or -v to list them" };
+    println!(
+        "  {} {}",
+        "⚠".yellow(),
+        format!(
+            "{} directories could not be read and were skipped - results may be incomplete (re-run with sudo{})",
+            paths.len(),
+            hint
+        )
+        .yellow()
+    );
+    if verbose {
+        for path in paths {
+            println!("    {} {}", "-".dimmed(), path.display());
+        }
+    }
+}
+
+/// How much of the filesystem a scan actually covered
+#[derive(Default)]
+struct ScanCoverage {
+    dirs_scanned: usize,
+    duration: Duration,
+    /// Stopped early by `--timeout`
+    partial: bool,
+    /// Directories skipped because they couldn't be read
+    unreadable: Vec<PathBuf>,
+    /// Where the time went, for local scans
+    phases: Option<null_e::core::PhaseTimings>,
+}
+
+fn display_scan_results(
+    cli: &Cli,
+    config: &ScanConfig,
+    mut projects: Vec<Project>,
+    coverage: &ScanCoverage,
+    view: ScanView,
+) -> Result<()> {
+    let ignored_count = apply_ignore_list(&mut projects, cli.show_ignored);
+    sort_projects(&mut projects, view.sort, view.reverse);
+
+    // Determine display limit; a tree rolls up every project, so it shows them all
+    let display_limit = match cli.output {
+        OutputFormat::Tree => projects.len(),
+        _ => row_limit(cli, Some(25)).unwrap_or(projects.len()),
+    };
+
+    let total_projects = projects.len();
+    let total_size: u64 = projects.iter().map(|p| p.cleanable_size).sum();
+    let shared_size: u64 = projects
+        .iter()
+        .flat_map(|p| &p.artifacts)
+        .filter_map(|a| a.metadata.shared_size)
+        .sum();
+    let per_root = if config.roots.len() > 1 {
+        root_subtotals(&config.roots, &projects)
+    } else {
+        Vec::new()
+    };
+
+    // Split into displayed and hidden
+    let (displayed, hidden): (Vec<_>, Vec<_>) = if display_limit > 0 && display_limit < projects.len() {
+        let (d, h) = projects.split_at(display_limit);
+        (d.to_vec(), h.to_vec())
+    } else {
+        (projects, vec![])
+    };
+
+    // Display header
+    let approx = if config.fast { "~" } else { "" };
+    println!(
+        "{} Found {} projects with {} cleanable",
+        "✓".green(),
+        total_projects.to_string().cyan(),
+        format!("{}{}", approx, format_size(total_size)).yellow().bold()
+    );
+
+    if cli.summary_only {
+        return Ok(());
+    }
+
+    if coverage.dirs_scanned > 0 {
+        println!(
+            "  {} Scanned {} directories in {:.2}s",
+            "│".dimmed(),
+            coverage.dirs_scanned.to_string().dimmed(),
+            coverage.duration.as_secs_f64()
+        );
+    }
+    if let Some(phases) = coverage.phases.filter(|_| cli.verbose) {
+        print_phase_timings(&phases);
+    }
+
+    if coverage.partial {
+        println!(
+            "  {} {}",
+            "⚠".yellow(),
+            "Partial results: scan timed out before finishing (raise --timeout)".yellow()
+        );
+    }
+
+    if shared_size > 0 {
+        println!(
+            "  {} {}",
+            "│".dimmed(),
+            format!(
+                "{} more is shared with other files (clones, snapshots, hard links) and won't be freed",
+                format_size(shared_size)
+            )
+            .dimmed()
+        );
+    }
+
+    if config.fast {
+        println!(
+            "  {} {}",
+            "│".dimmed(),
+            "Sizes are estimated from top-level entries (--fast); run without it for exact sizes"
+                .dimmed()
+        );
+    }
+
+    print_unreadable(&coverage.unreadable, cli.verbose);
+
+    if ignored_count > 0 && !cli.show_ignored {
+        println!(
+            "  {} {} ignored artifacts hidden (use {} to show them)",
+            "│".dimmed(),
+            ignored_count.to_string().dimmed(),
+            "--show-ignored".cyan()
+        );
+    }
+
+    println!();
+
+    if !per_root.is_empty() {
+        println!("{}", "By scan root".bold());
+        for (root, size, count) in &per_root {
+            let root = root
+                .as_ref()
+                .map_or_else(|| "(elsewhere)".to_string(), |r| r.display().to_string());
+            println!(
+                "  {} {} {}",
+                format!("{:>10}", format_size(*size)).yellow().bold(),
+                root,
+                format!("({} projects)", count).dimmed()
+            );
+        }
+        println!();
+    }
+
+    if displayed.is_empty() {
+        println!("  No cleanable artifacts found (min size: 1MB).");
+        println!("  {}", "Use --min-size 0 or -v to see smaller items".dimmed());
+        return Ok(());
+    }
+
+    // Display top projects
+    if !hidden.is_empty() {
+        println!(
+            "{} {} (use {} to see all)",
+            "Top".bold(),
+            format!("{}", display_limit).cyan(),
+            "-a".cyan()
+        );
+        println!();
+    }
+
+    match view.group_by {
+        _ if cli.output == OutputFormat::Tree => print_scan_tree(config, &displayed),
+        GroupBy::None => {
+            for (i, project) in displayed.iter().enumerate() {
+                print_project_line(project, config, &view, "");
+
+                // Add spacing between entries for readability
+                if i < displayed.len() - 1 && (i + 1) % 10 == 0 {
+                    println!();
+                }
+            }
+        }
+        GroupBy::Dir => print_grouped_by_dir(config, &displayed, &hidden, &view),
+    }
+
+    // Show summary of hidden projects
+    if !hidden.is_empty() {
+        let hidden_size: u64 = hidden.iter().map(|p| p.cleanable_size).sum();
+        println!();
+        println!(
+            "{} {} more projects ({}) - use {} or {} to see all",
+            "...".dimmed(),
+            hidden.len().to_string().dimmed(),
+            format_size(hidden_size).dimmed(),
+            "-a".cyan(),
+            "-v".cyan()
+        );
+    }
+
+    println!();
+
+    if cli.age_report {
+        let mut histogram = AgeHistogram::new();
+        for artifact in displayed.iter().chain(hidden.iter()).flat_map(|p| &p.artifacts) {
+            histogram.add(artifact.age_days(), artifact.size);
+        }
+        print_age_report(&histogram);
+    }
+
+    // Quick actions hint
+    if total_size > 100_000_000 { // > 100MB
+        println!(
+            "{} {} {} {}",
+            "💡".dimmed(),
+            "Quick clean:".dimmed(),
             "null-eclean --dry-run".cyan(),
             "(preview what would be deleted)".dimmed()
         );
@@ -637,7 +1706,59 @@ fn display_scan_results(
     Ok(())
 }
 
-fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
+/// Drop artifacts on the ignore list, or just mark them when `show` is set.
+///
+/// Returns how many artifacts were ignored.
+fn apply_ignore_list(projects: &mut Vec<Project>, show: bool) -> usize {
+    let ignore = null_e::config::IgnoreList::load_default().unwrap_or_default();
+    let count = ignore.apply(projects, show);
+    projects.sort_by(Project::cmp_by_size);
+    count
+}
+
+fn cmd_ignore(cli: &Cli, remove: bool) -> Result<()> {
+    let mut ignore = null_e::config::IgnoreList::load_default()?;
+
+    if cli.paths.is_empty() {
+        if ignore.is_empty() {
+            println!("  No ignored artifacts. Add one with {}", "null-e ignore <path>".cyan());
+        } else {
+            println!("{} {} ignored artifacts:", "🙈".dimmed(), ignore.len());
+            for path in ignore.iter() {
+                println!("    {} {}", "•".dimmed(), path.display());
+            }
+        }
+        return Ok(());
+    }
+
+    for path in &cli.paths {
+        // Store absolute paths so the list works from any directory
+        let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if remove {
+            if ignore.remove(&path) {
+                println!("{} No longer ignoring {}", "✓".green(), path.display());
+            } else {
+                println!("  {} was not ignored", path.display());
+            }
+        } else if ignore.add(path.clone()) {
+            println!("{} Ignoring {}", "✓".green(), path.display());
+        } else {
+            println!("  {} is already ignored", path.display());
+        }
+    }
+
+    ignore.save_default()
+}
+
+fn cmd_clean(
+    cli: &Cli,
+    _only: &[String],
+    _exclude: &[String],
+    interactive: bool,
+    since: Option<&str>,
+    from: Option<&Path>,
+    rebuild: bool,
+) -> Result<()> {
     let paths = get_scan_paths(cli)?;
 
     println!(
@@ -647,31 +1768,116 @@ fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
     );
     println!();
 
-    // Create scanner
-    let registry = Arc::new(PluginRegistry::with_builtins());
-    let scanner = ParallelScanner::new(registry);
-
     // Build config
-    let mut config = ScanConfig::default();
-    config.roots = paths;
-    if let Some(depth) = cli.max_depth {
-        config.max_depth = Some(depth);
-    }
+    let config = cli_scan_config(cli, paths, configured_size_mode(cli));
 
-    // Scan
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg}")
-            .unwrap(),
-    );
-    pb.set_message("Scanning for cleanable artifacts...");
+    let result = if let Some(file) = from {
+        let text = std::fs::read_to_string(file).with_path(file)?;
+        let mut result = ScanResult::from_json_compat(&text)
+            .context(format!("{} is not a scan result", file.display()))?;
+        println!("  {} Using scan result from {}", "│".dimmed(), file.display());
 
-    let result = scanner.scan(&config)?;
-    pb.finish_and_clear();
+        // Things may have been cleaned or moved since the export
+        let missing = drop_missing_artifacts(&mut result.projects);
+        if !missing.is_empty() {
+            println!(
+                "  {} Skipping {} artifacts that no longer exist",
+                "│".dimmed(),
+                missing.len()
+            );
+            if cli.verbose {
+                for artifact in &missing {
+                    println!("    {} {}", "•".dimmed(), artifact.path.display());
+                }
+            }
+        }
+
+        // Only delete what a plugin still reports for the project, so an
+        // edited or stale file can't point clean at arbitrary paths
+        let registry = PluginRegistry::with_builtins();
+        let unreported = drop_unreported_artifacts(&mut result.projects, &registry);
+        if !unreported.is_empty() {
+            println!(
+                "  {} Skipping {} artifacts no plugin reports for their project any more",
+                "│".dimmed(),
+                unreported.len()
+            );
+            if cli.verbose {
+                for artifact in &unreported {
+                    println!("    {} {}", "•".dimmed(), artifact.path.display());
+                }
+            }
+        }
+        result
+    } else {
+        // Create scanner
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+
+        // Scan
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.green} {msg}")
+                .unwrap(),
+        );
+        pb.set_message("Scanning for cleanable artifacts...");
+
+        let result = scanner.scan(&config)?;
+        pb.finish_and_clear();
+        result
+    };
+
+    if result.is_partial {
+        println!(
+            "{} {}",
+            "⚠".yellow(),
+            "Scan timed out - only the projects found so far will be cleaned".yellow()
+        );
+    }
+    let unreadable: Vec<PathBuf> = result.unreadable_paths().into_iter().map(Path::to_path_buf).collect();
+    print_unreadable(&unreadable, cli.verbose);
 
+    let mut phases = result.phase_timings;
     let mut projects = result.projects;
+    let git_start = Instant::now();
     null_e::git::enrich_with_git_status(&mut projects)?;
+    phases.git = Some(git_start.elapsed());
+    if cli.verbose {
+        print_phase_timings(&phases);
+    }
+
+    let ignored_count = apply_ignore_list(&mut projects, false);
+    if ignored_count > 0 {
+        println!(
+            "  {} Skipping {} ignored artifacts ({} to list them)",
+            "│".dimmed(),
+            ignored_count,
+            "null-e ignore".cyan()
+        );
+    }
+
+    // Don't force rebuilds of what is being worked on
+    if let Some(git_ref) = since {
+        let mut changed = Vec::new();
+        for root in &config.roots {
+            changed.extend(null_e::git::changed_since(root, git_ref)?);
+        }
+        let active = drop_changed_projects(&mut projects, &changed);
+        if !active.is_empty() {
+            println!(
+                "  {} Skipping {} projects changed since {}",
+                "│".dimmed(),
+                active.len(),
+                git_ref.cyan()
+            );
+            if cli.verbose {
+                for project in &active {
+                    println!("    {} {}", "•".dimmed(), project.root.display());
+                }
+            }
+        }
+    }
 
     if projects.is_empty() {
         println!("  No cleanable artifacts found.");
@@ -680,7 +1886,7 @@ fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
 
     // Filter projects by protection level
     let protection_level: ProtectionLevel = cli.protection.into();
-    let (cleanable, blocked): (Vec<_>, Vec<_>) = projects.into_iter().partition(|p| {
+    let (mut cleanable, blocked): (Vec<_>, Vec<_>) = projects.into_iter().partition(|p| {
         let check = null_e::git::check_project_protection(p, protection_level);
         check.allowed || cli.force
     });
@@ -698,11 +1904,121 @@ fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
         println!();
     }
 
+    // Per-artifact rules, e.g. a vendor/ directory that is committed to git
+    let protected = if cli.force { Vec::new() } else { drop_protected_artifacts(&mut cleanable, protection_level) };
+    if !protected.is_empty() {
+        println!(
+            "{} {} artifacts blocked (use --force to override):",
+            "🔒".red(),
+            protected.len()
+        );
+        for (artifact, reason) in &protected {
+            println!("    {} {} {}", "•".red(), artifact.path.display(), format!("({})", reason).dimmed());
+        }
+        println!();
+    }
+
+    // Find deletions that would fail on permissions before touching anything
+    let unwritable = check_permissions(&cleanable, cli.method.into());
+    if !unwritable.is_empty() {
+        println!(
+            "{} {} artifacts can't be deleted{}:",
+            "⚠".yellow(),
+            unwritable.len(),
+            if cli.dry_run { "" } else { " and will be skipped" }
+        );
+        for (path, err) in &unwritable {
+            println!("    {} {} {}", "•".yellow(), path.display(), format!("({})", err).dimmed());
+        }
+        if let Some(action) = unwritable.first().and_then(|(_, e)| e.suggested_action()) {
+            println!("  {}", action.dimmed());
+        }
+        println!();
+
+        if !cli.dry_run {
+            drop_artifacts(&mut cleanable, &unwritable);
+        }
+    }
+
+    // Leave alone what a running build or dev server is using, unless forced
+    let busy = if cli.force { Vec::new() } else { check_in_use(&cleanable) };
+    if !busy.is_empty() {
+        println!(
+            "{} {} artifacts are in use by a running process{}:",
+            "⚠".yellow(),
+            busy.len(),
+            if cli.dry_run { "" } else { " and will be skipped (use --force to clean anyway)" }
+        );
+        for (path, err) in &busy {
+            let reason = match err.root_cause() {
+                DevSweepError::InUse { reason, .. } => reason.clone(),
+                other => other.to_string(),
+            };
+            println!("    {} {} {}", "•".yellow(), path.display(), format!("({})", reason).dimmed());
+        }
+        println!();
+
+        if !cli.dry_run {
+            drop_artifacts(&mut cleanable, &busy);
+        }
+    }
+
+    // Keep what was just built, unless it is picked by hand or forced
+    let min_age = min_age_days(cli);
+    if min_age > 0 && !cli.force && !interactive && !cli.confirm_each {
+        let recent = drop_recent_artifacts(&mut cleanable, min_age);
+        if !recent.is_empty() {
+            let recent_size: u64 = recent.iter().map(|a| a.size).sum();
+            println!(
+                "{} {} artifacts ({}) were modified within the last {} and will be skipped \
+                 (use --min-age 0, --force or --interactive to include them)",
+                "⏱".yellow(),
+                recent.len(),
+                format_size(recent_size),
+                days_label(min_age)
+            );
+            if cli.verbose {
+                for artifact in &recent {
+                    println!("    {} {}", "•".dimmed(), artifact.path.display());
+                }
+            }
+            println!();
+        }
+    }
+
+    // Leave out what the chosen policy counts as too risky
+    if let Some(max) = cli.max_safety.map(SafetyLevel::from) {
+        let risky = drop_riskier_artifacts(&mut cleanable, max);
+        if !risky.is_empty() {
+            println!(
+                "{} {} artifacts ({}) are riskier than {} and will be skipped (--max-safety)",
+                "•".dimmed(),
+                risky.len(),
+                format_size(risky.iter().map(|a| a.size).sum()),
+                max.as_str()
+            );
+            if cli.verbose {
+                for artifact in &risky {
+                    println!("    {} {}", "•".dimmed(), artifact.path.display());
+                }
+            }
+            println!();
+        }
+    }
+
     if cleanable.is_empty() {
         println!("  No projects available to clean.");
         return Ok(());
     }
 
+    if interactive {
+        cleanable = select_projects_interactive(cleanable);
+        if cleanable.is_empty() {
+            return Ok(());
+        }
+        println!();
+    }
+
     // Calculate total
     let total_size: u64 = cleanable.iter().map(|p| p.cleanable_size).sum();
     let total_artifacts: usize = cleanable.iter().map(|p| p.artifacts.len()).sum();
@@ -714,6 +2030,47 @@ fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
         format_size(total_size).yellow().bold()
     );
 
+    // Artifacts reached through a symlink live outside the scanned tree
+    let linked: Vec<_> = cleanable
+        .iter()
+        .flat_map(|p| &p.artifacts)
+        .filter_map(|a| a.metadata.extra.get("via_symlink").map(|link| (a, link)))
+        .collect();
+    if !linked.is_empty() {
+        println!();
+        println!(
+            "{} {} artifacts are reached through a symlink and will be deleted at the link target:",
+            "⚠".yellow(),
+            linked.len()
+        );
+        for (artifact, link) in &linked {
+            println!(
+                "    {} {} {}",
+                "•".yellow(),
+                artifact.path.display(),
+                format!("(via {})", link).dimmed()
+            );
+        }
+    }
+
+    // A redirected target directory may hold other projects' builds too
+    let shared: Vec<_> = cleanable
+        .iter()
+        .flat_map(|p| &p.artifacts)
+        .filter(|a| a.metadata.extra.contains_key("shared_target"))
+        .collect();
+    if !shared.is_empty() {
+        println!();
+        println!(
+            "{} {} artifacts are target directories that other projects may share; they will all need a rebuild:",
+            "⚠".yellow(),
+            shared.len()
+        );
+        for artifact in &shared {
+            println!("    {} {}", "•".yellow(), artifact.path.display());
+        }
+    }
+
     // Determine delete method
     let method = if cli.dry_run {
         DeleteMethod::DryRun
@@ -721,8 +2078,9 @@ fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
         cli.method.into()
     };
 
-    // Confirm unless force or dry-run
-    if !cli.force && method != DeleteMethod::DryRun {
+    // Confirm unless force or dry-run; --confirm-each asks per artifact instead
+    let confirm_each = cli.confirm_each && method != DeleteMethod::DryRun;
+    if !cli.force && !confirm_each && method != DeleteMethod::DryRun {
         println!();
         println!(
             "{}",
@@ -738,87 +2096,469 @@ fn cmd_clean(cli: &Cli, _only: &[String], _exclude: &[String]) -> Result<()> {
         );
         println!();
 
-        print!("Continue? [y/N] ");
-        use std::io::Write;
-        std::io::stdout().flush().unwrap();
+        print!("Continue? [y/N] ");
+        use std::io::Write;
+        std::io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    // Clean
+    println!();
+    let pb = if confirm_each {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(total_artifacts as u64)
+    };
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("█▓░"),
+    );
+
+    let mut cleaned_size = 0u64;
+    let mut cleaned_count = 0usize;
+    let mut failed_count = 0usize;
+    let mut declined_count = 0usize;
+    let mut restore_commands = RestoreCommands::new();
+    let mut cleaned_projects: Vec<&Project> = Vec::new();
+    let clean_span = tracing::info_span!("clean", artifacts = total_artifacts, method = ?method);
+    let clean_entered = clean_span.enter();
+
+    'projects: for project in &cleanable {
+        for artifact in &project.artifacts {
+            pb.set_message(format!("{}/{}", project.name, artifact.name()));
+
+            if confirm_each {
+                let label = format!("{} ({})", artifact.path.display(), format_size(artifact.size));
+                match confirm_item(&label) {
+                    ItemChoice::Clean => {}
+                    ItemChoice::Skip => {
+                        declined_count += 1;
+                        continue;
+                    }
+                    ItemChoice::Quit => {
+                        let done = cleaned_count + failed_count + declined_count;
+                        declined_count += total_artifacts - done;
+                        break 'projects;
+                    }
+                }
+            }
+
+            // A dry run rehearses the method the real run would use
+            let result = if method == DeleteMethod::DryRun {
+                null_e::trash::rehearse_artifact(artifact, cli.method.into())
+            } else {
+                delete_artifact(artifact, method)
+            };
+            emit_record(&result.record(method.as_str()));
+
+            if result.success {
+                cleaned_size += result.bytes_freed;
+                cleaned_count += 1;
+                restore_commands.add(&project.root, artifact);
+                if !cleaned_projects.iter().any(|p| p.root == project.root) {
+                    cleaned_projects.push(project);
+                }
+            } else {
+                // Part of a multi-path artifact may be gone already
+                cleaned_size += result.bytes_freed;
+                failed_count += 1;
+                if cli.verbose {
+                    if let Some(err) = &result.error {
+                        eprintln!("  {} Failed: {}", "✗".red(), err);
+                    }
+                }
+            }
+
+            pb.inc(1);
+        }
+    }
+
+    tracing::info!(cleaned = cleaned_count, failed = failed_count, bytes_freed = cleaned_size, "clean finished");
+    drop(clean_entered);
+    pb.finish_and_clear();
+
+    // Summary
+    println!();
+    if method == DeleteMethod::DryRun {
+        println!(
+            "{} Dry run complete. Would clean {} from {} artifacts.",
+            "✓".green(),
+            format_size(cleaned_size).yellow().bold(),
+            cleaned_count
+        );
+    } else {
+        println!(
+            "{} Cleaned {} from {} artifacts.",
+            "✓".green(),
+            format_size(cleaned_size).yellow().bold(),
+            cleaned_count
+        );
+    }
+
+    if failed_count > 0 {
+        println!(
+            "{} {} artifacts {} to clean",
+            "⚠".yellow(),
+            failed_count,
+            if method == DeleteMethod::DryRun { "would fail" } else { "failed" }
+        );
+    }
+
+    if declined_count > 0 {
+        println!("{} {} artifacts skipped at your request", "•".dimmed(), declined_count);
+    }
+
+    if rebuild {
+        run_rebuilds(cli, &rebuild_commands(&cleaned_projects, &cli.config.plugins))?;
+    } else if method != DeleteMethod::DryRun {
+        print_restore_commands(&restore_commands);
+    }
+
+    report_completion(&cli.config, &null_e::notify::CompletionSummary {
+        bytes_freed: cleaned_size,
+        cleaned: cleaned_count,
+        failed: failed_count,
+        skipped: declined_count,
+        ..null_e::notify::CompletionSummary::new("clean", method == DeleteMethod::DryRun)
+    });
+
+    Ok(())
+}
+
+/// Destination of `--output ndjson` records, set up by [`init_record_stream`]
+static RECORDS: std::sync::OnceLock<parking_lot::Mutex<Box<dyn std::io::Write + Send>>> =
+    std::sync::OnceLock::new();
+
+/// Keep stdout for `--output ndjson` records and send all other output to stderr
+///
+/// Progress, prompts and summaries keep working unchanged, they just can't
+/// interleave with the records a CI job parses.
+fn init_record_stream() {
+    #[cfg(unix)]
+    let out: Box<dyn std::io::Write + Send> = {
+        use std::os::fd::FromRawFd;
+        // SAFETY: `dup` returns a new descriptor owned by nothing else, and
+        // fd 1 is re-pointed before anything has been written to it
+        unsafe {
+            let records = libc::dup(libc::STDOUT_FILENO);
+            if records >= 0 && libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) >= 0 {
+                Box::new(std::fs::File::from_raw_fd(records))
+            } else {
+                Box::new(std::io::stdout())
+            }
+        }
+    };
+    #[cfg(windows)]
+    let out: Box<dyn std::io::Write + Send> = {
+        use std::os::windows::io::{AsHandle, AsRawHandle, RawHandle};
+
+        #[link(name = "kernel32")]
+        extern "system" {
+            fn SetStdHandle(std_handle: u32, handle: RawHandle) -> i32;
+        }
+        const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+
+        // std looks the standard handle up on every write, so re-pointing it
+        // moves println! and friends over to stderr
+        let records = std::io::stdout().as_handle().try_clone_to_owned().ok();
+        // SAFETY: the stderr handle stays valid for the whole process, and
+        // nothing has been written to stdout yet
+        let redirected = records.is_some()
+            && unsafe { SetStdHandle(STD_OUTPUT_HANDLE, std::io::stderr().as_raw_handle()) } != 0;
+        match records {
+            Some(records) if redirected => Box::new(std::fs::File::from(records)),
+            _ => Box::new(std::io::stdout()),
+        }
+    };
+    #[cfg(not(any(unix, windows)))]
+    let out: Box<dyn std::io::Write + Send> = Box::new(std::io::stdout());
+
+    let _ = RECORDS.set(parking_lot::Mutex::new(out));
+}
+
+/// Write one `--output ndjson` record, if that output was requested
+fn emit_record(record: &impl serde::Serialize) {
+    let Some(out) = RECORDS.get() else {
+        return;
+    };
+    if let Ok(line) = serde_json::to_string(record) {
+        use std::io::Write;
+        let mut out = out.lock();
+        let _ = writeln!(out, "{}", line);
+        let _ = out.flush();
+    }
+}
+
+/// Check every artifact for permission problems without deleting anything
+fn check_permissions(projects: &[Project], method: DeleteMethod) -> Vec<(PathBuf, DevSweepError)> {
+    use rayon::prelude::*;
+
+    let artifacts: Vec<&Artifact> = projects.iter().flat_map(|p| &p.artifacts).collect();
+    artifacts
+        .par_iter()
+        .filter_map(|a| {
+            a.paths()
+                .into_iter()
+                .find_map(|path| null_e::trash::check_deletable(path, method).err())
+                .map(|e| (a.path.clone(), e))
+        })
+        .collect()
+}
+
+/// Run each artifact's plugin `pre_clean` hook, returning the ones it refuses
+fn check_in_use(projects: &[Project]) -> Vec<(PathBuf, DevSweepError)> {
+    use rayon::prelude::*;
+
+    let registry = PluginRegistry::with_builtins();
+    let artifacts: Vec<_> = projects
+        .iter()
+        .filter_map(|p| Some((registry.get_by_id(p.detected_by.as_deref()?)?, p)))
+        .flat_map(|(plugin, p)| p.artifacts.iter().map(move |a| (plugin.clone(), a)))
+        .collect();
+    artifacts
+        .par_iter()
+        .filter_map(|(plugin, a)| plugin.pre_clean(a).err().map(|e| (a.path.clone(), e)))
+        .collect()
+}
+
+/// Remove the artifacts at `skipped` paths and drop projects left empty
+fn drop_artifacts(projects: &mut Vec<Project>, skipped: &[(PathBuf, DevSweepError)]) {
+    for project in projects.iter_mut() {
+        project
+            .artifacts
+            .retain(|a| !skipped.iter().any(|(path, _)| *path == a.path));
+        project.calculate_totals();
+    }
+    projects.retain(|p| !p.artifacts.is_empty());
+}
+
+/// Remove artifacts their project's protection rules block, with the reasons
+fn drop_protected_artifacts(projects: &mut Vec<Project>, level: ProtectionLevel) -> Vec<(Artifact, String)> {
+    let mut protected = Vec::new();
+    for project in projects.iter_mut() {
+        let (allowed, blocked): (Vec<_>, Vec<_>) = std::mem::take(&mut project.artifacts)
+            .into_iter()
+            .map(|a| {
+                let check = null_e::git::check_artifact_protection(&a, project, level);
+                (a, check)
+            })
+            .partition(|(_, check)| check.allowed);
+        project.artifacts = allowed.into_iter().map(|(a, _)| a).collect();
+        project.calculate_totals();
+        protected.extend(blocked.into_iter().map(|(a, check)| (a, check.blocked_reason.unwrap_or_default())));
+    }
+    projects.retain(|p| !p.artifacts.is_empty());
+    protected
+}
+
+/// Remove artifacts with a path that is gone from disk, returning them
+fn drop_missing_artifacts(projects: &mut Vec<Project>) -> Vec<Artifact> {
+    let mut missing = Vec::new();
+    for project in projects.iter_mut() {
+        let (present, gone): (Vec<_>, Vec<_>) = std::mem::take(&mut project.artifacts)
+            .into_iter()
+            .partition(|a| a.paths().iter().all(|p| p.symlink_metadata().is_ok()));
+        project.artifacts = present;
+        project.calculate_totals();
+        missing.extend(gone);
+    }
+    projects.retain(|p| !p.artifacts.is_empty());
+    missing
+}
+
+/// Remove artifacts that detection on their project root doesn't report
+/// again, returning them
+fn drop_unreported_artifacts(projects: &mut Vec<Project>, registry: &PluginRegistry) -> Vec<Artifact> {
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut unreported = Vec::new();
+    for project in projects.iter_mut() {
+        let reported = match registry.detect_project(&project.root) {
+            Some((_, plugin)) => plugin.find_artifacts(&project.root).unwrap_or_default(),
+            None => registry
+                .find_orphans(&project.root)
+                .map(|(_, _, orphans)| orphans)
+                .unwrap_or_default(),
+        };
+        let reported: std::collections::HashSet<PathBuf> = reported
+            .iter()
+            .flat_map(|a| a.paths())
+            .map(canonical)
+            .collect();
+        let (kept, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut project.artifacts)
+            .into_iter()
+            .partition(|a| a.paths().into_iter().all(|p| reported.contains(&canonical(p))));
+        project.artifacts = kept;
+        project.calculate_totals();
+        unreported.extend(dropped);
+    }
+    projects.retain(|p| !p.artifacts.is_empty());
+    unreported
+}
+
+/// Remove artifacts modified fewer than `min_age_days` days ago, returning them
+fn drop_recent_artifacts(projects: &mut Vec<Project>, min_age_days: u64) -> Vec<Artifact> {
+    let mut recent = Vec::new();
+    for project in projects.iter_mut() {
+        let (young, old): (Vec<_>, Vec<_>) = std::mem::take(&mut project.artifacts)
+            .into_iter()
+            .partition(|a| a.is_recent(min_age_days));
+        project.artifacts = old;
+        project.calculate_totals();
+        recent.extend(young);
+    }
+    projects.retain(|p| !p.artifacts.is_empty());
+    recent
+}
 
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+/// Remove projects containing any of the `changed` files, returning them
+///
+/// A project nested in another keeps its parent too, since a workspace
+/// root's build output is shared with its members.
+fn drop_changed_projects(projects: &mut Vec<Project>, changed: &[PathBuf]) -> Vec<Project> {
+    let (active, rest): (Vec<_>, Vec<_>) = std::mem::take(projects).into_iter().partition(|p| {
+        let root = p.root.canonicalize().unwrap_or_else(|_| p.root.clone());
+        changed.iter().any(|file| file.starts_with(&root))
+    });
+    *projects = rest;
+    active
+}
 
-        if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Aborted.");
-            return Ok(());
-        }
+/// Remove artifacts riskier than `max`, returning them
+fn drop_riskier_artifacts(projects: &mut Vec<Project>, max: SafetyLevel) -> Vec<Artifact> {
+    let mut risky = Vec::new();
+    for project in projects.iter_mut() {
+        let (keep, drop): (Vec<_>, Vec<_>) = std::mem::take(&mut project.artifacts)
+            .into_iter()
+            .partition(|a| artifact_safety_level(a) <= max);
+        project.artifacts = keep;
+        risky.extend(drop);
+        project.calculate_totals();
     }
+    projects.retain(|p| !p.artifacts.is_empty());
+    risky
+}
 
-    // Clean
-    println!();
-    let pb = ProgressBar::new(total_artifacts as u64);
-    pb.set_style(
-        ProgressStyle::default_bar()
-            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
-            .unwrap()
-            .progress_chars("█▓░"),
-    );
+/// Where a project artifact sits on the cleaner safety scale
+fn artifact_safety_level(artifact: &Artifact) -> SafetyLevel {
+    use null_e::core::ArtifactSafety;
 
-    let mut cleaned_size = 0u64;
-    let mut cleaned_count = 0usize;
-    let mut failed_count = 0usize;
+    match artifact.safety() {
+        ArtifactSafety::AlwaysSafe => SafetyLevel::Safe,
+        ArtifactSafety::SafeIfGitClean | ArtifactSafety::SafeWithLockfile => SafetyLevel::SafeWithCost,
+        ArtifactSafety::RequiresConfirmation => SafetyLevel::Caution,
+        ArtifactSafety::NeverAuto => SafetyLevel::Dangerous,
+    }
+}
 
-    for project in &cleanable {
-        for artifact in &project.artifacts {
-            pb.set_message(format!("{}/{}", project.name, artifact.name()));
+/// Minimum age for unattended cleaning: `--min-age`, else `clean.min_age_days`
+fn min_age_days(cli: &Cli) -> u64 {
+    cli.min_age.unwrap_or(cli.config.clean.min_age_days)
+}
 
-            let result = delete_artifact(artifact, method);
+/// "day" or "N days"
+fn days_label(days: u64) -> String {
+    if days == 1 {
+        "day".to_string()
+    } else {
+        format!("{} days", days)
+    }
+}
 
-            if result.success {
-                cleaned_size += result.bytes_freed;
-                cleaned_count += 1;
-            } else {
-                failed_count += 1;
-                if cli.verbose {
-                    if let Some(err) = &result.error {
-                        eprintln!("  {} Failed: {}", "✗".red(), err);
-                    }
-                }
-            }
+/// Show how to rebuild or reinstall what was just cleaned
+fn print_restore_commands(commands: &RestoreCommands) {
+    if commands.is_empty() {
+        return;
+    }
 
-            pb.inc(1);
+    println!();
+    println!("{}", "To restore, run:".bold());
+    for (command, roots) in commands.iter() {
+        println!("  {} {}", "$".dimmed(), command.cyan());
+        for root in roots {
+            println!("      {} {}", "in".dimmed(), root.display());
         }
     }
+}
 
-    pb.finish_and_clear();
+/// The command rebuilding each project, from `[plugins.rebuild]` or the project's plugin
+fn rebuild_commands(projects: &[&Project], settings: &null_e::config::PluginSettings) -> Vec<(PathBuf, String)> {
+    let registry = PluginRegistry::with_builtins();
+
+    projects
+        .iter()
+        .filter_map(|project| {
+            let id = project.detected_by.as_deref()?;
+            let default = registry.get_by_id(id).and_then(|p| p.rebuild_command(&project.root));
+            let command = settings.rebuild_command(id, default)?;
+            Some((project.root.clone(), command))
+        })
+        .collect()
+}
+
+/// Program and arguments of a rebuild command, with `{root}` filled in
+fn rebuild_args(command: &str, root: &Path) -> Vec<String> {
+    let root = root.display().to_string();
+    command
+        .split_whitespace()
+        .map(|arg| arg.replace("{root}", &root))
+        .collect()
+}
+
+/// Offer to run each rebuild command in its project (`clean --rebuild`)
+///
+/// `--force` runs them all without asking and `--dry-run` only lists them.
+/// A failed build is reported and doesn't stop the others.
+fn run_rebuilds(cli: &Cli, rebuilds: &[(PathBuf, String)]) -> Result<()> {
+    if rebuilds.is_empty() {
+        return Ok(());
+    }
 
-    // Summary
     println!();
-    if method == DeleteMethod::DryRun {
-        println!(
-            "{} Dry run complete. Would clean {} from {} artifacts.",
-            "✓".green(),
-            format_size(cleaned_size).yellow().bold(),
-            cleaned_count
-        );
-    } else {
-        println!(
-            "{} Cleaned {} from {} artifacts.",
-            "✓".green(),
-            format_size(cleaned_size).yellow().bold(),
-            cleaned_count
-        );
+    if cli.dry_run {
+        println!("{}", "Would offer to rebuild:".bold());
+        for (root, command) in rebuilds {
+            println!("  {} {}", "$".dimmed(), command.cyan());
+            println!("      {} {}", "in".dimmed(), root.display());
+        }
+        return Ok(());
     }
 
-    if failed_count > 0 {
-        println!(
-            "{} {} artifacts failed to clean",
-            "⚠".yellow(),
-            failed_count
-        );
+    println!("{}", "Rebuild:".bold());
+    for (root, command) in rebuilds {
+        if !cli.force {
+            match ask_item(&format!("Run `{}` in {}?", command, root.display())) {
+                ItemChoice::Clean => {}
+                ItemChoice::Skip => continue,
+                ItemChoice::Quit => break,
+            }
+        }
+
+        let args = rebuild_args(command, root);
+        let Some((program, args)) = args.split_first() else {
+            continue;
+        };
+        println!("  {} {}", "$".dimmed(), command.cyan());
+        // Builds print their own progress and can take as long as they need
+        match std::process::Command::new(program).args(args).current_dir(root).status() {
+            Ok(status) if status.success() => println!("  {} Rebuilt {}", "✓".green(), root.display()),
+            Ok(status) => println!("  {} `{}` failed ({})", "✗".red(), command, status),
+            Err(e) => println!("  {} Could not run `{}`: {}", "✗".red(), command, e),
+        }
     }
 
     Ok(())
 }
 
-fn cmd_config(init: bool, show_path: bool) -> Result<()> {
+fn cmd_config(config: &null_e::config::Config, init: bool, show_path: bool) -> Result<()> {
     if init {
         let path = null_e::config::init_config()?;
         println!("{} Created config file at:", "✓".green());
@@ -833,8 +2573,85 @@ fn cmd_config(init: bool, show_path: bool) -> Result<()> {
     }
 
     // Show current config
-    let config = null_e::config::load_default_config()?;
-    println!("{}", toml::to_string_pretty(&config)?);
+    println!("{}", toml::to_string_pretty(config)?);
+
+    Ok(())
+}
+
+fn cmd_cache(inspect: bool) -> Result<()> {
+    use null_e::cache::ScanCache;
+
+    let path = null_e::cache::default_cache_path()?;
+    println!("{} {}", "Cache file:".bold(), path.display());
+    if let Ok(meta) = std::fs::metadata(&path) {
+        println!("  File size:   {} (gzip)", format_size(meta.len()));
+    }
+    if let Ok(meta) = std::fs::metadata(path.with_extension("")) {
+        println!(
+            "  {} Uncompressed cache from an older version ({}) is removed by the next scan",
+            "⚠".yellow(),
+            format_size(meta.len())
+        );
+    }
+
+    let cache = match null_e::cache::read_cache_file(&path) {
+        Ok(Some(cache)) => cache,
+        Ok(None) => {
+            println!("  No cache yet - it is written by the first `null-e scan`.");
+            return Ok(());
+        }
+        Err(e) => {
+            println!("  {} Unreadable ({}); the next scan starts fresh.", "⚠".yellow(), e);
+            return Ok(());
+        }
+    };
+
+    let version_note = if cache.version == ScanCache::VERSION {
+        "current".green()
+    } else {
+        format!("expected {}, the next scan discards it", ScanCache::VERSION).yellow()
+    };
+    let age = cache.age_secs();
+    println!("  Version:     {} ({})", cache.version, version_note);
+    println!(
+        "  Updated:     {}h {}m ago{}",
+        age / 3600,
+        age % 3600 / 60,
+        if cache.is_valid() { String::new() } else { " (stale)".yellow().to_string() }
+    );
+
+    let mut entries: Vec<_> = cache
+        .projects
+        .keys()
+        .filter_map(|root| Some((root, cache.entry_validity(root)?)))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    let valid = entries.iter().filter(|(_, v)| v.is_valid()).count();
+    println!(
+        "  Projects:    {} ({} valid, {} would be rescanned)",
+        entries.len(),
+        valid,
+        entries.len() - valid
+    );
+    println!("  Directories: {}", cache.directories.len());
+    println!("  Size mode:   {}", format!("{:?}", cache.size_mode).to_lowercase());
+
+    if !inspect {
+        if !entries.is_empty() {
+            println!();
+            println!("{} Use {} to see each entry", "💡".dimmed(), "null-e cache --inspect".cyan());
+        }
+        return Ok(());
+    }
+
+    println!();
+    for (root, validity) in entries {
+        let mark = if validity.is_valid() { "✓".green() } else { "✗".red() };
+        println!("  {} {}", mark, root.display());
+        if !validity.is_valid() {
+            println!("      {}", validity.reason().dimmed());
+        }
+    }
 
     Ok(())
 }
@@ -868,8 +2685,212 @@ fn cmd_list() -> Result<()> {
     Ok(())
 }
 
-fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Result<()> {
-    use null_e::caches::{detect_caches, calculate_all_sizes, CachesSummary};
+fn cmd_stats(config: &null_e::config::Config) -> Result<()> {
+    use chrono::Datelike;
+    use null_e::stats::{monthly, StatsLog, StatsTotals};
+
+    println!(
+        "{} {}",
+        "🤖 null-e Stats".green().bold(),
+        format!("v{}", null_e::VERSION).dimmed()
+    );
+    println!();
+
+    let enabled = config.stats.enabled;
+    let log = StatsLog::new()?;
+    let entries = log.load()?;
+
+    if entries.is_empty() {
+        println!("  No cleans recorded yet.");
+        if !enabled {
+            println!(
+                "  {}",
+                "Add `[stats] enabled = true` to the config file to start keeping a local tally".dimmed()
+            );
+        }
+        return Ok(());
+    }
+
+    let total = StatsTotals::of(&entries);
+    let this_year = chrono::Utc::now().year();
+    let year = StatsTotals::of(entries.iter().filter(|e| e.at.year() == this_year));
+    println!(
+        "{} Reclaimed {} in {} cleans ({} items)",
+        "✓".green(),
+        format_size(total.bytes_freed).yellow().bold(),
+        total.runs,
+        total.items
+    );
+    println!(
+        "  {} {} in {}",
+        "│".dimmed(),
+        format_size(year.bytes_freed).yellow(),
+        this_year
+    );
+    if !enabled {
+        println!("  {} {}", "│".dimmed(), "Recording is off ([stats] enabled = false)".dimmed());
+    }
+    println!();
+
+    println!("   {}", "By month:".bold());
+    for ((year, month), totals) in monthly(&entries).iter().rev() {
+        println!(
+            "   {}-{:02} {:>12}  ({} cleans, {} items)",
+            year,
+            month,
+            format_size(totals.bytes_freed).yellow(),
+            totals.runs,
+            totals.items
+        );
+    }
+    println!();
+    println!("  {}", format!("Kept locally in {}", log.path().display()).dimmed());
+
+    Ok(())
+}
+
+fn cmd_doctor() -> Result<()> {
+    use null_e::cleaners::{
+        android::AndroidCleaner, binaries::BinaryAnalyzer, browsers_test::TestBrowsersCleaner,
+        cloud::CloudCliCleaner, docker::DockerCleaner, electron::ElectronCleaner,
+        gamedev::GameDevCleaner, homebrew::HomebrewCleaner, ide::IdeCleaner,
+        ios_deps::IosDependencyCleaner, logs::LogsCleaner, macos::MacOsCleaner, misc::MiscCleaner,
+        ml::MlCleaner, runtimes::RuntimesCleaner, system::SystemCleaner, xcode::XcodeCleaner,
+    };
+
+    println!(
+        "{} {}",
+        "🤖 null-e Doctor".green().bold(),
+        format!("v{}", null_e::VERSION).dimmed()
+    );
+    println!();
+
+    println!("{}", "Environment:".bold());
+    let home = dirs::home_dir();
+    print_check(
+        home.is_some(),
+        "Home directory",
+        &home.as_ref().map_or("not resolved; most cleaners are disabled".into(), |h| h.display().to_string()),
+    );
+    match null_e::cache::default_cache_path() {
+        Ok(path) => {
+            let dir = path.parent().unwrap_or(&path);
+            let result = probe_writable(dir);
+            print_check(result.is_ok(), "Cache directory", &doctor_detail(dir, result));
+        }
+        Err(e) => print_check(false, "Cache directory", &e.to_string()),
+    }
+    match null_e::config::default_config_path() {
+        Ok(path) if path.exists() => match null_e::config::load_config(&path) {
+            Ok(_) => print_check(true, "Config file", &path.display().to_string()),
+            Err(e) => print_check(false, "Config file", &e.to_string()),
+        },
+        Ok(path) => print_check(true, "Config file", &format!("{} (not created, using defaults)", path.display())),
+        Err(e) => print_check(false, "Config file", &e.to_string()),
+    }
+    match trash_dir() {
+        Some(dir) => {
+            let result = probe_writable(&dir);
+            print_check(result.is_ok(), "Trash", &doctor_detail(&dir, result));
+        }
+        None if cfg!(any(target_os = "macos", windows)) => print_check(true, "Trash", "system trash"),
+        None => print_check(false, "Trash", "no trash location; use --permanent"),
+    }
+    println!();
+
+    println!("{}", "Tools:".bold());
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    for tool in ["git", "brew", "which", "docker"] {
+        let found = find_in_path(tool, &path_var);
+        print_check(
+            found.is_some(),
+            tool,
+            &found.map_or("not on PATH".into(), |p| p.display().to_string()),
+        );
+    }
+    let docker = if !null_e::cleaners::docker_enabled() {
+        (false, "disabled (--no-docker or --no-external-commands)")
+    } else if null_e::docker::is_docker_available() {
+        (true, "daemon is running")
+    } else {
+        (false, "daemon not reachable; Docker items won't be detected")
+    };
+    print_check(docker.0, "docker daemon", docker.1);
+    println!();
+
+    println!("{}", "Cleaners on this system:".bold());
+    // Most cleaners only need a home directory
+    let macos_only = if cfg!(target_os = "macos") { "no home directory" } else { "macOS only" };
+    let cleaners = [
+        ("Xcode", XcodeCleaner::new().is_some(), macos_only),
+        ("Android", AndroidCleaner::new().is_some(), "no home directory"),
+        ("Docker", DockerCleaner::new().is_available(), "docker daemon not available"),
+        ("ML/AI", MlCleaner::new().is_some(), "no home directory"),
+        ("IDEs", IdeCleaner::new().is_some(), "no home directory"),
+        ("Logs", LogsCleaner::new().is_some(), "no home directory"),
+        ("Homebrew", HomebrewCleaner::new().is_some_and(|c| c.is_available()), "brew not available"),
+        ("iOS Dependencies", IosDependencyCleaner::new().is_some(), "no home directory"),
+        ("Electron Apps", ElectronCleaner::new().is_some(), "no home directory"),
+        ("Game Dev", GameDevCleaner::new().is_some(), "no home directory"),
+        ("Cloud CLI", CloudCliCleaner::new().is_some(), "no home directory"),
+        ("macOS System", cfg!(target_os = "macos") && MacOsCleaner::new().is_some(), macos_only),
+        ("Misc tools", MiscCleaner::new().is_some(), "no home directory"),
+        ("Test browsers", TestBrowsersCleaner::new().is_some(), "no home directory"),
+        ("System", SystemCleaner::new().is_some(), "no home directory"),
+        ("Runtimes", RuntimesCleaner::new().is_some(), "no home directory"),
+        ("Binaries", BinaryAnalyzer::new().is_some(), "no home directory"),
+    ];
+    for (name, applicable, reason) in cleaners {
+        print_check(applicable, name, if applicable { "available" } else { reason });
+    }
+
+    Ok(())
+}
+
+/// One `doctor` line: a mark, the label and what was found
+fn print_check(ok: bool, label: &str, detail: &str) {
+    let mark = if ok { "✓".green() } else { "✗".red() };
+    println!("  {} {:<18} {}", mark, label, detail.dimmed());
+}
+
+/// A directory, followed by why it can't be written to
+fn doctor_detail(dir: &Path, result: std::io::Result<()>) -> String {
+    match result {
+        Ok(()) => format!("{} (writable)", dir.display()),
+        Err(e) => format!("{} (not writable: {})", dir.display(), e),
+    }
+}
+
+/// Create `dir` if needed and write and remove a file in it
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(format!(".null-e-doctor.{}", std::process::id()));
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(&probe)
+}
+
+/// The freedesktop trash that deletes are moved to, where there is one
+fn trash_dir() -> Option<PathBuf> {
+    if cfg!(any(target_os = "macos", windows)) {
+        return None;
+    }
+    let data = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| Some(dirs::home_dir()?.join(".local/share")))?;
+    Some(data.join("Trash"))
+}
+
+/// First executable called `name` in the directories of a `PATH` value
+fn find_in_path(name: &str, path_var: &std::ffi::OsStr) -> Option<PathBuf> {
+    let file = format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(path_var)
+        .map(|dir| dir.join(&file))
+        .find(|candidate| candidate.is_file())
+}
+
+fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool, group: bool) -> Result<()> {
+    use null_e::caches::{detect_caches_with, calculate_all_sizes_with_mode, CachesSummary};
 
     println!(
         "{} {}",
@@ -888,7 +2909,7 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
     pb.set_message("Detecting global caches...");
     pb.enable_steady_tick(Duration::from_millis(100));
 
-    let mut caches = detect_caches()?;
+    let mut caches = detect_caches_with(&cli.config.caches.custom)?;
 
     if caches.is_empty() {
         pb.finish_and_clear();
@@ -899,13 +2920,16 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
     pb.set_message(format!("Calculating sizes for {} caches...", caches.len()));
 
     // Calculate sizes
-    calculate_all_sizes(&mut caches)?;
+    calculate_all_sizes_with_mode(&mut caches, configured_size_mode(cli))?;
 
     // Filter out empty caches
     caches.retain(|c| c.size > 0);
 
-    // Sort by size descending
-    caches.sort_by(|a, b| b.size.cmp(&a.size));
+    // Sort by size descending, within each ecosystem when grouped
+    caches.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    if group {
+        caches.sort_by_key(|c| c.ecosystem);
+    }
 
     pb.finish_and_clear();
 
@@ -938,11 +2962,20 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
     println!("   {}", "─".repeat(80).dimmed());
 
     // Display each cache with selection number
-    for (i, cache) in caches.iter().enumerate() {
+    let display_count = row_limit(cli, None).map_or(caches.len(), |n| n.min(caches.len()));
+    for (i, cache) in caches.iter().take(display_count).enumerate() {
+        if group && (i == 0 || caches[i - 1].ecosystem != cache.ecosystem) {
+            let subtotal: u64 = caches.iter().filter(|c| c.ecosystem == cache.ecosystem).map(|c| c.size).sum();
+            println!(
+                "   {} {}",
+                cache.ecosystem.label().bold(),
+                format!("{} · {}", format_size(subtotal), cache.ecosystem.as_str()).dimmed()
+            );
+        }
         let num = format!("[{}]", i + 1);
         let size_str = format_size(cache.size);
         let last_used = cache.last_used_display();
-        let cmd = cache.clean_command.unwrap_or("-");
+        let cmd = cache.clean_command.as_deref().unwrap_or("-");
 
         // Color code by size
         let size_colored = if cache.size > 1_000_000_000 {
@@ -963,6 +2996,8 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
             cmd.dimmed()
         );
     }
+    let hidden = &caches[display_count..];
+    print_hidden_rows(hidden.len(), hidden.iter().map(|c| c.size).sum(), "caches");
 
     println!("   {}", "─".repeat(80).dimmed());
     println!(
@@ -973,6 +3008,14 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
     );
     println!();
 
+    if cli.age_report {
+        let mut histogram = AgeHistogram::new();
+        for cache in &caches {
+            histogram.add(cache.age_days(), cache.size);
+        }
+        print_age_report(&histogram);
+    }
+
     // If not cleaning, show hints
     if !clean && !clean_all {
         println!(
@@ -993,62 +3036,79 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
         return Ok(());
     }
 
+    // Global caches are refetched on demand, so they count as safe-with-cost
+    if let Some(max) = cli.max_safety.map(SafetyLevel::from).filter(|max| *max < SafetyLevel::SafeWithCost) {
+        println!(
+            "  Caches are {}, riskier than {}; nothing to clean (--max-safety).",
+            SafetyLevel::SafeWithCost.as_str(),
+            max.as_str()
+        );
+        return Ok(());
+    }
+
     // Clean all without prompting
     if clean_all {
-        if !cli.force && !cli.dry_run {
-            println!(
-                "{} This will clean ALL {} caches ({})!",
-                "⚠️".yellow(),
-                caches.len(),
-                format_size(summary.total_size).yellow()
-            );
-            print!("Continue? [y/N] ");
-            use std::io::Write;
-            std::io::stdout().flush().unwrap();
-
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
-
-            if !input.trim().eq_ignore_ascii_case("y") {
-                println!("Aborted.");
+        // Keep caches that were just filled, unless forced
+        let min_age = min_age_days(cli);
+        if min_age > 0 && !cli.force {
+            let (recent, old): (Vec<_>, Vec<_>) =
+                caches.into_iter().partition(|c| c.is_recent(min_age));
+            caches = old;
+            if !recent.is_empty() {
+                println!(
+                    "{} Skipping {} caches used within the last {}: {} (use --min-age 0, --force or --clean to include them)",
+                    "⏱".yellow(),
+                    recent.len(),
+                    days_label(min_age),
+                    recent.iter().map(|c| c.name.as_str()).collect::<Vec<_>>().join(", ")
+                );
+                println!();
+            }
+            if caches.is_empty() {
+                println!("  No caches old enough to clean.");
                 return Ok(());
             }
         }
+        let summary = CachesSummary::from_caches(&caches);
 
-        return clean_selected_caches(&caches, cli, use_official);
-    }
-
-    // Interactive selection
-    if clean {
-        println!(
-            "Enter cache numbers to clean (e.g., {} or {} or {}):",
-            "1,3,5".cyan(),
-            "1-5".cyan(),
-            "all".cyan()
-        );
-        print!("> ");
-        use std::io::Write;
-        std::io::stdout().flush().unwrap();
-
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
-        let input = input.trim().to_lowercase();
+        if use_official {
+            print_cache_commands(&caches, cli);
+            println!();
+        }
 
-        if input.is_empty() || input == "q" || input == "quit" {
-            println!("Aborted.");
-            return Ok(());
+        if !cli.force && !cli.dry_run {
+            println!(
+                "{} This will clean ALL {} caches ({})!",
+                "⚠️".yellow(),
+                caches.len(),
+                format_size(summary.total_size).yellow()
+            );
         }
-
-        let selected_indices: Vec<usize> = if input == "all" || input == "a" {
-            (0..caches.len()).collect()
-        } else {
-            parse_selection(&input, caches.len())
+        let Some(caches) = confirm_caches(caches, cli) else {
+            return Ok(());
         };
 
-        if selected_indices.is_empty() {
-            println!("No valid selection.");
+        return clean_selected_caches(&caches, cli, use_official);
+    }
+
+    // Interactive selection
+    if clean {
+        let labels: Vec<_> = caches
+            .iter()
+            .map(|c| format!("{} {:<22} {:>12}", c.icon, c.name, format_size(c.size)))
+            .collect();
+        // An ecosystem name selects all of its caches
+        let groups: Vec<(&str, Vec<usize>)> = null_e::caches::CacheEcosystem::all()
+            .iter()
+            .map(|e| {
+                let members = caches.iter().enumerate().filter(|(_, c)| c.ecosystem == *e).map(|(i, _)| i);
+                (e.as_str(), members.collect::<Vec<_>>())
+            })
+            .filter(|(_, members)| !members.is_empty())
+            .collect();
+        let Some(selected_indices) = read_selection(cli, "cache", &labels, &groups) else {
             return Ok(());
-        }
+        };
 
         let selected_caches: Vec<_> = selected_indices
             .iter()
@@ -1064,19 +3124,15 @@ fn cmd_caches(cli: &Cli, clean: bool, clean_all: bool, use_official: bool) -> Re
             format_size(selected_size).yellow()
         );
 
-        if !cli.force && !cli.dry_run {
-            print!("Continue? [y/N] ");
-            std::io::stdout().flush().unwrap();
-
-            let mut confirm = String::new();
-            std::io::stdin().read_line(&mut confirm).unwrap();
-
-            if !confirm.trim().eq_ignore_ascii_case("y") {
-                println!("Aborted.");
-                return Ok(());
-            }
+        if use_official {
+            print_cache_commands(&selected_caches, cli);
+            println!();
         }
 
+        let Some(selected_caches) = confirm_caches(selected_caches, cli) else {
+            return Ok(());
+        };
+
         return clean_selected_caches(&selected_caches, cli, use_official);
     }
 
@@ -1088,7 +3144,12 @@ fn clean_selected_caches(
     cli: &Cli,
     use_official: bool,
 ) -> Result<()> {
-    use null_e::caches::clean_cache;
+    use null_e::caches::clean_cache_with_method;
+
+    let safe_mode = safe_mode_enabled(cli);
+    // Manual deletion has always been permanent here; safe mode trashes instead
+    let method = if safe_mode { DeleteMethod::Trash } else { DeleteMethod::Permanent };
+    let size_mode = configured_size_mode(cli);
 
     println!();
 
@@ -1107,22 +3168,48 @@ fn clean_selected_caches(
     for cache in caches {
         pb.set_message(cache.name.clone());
 
+        let official = use_official
+            && (!safe_mode || allowed_command(cache.clean_command.as_deref(), safe_mode).is_some());
+
         if cli.dry_run {
-            // Dry run - just pretend
-            cleaned_size += cache.size;
-            cleaned_count += 1;
+            // Dry run - rehearse a manual delete; an official command can't be checked ahead
+            let rehearsal = if official { Ok(cache.size) } else { null_e::trash::rehearse_path(&cache.path, method) };
+            match rehearsal {
+                Ok(size) => {
+                    cleaned_size += size;
+                    cleaned_count += 1;
+                    emit_record(&CleanRecord::succeeded(&cache.path, DeleteMethod::DryRun.as_str(), size));
+                }
+                Err(e) => {
+                    emit_record(&CleanRecord::failed(&cache.path, DeleteMethod::DryRun.as_str(), e.to_string()));
+                    failed_count += 1;
+                    if cli.verbose {
+                        eprintln!("  {} Would fail: {}: {}", "✗".red(), cache.name, e);
+                    }
+                }
+            }
         } else {
-            match clean_cache(cache, use_official) {
+            match clean_cache_with_method(cache, official, method, size_mode) {
                 Ok(result) => {
+                    let record = CleanRecord::succeeded(&cache.path, method.as_str(), result.bytes_freed);
+                    emit_record(&match result.method {
+                        null_e::caches::CleanMethod::OfficialCommand(cmd) => record.with_command(cmd),
+                        _ => record,
+                    });
                     if result.success {
                         cleaned_size += result.bytes_freed;
                         cleaned_count += 1;
                     }
                 }
                 Err(e) => {
+                    emit_record(&CleanRecord::failed(&cache.path, method.as_str(), e.to_string()));
+                    if e.is_user_interrupt() {
+                        pb.finish_and_clear();
+                        return Err(e);
+                    }
                     failed_count += 1;
                     if cli.verbose {
-                        eprintln!("  {} Failed to clean {}: {}", "✗".red(), cache.name, e);
+                        eprintln!("  {} {}", "✗".red(), e);
                     }
                 }
             }
@@ -1153,47 +3240,220 @@ fn clean_selected_caches(
 
     if failed_count > 0 {
         println!(
-            "{} {} caches failed to clean",
+            "{} {} caches {} to clean",
             "⚠".yellow(),
-            failed_count
+            failed_count,
+            if cli.dry_run { "would fail" } else { "failed" }
         );
     }
 
+    report_completion(&cli.config, &null_e::notify::CompletionSummary {
+        bytes_freed: cleaned_size,
+        cleaned: cleaned_count,
+        failed: failed_count,
+        ..null_e::notify::CompletionSummary::new("caches", cli.dry_run)
+    });
+
     Ok(())
 }
 
-/// Parse selection like "1,3,5" or "1-5" or "1,3-5,7"
+/// Print an age histogram with per-bucket sizes
+fn print_age_report(histogram: &AgeHistogram) {
+    const BAR_WIDTH: u64 = 30;
+
+    println!("{}", "Age distribution (last modified):".bold());
+    let max = histogram.max_size().max(1);
+    for (bucket, stats) in histogram.iter() {
+        let bar_len = (stats.size * BAR_WIDTH).div_ceil(max) as usize;
+        println!(
+            "   {:<11} {:<30} {:>12} {}",
+            bucket.label(),
+            "█".repeat(bar_len).cyan(),
+            format_size(stats.size).yellow(),
+            format!("({} items)", stats.count).dimmed()
+        );
+    }
+    println!();
+}
+
+/// Preview the official commands `clean_selected_caches` will run for these caches
+fn print_cache_commands(caches: &[null_e::caches::GlobalCache], cli: &Cli) {
+    let safe_mode = safe_mode_enabled(cli);
+    let (commands, refused): (Vec<_>, Vec<_>) =
+        unique_clean_commands(caches.iter().filter_map(|c| c.clean_command.as_deref()))
+            .into_iter()
+            .partition(|cmd| allowed_command(Some(cmd), safe_mode).is_some());
+
+    print_clean_commands(&commands, cli.dry_run);
+    print_refused_commands(&refused);
+}
+
+/// List projects with numbers and keep only the ones the user picks
+///
+/// Returns an empty list when the user aborts or selects nothing valid.
+fn select_projects_interactive(projects: Vec<Project>) -> Vec<Project> {
+    println!();
+    for (i, project) in projects.iter().enumerate() {
+        let artifacts: Vec<_> = project.artifacts.iter().map(|a| a.name()).collect();
+        println!(
+            "  {:>3}. {} {:<30} {:>10}  {}",
+            (i + 1).to_string().cyan(),
+            project.kind.icon(),
+            project.name,
+            format_size(project.cleanable_size).yellow(),
+            artifacts.join(", ").dimmed()
+        );
+    }
+
+    println!();
+    println!(
+        "Enter project numbers to clean (e.g., {} or {} or {} or {}):",
+        "1,3,5".cyan(),
+        "1-5".cyan(),
+        "all".cyan(),
+        "all,!3".cyan()
+    );
+    print!("> ");
+    use std::io::Write;
+    std::io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).unwrap();
+    let input = input.trim().to_lowercase();
+
+    if input.is_empty() || input == "q" || input == "quit" {
+        println!("Aborted.");
+        return Vec::new();
+    }
+
+    let selected = parse_selection(&input, projects.len());
+
+    if selected.is_empty() {
+        println!("No valid selection.");
+    }
+
+    projects
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| selected.contains(i))
+        .map(|(_, p)| p)
+        .collect()
+}
+
+/// Parse selection like "1,3,5", "1-5", "5-", "all" or "all,!3"
+///
+/// Numbers are 1-based and come back 0-indexed, sorted and deduplicated.
+/// `!` excludes a number or range from what the rest selects; a selection of
+/// only exclusions ("!3") starts from everything.
+/// Ask which of the listed entries to clean
+///
+/// Uses the arrow-key picker with `--picker` or `ui.picker` in a terminal,
+/// and the numbered prompt otherwise or if the picker fails. `None` after
+/// printing why nothing was picked.
+fn read_selection(cli: &Cli, noun: &str, labels: &[String], groups: &[(&str, Vec<usize>)]) -> Option<Vec<usize>> {
+    use std::io::{IsTerminal, Write};
+
+    let use_picker = cli.picker || cli.config.ui.picker;
+    let picked = if use_picker && std::io::stdin().is_terminal() && std::io::stdout().is_terminal() {
+        let prompt = format!("Select {}s to clean", noun);
+        match null_e::tui::picker::multi_select(&prompt, labels) {
+            Ok(Some(selected)) => Some(selected),
+            Ok(None) => {
+                println!("Aborted.");
+                return None;
+            }
+            Err(e) => {
+                eprintln!("{} Picker unavailable ({}), using the numbered prompt", "⚠".yellow(), e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let selected = if let Some(selected) = picked {
+        selected
+    } else {
+        println!(
+            "Enter {} numbers to clean (e.g., {} or {} or {} or {}):",
+            noun,
+            "1,3,5".cyan(),
+            "1-5".cyan(),
+            "all".cyan(),
+            "all,!3".cyan()
+        );
+        if let Some((name, _)) = groups.first() {
+            println!("A group name selects all of its {}s (e.g. {}).", noun, name.cyan());
+        }
+        print!("> ");
+        std::io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+        let input = input.trim().to_lowercase();
+
+        if input.is_empty() || input == "q" || input == "quit" {
+            println!("Aborted.");
+            return None;
+        }
+        parse_selection_with_groups(&input, labels.len(), groups)
+    };
+
+    if selected.is_empty() {
+        println!("No valid selection.");
+        return None;
+    }
+    Some(selected)
+}
+
 fn parse_selection(input: &str, max: usize) -> Vec<usize> {
-    let mut result = Vec::new();
+    parse_selection_with_groups(input, max, &[])
+}
+
+/// Parse a selection where a group's name stands for all of its 0-indexed members
+fn parse_selection_with_groups(input: &str, max: usize, groups: &[(&str, Vec<usize>)]) -> Vec<usize> {
+    let mut selected = std::collections::BTreeSet::new();
+    let mut excluded = std::collections::BTreeSet::new();
+    let mut any_included = false;
 
     for part in input.split(',') {
         let part = part.trim();
+        let (negated, part) = match part.strip_prefix('!') {
+            Some(rest) => (true, rest.trim()),
+            None => (false, part),
+        };
+        let target = if negated { &mut excluded } else { &mut selected };
+        any_included |= !negated && !part.is_empty();
 
-        if part.contains('-') {
-            // Range like "1-5"
-            let parts: Vec<&str> = part.split('-').collect();
-            if parts.len() == 2 {
-                if let (Ok(start), Ok(end)) = (parts[0].parse::<usize>(), parts[1].parse::<usize>()) {
-                    for i in start..=end {
-                        if i >= 1 && i <= max {
-                            result.push(i - 1); // Convert to 0-indexed
-                        }
-                    }
-                }
-            }
-        } else {
-            // Single number
-            if let Ok(n) = part.parse::<usize>() {
-                if n >= 1 && n <= max {
-                    result.push(n - 1); // Convert to 0-indexed
-                }
-            }
+        if let Some((_, members)) = groups.iter().find(|(name, _)| *name == part) {
+            target.extend(members.iter().copied().filter(|&i| i < max));
+            continue;
+        }
+
+        let range = match part {
+            "all" | "a" => Some((1, max)),
+            _ => match part.split_once('-') {
+                // Range like "1-5", or open-ended like "5-"
+                Some((start, end)) => match (start.trim().parse(), end.trim()) {
+                    (Ok(start), "") => Some((start, max)),
+                    (Ok(start), end) => end.parse().ok().map(|end| (start, end)),
+                    _ => None,
+                },
+                // Single number
+                None => part.parse().ok().map(|n| (n, n)),
+            },
+        };
+
+        if let Some((start, end)) = range {
+            // Convert to 0-indexed
+            target.extend((start.max(1)..=end.min(max)).map(|i| i - 1));
         }
     }
 
-    result.sort();
-    result.dedup();
-    result
+    if !any_included && !excluded.is_empty() {
+        selected.extend(0..max);
+    }
+    selected.difference(&excluded).copied().collect()
 }
 
 fn get_scan_paths(cli: &Cli) -> Result<Vec<PathBuf>> {
@@ -1206,6 +3466,12 @@ fn get_scan_paths(cli: &Cli) -> Result<Vec<PathBuf>> {
     } else {
         // Validate paths
         for path in &cli.paths {
+            if RemoteTarget::is_remote(path) {
+                return Err(DevSweepError::Config(format!(
+                    "Remote paths are only supported by 'scan': {}",
+                    path.display()
+                )));
+            }
             if !path.exists() {
                 return Err(DevSweepError::PathNotFound(path.clone()));
             }
@@ -1214,37 +3480,259 @@ fn get_scan_paths(cli: &Cli) -> Result<Vec<PathBuf>> {
     }
 }
 
+/// Apply `--nice` and the `[scan]` I/O settings from the config file
+fn apply_io_settings(cli: &Cli, config: ScanConfig) -> ScanConfig {
+    let settings = &cli.config.scan;
+    let mut priority = settings.io_priority;
+    let mut throttle = settings.throttle;
+
+    if let Some(rate) = cli.nice {
+        priority = null_e::core::IoPriority::Low;
+        throttle = rate.or(throttle);
+    }
+
+    let config = config.with_io_priority(priority);
+    match throttle {
+        Some(dirs_per_sec) => config.with_throttle(dirs_per_sec),
+        None => config,
+    }
+}
+
+/// Scan config for `roots` with the depth, timeout, symlink and I/O flags applied
+fn cli_scan_config(cli: &Cli, roots: Vec<PathBuf>, size_mode: null_e::core::SizeMode) -> ScanConfig {
+    let mut config = ScanConfig::default().with_roots(roots).with_size_mode(size_mode);
+    if let Some(depth) = cli.max_depth {
+        config = config.with_max_depth(depth);
+    }
+    if let Some(secs) = cli.timeout {
+        config = config.with_timeout(Duration::from_secs(secs));
+    }
+    if let Some(count) = cli.min_files {
+        config = config.with_min_files(count);
+    }
+    if cli.follow_symlinks {
+        config = config.with_follow_symlinks();
+    }
+    if cli.include_build_logs {
+        config = config.with_build_logs();
+    }
+    apply_io_settings(cli, config)
+}
+
+/// Size mode from `ui.size_mode` in the config file
+fn configured_size_mode(cli: &Cli) -> null_e::core::SizeMode {
+    cli.config.ui.size_mode
+}
+
+/// Where a scan's time went (`--verbose`)
+fn print_phase_timings(phases: &null_e::core::PhaseTimings) {
+    println!("  {} {}", "│".dimmed(), format!("Time by phase: {}", phases.summary()).dimmed());
+}
+
+/// Rows a table shows, `None` for all
+///
+/// `-a`/`-v` show everything, then `--top N` (0 for all), then the table's
+/// own `default`.
+fn row_limit(cli: &Cli, default: Option<usize>) -> Option<usize> {
+    if cli.all || cli.verbose {
+        return None;
+    }
+    match cli.top {
+        Some(0) => None,
+        Some(n) => Some(n),
+        None => default,
+    }
+}
+
+/// Note the rows a table left out because of [`row_limit`]
+fn print_hidden_rows(count: usize, size: u64, noun: &str) {
+    if count > 0 {
+        println!(
+            "   {} {} more {} ({}) - use {} or {} to see all",
+            "...".dimmed(),
+            count,
+            noun,
+            format_size(size).dimmed(),
+            "--top 0".cyan(),
+            "-a".cyan()
+        );
+    }
+}
+
 fn format_size(bytes: u64) -> String {
     humansize::format_size(bytes, humansize::BINARY)
 }
 
+/// "412,803 files", grouped in thousands so inode-heavy directories stand out
+fn format_file_count(count: u64) -> String {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{} {}", grouped, if count == 1 { "file" } else { "files" })
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Sweep Command - The Big One!
 // ═══════════════════════════════════════════════════════════════════════════════
 
-fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
+/// How long `sweep` waits for the Docker detector, and each `docker` query, before giving up
+const SWEEP_DOCKER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Display label and detect function for one sweep category
+type SweepDetector = (&'static str, fn() -> Result<Vec<null_e::cleaners::CleanableItem>>);
+
+/// Display label and detector for a sweep category
+fn sweep_detector(category: &str) -> Option<SweepDetector> {
     use null_e::cleaners::{
-        xcode::XcodeCleaner,
-        android::AndroidCleaner,
-        docker::DockerCleaner,
-        ml::MlCleaner,
-        ide::IdeCleaner,
-        logs::LogsCleaner,
-        homebrew::HomebrewCleaner,
-        ios_deps::IosDependencyCleaner,
-        electron::ElectronCleaner,
-        gamedev::GameDevCleaner,
-        cloud::CloudCliCleaner,
-        macos::MacOsCleaner,
-        CleanableItem, CleanerSummary,
+        android::AndroidCleaner, cloud::CloudCliCleaner, docker::DockerCleaner,
+        electron::ElectronCleaner, gamedev::GameDevCleaner, homebrew::HomebrewCleaner,
+        ide::IdeCleaner, ios_deps::IosDependencyCleaner, logs::LogsCleaner, macos::MacOsCleaner,
+        ml::MlCleaner, xcode::XcodeCleaner,
     };
 
-    println!(
-        "{} {}",
-        "🤖 null-e Deep Scan".green().bold(),
-        format!("v{}", null_e::VERSION).dimmed()
-    );
-    println!();
+    let detector: SweepDetector = match category {
+        "xcode" => ("Xcode", || {
+            XcodeCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "android" => ("Android", || {
+            AndroidCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "docker" => ("Docker", || DockerCleaner::new().with_probe_timeout(SWEEP_DOCKER_TIMEOUT).detect()),
+        "ml" => ("ML/AI", || {
+            MlCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "ide" => ("IDEs", || {
+            IdeCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "logs" => ("Logs", || {
+            LogsCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "homebrew" => ("Homebrew", || {
+            HomebrewCleaner::new()
+                .filter(|c| c.is_available())
+                .map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "ios" | "ios-deps" => ("iOS Dependencies", || {
+            IosDependencyCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "electron" => ("Electron Apps", || {
+            ElectronCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "gamedev" | "unity" | "unreal" => ("Game Dev", || {
+            GameDevCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "cloud" | "aws" | "gcp" | "azure" => ("Cloud CLI", || {
+            CloudCliCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        "macos" | "system" => ("macOS System", || {
+            MacOsCleaner::new().map_or(Ok(Vec::new()), |c| c.detect())
+        }),
+        _ => return None,
+    };
+
+    Some(detector)
+}
+
+/// How long `sweep` waits for a detector before giving up on it
+///
+/// A wedged Docker daemon can block `docker` forever; the rest only read files.
+fn sweep_time_limit(label: &str) -> Option<Duration> {
+    (label == "Docker").then_some(SWEEP_DOCKER_TIMEOUT)
+}
+
+/// What the sweep detectors found, and which of them came back empty-handed
+#[derive(Default)]
+struct SweepFindings {
+    items: Vec<null_e::cleaners::CleanableItem>,
+    /// Detectors that returned an error or panicked, with the reason
+    failed: Vec<(&'static str, String)>,
+    /// Detectors still running when their time limit was up
+    timed_out: Vec<&'static str>,
+}
+
+/// Run `detectors` on their own threads and gather what they find
+///
+/// Detectors are independent and mostly I/O bound. A failing or panicking
+/// one is reported instead of silently finding nothing, and one that outlives
+/// its `time_limit` is left behind. `progress` gets the labels still running,
+/// how many are done and the total while waiting.
+fn run_sweep_detectors(
+    detectors: Vec<SweepDetector>,
+    time_limit: fn(&str) -> Option<Duration>,
+    mut progress: impl FnMut(&[&str], usize, usize),
+) -> SweepFindings {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut pending: Vec<&'static str> = Vec::new();
+    for (label, detect) in detectors {
+        pending.push(label);
+        let tx = tx.clone();
+        thread::spawn(move || {
+            let span = tracing::info_span!("sweep_detect", category = label, items = tracing::field::Empty);
+            let _entered = span.enter();
+            let result = match std::panic::catch_unwind(detect) {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(panic) => Err(panic
+                    .downcast_ref::<&str>()
+                    .map(|msg| msg.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .map_or_else(|| "panicked".to_string(), |msg| format!("panicked: {}", msg))),
+            };
+            if let Ok(items) = &result {
+                span.record("items", items.len());
+            }
+            let _ = tx.send((label, result));
+        });
+    }
+    drop(tx);
+
+    let started = Instant::now();
+    let total = pending.len();
+    let mut findings = SweepFindings::default();
+    while !pending.is_empty() {
+        progress(&pending, total - pending.len(), total);
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok((label, result)) => {
+                pending.retain(|p| *p != label);
+                match result {
+                    Ok(items) => findings.items.extend(items),
+                    Err(reason) => findings.failed.push((label, reason)),
+                }
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                let expired: Vec<_> = pending
+                    .iter()
+                    .copied()
+                    .filter(|label| time_limit(label).is_some_and(|limit| started.elapsed() >= limit))
+                    .collect();
+                pending.retain(|p| !expired.contains(p));
+                findings.timed_out.extend(expired);
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+    findings
+}
+
+fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>, junk_files: bool) -> Result<()> {
+    use null_e::cleaners::{CleanableItem, CleanerSummary};
+
+    if !cli.summary_only {
+        println!(
+            "{} {}",
+            "🤖 null-e Deep Scan".green().bold(),
+            format!("v{}", null_e::VERSION).dimmed()
+        );
+        println!();
+    }
+
+    if junk_files {
+        sweep_junk_files(cli, clean)?;
+    }
 
     let pb = ProgressBar::new_spinner();
     pb.set_style(
@@ -1256,116 +3744,41 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
 
     let mut all_items: Vec<CleanableItem> = Vec::new();
 
-    // Collect items from all cleaners based on category filter
+    // Collect items from all cleaners based on category filter, then the profile's
     let categories: Vec<&str> = match category {
         Some(c) => vec![c],
+        None if !cli.categories.is_empty() => cli.categories.iter().map(String::as_str).collect(),
         None => vec!["xcode", "android", "docker", "ml", "ide", "logs", "homebrew", "ios", "electron", "gamedev", "cloud", "macos"],
     };
 
-    for cat in &categories {
-        match *cat {
-            "xcode" => {
-                pb.set_message("Scanning Xcode...");
-                if let Some(cleaner) = XcodeCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "android" => {
-                pb.set_message("Scanning Android...");
-                if let Some(cleaner) = AndroidCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "docker" => {
-                pb.set_message("Scanning Docker...");
-                let cleaner = DockerCleaner::new();
-                if let Ok(items) = cleaner.detect() {
-                    all_items.extend(items);
-                }
-            }
-            "ml" => {
-                pb.set_message("Scanning ML/AI...");
-                if let Some(cleaner) = MlCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "ide" => {
-                pb.set_message("Scanning IDEs...");
-                if let Some(cleaner) = IdeCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "logs" => {
-                pb.set_message("Scanning Logs...");
-                if let Some(cleaner) = LogsCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "homebrew" => {
-                pb.set_message("Scanning Homebrew...");
-                if let Some(cleaner) = HomebrewCleaner::new() {
-                    if cleaner.is_available() {
-                        if let Ok(items) = cleaner.detect() {
-                            all_items.extend(items);
-                        }
-                    }
-                }
-            }
-            "ios" | "ios-deps" => {
-                pb.set_message("Scanning iOS Dependencies...");
-                if let Some(cleaner) = IosDependencyCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "electron" => {
-                pb.set_message("Scanning Electron Apps...");
-                if let Some(cleaner) = ElectronCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "gamedev" | "unity" | "unreal" => {
-                pb.set_message("Scanning Game Dev...");
-                if let Some(cleaner) = GameDevCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "cloud" | "aws" | "gcp" | "azure" => {
-                pb.set_message("Scanning Cloud CLI...");
-                if let Some(cleaner) = CloudCliCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            "macos" | "system" => {
-                pb.set_message("Scanning macOS System...");
-                if let Some(cleaner) = MacOsCleaner::new() {
-                    if let Ok(items) = cleaner.detect() {
-                        all_items.extend(items);
-                    }
-                }
-            }
-            _ => {}
+    let detectors: Vec<SweepDetector> = categories.iter().filter_map(|cat| sweep_detector(cat)).collect();
+    let findings = run_sweep_detectors(detectors, sweep_time_limit, |pending, done, total| {
+        pb.set_message(format!("Scanning {}... ({}/{} categories done)", pending.join(", "), done, total));
+    });
+    all_items.extend(findings.items);
+
+    pb.finish_and_clear();
+
+    for (label, reason) in &findings.failed {
+        eprintln!("  {} {} detection failed: {}", "⚠".yellow(), label, reason);
+    }
+    if !cli.summary_only {
+        for label in &findings.timed_out {
+            let secs = sweep_time_limit(label).unwrap_or_default().as_secs();
+            println!("  {} {} did not respond within {}s, skipping", "⚠".yellow(), label, secs);
         }
     }
 
-    pb.finish_and_clear();
+    apply_safety_policy(&cli.config, &mut all_items);
+    let duplicates = null_e::cleaners::dedupe_items(&mut all_items);
+    if duplicates > 0 && cli.verbose {
+        println!(
+            "  {} {}",
+            "│".dimmed(),
+            format!("{} items reported twice or inside another item counted once", duplicates).dimmed()
+        );
+    }
+    drop_riskier_items(cli, &mut all_items);
 
     if all_items.is_empty() {
         println!("  No cleanable items found.");
@@ -1373,7 +3786,7 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
     }
 
     // Sort by size descending
-    all_items.sort_by(|a, b| b.size.cmp(&a.size));
+    all_items.sort_by(CleanableItem::cmp_by_size);
 
     // Calculate summary
     let summary = CleanerSummary::from_items(&all_items);
@@ -1389,7 +3802,7 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
 
     // Display by category
     let mut sorted_categories: Vec<_> = summary.by_category.values().collect();
-    sorted_categories.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+    sorted_categories.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.name.cmp(&b.name)));
 
     println!("   {}", "By Category:".bold());
     for cat in &sorted_categories {
@@ -1403,6 +3816,10 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
     }
     println!();
 
+    if cli.summary_only {
+        return Ok(());
+    }
+
     // Table header
     println!(
         "   {:3} {:<40} {:>12}   {}",
@@ -1413,8 +3830,8 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
     );
     println!("   {}", "─".repeat(75).dimmed());
 
-    // Display items (top 30 or all if verbose)
-    let display_count = if cli.verbose || cli.all { all_items.len() } else { 30.min(all_items.len()) };
+    // Display items (top 30 unless --top, -a or -v say otherwise)
+    let display_count = row_limit(cli, Some(30)).map_or(all_items.len(), |n| n.min(all_items.len()));
 
     for (i, item) in all_items.iter().take(display_count).enumerate() {
         let num = format!("[{}]", i + 1);
@@ -1451,15 +3868,16 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
             size_colored,
             safety
         );
+        if cli.explain {
+            println!(
+                "        {}",
+                format!("why: {} ({})", item.description, item.safe_to_delete.explain()).dimmed()
+            );
+        }
     }
 
-    if all_items.len() > display_count {
-        println!(
-            "   {} {} more items (use -v to show all)",
-            "...".dimmed(),
-            all_items.len() - display_count
-        );
-    }
+    let hidden = &all_items[display_count..];
+    print_hidden_rows(hidden.len(), hidden.iter().map(|i| i.size).sum(), "items");
 
     println!("   {}", "─".repeat(75).dimmed());
     println!(
@@ -1470,6 +3888,14 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
     );
     println!();
 
+    if cli.age_report {
+        let mut histogram = AgeHistogram::new();
+        for item in &all_items {
+            histogram.add(item.age_days(), item.size);
+        }
+        print_age_report(&histogram);
+    }
+
     // If not cleaning, show hints
     if !clean {
         println!("{} {}", "💡".dimmed(), "Commands:".dimmed());
@@ -1487,45 +3913,66 @@ fn cmd_sweep(cli: &Cli, clean: bool, category: Option<&str>) -> Result<()> {
     }
 
     // Interactive cleaning
-    clean_items_interactive(&all_items, cli)
+    clean_items_interactive(&all_items, cli, "sweep")
 }
 
-fn clean_items_interactive(items: &[null_e::cleaners::CleanableItem], cli: &Cli) -> Result<()> {
-    println!(
-        "Enter item numbers to clean (e.g., {} or {} or {}):",
-        "1,3,5".cyan(),
-        "1-5".cyan(),
-        "all".cyan()
-    );
-    print!("> ");
-    use std::io::Write;
-    std::io::stdout().flush().unwrap();
-
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input).unwrap();
-    let input = input.trim().to_lowercase();
-
-    if input.is_empty() || input == "q" || input == "quit" {
-        println!("Aborted.");
+fn clean_items_interactive(
+    items: &[null_e::cleaners::CleanableItem],
+    cli: &Cli,
+    source: &str,
+) -> Result<()> {
+    let labels: Vec<_> = items
+        .iter()
+        .map(|i| format!("{} {} {}", i.icon, i.name, format_size(i.size)))
+        .collect();
+    let Some(selected_indices) = read_selection(cli, "item", &labels, &[]) else {
         return Ok(());
-    }
-
-    let selected_indices: Vec<usize> = if input == "all" || input == "a" {
-        (0..items.len()).collect()
-    } else {
-        parse_selection(&input, items.len())
     };
 
-    if selected_indices.is_empty() {
-        println!("No valid selection.");
-        return Ok(());
-    }
-
     let selected_items: Vec<_> = selected_indices
         .iter()
         .filter_map(|&i| items.get(i))
         .collect();
 
+    // Items inside a git repository get the same protection as project artifacts
+    let protection_level: ProtectionLevel = cli.protection.into();
+    let mut blocked = Vec::new();
+    let mut warnings = Vec::new();
+    let selected_items: Vec<_> = selected_items
+        .into_iter()
+        .filter(|item| {
+            let check = null_e::git::check_path_protection(&item.path, protection_level);
+            warnings.extend(check.warnings.into_iter().map(|w| (item.name.clone(), w)));
+            if check.allowed || cli.force {
+                return true;
+            }
+            blocked.push((item.name.clone(), check.blocked_reason.unwrap_or_default()));
+            false
+        })
+        .collect();
+
+    if !blocked.is_empty() {
+        println!();
+        println!(
+            "{} {} items blocked (use --force to override):",
+            "🔒".red(),
+            blocked.len()
+        );
+        for (name, reason) in &blocked {
+            println!("    {} {} {}", "•".red(), name, format!("({})", reason).dimmed());
+        }
+    }
+    if !warnings.is_empty() {
+        println!();
+        for (name, warning) in &warnings {
+            println!("  {} {}: {}", "⚠".yellow(), name, warning);
+        }
+    }
+    if selected_items.is_empty() {
+        println!("Nothing left to clean.");
+        return Ok(());
+    }
+
     let selected_size: u64 = selected_items.iter().map(|i| i.size).sum();
 
     println!();
@@ -1540,21 +3987,42 @@ fn clean_items_interactive(items: &[null_e::cleaners::CleanableItem], cli: &Cli)
         println!("  {} {} {}", item.icon, item.name, format_size(item.size).dimmed());
     }
 
-    if !cli.force && !cli.dry_run {
-        print!("\nContinue? [y/N] ");
-        std::io::stdout().flush().unwrap();
-
-        let mut confirm = String::new();
-        std::io::stdin().read_line(&mut confirm).unwrap();
-
-        if !confirm.trim().eq_ignore_ascii_case("y") {
-            println!("Aborted.");
-            return Ok(());
+    // Show the official commands that will run, so nothing executes unseen
+    let safe_mode = safe_mode_enabled(cli);
+    let (commands, refused): (Vec<_>, Vec<_>) = unique_clean_commands(
+        selected_items.iter().filter_map(|i| i.clean_command.as_deref()),
+    )
+    .into_iter()
+    .partition(|cmd| allowed_command(Some(cmd), safe_mode).is_some());
+    print_clean_commands(&commands, cli.dry_run);
+    print_refused_commands(&refused);
+
+    let selected_items = if cli.confirm_each {
+        selected_items
+    } else {
+        let policy = no_confirm_policy(cli);
+        let confirmed = confirm_unlisted(
+            selected_items,
+            cli,
+            "items",
+            |item| policy.skips_confirmation(&item.category, &item.subcategory, item.safe_to_delete),
+            |item| format!("{} {} {}", item.icon, item.name, format_size(item.size).dimmed()),
+        );
+        match confirmed {
+            Some(items) => items,
+            None => return Ok(()),
         }
-    }
+    };
 
     // Clean selected items
-    let pb = ProgressBar::new(selected_items.len() as u64);
+    let confirm_each = cli.confirm_each && !cli.dry_run;
+    let total_selected = selected_items.len();
+    let pb = if confirm_each {
+        println!();
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new(total_selected as u64)
+    };
     pb.set_style(
         ProgressStyle::default_bar()
             .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
@@ -1566,51 +4034,111 @@ fn clean_items_interactive(items: &[null_e::cleaners::CleanableItem], cli: &Cli)
     let mut cleaned_count = 0usize;
     let mut failed_count = 0usize;
 
-    let delete_method: DeleteMethod = if cli.dry_run {
-        DeleteMethod::DryRun
-    } else {
-        cli.method.into()
-    };
+    let rehearsal_method: DeleteMethod = if safe_mode { DeleteMethod::Trash } else { cli.method.into() };
+    let delete_method = if cli.dry_run { DeleteMethod::DryRun } else { rehearsal_method };
+
+    // Official commands shared by several items only need to run once
+    let mut commands_run: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let mut skipped: Vec<&str> = Vec::new();
+    let mut declined_count = 0usize;
 
-    for item in selected_items {
+    for (i, item) in selected_items.into_iter().enumerate() {
         pb.set_message(item.name.clone());
 
         if cli.dry_run {
+            // Rehearse what a real run would delete directly; official commands can't be checked ahead
+            let by_command = allowed_command(item.clean_command.as_deref(), safe_mode).is_some();
+            let rehearsal = if by_command {
+                Ok(item.size)
+            } else {
+                null_e::trash::rehearse_path(&item.path, rehearsal_method)
+            };
+            match rehearsal {
+                Ok(size) => {
+                    cleaned_size += size;
+                    cleaned_count += 1;
+                    emit_record(&CleanRecord::succeeded(&item.path, DeleteMethod::DryRun.as_str(), size));
+                }
+                Err(e) => {
+                    emit_record(&CleanRecord::failed(&item.path, DeleteMethod::DryRun.as_str(), e.to_string()));
+                    failed_count += 1;
+                    if cli.verbose {
+                        eprintln!("  {} Would fail: {}: {}", "✗".red(), item.name, e);
+                    }
+                }
+            }
+            pb.inc(1);
+            continue;
+        }
+
+        if confirm_each {
+            match confirm_item(&format!("{} {} ({})", item.icon, item.name, format_size(item.size))) {
+                ItemChoice::Clean => {}
+                ItemChoice::Skip => {
+                    declined_count += 1;
+                    continue;
+                }
+                ItemChoice::Quit => {
+                    declined_count += total_selected - i;
+                    break;
+                }
+            }
+        }
+
+        // Safe mode only allows official commands that can be undone
+        let command = allowed_command(item.clean_command.as_deref(), safe_mode);
+
+        // Refused command and nothing we can safely trash instead: skip it
+        if item.clean_command.is_some() && command.is_none() && !is_trashable_path(&item.path) {
+            emit_record(&CleanRecord::failed(
+                &item.path,
+                delete_method.as_str(),
+                "skipped by safe mode: official command is irreversible",
+            ));
+            skipped.push(&item.name);
+            pb.inc(1);
+            continue;
+        }
+
+        // Use official command if available, otherwise delete directly
+        let cleaned_by_command = match command {
+            Some(cmd) if commands_run.contains(cmd) => true,
+            Some(cmd) => match null_e::cleaners::run_clean_command(cmd) {
+                Ok(()) => true,
+                Err(e) if e.is_user_interrupt() => {
+                    emit_record(&CleanRecord::failed(&item.path, delete_method.as_str(), e.to_string()));
+                    pb.finish_and_clear();
+                    return Err(e);
+                }
+                // Fall back to deleting the item's path
+                Err(e) => {
+                    if cli.verbose || matches!(e, DevSweepError::CommandTimeout { .. }) {
+                        pb.suspend(|| eprintln!("  {} {}; deleting {} directly", "⚠".yellow(), e, item.name));
+                    }
+                    false
+                }
+            },
+            None => false,
+        };
+
+        if cleaned_by_command {
+            let cmd = command.unwrap_or_default();
+            commands_run.insert(cmd);
             cleaned_size += item.size;
             cleaned_count += 1;
+            emit_record(&CleanRecord::succeeded(&item.path, delete_method.as_str(), item.size).with_command(cmd));
         } else {
-            // Use official command if available, otherwise delete directly
-            if let Some(ref cmd) = item.clean_command {
-                // Try running the clean command
-                if run_clean_command_silent(cmd).is_ok() {
-                    cleaned_size += item.size;
+            match null_e::trash::delete_path_as(&item.path, delete_method, &item.category, &item.subcategory) {
+                Ok(freed) => {
+                    cleaned_size += freed;
                     cleaned_count += 1;
-                } else {
-                    // Fall back to direct deletion
-                    match null_e::trash::delete_path(&item.path, delete_method) {
-                        Ok(_) => {
-                            cleaned_size += item.size;
-                            cleaned_count += 1;
-                        }
-                        Err(e) => {
-                            failed_count += 1;
-                            if cli.verbose {
-                                eprintln!("  {} Failed: {}: {}", "✗".red(), item.name, e);
-                            }
-                        }
-                    }
+                    emit_record(&CleanRecord::succeeded(&item.path, delete_method.as_str(), freed));
                 }
-            } else {
-                match null_e::trash::delete_path(&item.path, delete_method) {
-                    Ok(_) => {
-                        cleaned_size += item.size;
-                        cleaned_count += 1;
-                    }
-                    Err(e) => {
-                        failed_count += 1;
-                        if cli.verbose {
-                            eprintln!("  {} Failed: {}: {}", "✗".red(), item.name, e);
-                        }
+                Err(e) => {
+                    emit_record(&CleanRecord::failed(&item.path, delete_method.as_str(), e.to_string()));
+                    failed_count += 1;
+                    if cli.verbose {
+                        eprintln!("  {} Failed: {}: {}", "✗".red(), item.name, e);
                     }
                 }
             }
@@ -1639,34 +4167,375 @@ fn clean_items_interactive(items: &[null_e::cleaners::CleanableItem], cli: &Cli)
     }
 
     if failed_count > 0 {
-        println!("{} {} items failed to clean", "⚠".yellow(), failed_count);
+        let outcome = if cli.dry_run { "would fail" } else { "failed" };
+        println!("{} {} items {} to clean", "⚠".yellow(), failed_count, outcome);
+    }
+
+    if declined_count > 0 {
+        println!("{} {} items skipped at your request", "•".dimmed(), declined_count);
+    }
+
+    if !skipped.is_empty() {
+        println!(
+            "{} {} items skipped by safe mode (official command is irreversible):",
+            "⚠".yellow(),
+            skipped.len()
+        );
+        for name in &skipped {
+            println!("    {} {}", "•".dimmed(), name);
+        }
     }
 
+    report_completion(&cli.config, &null_e::notify::CompletionSummary {
+        bytes_freed: cleaned_size,
+        cleaned: cleaned_count,
+        failed: failed_count,
+        skipped: declined_count + skipped.len(),
+        ..null_e::notify::CompletionSummary::new(source, cli.dry_run)
+    });
+
     Ok(())
 }
 
-fn run_clean_command_silent(cmd: &str) -> Result<()> {
-    use std::process::Command;
+/// Count `.DS_Store`-style junk under the scanned paths, removing it with `clean`
+///
+/// The files are tiny and recreated on demand, so they are summarized by
+/// name rather than listed, and removed together after one confirmation.
+/// Drop junk files whose git repository is protected, returning the rest and the number dropped
+///
+/// Files are grouped by the repository containing them, so each repository's
+/// status is checked once no matter how many junk files it holds.
+fn protect_junk_files(
+    items: Vec<null_e::cleaners::CleanableItem>,
+    level: ProtectionLevel,
+    force: bool,
+    verbose: bool,
+) -> (Vec<null_e::cleaners::CleanableItem>, usize) {
+    let mut verdicts: std::collections::HashMap<PathBuf, bool> = std::collections::HashMap::new();
+    let mut blocked = 0usize;
+    let items = items
+        .into_iter()
+        .filter(|item| {
+            let Some(root) = item.path.ancestors().skip(1).find(|dir| dir.join(".git").exists()) else {
+                return true;
+            };
+            let allowed = *verdicts.entry(root.to_path_buf()).or_insert_with(|| {
+                let check = null_e::git::check_path_protection(root, level);
+                if verbose {
+                    for warning in &check.warnings {
+                        println!("  {} {}: {}", "⚠".yellow(), root.display(), warning);
+                    }
+                }
+                check.allowed || force
+            });
+            if !allowed {
+                blocked += 1;
+            }
+            allowed
+        })
+        .collect();
+    (items, blocked)
+}
 
-    let parts: Vec<&str> = cmd.split_whitespace().collect();
-    if parts.is_empty() {
-        return Err(DevSweepError::Config("Empty clean command".into()));
+/// How junk files are removed: `--method`, except that safe mode always uses the trash
+fn junk_delete_method(cli: &Cli) -> DeleteMethod {
+    if safe_mode_enabled(cli) {
+        DeleteMethod::Trash
+    } else {
+        cli.method.into()
     }
+}
 
-    let output = Command::new(parts[0])
-        .args(&parts[1..])
-        .output()?;
+fn sweep_junk_files(cli: &Cli, clean: bool) -> Result<()> {
+    let paths = get_scan_paths(cli)?;
 
-    if output.status.success() {
-        Ok(())
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} {msg}")
+            .unwrap(),
+    );
+    pb.set_message("Looking for .DS_Store, Thumbs.db and desktop.ini files...");
+    pb.enable_steady_tick(Duration::from_millis(100));
+    let items = null_e::cleaners::system::find_junk_files_in(&paths, cli.max_depth);
+    pb.finish_and_clear();
+
+    if items.is_empty() {
+        println!("  No junk files found.");
+        println!();
+        return Ok(());
+    }
+
+    let total: u64 = items.iter().map(|i| i.size).sum();
+    println!(
+        "{} Found {} junk files with {} total",
+        "✓".green(),
+        items.len().to_string().cyan(),
+        format_size(total).yellow().bold()
+    );
+    for name in null_e::cleaners::system::JUNK_FILE_NAMES {
+        let (count, size) = items
+            .iter()
+            .filter(|i| i.subcategory == *name)
+            .fold((0usize, 0u64), |(count, size), i| (count + 1, size + i.size));
+        if count > 0 {
+            println!("   🗑️ {:<20} {:>12}  ({} files)", name, format_size(size).yellow(), count);
+        }
+    }
+    println!();
+
+    if !clean || cli.summary_only {
+        return Ok(());
+    }
+
+    // Junk files inside a git repository get the same protection as any other item
+    let (items, blocked) = protect_junk_files(items, cli.protection.into(), cli.force, cli.verbose);
+    if blocked > 0 {
+        println!("{} {} junk files blocked (use --force to override)", "🔒".red(), blocked);
+    }
+    if items.is_empty() {
+        println!();
+        return Ok(());
+    }
+    let total: u64 = items.iter().map(|i| i.size).sum();
+
+    if cli.dry_run {
+        println!("  Would remove {} junk files ({}).", items.len(), format_size(total));
+        println!();
+        return Ok(());
+    }
+    if !cli.force {
+        print!("Remove {} junk files ({})? [y/N] ", items.len(), format_size(total));
+        use std::io::Write;
+        std::io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Aborted.");
+            println!();
+            return Ok(());
+        }
+    }
+
+    let method = junk_delete_method(cli);
+    let (mut removed, mut freed, mut failed) = (0usize, 0u64, 0usize);
+    for item in &items {
+        match null_e::trash::delete_path_as(&item.path, method, &item.category, &item.subcategory) {
+            Ok(bytes) => {
+                removed += 1;
+                freed += bytes;
+                emit_record(&CleanRecord::succeeded(&item.path, method.as_str(), bytes));
+            }
+            Err(e) => {
+                failed += 1;
+                emit_record(&CleanRecord::failed(&item.path, method.as_str(), e.to_string()));
+                if cli.verbose {
+                    eprintln!("  {} Failed: {}: {}", "✗".red(), item.path.display(), e);
+                }
+            }
+        }
+    }
+
+    println!("{} Removed {} junk files ({}).", "✓".green(), removed, format_size(freed).yellow().bold());
+    if failed > 0 {
+        println!("{} {} junk files failed to remove", "⚠".yellow(), failed);
+    }
+    println!();
+
+    report_completion(&cli.config, &null_e::notify::CompletionSummary {
+        bytes_freed: freed,
+        cleaned: removed,
+        failed,
+        ..null_e::notify::CompletionSummary::new("sweep", false)
+    });
+
+    Ok(())
+}
+
+/// Answer to a `--confirm-each` prompt
+#[derive(Debug, PartialEq, Eq)]
+enum ItemChoice {
+    Clean,
+    Skip,
+    Quit,
+}
+
+impl ItemChoice {
+    /// Parse a y/n/q answer; anything unrecognized skips the item
+    fn parse(input: &str) -> Self {
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => Self::Clean,
+            "q" | "quit" => Self::Quit,
+            _ => Self::Skip,
+        }
+    }
+}
+
+/// Ask whether to clean a single item
+///
+/// A closed stdin stops the run rather than skipping every remaining item.
+fn confirm_item(label: &str) -> ItemChoice {
+    ask_item(&format!("Clean {}?", label))
+}
+
+/// Ask a y/n/q `question`; end of input counts as q
+fn ask_item(question: &str) -> ItemChoice {
+    use std::io::Write;
+
+    print!("  {} [y/n/q] ", question);
+    std::io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    match std::io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => ItemChoice::Quit,
+        Ok(_) => ItemChoice::parse(&input),
+    }
+}
+
+/// The `clean.no_confirm` allowlist from the config file
+fn no_confirm_policy(cli: &Cli) -> &null_e::config::CleanSettings {
+    &cli.config.clean
+}
+
+/// Ask before cleaning caches, unless `clean.no_confirm` lists them all
+///
+/// Global caches are re-downloaded on next use, so they count as
+/// `Caches/<id>` at the safe-with-cost level.
+fn confirm_caches(
+    caches: Vec<null_e::caches::GlobalCache>,
+    cli: &Cli,
+) -> Option<Vec<null_e::caches::GlobalCache>> {
+    let policy = no_confirm_policy(cli);
+    confirm_unlisted(
+        caches,
+        cli,
+        "caches",
+        |cache| {
+            policy.skips_confirmation("Caches", &cache.id, null_e::cleaners::SafetyLevel::SafeWithCost)
+        },
+        |cache| format!("{} {} {}", cache.icon, cache.name, format_size(cache.size).dimmed()),
+    )
+}
+
+/// Ask once about the items `clean.no_confirm` doesn't cover
+///
+/// Returns what to clean: everything when confirmed, only the allowlisted
+/// items when declined, and `None` when that leaves nothing. `--force` and
+/// `--dry-run` never ask.
+fn confirm_unlisted<T>(
+    items: Vec<T>,
+    cli: &Cli,
+    noun: &str,
+    skips_confirmation: impl Fn(&T) -> bool,
+    describe: impl Fn(&T) -> String,
+) -> Option<Vec<T>> {
+    use std::io::Write;
+
+    if cli.force || cli.dry_run {
+        return Some(items);
+    }
+
+    let allowed = items.iter().filter(|item| skips_confirmation(item)).count();
+    if allowed == items.len() {
+        println!(
+            "{} {} {} allowed without confirmation (clean.no_confirm)",
+            "✓".green(),
+            allowed,
+            noun
+        );
+        return Some(items);
+    }
+    if allowed > 0 {
+        println!();
+        println!(
+            "{} {} {} will be cleaned without asking (clean.no_confirm); these need confirmation:",
+            "•".dimmed(),
+            allowed,
+            noun
+        );
+        for item in items.iter().filter(|item| !skips_confirmation(item)) {
+            println!("    {} {}", "•".yellow(), describe(item));
+        }
+    }
+
+    print!("\nContinue? [y/N] ");
+    std::io::stdout().flush().unwrap();
+    let mut confirm = String::new();
+    std::io::stdin().read_line(&mut confirm).unwrap();
+    if confirm.trim().eq_ignore_ascii_case("y") {
+        return Some(items);
+    }
+
+    if allowed == 0 {
+        println!("Aborted.");
+        return None;
+    }
+    println!("Cleaning only the {} {} that need no confirmation.", allowed, noun);
+    Some(items.into_iter().filter(|item| skips_confirmation(item)).collect())
+}
+
+/// Collect distinct clean commands, keeping the order they were first seen
+fn unique_clean_commands<'a>(commands: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut seen = std::collections::HashSet::new();
+    commands.into_iter().filter(|cmd| seen.insert(*cmd)).collect()
+}
+
+/// Print the official clean commands that will run (or would run, in dry-run mode)
+fn print_clean_commands(commands: &[&str], dry_run: bool) {
+    if commands.is_empty() {
+        return;
+    }
+
+    println!();
+    if dry_run {
+        println!("{}", "Would run these commands:".bold());
     } else {
-        Err(DevSweepError::CleanFailed {
-            path: std::path::PathBuf::from(cmd),
-            reason: String::from_utf8_lossy(&output.stderr).to_string(),
-        })
+        println!("{}", "The following commands will be run:".bold());
+    }
+    for cmd in commands {
+        println!("  {} {}", "$".dimmed(), cmd.cyan());
+    }
+}
+
+/// Print official commands that safe mode will not run
+fn print_refused_commands(commands: &[&str]) {
+    if commands.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{}",
+        "Safe mode: these irreversible commands will NOT run (trashing instead where possible):"
+            .yellow()
+    );
+    for cmd in commands {
+        println!("  {} {}", "✗".yellow(), cmd.dimmed());
     }
 }
 
+/// Whether safe mode is on, via `--safe-mode` or `clean.safe_mode` in the config
+fn safe_mode_enabled(cli: &Cli) -> bool {
+    cli.safe_mode || cli.config.clean.safe_mode
+}
+
+/// The official command the clean path may run, if any
+///
+/// Safe mode only lets through commands that can be undone.
+fn allowed_command(command: Option<&str>, safe_mode: bool) -> Option<&str> {
+    command.filter(|cmd| !safe_mode || null_e::cleaners::is_reversible_command(cmd))
+}
+
+/// Whether a path can be moved to the trash as a fallback for a refused command
+///
+/// Only user-owned locations under the home directory qualify; daemon-managed
+/// paths like `/var/lib/docker` must never be trashed behind the tool's back.
+fn is_trashable_path(path: &std::path::Path) -> bool {
+    path.exists() && dirs::home_dir().is_some_and(|home| path.starts_with(home))
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // Individual Category Commands
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1685,7 +4554,7 @@ fn cmd_xcode(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting Xcode caches")?;
     display_and_optionally_clean(&items, cli, clean, "Xcode")
 }
 
@@ -1703,23 +4572,28 @@ fn cmd_android(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting Android caches")?;
     display_and_optionally_clean(&items, cli, clean, "Android")
 }
 
-fn cmd_docker(cli: &Cli, clean: bool, include_volumes: bool) -> Result<()> {
+fn cmd_docker(cli: &Cli, clean: bool, include_volumes: bool, build_cache_days: u64) -> Result<()> {
     use null_e::cleaners::docker::DockerCleaner;
 
     println!("{} {}", "🐳 Docker Cleanup".green().bold(), format!("v{}", null_e::VERSION).dimmed());
     println!();
 
-    let cleaner = DockerCleaner::new();
+    if !null_e::cleaners::docker_enabled() {
+        println!("  Docker detection is disabled (--no-docker / --no-external-commands).");
+        return Ok(());
+    }
+
+    let cleaner = DockerCleaner::new().with_stale_build_cache_days(build_cache_days);
     if !cleaner.is_available() {
         println!("  Docker is not available or not running.");
         return Ok(());
     }
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting Docker resources")?;
 
     if items.is_empty() {
         println!("  No Docker resources to clean.");
@@ -1727,7 +4601,12 @@ fn cmd_docker(cli: &Cli, clean: bool, include_volumes: bool) -> Result<()> {
     }
 
     if clean {
-        let total_size: u64 = items.iter().map(|i| i.size).sum();
+        // Stale build cache is part of the build cache, not on top of it
+        let total_size: u64 = items
+            .iter()
+            .filter(|i| i.subcategory != "Stale Build Cache")
+            .map(|i| i.size)
+            .sum();
         println!(
             "This will clean {} of Docker resources.",
             format_size(total_size).yellow()
@@ -1751,7 +4630,12 @@ fn cmd_docker(cli: &Cli, clean: bool, include_volumes: bool) -> Result<()> {
             }
         }
 
-        if cli.dry_run {
+        if safe_mode_enabled(cli) {
+            println!(
+                "{} Safe mode: Docker cleanup only runs irreversible prune commands, skipping.",
+                "⚠".yellow()
+            );
+        } else if cli.dry_run {
             println!("{} Dry run: would free {}", "✓".green(), format_size(total_size).yellow());
         } else {
             match cleaner.clean_all(include_volumes) {
@@ -1784,7 +4668,7 @@ fn cmd_ml(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting ML model caches")?;
     display_and_optionally_clean(&items, cli, clean, "ML/AI")
 }
 
@@ -1802,7 +4686,7 @@ fn cmd_ide(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting IDE caches")?;
     display_and_optionally_clean(&items, cli, clean, "IDE")
 }
 
@@ -1813,19 +4697,24 @@ fn cmd_homebrew(cli: &Cli, clean: bool, scrub: bool) -> Result<()> {
     println!();
 
     let cleaner = match HomebrewCleaner::new() {
-        Some(c) => c,
+        Some(c) => c.with_scrub(scrub),
         None => {
             println!("  Could not initialize Homebrew cleaner.");
             return Ok(());
         }
     };
 
+    if !null_e::cleaners::external_commands_enabled() {
+        println!("  Homebrew detection is disabled (--no-external-commands).");
+        return Ok(());
+    }
+
     if !cleaner.is_available() {
         println!("  Homebrew is not installed.");
         return Ok(());
     }
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting Homebrew caches")?;
 
     if items.is_empty() {
         println!("  No Homebrew caches found to clean.");
@@ -1833,6 +4722,17 @@ fn cmd_homebrew(cli: &Cli, clean: bool, scrub: bool) -> Result<()> {
     }
 
     if clean {
+        // Show what brew will remove before committing to it
+        for item in &items {
+            println!(
+                "  {} {} {}",
+                item.icon,
+                format!("{:>10}", format_size(item.size)).yellow(),
+                item.name
+            );
+        }
+        println!();
+
         let total_size: u64 = items.iter().map(|i| i.size).sum();
         println!(
             "This will clean {} of Homebrew caches.",
@@ -1853,7 +4753,12 @@ fn cmd_homebrew(cli: &Cli, clean: bool, scrub: bool) -> Result<()> {
             }
         }
 
-        if cli.dry_run {
+        if safe_mode_enabled(cli) {
+            println!(
+                "{} Safe mode: 'brew cleanup' deletes permanently, skipping.",
+                "⚠".yellow()
+            );
+        } else if cli.dry_run {
             println!("{} Dry run: would run 'brew cleanup{}'", "✓".green(), if scrub { " -s" } else { "" });
         } else {
             match cleaner.clean_all(scrub) {
@@ -1886,7 +4791,7 @@ fn cmd_ios_deps(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting iOS dependency caches")?;
     display_and_optionally_clean(&items, cli, clean, "iOS Dependencies")
 }
 
@@ -1904,7 +4809,7 @@ fn cmd_electron(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting Electron caches")?;
     display_and_optionally_clean(&items, cli, clean, "Electron Apps")
 }
 
@@ -1922,7 +4827,9 @@ fn cmd_gamedev(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let mut items = cleaner.detect().context("while detecting game engine caches")?;
+    // Per-project Unreal folders under the given paths
+    items.extend(cleaner.detect_unreal_projects(&get_scan_paths(cli)?, cli.max_depth)?);
     display_and_optionally_clean(&items, cli, clean, "Game Development")
 }
 
@@ -1940,7 +4847,7 @@ fn cmd_cloud(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting cloud CLI caches")?;
     display_and_optionally_clean(&items, cli, clean, "Cloud CLI")
 }
 
@@ -1959,10 +4866,49 @@ fn cmd_macos(cli: &Cli, clean: bool) -> Result<()> {
         }
     };
 
-    let items = cleaner.detect()?;
+    let items = cleaner.detect().context("while detecting macOS caches")?;
     display_and_optionally_clean(&items, cli, clean, "macOS System")
 }
 
+/// Record a finished clean in the local stats log, then send the configured
+/// `[notify]` notifications
+///
+/// Problems are printed as warnings; they never fail the clean.
+fn report_completion(config: &null_e::config::Config, summary: &null_e::notify::CompletionSummary) {
+    if config.stats.enabled {
+        if let Some(entry) = null_e::stats::StatsEntry::from_summary(summary) {
+            // Stats are a nicety, losing one entry must not fail the clean
+            if let Err(e) = null_e::stats::StatsLog::new().and_then(|log| log.append(&entry)) {
+                eprintln!("{} Could not record stats: {}", "⚠".yellow(), e);
+            }
+        }
+    }
+    for warning in null_e::notify::notify_completion(&config.notify, summary) {
+        eprintln!("{} {}", "⚠".yellow(), warning);
+    }
+}
+
+/// Apply the `[safety.overrides]` policy from the config file to detected items
+fn apply_safety_policy(config: &null_e::config::Config, items: &mut [null_e::cleaners::CleanableItem]) {
+    config.safety.apply(items);
+}
+
+/// Leave out cleaner items riskier than `--max-safety`, saying how many
+fn drop_riskier_items(cli: &Cli, items: &mut Vec<null_e::cleaners::CleanableItem>) {
+    let Some(max) = cli.max_safety.map(SafetyLevel::from) else {
+        return;
+    };
+    let before = items.len();
+    items.retain(|item| item.safe_to_delete <= max);
+    if items.len() < before {
+        println!(
+            "  {} {}",
+            "│".dimmed(),
+            format!("{} items riskier than {} left out (--max-safety)", before - items.len(), max.as_str()).dimmed()
+        );
+    }
+}
+
 fn display_and_optionally_clean(
     items: &[null_e::cleaners::CleanableItem],
     cli: &Cli,
@@ -1974,6 +4920,14 @@ fn display_and_optionally_clean(
         return Ok(());
     }
 
+    let mut items = items.to_vec();
+    apply_safety_policy(&cli.config, &mut items);
+    drop_riskier_items(cli, &mut items);
+    if items.is_empty() {
+        return Ok(());
+    }
+    let items = items.as_slice();
+
     let total_size: u64 = items.iter().map(|i| i.size).sum();
 
     println!(
@@ -1994,7 +4948,8 @@ fn display_and_optionally_clean(
     );
     println!("   {}", "─".repeat(70).dimmed());
 
-    for (i, item) in items.iter().enumerate() {
+    let display_count = row_limit(cli, None).map_or(items.len(), |n| n.min(items.len()));
+    for (i, item) in items.iter().take(display_count).enumerate() {
         let num = format!("[{}]", i + 1);
         let size_str = format_size(item.size);
 
@@ -2028,6 +4983,8 @@ fn display_and_optionally_clean(
             safety
         );
     }
+    let hidden = &items[display_count..];
+    print_hidden_rows(hidden.len(), hidden.iter().map(|i| i.size).sum(), "items");
 
     println!("   {}", "─".repeat(70).dimmed());
     println!(
@@ -2039,7 +4996,7 @@ fn display_and_optionally_clean(
     println!();
 
     if clean {
-        clean_items_interactive(items, cli)
+        clean_items_interactive(items, cli, category)
     } else {
         println!(
             "{} Use {} to clean interactively",
@@ -2359,7 +5316,81 @@ fn cmd_stale(cli: &Cli, days: u64, clean: bool) -> Result<()> {
     Ok(())
 }
 
-fn cmd_duplicates(cli: &Cli) -> Result<()> {
+fn cmd_bigfiles(cli: &Cli) -> Result<()> {
+    println!(
+        "{} {}",
+        "📄 Big File Finder".green().bold(),
+        format!("v{}", null_e::VERSION).dimmed()
+    );
+    println!();
+
+    let paths = get_scan_paths(cli)?;
+    let min_size = match &cli.min_size {
+        Some(size_str) => parse_size(size_str)
+            .ok_or_else(|| DevSweepError::Config(format!("Invalid size: {}", size_str)))?,
+        None => 1_000_000_000,
+    };
+    let limit = row_limit(cli, Some(25)).unwrap_or(0);
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} {msg}")
+            .unwrap(),
+    );
+    pb.set_message(format!("Looking for files over {}...", format_size(min_size)));
+    pb.enable_steady_tick(Duration::from_millis(100));
+
+    let files = null_e::cleaners::system::find_big_files_in(&paths, min_size, cli.max_depth, limit);
+
+    pb.finish_and_clear();
+
+    if files.is_empty() {
+        println!("  No files over {} found.", format_size(min_size));
+        return Ok(());
+    }
+
+    let total: u64 = files.iter().map(|f| f.size).sum();
+    println!(
+        "{} Found {} files over {} totaling {}",
+        "✓".green(),
+        files.len().to_string().cyan(),
+        format_size(min_size),
+        format_size(total).yellow().bold()
+    );
+    println!();
+
+    println!(
+        "   {:3} {:>12}   {:<14} {}",
+        "".dimmed(),
+        "Size".bold(),
+        "Modified".bold(),
+        "Path".bold()
+    );
+    println!("   {}", "─".repeat(80).dimmed());
+
+    for (i, file) in files.iter().enumerate() {
+        println!(
+            "   {} {:>12}   {:<14} {}",
+            format!("[{}]", i + 1).cyan(),
+            format_size(file.size).yellow(),
+            file.last_used_display(),
+            file.path.display()
+        );
+    }
+
+    println!("   {}", "─".repeat(80).dimmed());
+    println!();
+    println!(
+        "{} {}",
+        "💡".dimmed(),
+        "These are reported only; review them and delete what you no longer need.".dimmed()
+    );
+
+    Ok(())
+}
+
+fn cmd_duplicates(cli: &Cli, dedupe_hardlink: bool) -> Result<()> {
     use null_e::analysis::duplicates::DuplicateFinder;
 
     println!(
@@ -2400,6 +5431,10 @@ fn cmd_duplicates(cli: &Cli) -> Result<()> {
     if all_recommendations.is_empty() {
         println!("  No significant duplicate dependencies found.");
         println!("  (Only duplicates totaling >10MB are reported)");
+        if dedupe_hardlink {
+            println!();
+            return hardlink_duplicates(cli, &finder, &paths, max_depth);
+        }
         return Ok(());
     }
 
@@ -2479,35 +5514,366 @@ fn cmd_duplicates(cli: &Cli) -> Result<()> {
         "poetry".cyan()
     );
 
+    if dedupe_hardlink {
+        println!();
+        return hardlink_duplicates(cli, &finder, &paths, max_depth);
+    }
+    println!(
+        "   {} Run with {} to hardlink identical copies now",
+        "•".dimmed(),
+        "--dedupe-hardlink".cyan()
+    );
+
     Ok(())
 }
 
-fn parse_size(s: &str) -> Option<u64> {
-    let s = s.trim().to_uppercase();
-
-    let (num_str, multiplier) = if s.ends_with("GB") {
-        (&s[..s.len() - 2], 1_000_000_000u64)
-    } else if s.ends_with("MB") {
-        (&s[..s.len() - 2], 1_000_000u64)
-    } else if s.ends_with("KB") {
-        (&s[..s.len() - 2], 1_000u64)
-    } else if s.ends_with('G') {
-        (&s[..s.len() - 1], 1_000_000_000u64)
-    } else if s.ends_with('M') {
-        (&s[..s.len() - 1], 1_000_000u64)
-    } else if s.ends_with('K') {
-        (&s[..s.len() - 1], 1_000u64)
-    } else {
-        (s.as_str(), 1u64)
-    };
+/// Replace identical files in duplicated dependencies with hardlinks
+fn hardlink_duplicates(
+    cli: &Cli,
+    finder: &null_e::analysis::duplicates::DuplicateFinder,
+    paths: &[PathBuf],
+    max_depth: usize,
+) -> Result<()> {
+    use null_e::analysis::dedupe::{find_identical_files, hardlink_identical, MIN_LINK_SIZE};
+
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.green} {msg}")
+            .unwrap(),
+    );
+    pb.set_message("Hashing duplicated files...");
+    pb.enable_steady_tick(Duration::from_millis(100));
+
+    let sets: Vec<Vec<PathBuf>> = paths
+        .iter()
+        .flat_map(|path| finder.dedupe_candidates(path, max_depth))
+        .collect();
+    let identical = find_identical_files(&sets, MIN_LINK_SIZE);
+    pb.finish_and_clear();
+
+    if identical.is_empty() {
+        println!("  No identical duplicate files to hardlink.");
+        return Ok(());
+    }
+
+    let copies: usize = identical.iter().map(|group| group.copies.len()).sum();
+    let reclaimable: u64 = identical.iter().map(|group| group.reclaimable).sum();
+    println!(
+        "{} {} duplicate files are identical to another copy; hardlinking them reclaims {}",
+        "🔗".cyan(),
+        copies.to_string().cyan(),
+        format_size(reclaimable).yellow().bold()
+    );
+    if cli.verbose {
+        for group in &identical {
+            println!("   {} {}", format_size(group.size).dimmed(), group.keep.display());
+            for copy in &group.copies {
+                println!("     {} {}", "↳".dimmed(), copy.display());
+            }
+        }
+    }
+
+    if cli.dry_run {
+        println!("{} Dry run: nothing was linked", "✓".green());
+        return Ok(());
+    }
+
+    if !cli.force {
+        print!("Replace the copies with hardlinks? Files stay in place but share storage. [y/N] ");
+        use std::io::Write;
+        std::io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut freed = 0;
+    let mut failed = Vec::new();
+    for group in &identical {
+        let (bytes, errors) = hardlink_identical(group);
+        freed += bytes;
+        failed.extend(errors);
+    }
+
+    println!(
+        "{} Linked {} files, reclaimed {}",
+        "✓".green(),
+        (copies - failed.len()).to_string().cyan(),
+        format_size(freed).yellow().bold()
+    );
+    for (path, error) in &failed {
+        println!("  {} {}: {}", "✗".red(), path.display(), error);
+    }
 
-    num_str.trim().parse::<u64>().ok().map(|n| n * multiplier)
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sweep_fan_in() {
+        use null_e::cleaners::{CleanableItem, SafetyLevel};
+
+        fn found() -> Result<Vec<CleanableItem>> {
+            Ok(vec![CleanableItem {
+                name: "Found".into(),
+                category: "Test".into(),
+                subcategory: "Found".into(),
+                icon: "•",
+                path: PathBuf::from("/tmp/found"),
+                size: 1,
+                file_count: None,
+                last_modified: None,
+                description: "",
+                safe_to_delete: SafetyLevel::Safe,
+                clean_command: None,
+            }])
+        }
+        let detectors: Vec<SweepDetector> = vec![
+            ("Found", found),
+            ("Failing", || Err(DevSweepError::Config("no access".into()))),
+            ("Panicking", || panic!("detector bug")),
+            ("Slow", || {
+                thread::sleep(Duration::from_secs(30));
+                found()
+            }),
+        ];
+
+        let started = Instant::now();
+        let mut updates = 0;
+        let findings = run_sweep_detectors(
+            detectors,
+            |label| (label == "Slow").then_some(Duration::from_millis(300)),
+            |_, _, total| {
+                assert_eq!(total, 4);
+                updates += 1;
+            },
+        );
+
+        // The slow one is abandoned, the others all reported
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(updates > 0);
+        assert_eq!(findings.items.len(), 1);
+        assert_eq!(findings.timed_out, ["Slow"]);
+        let mut failed = findings.failed;
+        failed.sort();
+        assert_eq!(failed[0].0, "Failing");
+        assert!(failed[0].1.contains("no access"));
+        assert_eq!(failed[1], ("Panicking", "panicked: detector bug".to_string()));
+    }
+
+    #[test]
+    fn test_allowed_command() {
+        // Safe mode refuses anything that removes data, however it's phrased
+        assert_eq!(allowed_command(Some("trash-empty"), true), None);
+        assert_eq!(allowed_command(Some("sudo journalctl --vacuum-size=500M"), true), None);
+        assert_eq!(allowed_command(Some("docker system prune -af"), true), None);
+        assert_eq!(allowed_command(Some("gradle --stop"), true), Some("gradle --stop"));
+
+        // Outside safe mode every official command runs
+        assert_eq!(allowed_command(Some("trash-empty"), false), Some("trash-empty"));
+        assert_eq!(allowed_command(Some("gradle --stop"), false), Some("gradle --stop"));
+        assert_eq!(allowed_command(None, false), None);
+    }
+
+    #[test]
+    fn test_protect_junk_files() {
+        use null_e::cleaners::CleanableItem;
+        use std::process::Command;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let outside = temp.path().join("photos");
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        Command::new("git").arg("init").current_dir(&repo).output().expect("git failed");
+
+        let junk = |path: PathBuf| {
+            std::fs::write(&path, "junk").unwrap();
+            CleanableItem {
+                name: ".DS_Store".into(),
+                category: "System".into(),
+                subcategory: ".DS_Store".into(),
+                icon: "🗑️",
+                path,
+                size: 4,
+                file_count: None,
+                last_modified: None,
+                description: "",
+                safe_to_delete: SafetyLevel::Safe,
+                clean_command: None,
+            }
+        };
+        let items = || {
+            vec![
+                junk(repo.join(".DS_Store")),
+                junk(repo.join("src/.DS_Store")),
+                junk(outside.join(".DS_Store")),
+            ]
+        };
+
+        // The repository has untracked files, which paranoid mode refuses
+        let (kept, blocked) = protect_junk_files(items(), ProtectionLevel::Paranoid, false, false);
+        assert_eq!(blocked, 2);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].path, outside.join(".DS_Store"));
+
+        let (kept, blocked) = protect_junk_files(items(), ProtectionLevel::Paranoid, true, false);
+        assert_eq!((kept.len(), blocked), (3, 0));
+
+        let (kept, blocked) = protect_junk_files(items(), ProtectionLevel::None, false, false);
+        assert_eq!((kept.len(), blocked), (3, 0));
+    }
+
+    #[test]
+    fn test_junk_delete_method() {
+        let cli = |args: &[&str]| Cli::parse_from(std::iter::once("null-e").chain(args.iter().copied()));
+
+        assert_eq!(junk_delete_method(&cli(&["--method", "permanent"])), DeleteMethod::Permanent);
+        assert_eq!(junk_delete_method(&cli(&["--method", "trash"])), DeleteMethod::Trash);
+        assert_eq!(junk_delete_method(&cli(&["--safe-mode", "--method", "permanent"])), DeleteMethod::Trash);
+
+        let mut safe = cli(&["--method", "permanent"]);
+        safe.config.clean.safe_mode = true;
+        assert_eq!(junk_delete_method(&safe), DeleteMethod::Trash);
+    }
+
+    #[test]
+    fn test_row_limit() {
+        let cli = |args: &[&str]| Cli::parse_from(std::iter::once("null-e").chain(args.iter().copied()));
+
+        assert_eq!(row_limit(&cli(&[]), Some(30)), Some(30));
+        assert_eq!(row_limit(&cli(&[]), None), None);
+        assert_eq!(row_limit(&cli(&["--top", "5"]), None), Some(5));
+        assert_eq!(row_limit(&cli(&["--top", "0"]), Some(30)), None);
+        assert_eq!(row_limit(&cli(&["--top", "5", "-a"]), Some(30)), None);
+    }
+
+    #[test]
+    fn test_doctor_probes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir(&bin).unwrap();
+        let tool = bin.join(format!("git{}", std::env::consts::EXE_SUFFIX));
+        std::fs::write(&tool, "").unwrap();
+
+        let path_var = std::env::join_paths([temp.path().join("missing"), bin]).unwrap();
+        assert_eq!(find_in_path("git", &path_var), Some(tool));
+        assert_eq!(find_in_path("brew", &path_var), None);
+
+        let cache = temp.path().join("cache/null-e");
+        assert!(probe_writable(&cache).is_ok());
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("1,3,5", 5), vec![0, 2, 4]);
+        assert_eq!(parse_selection("1-3, 7", 5), vec![0, 1, 2]);
+        assert_eq!(parse_selection("4-", 6), vec![3, 4, 5]);
+        assert_eq!(parse_selection("all", 3), vec![0, 1, 2]);
+        assert_eq!(parse_selection("all,!2", 4), vec![0, 2, 3]);
+        assert_eq!(parse_selection("!2,!4", 4), vec![0, 2]);
+        assert_eq!(parse_selection("1-5,!2-3", 5), vec![0, 3, 4]);
+        assert_eq!(parse_selection("a,!3-", 5), vec![0, 1]);
+        assert_eq!(parse_selection("0,9,x,-", 5), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_parse_selection_with_groups() {
+        let groups = [("js", vec![0, 1]), ("rust", vec![3])];
+        assert_eq!(parse_selection_with_groups("js", 5, &groups), vec![0, 1]);
+        assert_eq!(parse_selection_with_groups("rust,5", 5, &groups), vec![3, 4]);
+        assert_eq!(parse_selection_with_groups("all,!js", 5, &groups), vec![2, 3, 4]);
+        assert_eq!(parse_selection_with_groups("!rust", 4, &groups), vec![0, 1, 2]);
+        assert_eq!(parse_selection_with_groups("python", 5, &groups), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_colors_enabled() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |var: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == var)
+                    .map(|(_, value)| std::ffi::OsString::from(value))
+            }
+        };
+
+        assert!(colors_enabled(ColorChoice::Auto, env(&[]), true));
+        assert!(!colors_enabled(ColorChoice::Auto, env(&[]), false));
+        assert!(!colors_enabled(ColorChoice::Auto, env(&[("NO_COLOR", "1")]), true));
+        // An empty NO_COLOR doesn't count
+        assert!(colors_enabled(ColorChoice::Auto, env(&[("NO_COLOR", "")]), true));
+        assert!(!colors_enabled(ColorChoice::Auto, env(&[("CLICOLOR", "0")]), true));
+        assert!(colors_enabled(ColorChoice::Auto, env(&[("CLICOLOR_FORCE", "1")]), false));
+        assert!(colors_enabled(ColorChoice::Always, env(&[("NO_COLOR", "1")]), false));
+        assert!(!colors_enabled(ColorChoice::Never, env(&[("CLICOLOR_FORCE", "1")]), true));
+    }
+
+    #[test]
+    fn test_root_subtotals() {
+        let project = |root: &str, size| {
+            let mut project = Project::new(null_e::core::ProjectKind::Rust, PathBuf::from(root));
+            project.cleanable_size = size;
+            project
+        };
+        let roots = [PathBuf::from("/work"), PathBuf::from("/data"), PathBuf::from("/work/big")];
+        let projects = [
+            project("/work/a", 10),
+            project("/work/big/b", 50),
+            project("/data/c", 30),
+            project("/data/d", 5),
+            project("/elsewhere/e", 1),
+        ];
+
+        let totals = root_subtotals(&roots, &projects);
+        assert_eq!(
+            totals,
+            vec![
+                (Some(PathBuf::from("/work/big")), 50, 1),
+                (Some(PathBuf::from("/data")), 35, 2),
+                (Some(PathBuf::from("/work")), 10, 1),
+                (None, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unique_clean_commands() {
+        let commands = unique_clean_commands([
+            "docker builder prune -f",
+            "brew cleanup --prune=all",
+            "docker builder prune -f",
+        ]);
+        assert_eq!(commands, vec!["docker builder prune -f", "brew cleanup --prune=all"]);
+    }
+
+    #[test]
+    fn test_item_choice_parse() {
+        assert_eq!(ItemChoice::parse("y\n"), ItemChoice::Clean);
+        assert_eq!(ItemChoice::parse(" YES "), ItemChoice::Clean);
+        assert_eq!(ItemChoice::parse("q"), ItemChoice::Quit);
+        assert_eq!(ItemChoice::parse("n"), ItemChoice::Skip);
+        assert_eq!(ItemChoice::parse(""), ItemChoice::Skip);
+    }
+
+    #[test]
+    fn test_parse_path_list() {
+        let paths = parse_path_list("/src/a\n\n  /src/b  \r\n/mnt/data/c\n");
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("/src/a"), PathBuf::from("/src/b"), PathBuf::from("/mnt/data/c")]
+        );
+    }
+
     #[test]
     fn test_parse_size() {
         assert_eq!(parse_size("1MB"), Some(1_000_000));
@@ -2517,4 +5883,258 @@ mod tests {
         assert_eq!(parse_size("1000"), Some(1000));
         assert_eq!(parse_size("invalid"), None);
     }
+
+    #[test]
+    fn test_sort_projects() {
+        let project = |root: &str, size, age_days: u64| {
+            let mut artifact = Artifact::new(PathBuf::from(root).join("target"), ArtifactKind::BuildOutput);
+            artifact.size = size;
+            artifact.age = Some(Duration::from_secs(age_days * 86400));
+            let mut project = Project::new(ProjectKind::Rust, PathBuf::from(root));
+            project.artifacts = vec![artifact];
+            project.calculate_totals();
+            project
+        };
+        let names = |projects: &[Project]| projects.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+
+        let mut projects = vec![
+            project("/code/active", 8_000_000_000, 1),
+            project("/code/abandoned", 900_000_000, 400),
+            project("/code/bench", 2_000_000_000, 3),
+        ];
+
+        sort_projects(&mut projects, SortKey::Size, false);
+        assert_eq!(names(&projects), ["active", "bench", "abandoned"]);
+
+        sort_projects(&mut projects, SortKey::Size, true);
+        assert_eq!(names(&projects), ["abandoned", "bench", "active"]);
+
+        sort_projects(&mut projects, SortKey::Score, false);
+        assert_eq!(names(&projects), ["abandoned", "active", "bench"]);
+
+        sort_projects(&mut projects, SortKey::Name, false);
+        assert_eq!(names(&projects), ["abandoned", "active", "bench"]);
+
+        sort_projects(&mut projects, SortKey::Date, false);
+        assert_eq!(names(&projects), ["abandoned", "bench", "active"]);
+
+        projects[2].artifacts[0].file_count = 400_000;
+        sort_projects(&mut projects, SortKey::Files, false);
+        assert_eq!(names(&projects)[0], "active");
+    }
+
+    #[test]
+    fn test_format_file_count() {
+        assert_eq!(format_file_count(0), "0 files");
+        assert_eq!(format_file_count(1), "1 file");
+        assert_eq!(format_file_count(999), "999 files");
+        assert_eq!(format_file_count(1_000), "1,000 files");
+        assert_eq!(format_file_count(412_803), "412,803 files");
+        assert_eq!(format_file_count(12_345_678), "12,345,678 files");
+    }
+
+    #[test]
+    fn test_drop_missing_artifacts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("app");
+        std::fs::create_dir_all(root.join("target")).unwrap();
+
+        let mut project = Project::new(ProjectKind::Rust, root.clone());
+        project.artifacts = vec![
+            Artifact::new(root.join("target"), ArtifactKind::BuildOutput),
+            Artifact::new(root.join("node_modules"), ArtifactKind::Dependencies),
+        ];
+        let mut gone = Project::new(ProjectKind::NodeNpm, temp.path().join("gone"));
+        gone.artifacts = vec![Artifact::new(temp.path().join("gone/node_modules"), ArtifactKind::Dependencies)];
+
+        // Round-trip through the export format first
+        let result = ScanResult {
+            projects: vec![project, gone],
+            total_size: 0,
+            total_cleanable: 0,
+            duration: Duration::ZERO,
+            directories_scanned: 2,
+            errors: vec![],
+            stats: Default::default(),
+            is_partial: false,
+            phase_timings: Default::default(),
+        };
+        let json = serde_json::to_string_pretty(&result).unwrap();
+        let mut projects = ScanResult::from_json_compat(&json).unwrap().projects;
+
+        let missing = drop_missing_artifacts(&mut projects);
+        assert_eq!(missing.len(), 2);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].artifacts.len(), 1);
+        assert_eq!(projects[0].artifacts[0].path, root.join("target"));
+    }
+
+    #[test]
+    fn test_drop_unreported_artifacts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("app");
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+        std::fs::create_dir_all(temp.path().join("elsewhere")).unwrap();
+
+        let mut project = Project::new(ProjectKind::Rust, root.clone());
+        project.artifacts = vec![
+            Artifact::new(root.join("target"), ArtifactKind::BuildOutput),
+            // An edited export pointing at source, or outside the project
+            Artifact::new(root.join("src"), ArtifactKind::BuildOutput),
+            Artifact::new(temp.path().join("elsewhere"), ArtifactKind::BuildOutput),
+        ];
+        // No project is detected here any more
+        let mut stale = Project::new(ProjectKind::NodeNpm, temp.path().to_path_buf());
+        stale.artifacts = vec![Artifact::new(temp.path().join("elsewhere"), ArtifactKind::Dependencies)];
+        let mut projects = vec![project, stale];
+
+        let unreported = drop_unreported_artifacts(&mut projects, &PluginRegistry::with_builtins());
+        assert_eq!(unreported.len(), 3);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].artifacts.len(), 1);
+        assert_eq!(projects[0].artifacts[0].path, root.join("target"));
+    }
+
+    #[test]
+    fn test_drop_recent_artifacts() {
+        let artifact = |path: &str, age_days: Option<u64>| {
+            let mut artifact = Artifact::new(PathBuf::from(path), ArtifactKind::BuildOutput);
+            artifact.size = 100;
+            artifact.age = age_days.map(|d| Duration::from_secs(d * 86400 + 60));
+            artifact
+        };
+        let mut fresh = Project::new(ProjectKind::NodeNpm, PathBuf::from("/code/fresh"));
+        fresh.artifacts = vec![artifact("/code/fresh/node_modules", Some(0))];
+        let mut mixed = Project::new(ProjectKind::Rust, PathBuf::from("/code/mixed"));
+        mixed.artifacts = vec![
+            artifact("/code/mixed/target", Some(0)),
+            artifact("/code/mixed/old", Some(30)),
+            artifact("/code/mixed/unknown", None),
+        ];
+        let mut projects = vec![fresh, mixed];
+        for project in &mut projects {
+            project.calculate_totals();
+        }
+
+        let recent = drop_recent_artifacts(&mut projects, 1);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].artifacts.len(), 2);
+        assert_eq!(projects[0].cleanable_size, 200);
+
+        // 0 keeps everything
+        assert!(drop_recent_artifacts(&mut projects, 0).is_empty());
+    }
+
+    #[test]
+    fn test_drop_riskier_artifacts() {
+        let artifact = |path: &str, kind| {
+            let mut artifact = Artifact::new(PathBuf::from(path), kind);
+            artifact.size = 100;
+            artifact
+        };
+        let mut app = Project::new(ProjectKind::PythonPip, PathBuf::from("/code/app"));
+        app.artifacts = vec![
+            artifact("/code/app/__pycache__", ArtifactKind::Bytecode),
+            artifact("/code/app/build", ArtifactKind::BuildOutput),
+            artifact("/code/app/.venv", ArtifactKind::VirtualEnv),
+        ];
+        let mut env = Project::new(ProjectKind::PythonPip, PathBuf::from("/code/env"));
+        env.artifacts = vec![artifact("/code/env/.venv", ArtifactKind::VirtualEnv)];
+        let mut projects = vec![app, env];
+
+        assert_eq!(artifact_safety_level(&projects[0].artifacts[1]), SafetyLevel::SafeWithCost);
+        assert!(drop_riskier_artifacts(&mut projects.clone(), SafetyLevel::Dangerous).is_empty());
+
+        let risky = drop_riskier_artifacts(&mut projects, SafetyLevel::SafeWithCost);
+        assert_eq!(risky.len(), 2);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].cleanable_size, 200);
+
+        let risky = drop_riskier_artifacts(&mut projects, SafetyLevel::Safe);
+        assert_eq!(risky[0].path, PathBuf::from("/code/app/build"));
+        assert_eq!(projects[0].artifacts.len(), 1);
+    }
+
+    #[test]
+    fn test_drop_changed_projects() {
+        let mut projects: Vec<Project> = ["/mono", "/mono/api", "/mono/web", "/mono/docs"]
+            .iter()
+            .map(|root| Project::new(ProjectKind::NodeNpm, PathBuf::from(root)))
+            .collect();
+        let changed = [PathBuf::from("/mono/api/src/index.ts"), PathBuf::from("/mono/webapp/x.ts")];
+
+        let active = drop_changed_projects(&mut projects, &changed);
+        let roots = |projects: &[Project]| projects.iter().map(|p| p.root.clone()).collect::<Vec<_>>();
+        assert_eq!(roots(&active), vec![PathBuf::from("/mono"), PathBuf::from("/mono/api")]);
+        // "/mono/webapp" isn't inside "/mono/web"
+        assert_eq!(roots(&projects), vec![PathBuf::from("/mono/web"), PathBuf::from("/mono/docs")]);
+    }
+
+    #[test]
+    fn test_rebuild_args() {
+        let root = Path::new("/code/app");
+        assert_eq!(rebuild_args("cargo build --release", root), ["cargo", "build", "--release"]);
+        assert_eq!(
+            rebuild_args("make -C {root} all", root),
+            ["make", "-C", "/code/app", "all"]
+        );
+        assert!(rebuild_args("  ", root).is_empty());
+    }
+
+    #[test]
+    fn test_top_level_dir() {
+        let config = ScanConfig {
+            roots: vec![PathBuf::from("/code"), PathBuf::from("/work")],
+            ..Default::default()
+        };
+        let project = |root: &str| Project::new(ProjectKind::Rust, PathBuf::from(root));
+
+        assert_eq!(top_level_dir(&config, &project("/code/client-a/api")), "client-a");
+        assert_eq!(top_level_dir(&config, &project("/work/tool")), "tool");
+        assert_eq!(top_level_dir(&config, &project("/code")), ".");
+    }
+
+    #[test]
+    fn test_build_scan_tree() {
+        let project = |root: &str, size: u64| {
+            let mut project = Project::new(ProjectKind::Rust, PathBuf::from(root));
+            project.cleanable_size = size;
+            project
+        };
+        let projects = vec![
+            project("/code/client-a/api", 600),
+            project("/code/client-a/web", 300),
+            project("/code/client-a/web/docs", 50),
+            project("/code/tools/deep/nested/cli", 100),
+            project("/tmp/scratch", 10),
+        ];
+        let trees = build_scan_tree(&[PathBuf::from("/code")], &projects);
+
+        // Largest root first; projects outside every root go under /
+        assert_eq!(trees.len(), 2);
+        let (root, code) = &trees[0];
+        assert_eq!(root, Path::new("/code"));
+        assert_eq!(code.size, 1_050);
+        assert_eq!(trees[1].0, PathBuf::from("/"));
+        assert_eq!(trees[1].1.size, 10);
+
+        // Sizes roll up, and a project's size includes projects nested inside it
+        let client_a = &code.children["client-a"];
+        assert_eq!(client_a.size, 950);
+        assert_eq!(client_a.children["web"].size, 350);
+        assert!(client_a.children["web"].project.is_some());
+        assert!(client_a.project.is_none());
+
+        let order: Vec<_> = code.sorted_children().into_iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(order, ["client-a", "tools"]);
+
+        // Directories holding a single subdirectory are joined into one line
+        let (name, cli) = code.children["tools"].collapsed("tools");
+        assert_eq!(name, "tools/deep/nested/cli");
+        assert_eq!(cli.size, 100);
+        assert!(cli.project.is_some());
+    }
 }
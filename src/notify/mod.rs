@@ -0,0 +1,247 @@
+//! Completion notifications
+//!
+//! After a clean finishes, the summary can be handed to a user command, posted
+//! to a webhook, or shown as a desktop notification - handy for unattended
+//! runs from cron. Notifying is best effort: failures are returned as
+//! warnings and never fail the clean itself.
+
+use crate::config::NotifySettings;
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Seconds a webhook request may take before it is abandoned
+const WEBHOOK_TIMEOUT_SECS: u32 = 10;
+
+/// What a finished clean did
+#[derive(Debug, Clone, Serialize)]
+pub struct CompletionSummary {
+    /// Command that ran, e.g. `clean` or `caches`
+    pub command: String,
+    /// Whether nothing was actually deleted
+    pub dry_run: bool,
+    /// Bytes freed (or that would be freed in a dry run)
+    pub bytes_freed: u64,
+    /// Items cleaned successfully
+    pub cleaned: usize,
+    /// Items that failed to clean
+    pub failed: usize,
+    /// Items skipped (declined or refused)
+    pub skipped: usize,
+    /// When the clean finished
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl CompletionSummary {
+    /// Summary for `command` finishing now
+    pub fn new(command: impl Into<String>, dry_run: bool) -> Self {
+        Self {
+            command: command.into(),
+            dry_run,
+            bytes_freed: 0,
+            cleaned: 0,
+            failed: 0,
+            skipped: 0,
+            finished_at: chrono::Utc::now(),
+        }
+    }
+
+    /// One-line human-readable message
+    pub fn message(&self) -> String {
+        let size = humansize::format_size(self.bytes_freed, humansize::BINARY);
+        let mut message = if self.dry_run {
+            format!("Dry run: would free {} from {} items", size, self.cleaned)
+        } else {
+            format!("Freed {} from {} items", size, self.cleaned)
+        };
+        if self.failed > 0 {
+            message.push_str(&format!(", {} failed", self.failed));
+        }
+        message
+    }
+
+    /// Environment variables passed to the notification command
+    fn env_vars(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("NULL_E_COMMAND", self.command.clone()),
+            ("NULL_E_DRY_RUN", self.dry_run.to_string()),
+            ("NULL_E_BYTES_FREED", self.bytes_freed.to_string()),
+            ("NULL_E_CLEANED", self.cleaned.to_string()),
+            ("NULL_E_FAILED", self.failed.to_string()),
+            ("NULL_E_SKIPPED", self.skipped.to_string()),
+        ]
+    }
+}
+
+/// Dispatch `summary` to every configured target
+///
+/// Returns one warning per target that could not be notified.
+pub fn notify_completion(settings: &NotifySettings, summary: &CompletionSummary) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !settings.is_enabled() {
+        return warnings;
+    }
+
+    let json = match serde_json::to_string(summary) {
+        Ok(json) => json,
+        Err(e) => return vec![format!("Failed to serialize summary: {}", e)],
+    };
+
+    if let Some(command) = settings.command.as_deref().filter(|c| !c.trim().is_empty()) {
+        if let Err(e) = run_command(command, summary, &json) {
+            warnings.push(format!("Notification command failed: {}", e));
+        }
+    }
+    if let Some(url) = settings.webhook.as_deref().filter(|u| !u.trim().is_empty()) {
+        if let Err(e) = post_webhook(url, &json) {
+            warnings.push(format!("Webhook notification failed: {}", e));
+        }
+    }
+    if settings.desktop {
+        if let Err(e) = desktop_notify("null-e", &summary.message()) {
+            warnings.push(format!("Desktop notification failed: {}", e));
+        }
+    }
+
+    warnings
+}
+
+/// Run a shell command with the JSON summary on stdin and `NULL_E_*` variables set
+fn run_command(command: &str, summary: &CompletionSummary, json: &str) -> Result<(), String> {
+    let mut cmd = shell(command);
+    cmd.envs(summary.env_vars());
+    run_with_stdin(cmd, json)
+}
+
+/// POST the JSON summary to a URL using `curl`
+fn post_webhook(url: &str, json: &str) -> Result<(), String> {
+    let mut cmd = Command::new("curl");
+    cmd.args(["-sS", "-f", "-X", "POST", "-H", "Content-Type: application/json"])
+        .args(["--max-time", &WEBHOOK_TIMEOUT_SECS.to_string()])
+        .args(["--data-binary", "@-", url]);
+    run_with_stdin(cmd, json)
+}
+
+/// Show a desktop notification with the platform's stock tool
+fn desktop_notify(title: &str, message: &str) -> Result<(), String> {
+    let cmd = if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("osascript");
+        cmd.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            applescript_string(message),
+            applescript_string(title)
+        ));
+        cmd
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("powershell");
+        cmd.args(["-NoProfile", "-Command", WINDOWS_TOAST_SCRIPT])
+            .env("NULL_E_TITLE", title)
+            .env("NULL_E_MESSAGE", message);
+        cmd
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.args([title, message]);
+        cmd
+    };
+    run_with_stdin(cmd, "")
+}
+
+/// Toast via the WinRT API; title and message come from the environment
+const WINDOWS_TOAST_SCRIPT: &str = "\
+[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] > $null;\
+$t = [Windows.UI.Notifications.ToastNotificationManager]::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02);\
+$n = $t.GetElementsByTagName('text');\
+$n.Item(0).AppendChild($t.CreateTextNode($env:NULL_E_TITLE)) > $null;\
+$n.Item(1).AppendChild($t.CreateTextNode($env:NULL_E_MESSAGE)) > $null;\
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('null-e').Show([Windows.UI.Notifications.ToastNotification]::new($t))";
+
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// Spawn `cmd`, feed it `input` and wait; a non-zero exit is an error
+fn run_with_stdin(mut cmd: Command, input: &str) -> Result<(), String> {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that doesn't read its input is fine
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match stderr.trim() {
+            "" => Err(format!("exited with {}", output.status)),
+            err => Err(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_summary_message() {
+        let mut summary = CompletionSummary::new("clean", false);
+        summary.bytes_freed = 2048;
+        summary.cleaned = 3;
+        assert_eq!(summary.message(), "Freed 2 KiB from 3 items");
+
+        summary.dry_run = true;
+        summary.failed = 1;
+        assert_eq!(summary.message(), "Dry run: would free 2 KiB from 3 items, 1 failed");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_notify_command_receives_summary() {
+        let temp = TempDir::new().unwrap();
+        let out = temp.path().join("summary.json");
+        let settings = NotifySettings {
+            command: Some(format!(
+                "cat > '{}' && test \"$NULL_E_CLEANED\" = 4",
+                out.display()
+            )),
+            ..Default::default()
+        };
+
+        let mut summary = CompletionSummary::new("caches", false);
+        summary.cleaned = 4;
+        summary.bytes_freed = 100;
+        assert!(notify_completion(&settings, &summary).is_empty());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(json["command"], "caches");
+        assert_eq!(json["bytes_freed"], 100);
+
+        // A failing command is reported, not raised
+        let settings = NotifySettings {
+            command: Some("exit 3".into()),
+            ..Default::default()
+        };
+        assert_eq!(notify_completion(&settings, &summary).len(), 1);
+    }
+}
@@ -0,0 +1,166 @@
+//! Detection of processes still using an artifact
+//!
+//! Deleting `target/` under a running `cargo watch`, or `node_modules` under
+//! a dev server, breaks the running tool in confusing ways. Plugins call into
+//! this module from [`Plugin::pre_clean`](super::Plugin::pre_clean) to refuse
+//! those deletions. Only obvious signs are checked:
+//!
+//! - `*.pid` files naming a live process
+//! - processes of known tools working inside the project (Linux `/proc` only)
+//! - any process working inside the artifact itself (Linux `/proc` only)
+
+use crate::error::{DevSweepError, Result};
+use std::path::{Path, PathBuf};
+
+/// Refuse to clean `artifact` while something is evidently using it
+///
+/// `project_root` is where `tools` (process names such as `cargo` or `node`)
+/// are looked for. Returns [`DevSweepError::InUse`] naming the first sign found.
+pub fn ensure_not_in_use(artifact: &Path, project_root: &Path, tools: &[&str]) -> Result<()> {
+    let in_use = |reason: String| DevSweepError::InUse {
+        path: artifact.to_path_buf(),
+        reason,
+    };
+
+    for dir in [project_root, artifact, &project_root.join("tmp/pids")] {
+        if let Some((pid_file, pid)) = live_pid_file(dir) {
+            return Err(in_use(format!(
+                "process {} from {} is running",
+                pid,
+                pid_file.display()
+            )));
+        }
+    }
+
+    let busy = |p: &RunningProcess| {
+        p.cwd.starts_with(artifact)
+            || (p.cwd.starts_with(project_root) && tools.contains(&p.name.as_str()))
+    };
+    if let Some(process) = running_processes().into_iter().find(busy) {
+        return Err(in_use(format!(
+            "{} (pid {}) is running in {}",
+            process.name,
+            process.pid,
+            process.cwd.display()
+        )));
+    }
+
+    Ok(())
+}
+
+/// First `*.pid` file directly in `dir` whose process is still alive
+fn live_pid_file(dir: &Path) -> Option<(PathBuf, u32)> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "pid") && p.is_file())
+        .find_map(|path| {
+            let pid = std::fs::read_to_string(&path).ok()?.trim().parse::<u32>().ok()?;
+            is_alive(pid).then_some((path, pid))
+        })
+}
+
+#[cfg(unix)]
+fn is_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 {
+        return false;
+    }
+    // SAFETY: signal 0 only checks whether the process exists
+    let rc = unsafe { libc::kill(pid, 0) };
+    // EPERM: it exists, it just isn't ours
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn is_alive(_pid: u32) -> bool {
+    false
+}
+
+/// A running process and the directory it works in
+struct RunningProcess {
+    pid: u32,
+    name: String,
+    cwd: PathBuf,
+}
+
+/// Processes whose working directory can be read (other users' usually can't)
+#[cfg(target_os = "linux")]
+fn running_processes() -> Vec<RunningProcess> {
+    let own_pid = std::process::id();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            if pid == own_pid {
+                return None;
+            }
+            let cwd = std::fs::read_link(entry.path().join("cwd")).ok()?;
+            let name = std::fs::read_to_string(entry.path().join("comm")).ok()?;
+            Some(RunningProcess {
+                pid,
+                name: name.trim().to_string(),
+                cwd,
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn running_processes() -> Vec<RunningProcess> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_live_pid_file_blocks() {
+        let temp = TempDir::new().unwrap();
+        let artifact = temp.path().join("node_modules");
+        std::fs::create_dir(&artifact).unwrap();
+        assert!(ensure_not_in_use(&artifact, temp.path(), &[]).is_ok());
+
+        // A process that has exited doesn't count
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead = child.id();
+        child.wait().unwrap();
+        std::fs::write(temp.path().join("server.pid"), dead.to_string()).unwrap();
+        assert!(ensure_not_in_use(&artifact, temp.path(), &[]).is_ok());
+
+        std::fs::write(temp.path().join("server.pid"), std::process::id().to_string()).unwrap();
+        let err = ensure_not_in_use(&artifact, temp.path(), &[]).unwrap_err();
+        assert!(matches!(err, DevSweepError::InUse { path, .. } if path == artifact));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_running_tool_blocks() {
+        let temp = TempDir::new().unwrap();
+        let artifact = temp.path().join("target");
+        std::fs::create_dir(&artifact).unwrap();
+
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .current_dir(temp.path())
+            .spawn()
+            .unwrap();
+        let unrelated = ensure_not_in_use(&artifact, temp.path(), &["cargo"]);
+        let tool = ensure_not_in_use(&artifact, temp.path(), &["sleep"]);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert!(unrelated.is_ok());
+        assert!(matches!(tool, Err(DevSweepError::InUse { .. })));
+    }
+}
@@ -118,6 +118,10 @@ impl Plugin for DotNetPlugin {
         &["bin", "obj", "packages", "TestResults"]
     }
 
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("dotnet build".into())
+    }
+
     fn priority(&self) -> u8 {
         55
     }
@@ -0,0 +1,135 @@
+//! Elixir plugin
+//!
+//! Mix compiles into `_build` and fetches dependencies into `deps`. The deps
+//! are pinned by `mix.lock`, so they are only as safe to remove as the
+//! lockfile is present.
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::Plugin;
+use std::path::Path;
+
+/// Plugin for Elixir (Mix) projects
+pub struct ElixirPlugin;
+
+impl Plugin for ElixirPlugin {
+    fn id(&self) -> &'static str {
+        "elixir"
+    }
+
+    fn name(&self) -> &'static str {
+        "Elixir"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::Elixir]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![
+            ProjectMarker {
+                indicator: MarkerKind::File("mix.exs"),
+                kind: ProjectKind::Elixir,
+                priority: 60,
+            },
+            ProjectMarker {
+                indicator: MarkerKind::File("mix.lock"),
+                kind: ProjectKind::Elixir,
+                priority: 55,
+            },
+        ]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        if path.join("mix.exs").is_file() {
+            Some(ProjectKind::Elixir)
+        } else {
+            None
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+
+        // Compiled build output (per MIX_ENV)
+        let build = project_root.join("_build");
+        if build.is_dir() {
+            artifacts.push(Artifact {
+                path: build,
+                kind: ArtifactKind::BuildOutput,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata: ArtifactMetadata::restorable("mix compile"),
+            });
+        }
+
+        // Fetched dependencies
+        let deps = project_root.join("deps");
+        if deps.is_dir() {
+            let lockfile = project_root.join("mix.lock");
+            let mut metadata = ArtifactMetadata::restorable("mix deps.get");
+            if lockfile.is_file() {
+                metadata = metadata.with_lockfile(lockfile);
+            }
+
+            artifacts.push(Artifact {
+                path: deps,
+                kind: ArtifactKind::Dependencies,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata,
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn cleanable_dirs(&self) -> &[&'static str] {
+        &["_build", "deps"]
+    }
+
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("mix compile".into())
+    }
+
+    fn priority(&self) -> u8 {
+        60
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArtifactSafety;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_elixir() {
+        let temp = TempDir::new().unwrap();
+        let plugin = ElixirPlugin;
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::write(temp.path().join("mix.exs"), "defmodule App.MixProject do\nend\n").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::Elixir));
+    }
+
+    #[test]
+    fn test_deps_need_lockfile() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("mix.exs"), "").unwrap();
+        std::fs::create_dir(temp.path().join("_build")).unwrap();
+        std::fs::create_dir(temp.path().join("deps")).unwrap();
+
+        let artifacts = ElixirPlugin.find_artifacts(temp.path()).unwrap();
+        let names: Vec<_> = artifacts.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["_build", "deps"]);
+        assert_eq!(artifacts[1].safety(), ArtifactSafety::SafeWithLockfile);
+        assert!(!artifacts[1].is_safe_to_clean());
+
+        std::fs::write(temp.path().join("mix.lock"), "%{}\n").unwrap();
+        let artifacts = ElixirPlugin.find_artifacts(temp.path()).unwrap();
+        assert!(artifacts[1].is_safe_to_clean());
+    }
+}
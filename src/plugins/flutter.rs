@@ -0,0 +1,169 @@
+//! Flutter/Dart plugin
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::Plugin;
+use std::path::Path;
+
+/// Generated per-platform directories regenerated by `flutter pub get`
+const EPHEMERAL_DIRS: &[&str] = &[
+    "ios/Flutter/ephemeral",
+    "macos/Flutter/ephemeral",
+    "linux/flutter/ephemeral",
+    "windows/flutter/ephemeral",
+];
+
+/// Plugin for Flutter and Dart projects
+pub struct FlutterPlugin;
+
+impl Plugin for FlutterPlugin {
+    fn id(&self) -> &'static str {
+        "flutter"
+    }
+
+    fn name(&self) -> &'static str {
+        "Flutter/Dart"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::Flutter, ProjectKind::Dart]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![ProjectMarker {
+            indicator: MarkerKind::File("pubspec.yaml"),
+            kind: ProjectKind::Dart,
+            priority: 60,
+        }]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        let pubspec = std::fs::read_to_string(path.join("pubspec.yaml")).ok()?;
+        if is_flutter_pubspec(&pubspec) {
+            Some(ProjectKind::Flutter)
+        } else {
+            Some(ProjectKind::Dart)
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+        let is_flutter = self.detect(project_root) == Some(ProjectKind::Flutter);
+        let tool = if is_flutter { "flutter" } else { "dart" };
+
+        // .dart_tool (package config, build_runner and compiler caches)
+        let dart_tool = project_root.join(".dart_tool");
+        if dart_tool.exists() {
+            artifacts.push(Artifact {
+                path: dart_tool,
+                kind: ArtifactKind::Cache,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata: ArtifactMetadata::restorable(format!("{} pub get", tool))
+                    .with_lockfile(project_root.join("pubspec.lock")),
+            });
+        }
+
+        // build directory (only Flutter owns it; plain Dart packages may commit one)
+        let build = project_root.join("build");
+        if is_flutter && build.exists() {
+            artifacts.push(Artifact {
+                path: build,
+                kind: ArtifactKind::BuildOutput,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata: ArtifactMetadata::restorable("flutter build").with_restore_time(120),
+            });
+        }
+
+        // Platform ephemeral directories (generated plugin registrants, engine copies)
+        if is_flutter {
+            for dir in EPHEMERAL_DIRS {
+                let ephemeral = project_root.join(dir);
+                if ephemeral.exists() {
+                    artifacts.push(Artifact {
+                        path: ephemeral,
+                        kind: ArtifactKind::BuildOutput,
+                        size: 0,
+                        file_count: 0,
+                        age: None,
+                        metadata: ArtifactMetadata::restorable("flutter pub get"),
+                    });
+                }
+            }
+        }
+
+        Ok(artifacts)
+    }
+
+    fn cleanable_dirs(&self) -> &[&'static str] {
+        &[".dart_tool"]
+    }
+
+    fn priority(&self) -> u8 {
+        60
+    }
+}
+
+/// Check whether a pubspec depends on the Flutter SDK
+fn is_flutter_pubspec(pubspec: &str) -> bool {
+    pubspec.lines().any(|line| {
+        let line = line.trim();
+        line == "flutter:" || line.replace(' ', "") == "sdk:flutter"
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FLUTTER_PUBSPEC: &str = "name: app\ndependencies:\n  flutter:\n    sdk: flutter\n";
+
+    #[test]
+    fn test_detect_flutter_and_dart() {
+        let temp = TempDir::new().unwrap();
+        let plugin = FlutterPlugin;
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::write(temp.path().join("pubspec.yaml"), "name: cli\n").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::Dart));
+
+        std::fs::write(temp.path().join("pubspec.yaml"), FLUTTER_PUBSPEC).unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::Flutter));
+    }
+
+    #[test]
+    fn test_find_artifacts() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("pubspec.yaml"), FLUTTER_PUBSPEC).unwrap();
+        std::fs::create_dir(temp.path().join(".dart_tool")).unwrap();
+        std::fs::create_dir(temp.path().join("build")).unwrap();
+        std::fs::create_dir_all(temp.path().join("ios/Flutter/ephemeral")).unwrap();
+
+        let plugin = FlutterPlugin;
+        let artifacts = plugin.find_artifacts(temp.path()).unwrap();
+
+        let names: Vec<_> = artifacts.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec![".dart_tool", "build", "ephemeral"]);
+    }
+
+    #[test]
+    fn test_dart_package_keeps_build() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("pubspec.yaml"), "name: cli\n").unwrap();
+        std::fs::create_dir(temp.path().join(".dart_tool")).unwrap();
+        std::fs::create_dir(temp.path().join("build")).unwrap();
+
+        let plugin = FlutterPlugin;
+        let artifacts = plugin.find_artifacts(temp.path()).unwrap();
+
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(
+            artifacts[0].metadata.restore_command.as_deref(),
+            Some("dart pub get")
+        );
+    }
+}
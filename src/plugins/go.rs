@@ -47,21 +47,25 @@ impl Plugin for GoPlugin {
     fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
         let mut artifacts = Vec::new();
 
-        // vendor directory (vendored dependencies)
+        // vendor directory (vendored dependencies), often committed on
+        // purpose: git protection keeps a tracked one
         let vendor = project_root.join("vendor");
         if vendor.exists() {
+            let mut metadata = ArtifactMetadata {
+                restorable: true,
+                restore_command: Some("go mod vendor".into()),
+                lockfile: Some(project_root.join("go.sum")),
+                ..Default::default()
+            };
+            metadata.extra.insert("vendored".into(), "true".into());
+
             artifacts.push(Artifact {
                 path: vendor,
                 kind: ArtifactKind::Dependencies,
                 size: 0,
                 file_count: 0,
                 age: None,
-                metadata: ArtifactMetadata {
-                    restorable: true,
-                    restore_command: Some("go mod vendor".into()),
-                    lockfile: Some(project_root.join("go.sum")),
-                    ..Default::default()
-                },
+                metadata,
             });
         }
 
@@ -98,6 +102,10 @@ impl Plugin for GoPlugin {
         &["vendor"]
     }
 
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("go build ./...".into())
+    }
+
     fn priority(&self) -> u8 {
         55
     }
@@ -132,5 +140,6 @@ mod tests {
 
         assert_eq!(artifacts.len(), 1);
         assert_eq!(artifacts[0].name(), "vendor");
+        assert!(artifacts[0].is_vendored());
     }
 }
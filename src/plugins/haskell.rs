@@ -0,0 +1,132 @@
+//! Haskell plugin
+//!
+//! Cabal builds into `dist-newstyle` and Stack into `.stack-work`. Both are
+//! per-project build directories; the shared package stores in `~/.cabal`
+//! and `~/.stack` are left to the global cache cleaners.
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::Plugin;
+use std::path::Path;
+
+/// Plugin for Haskell (Cabal and Stack) projects
+pub struct HaskellPlugin;
+
+impl Plugin for HaskellPlugin {
+    fn id(&self) -> &'static str {
+        "haskell"
+    }
+
+    fn name(&self) -> &'static str {
+        "Haskell"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::Haskell]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![
+            ProjectMarker {
+                indicator: MarkerKind::AnyOf(vec!["stack.yaml", "cabal.project"]),
+                kind: ProjectKind::Haskell,
+                priority: 60,
+            },
+            ProjectMarker {
+                indicator: MarkerKind::File("*.cabal"),
+                kind: ProjectKind::Haskell,
+                priority: 55,
+            },
+        ]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        if path.join("stack.yaml").is_file()
+            || path.join("cabal.project").is_file()
+            || has_cabal_file(path)
+        {
+            Some(ProjectKind::Haskell)
+        } else {
+            None
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+
+        for (name, command) in [("dist-newstyle", "cabal build"), (".stack-work", "stack build")] {
+            let path = project_root.join(name);
+            if path.is_dir() {
+                artifacts.push(Artifact {
+                    path,
+                    kind: ArtifactKind::BuildOutput,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata: ArtifactMetadata::restorable(command),
+                });
+            }
+        }
+
+        Ok(artifacts)
+    }
+
+    fn cleanable_dirs(&self) -> &[&'static str] {
+        &["dist-newstyle", ".stack-work"]
+    }
+
+    fn rebuild_command(&self, project_root: &Path) -> Option<String> {
+        let tool = if project_root.join("stack.yaml").exists() { "stack" } else { "cabal" };
+        Some(format!("{} build", tool))
+    }
+
+    fn priority(&self) -> u8 {
+        60
+    }
+}
+
+/// Check whether a directory holds a `.cabal` package description
+fn has_cabal_file(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries.filter_map(|e| e.ok()).any(|e| {
+                e.file_name().to_string_lossy().ends_with(".cabal") && e.path().is_file()
+            })
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_haskell() {
+        let plugin = HaskellPlugin;
+
+        let cabal = TempDir::new().unwrap();
+        assert_eq!(plugin.detect(cabal.path()), None);
+        std::fs::write(cabal.path().join("app.cabal"), "name: app\n").unwrap();
+        assert_eq!(plugin.detect(cabal.path()), Some(ProjectKind::Haskell));
+
+        let stack = TempDir::new().unwrap();
+        std::fs::write(stack.path().join("stack.yaml"), "resolver: lts-22.0\n").unwrap();
+        assert_eq!(plugin.detect(stack.path()), Some(ProjectKind::Haskell));
+    }
+
+    #[test]
+    fn test_find_artifacts() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("stack.yaml"), "").unwrap();
+        std::fs::create_dir(temp.path().join("dist-newstyle")).unwrap();
+        std::fs::create_dir(temp.path().join(".stack-work")).unwrap();
+
+        let artifacts = HaskellPlugin.find_artifacts(temp.path()).unwrap();
+        let commands: Vec<_> = artifacts
+            .iter()
+            .map(|a| a.metadata.restore_command.as_deref().unwrap())
+            .collect();
+        assert_eq!(commands, vec!["cabal build", "stack build"]);
+    }
+}
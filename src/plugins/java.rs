@@ -1,9 +1,15 @@
 //! Java plugin (Maven and Gradle)
+//!
+//! Gradle keeps state in two places: the per-project `.gradle/` and
+//! `build/` directories, reported here as project artifacts, and the Gradle
+//! user home (`~/.gradle`, or `$GRADLE_USER_HOME`), whose `caches/` is a
+//! global cache. A project's `.gradle/` is never reported when it *is* the
+//! user home (a build script in `~`), so cache and project totals don't overlap.
 
 use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
 use crate::error::Result;
 use crate::plugins::Plugin;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Plugin for Maven projects
 pub struct MavenPlugin;
@@ -66,6 +72,10 @@ impl Plugin for MavenPlugin {
         &["target"]
     }
 
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("mvn compile".into())
+    }
+
     fn priority(&self) -> u8 {
         60
     }
@@ -144,9 +154,9 @@ impl Plugin for GradlePlugin {
             });
         }
 
-        // .gradle directory (local cache)
+        // .gradle directory (local cache), unless it is the global Gradle home
         let gradle_cache = project_root.join(".gradle");
-        if gradle_cache.exists() {
+        if gradle_cache.exists() && !is_gradle_user_home(&gradle_cache, gradle_user_home().as_deref()) {
             artifacts.push(Artifact {
                 path: gradle_cache,
                 kind: ArtifactKind::Cache,
@@ -170,11 +180,29 @@ impl Plugin for GradlePlugin {
             });
         }
 
+        // .cxx directory (Android NDK / CMake intermediates)
+        let cxx = project_root.join(".cxx");
+        if cxx.exists() {
+            artifacts.push(Artifact {
+                path: cxx,
+                kind: ArtifactKind::BuildOutput,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata: ArtifactMetadata::restorable("./gradlew build"),
+            });
+        }
+
         Ok(artifacts)
     }
 
     fn cleanable_dirs(&self) -> &[&'static str] {
-        &["build", ".gradle", "out"]
+        &["build", ".gradle", "out", ".cxx"]
+    }
+
+    fn rebuild_command(&self, project_root: &Path) -> Option<String> {
+        let gradle = if project_root.join("gradlew").exists() { "./gradlew" } else { "gradle" };
+        Some(format!("{} build", gradle))
     }
 
     fn priority(&self) -> u8 {
@@ -182,6 +210,25 @@ impl Plugin for GradlePlugin {
     }
 }
 
+/// The Gradle user home: `$GRADLE_USER_HOME`, or `~/.gradle`
+pub fn gradle_user_home() -> Option<PathBuf> {
+    std::env::var_os("GRADLE_USER_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".gradle")))
+}
+
+/// Whether `dir` is the Gradle user home, resolving symlinks on both sides
+fn is_gradle_user_home(dir: &Path, user_home: Option<&Path>) -> bool {
+    let Some(user_home) = user_home else {
+        return false;
+    };
+    match (dir.canonicalize(), user_home.canonicalize()) {
+        (Ok(dir), Ok(home)) => dir == home,
+        _ => dir == user_home,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +277,29 @@ mod tests {
 
         assert_eq!(artifacts.len(), 2);
     }
+
+    #[test]
+    fn test_gradle_user_home_is_not_a_project_artifact() {
+        let temp = TempDir::new().unwrap();
+        let user_home = temp.path().join(".gradle");
+        std::fs::create_dir_all(user_home.join("caches")).unwrap();
+        let project = temp.path().join("app");
+        std::fs::create_dir_all(project.join(".gradle")).unwrap();
+
+        // A build script in the home directory puts its .gradle on top of the global cache
+        assert!(is_gradle_user_home(&user_home, Some(&user_home)));
+        assert!(!is_gradle_user_home(&project.join(".gradle"), Some(&user_home)));
+        assert!(!is_gradle_user_home(&project.join(".gradle"), None));
+    }
+
+    #[test]
+    fn test_find_android_native_artifacts() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("build.gradle.kts"), "").unwrap();
+        std::fs::create_dir(temp.path().join("build")).unwrap();
+        std::fs::create_dir(temp.path().join(".cxx")).unwrap();
+
+        let artifacts = GradlePlugin.find_artifacts(temp.path()).unwrap();
+        assert!(artifacts.iter().any(|a| a.name() == ".cxx"));
+    }
 }
@@ -3,6 +3,7 @@
 //! Each plugin handles detection and artifact discovery for a specific
 //! language/framework ecosystem.
 
+pub mod activity;
 mod registry;
 mod node;
 mod rust;
@@ -11,6 +12,13 @@ mod go;
 mod java;
 mod dotnet;
 mod swift;
+mod flutter;
+mod terraform;
+mod zig;
+mod elixir;
+mod haskell;
+mod php;
+mod ruby;
 
 pub use registry::*;
 pub use node::NodePlugin;
@@ -20,8 +28,15 @@ pub use go::GoPlugin;
 pub use java::{MavenPlugin, GradlePlugin};
 pub use dotnet::DotNetPlugin;
 pub use swift::SwiftPlugin;
+pub use flutter::FlutterPlugin;
+pub use terraform::TerraformPlugin;
+pub use zig::ZigPlugin;
+pub use elixir::ElixirPlugin;
+pub use haskell::HaskellPlugin;
+pub use php::PhpPlugin;
+pub use ruby::RubyPlugin;
 
-use crate::core::{Artifact, ProjectKind, ProjectMarker};
+use crate::core::{Artifact, ProjectKind, ProjectMarker, SizeMode};
 use crate::error::Result;
 use std::path::Path;
 
@@ -45,12 +60,31 @@ pub trait Plugin: Send + Sync {
     /// Find cleanable artifacts in a project directory
     fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>>;
 
+    /// Find leftover artifacts in a directory that isn't a project
+    ///
+    /// Only called for directories no plugin detected as a project. Returned
+    /// artifacts should carry the `orphaned` marker in their metadata.
+    fn find_orphans(&self, _path: &Path) -> Vec<Artifact> {
+        Vec::new()
+    }
+
     /// Custom size calculation (override for special cases)
     fn calculate_size(&self, artifact: &Artifact) -> Result<u64> {
-        default_calculate_size(&artifact.path)
+        self.calculate_size_until(artifact, SizeMode::Apparent, &|| false)
+    }
+
+    /// Size calculation the scanner uses, giving up once `stop` returns true
+    ///
+    /// Override this rather than [`Plugin::calculate_size`] so a custom size
+    /// still honors `--timeout` and Ctrl-C.
+    fn calculate_size_until(&self, artifact: &Artifact, mode: SizeMode, stop: &dyn Fn() -> bool) -> Result<u64> {
+        calculate_size_until(&artifact.path, mode, stop)
     }
 
-    /// Pre-clean hook (e.g., stop running processes)
+    /// Pre-clean hook, run before an artifact is deleted
+    ///
+    /// Returning an error (e.g. [`DevSweepError::InUse`](crate::error::DevSweepError::InUse)
+    /// while a dev server is using it) skips that artifact.
     fn pre_clean(&self, _artifact: &Artifact) -> Result<()> {
         Ok(())
     }
@@ -60,6 +94,13 @@ pub trait Plugin: Send + Sync {
         Ok(())
     }
 
+    /// Command that rebuilds a project after its artifacts were cleaned, run in `project_root`
+    ///
+    /// Used by `clean --rebuild`; `[plugins.rebuild]` in the config overrides it.
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        None
+    }
+
     /// Priority when multiple plugins match (higher = preferred)
     fn priority(&self) -> u8 {
         50
@@ -73,9 +114,21 @@ pub trait Plugin: Send + Sync {
 
 /// Calculate directory size using parallel walk
 pub fn default_calculate_size(path: &Path) -> Result<u64> {
+    calculate_size_with_mode(path, SizeMode::Apparent)
+}
+
+/// Calculate directory size, measuring each file with `mode`
+pub fn calculate_size_with_mode(path: &Path, mode: SizeMode) -> Result<u64> {
+    calculate_size_until(path, mode, &|| false)
+}
+
+/// [`calculate_size_with_mode`] that stops walking once `stop` returns true,
+/// returning the size counted so far
+pub fn calculate_size_until(path: &Path, mode: SizeMode, stop: &dyn Fn() -> bool) -> Result<u64> {
     use rayon::prelude::*;
     use walkdir::WalkDir;
 
+    let path = &*crate::core::extended_path(path);
     if !path.exists() {
         return Ok(0);
     }
@@ -83,6 +136,7 @@ pub fn default_calculate_size(path: &Path) -> Result<u64> {
     // For small directories, use simple walk
     let entries: Vec<_> = WalkDir::new(path)
         .into_iter()
+        .take_while(|_| !stop())
         .filter_map(|e| e.ok())
         .collect();
 
@@ -90,22 +144,128 @@ pub fn default_calculate_size(path: &Path) -> Result<u64> {
         .par_iter()
         .filter_map(|entry| entry.metadata().ok())
         .filter(|m| m.is_file())
-        .map(|m| m.len())
+        .map(|m| mode.of(&m))
         .sum();
 
     Ok(size)
 }
 
+/// Allocated size of a directory, and how much of it deleting the directory won't free
+///
+/// Hard-linked files count once, like `du` does, and are shared when some of
+/// their links are outside `path`. Blocks cloned from or into other files are
+/// found with [`cloned_bytes`](crate::core::cloned_bytes) where supported.
+pub fn disk_usage(path: &Path) -> Result<(u64, u64)> {
+    disk_usage_until(path, &|| false)
+}
+
+/// [`disk_usage`] that stops walking once `stop` returns true, returning
+/// what was counted so far
+pub fn disk_usage_until(path: &Path, stop: &dyn Fn() -> bool) -> Result<(u64, u64)> {
+    use rayon::prelude::*;
+    use std::collections::HashMap;
+    use walkdir::WalkDir;
+
+    let path = &*crate::core::extended_path(path);
+    if !path.exists() {
+        return Ok((0, 0));
+    }
+
+    let files: Vec<_> = WalkDir::new(path)
+        .into_iter()
+        .take_while(|_| !stop())
+        .filter_map(|e| e.ok())
+        .filter_map(|e| Some((e.metadata().ok()?, e.into_path())))
+        .filter(|(m, _)| m.is_file())
+        .collect();
+
+    // (size, shared) of singly linked files; multiply linked ones are settled below
+    let per_file: Vec<_> = files
+        .par_iter()
+        .map(|(meta, file)| {
+            let size = SizeMode::Disk.of(meta);
+            let cloned = crate::core::cloned_bytes(file, meta).unwrap_or(0);
+            (size, cloned)
+        })
+        .collect();
+
+    let (mut size, mut shared) = (0u64, 0u64);
+    let mut linked: HashMap<(u64, u64), (u64, u64, u64)> = HashMap::new();
+    for ((meta, _), (file_size, cloned)) in files.iter().zip(per_file) {
+        match hard_link_key(meta) {
+            Some((key, links)) => {
+                let entry = linked.entry(key).or_insert((links, 0, file_size));
+                entry.1 += 1;
+            }
+            None => {
+                size += file_size;
+                shared += cloned;
+            }
+        }
+    }
+    for (links, seen, file_size) in linked.into_values() {
+        size += file_size;
+        if seen < links {
+            shared += file_size;
+        }
+    }
+
+    Ok((size, shared))
+}
+
+/// Inode identity and link count of a file with more than one hard link
+fn hard_link_key(metadata: &std::fs::Metadata) -> Option<((u64, u64), u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        (metadata.nlink() > 1).then(|| ((metadata.dev(), metadata.ino()), metadata.nlink()))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/// Estimate directory size from its top-level entries, without recursing
+///
+/// Files count at their measured size and subdirectories only at the size of
+/// their own entry, so this is a lower bound that costs one stat per entry.
+/// Returns the estimate and the number of top-level entries.
+pub fn estimate_size(path: &Path, mode: SizeMode) -> Result<(u64, u64)> {
+    if !path.exists() {
+        return Ok((0, 0));
+    }
+
+    let (mut size, mut count) = (0, 0);
+    for entry in std::fs::read_dir(path)?.filter_map(|e| e.ok()) {
+        count += 1;
+        if let Ok(meta) = entry.metadata() {
+            size += mode.of(&meta);
+        }
+    }
+
+    Ok((size, count))
+}
+
 /// Count files in a directory
 pub fn count_files(path: &Path) -> Result<u64> {
+    count_files_until(path, &|| false)
+}
+
+/// [`count_files`] that stops walking once `stop` returns true, returning
+/// the count so far
+pub fn count_files_until(path: &Path, stop: &dyn Fn() -> bool) -> Result<u64> {
     use walkdir::WalkDir;
 
+    let path = &*crate::core::extended_path(path);
     if !path.exists() {
         return Ok(0);
     }
 
     let count = WalkDir::new(path)
         .into_iter()
+        .take_while(|_| !stop())
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
         .count() as u64;
@@ -124,5 +284,12 @@ pub fn builtin_plugins() -> Vec<Box<dyn Plugin>> {
         Box::new(GradlePlugin),
         Box::new(DotNetPlugin),
         Box::new(SwiftPlugin),
+        Box::new(FlutterPlugin),
+        Box::new(TerraformPlugin),
+        Box::new(ZigPlugin),
+        Box::new(ElixirPlugin),
+        Box::new(HaskellPlugin),
+        Box::new(PhpPlugin),
+        Box::new(RubyPlugin),
     ]
 }
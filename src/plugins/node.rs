@@ -2,7 +2,7 @@
 
 use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
 use crate::error::Result;
-use crate::plugins::Plugin;
+use crate::plugins::{activity, Plugin};
 use std::path::{Path, PathBuf};
 
 /// Plugin for Node.js ecosystem (npm, yarn, pnpm, bun)
@@ -90,33 +90,43 @@ impl Plugin for NodePlugin {
             });
         }
 
-        // .next (Next.js)
-        let next_dir = project_root.join(".next");
-        if next_dir.exists() {
-            artifacts.push(Artifact {
-                path: next_dir,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::restorable("npm run build"),
-            });
+        let manifest = read_manifest(project_root);
+        let framework = Framework::detect(project_root, manifest.as_ref());
+
+        // Framework caches (.next, .nuxt, .svelte-kit, ...): rebuilt from
+        // scratch by the next dev server or build
+        for name in FRAMEWORK_CACHES {
+            let path = project_root.join(name);
+            if path.is_dir() {
+                artifacts.push(Artifact {
+                    path,
+                    kind: ArtifactKind::Cache,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata: ArtifactMetadata::restorable("npm run build"),
+                });
+            }
         }
 
-        // .nuxt (Nuxt.js)
-        let nuxt_dir = project_root.join(".nuxt");
-        if nuxt_dir.exists() {
-            artifacts.push(Artifact {
-                path: nuxt_dir,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::restorable("npm run build"),
-            });
+        // Output directories only the detected framework writes, e.g. Next.js'
+        // static export in out/ or Gatsby's public/
+        for name in framework.map_or(&[][..], Framework::output_dirs) {
+            let path = project_root.join(name);
+            if path.is_dir() {
+                artifacts.push(Artifact {
+                    path,
+                    kind: ArtifactKind::BuildOutput,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata: ArtifactMetadata::restorable("npm run build"),
+                });
+            }
         }
 
-        // dist folder
+        // dist folder - an app bundle when a framework builds into it, but
+        // what gets published when the package points its entry points there
         let dist = project_root.join("dist");
         if dist.exists() && dist.is_dir() {
             artifacts.push(Artifact {
@@ -125,7 +135,7 @@ impl Plugin for NodePlugin {
                 size: 0,
                 file_count: 0,
                 age: None,
-                metadata: ArtifactMetadata::restorable("npm run build"),
+                metadata: output_metadata("dist", framework, manifest.as_ref()),
             });
         }
 
@@ -142,50 +152,11 @@ impl Plugin for NodePlugin {
                     size: 0,
                     file_count: 0,
                     age: None,
-                    metadata: ArtifactMetadata::restorable("npm run build"),
+                    metadata: output_metadata("build", framework, manifest.as_ref()),
                 });
             }
         }
 
-        // .cache (various tools)
-        let cache = project_root.join(".cache");
-        if cache.exists() {
-            artifacts.push(Artifact {
-                path: cache,
-                kind: ArtifactKind::Cache,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::default(),
-            });
-        }
-
-        // .parcel-cache
-        let parcel_cache = project_root.join(".parcel-cache");
-        if parcel_cache.exists() {
-            artifacts.push(Artifact {
-                path: parcel_cache,
-                kind: ArtifactKind::Cache,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::default(),
-            });
-        }
-
-        // .turbo (Turborepo)
-        let turbo = project_root.join(".turbo");
-        if turbo.exists() {
-            artifacts.push(Artifact {
-                path: turbo,
-                kind: ArtifactKind::Cache,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::default(),
-            });
-        }
-
         // coverage (test coverage)
         let coverage = project_root.join("coverage");
         if coverage.exists() {
@@ -225,33 +196,41 @@ impl Plugin for NodePlugin {
             });
         }
 
-        // .svelte-kit
-        let svelte_kit = project_root.join(".svelte-kit");
-        if svelte_kit.exists() {
-            artifacts.push(Artifact {
-                path: svelte_kit,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::restorable("npm run build"),
-            });
+        Ok(artifacts)
+    }
+
+    fn find_orphans(&self, path: &Path) -> Vec<Artifact> {
+        // Only called when there is no package.json here (no Node project),
+        // but Deno can vendor npm packages into node_modules too
+        let node_modules = path.join("node_modules");
+        if !node_modules.is_dir()
+            || path.join("package.json").exists()
+            || path.join("deno.json").exists()
+            || path.join("deno.jsonc").exists()
+        {
+            return Vec::new();
         }
 
-        // out (Next.js static export)
-        let out = project_root.join("out");
-        if out.exists() && project_root.join("next.config.js").exists() {
-            artifacts.push(Artifact {
-                path: out,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::restorable("npm run build"),
-            });
+        // <prefix>/lib/node_modules is where global packages (npm itself) live
+        if path.file_name().is_some_and(|name| name == "lib") {
+            return Vec::new();
         }
 
-        Ok(artifacts)
+        let mut metadata = ArtifactMetadata::default();
+        metadata.extra.insert("orphaned".into(), "true".into());
+        vec![Artifact {
+            path: node_modules,
+            kind: ArtifactKind::Dependencies,
+            size: 0,
+            file_count: 0,
+            age: None,
+            metadata,
+        }]
+    }
+
+    fn pre_clean(&self, artifact: &Artifact) -> Result<()> {
+        let project_root = artifact.path.parent().unwrap_or(&artifact.path);
+        activity::ensure_not_in_use(&artifact.path, project_root, NODE_TOOLS)
     }
 
     fn cleanable_dirs(&self) -> &[&'static str] {
@@ -259,21 +238,168 @@ impl Plugin for NodePlugin {
             "node_modules",
             ".next",
             ".nuxt",
+            ".svelte-kit",
+            ".angular",
+            ".astro",
             ".cache",
             ".parcel-cache",
             ".turbo",
             "coverage",
             ".nyc_output",
             "storybook-static",
-            ".svelte-kit",
         ]
     }
 
+    fn rebuild_command(&self, project_root: &Path) -> Option<String> {
+        Some(self.restore_command(project_root))
+    }
+
     fn priority(&self) -> u8 {
         50
     }
 }
 
+/// Processes that read `node_modules` or write build caches while running
+const NODE_TOOLS: &[&str] = &["node", "npm", "npx", "yarn", "pnpm", "bun", "deno"];
+
+/// Cache directories written by frameworks and bundlers, whichever one is in use
+const FRAMEWORK_CACHES: &[&str] = &[
+    ".next",
+    ".nuxt",
+    ".svelte-kit",
+    ".angular",
+    ".astro",
+    ".cache",
+    ".parcel-cache",
+    ".turbo",
+];
+
+/// package.json fields that name the files a package publishes
+const PUBLISH_FIELDS: &[&str] = &["main", "module", "types", "typings", "exports", "bin", "files"];
+
+/// Web framework or bundler that builds the project
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framework {
+    Next,
+    Nuxt,
+    SvelteKit,
+    Astro,
+    Gatsby,
+    Angular,
+    Vite,
+}
+
+impl Framework {
+    /// Checked in order: the meta-frameworks before Vite, which they build on
+    const ALL: [Framework; 7] = [
+        Self::Next,
+        Self::Nuxt,
+        Self::SvelteKit,
+        Self::Astro,
+        Self::Gatsby,
+        Self::Angular,
+        Self::Vite,
+    ];
+
+    /// Detect from a config file, or else a dependency in package.json
+    /// (Next.js and SvelteKit projects don't need a config file)
+    fn detect(root: &Path, manifest: Option<&serde_json::Value>) -> Option<Self> {
+        let has_config = |f: &Self| {
+            f.config_files().iter().any(|name| root.join(name).is_file())
+        };
+        let has_dependency = |f: &Self| {
+            manifest.is_some_and(|m| {
+                ["dependencies", "devDependencies"]
+                    .iter()
+                    .any(|field| m[field].get(f.package()).is_some())
+            })
+        };
+
+        Self::ALL
+            .into_iter()
+            .find(has_config)
+            .or_else(|| Self::ALL.into_iter().find(has_dependency))
+    }
+
+    fn config_files(self) -> &'static [&'static str] {
+        match self {
+            Self::Next => &["next.config.js", "next.config.mjs", "next.config.cjs", "next.config.ts"],
+            Self::Nuxt => &["nuxt.config.js", "nuxt.config.mjs", "nuxt.config.ts"],
+            // svelte.config.* alone is also used by plain Svelte on Vite
+            Self::SvelteKit => &[],
+            Self::Astro => &["astro.config.mjs", "astro.config.js", "astro.config.ts"],
+            Self::Gatsby => &["gatsby-config.js", "gatsby-config.mjs", "gatsby-config.ts"],
+            Self::Angular => &["angular.json"],
+            Self::Vite => &["vite.config.js", "vite.config.mjs", "vite.config.cjs", "vite.config.ts"],
+        }
+    }
+
+    fn package(self) -> &'static str {
+        match self {
+            Self::Next => "next",
+            Self::Nuxt => "nuxt",
+            Self::SvelteKit => "@sveltejs/kit",
+            Self::Astro => "astro",
+            Self::Gatsby => "gatsby",
+            Self::Angular => "@angular/core",
+            Self::Vite => "vite",
+        }
+    }
+
+    /// Build output directories that mean something else without this framework
+    fn output_dirs(self) -> &'static [&'static str] {
+        match self {
+            Self::Next => &["out"],
+            Self::Nuxt => &[".output"],
+            Self::Gatsby => &["public"],
+            _ => &[],
+        }
+    }
+
+    /// Whether `dir` holds the app this framework builds rather than a published package
+    fn builds_into(self, dir: &str) -> bool {
+        match self {
+            Self::Astro | Self::Angular | Self::Vite => dir == "dist",
+            Self::SvelteKit => dir == "build",
+            _ => false,
+        }
+    }
+}
+
+/// Parsed package.json, if it is readable
+fn read_manifest(root: &Path) -> Option<serde_json::Value> {
+    let text = std::fs::read_to_string(root.join("package.json")).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// Metadata for a `dist/` or `build/` directory; when package.json publishes
+/// from it, it is marked as a publish output so cleaning needs confirmation
+fn output_metadata(dir: &str, framework: Option<Framework>, manifest: Option<&serde_json::Value>) -> ArtifactMetadata {
+    let mut metadata = ArtifactMetadata::restorable("npm run build");
+    let app_build = framework.is_some_and(|f| f.builds_into(dir));
+    if !app_build && manifest.is_some_and(|m| publishes_from(m, dir)) {
+        metadata.extra.insert("publish_output".into(), "true".into());
+    }
+    metadata
+}
+
+/// Whether the package's entry points or `files` list point into `dir`
+fn publishes_from(manifest: &serde_json::Value, dir: &str) -> bool {
+    fn mentions(value: &serde_json::Value, dir: &str) -> bool {
+        match value {
+            serde_json::Value::String(path) => {
+                let path = path.trim_start_matches("./");
+                path == dir || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+            }
+            serde_json::Value::Array(values) => values.iter().any(|v| mentions(v, dir)),
+            serde_json::Value::Object(map) => map.values().any(|v| mentions(v, dir)),
+            _ => false,
+        }
+    }
+
+    PUBLISH_FIELDS.iter().any(|field| mentions(&manifest[field], dir))
+}
+
 impl NodePlugin {
     fn restore_command(&self, path: &Path) -> String {
         if path.join("bun.lockb").exists() {
@@ -352,6 +478,87 @@ mod tests {
         assert!(artifacts.iter().any(|a| a.name() == ".next"));
     }
 
+    #[test]
+    fn test_framework_outputs() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(&temp);
+        for dir in [".next", "out", "public", ".svelte-kit"] {
+            std::fs::create_dir(temp.path().join(dir)).unwrap();
+        }
+
+        let plugin = NodePlugin;
+        let names = |plugin: &NodePlugin| -> Vec<String> {
+            let mut names: Vec<_> = plugin
+                .find_artifacts(temp.path())
+                .unwrap()
+                .iter()
+                .map(|a| a.name().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        // out/ and public/ are only output for the framework that writes them
+        assert_eq!(names(&plugin), [".next", ".svelte-kit"]);
+
+        std::fs::write(temp.path().join("next.config.mjs"), "").unwrap();
+        assert_eq!(names(&plugin), [".next", ".svelte-kit", "out"]);
+
+        let artifacts = plugin.find_artifacts(temp.path()).unwrap();
+        let next = artifacts.iter().find(|a| a.name() == ".next").unwrap();
+        assert_eq!(next.safety(), crate::core::ArtifactSafety::AlwaysSafe);
+
+        let manifest = serde_json::json!({"devDependencies": {"gatsby": "^5"}});
+        assert_eq!(Framework::detect(temp.path(), Some(&manifest)), Some(Framework::Next));
+        std::fs::remove_file(temp.path().join("next.config.mjs")).unwrap();
+        assert_eq!(Framework::detect(temp.path(), Some(&manifest)), Some(Framework::Gatsby));
+    }
+
+    #[test]
+    fn test_publish_output_dist() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "lib", "main": "./dist/index.js", "files": ["dist"]}"#,
+        )
+        .unwrap();
+        std::fs::create_dir(temp.path().join("dist")).unwrap();
+
+        let plugin = NodePlugin;
+        let dist = plugin.find_artifacts(temp.path()).unwrap().remove(0);
+        assert!(dist.is_publish_output());
+        assert_eq!(dist.safety(), crate::core::ArtifactSafety::RequiresConfirmation);
+
+        // The same dist/ is just the app bundle when Vite builds into it
+        std::fs::write(temp.path().join("vite.config.ts"), "").unwrap();
+        let dist = plugin.find_artifacts(temp.path()).unwrap().remove(0);
+        assert!(!dist.is_publish_output());
+        assert_eq!(dist.safety(), crate::core::ArtifactSafety::SafeIfGitClean);
+
+        let manifest = serde_json::json!({"exports": {".": {"import": "./distribution/index.mjs"}}});
+        assert!(!publishes_from(&manifest, "dist"));
+    }
+
+    #[test]
+    fn test_find_orphaned_node_modules() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("node_modules")).unwrap();
+
+        let plugin = NodePlugin;
+        let orphans = plugin.find_orphans(temp.path());
+        assert_eq!(orphans.len(), 1);
+        assert!(orphans[0].is_orphaned());
+        assert!(orphans[0].is_safe_to_clean());
+
+        // With a package.json it's a regular project artifact
+        setup_node_project(&temp);
+        assert!(plugin.find_orphans(temp.path()).is_empty());
+
+        // Global install prefixes are not orphans
+        let lib = temp.path().join("usr/lib");
+        std::fs::create_dir_all(lib.join("node_modules/npm")).unwrap();
+        assert!(plugin.find_orphans(&lib).is_empty());
+    }
+
     #[test]
     fn test_no_artifacts_without_dirs() {
         let temp = TempDir::new().unwrap();
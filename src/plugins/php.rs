@@ -0,0 +1,140 @@
+//! PHP plugin
+//!
+//! Composer installs dependencies into `vendor`, pinned by `composer.lock`.
+//! Laravel apps are Composer projects with an `artisan` script.
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::{NodePlugin, Plugin};
+use std::path::Path;
+
+/// Plugin for PHP (Composer) projects
+pub struct PhpPlugin;
+
+impl Plugin for PhpPlugin {
+    fn id(&self) -> &'static str {
+        "php"
+    }
+
+    fn name(&self) -> &'static str {
+        "PHP"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::PhpComposer, ProjectKind::PhpLaravel]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![
+            ProjectMarker {
+                indicator: MarkerKind::File("composer.json"),
+                kind: ProjectKind::PhpComposer,
+                priority: 50,
+            },
+            ProjectMarker {
+                indicator: MarkerKind::File("artisan"),
+                kind: ProjectKind::PhpLaravel,
+                priority: 55,
+            },
+        ]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        if !path.join("composer.json").is_file() {
+            return None;
+        }
+        if path.join("artisan").is_file() {
+            Some(ProjectKind::PhpLaravel)
+        } else {
+            Some(ProjectKind::PhpComposer)
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        // Front-end assets built with npm/Vite live in the same project
+        let mut artifacts = if project_root.join("package.json").is_file() {
+            NodePlugin.find_artifacts(project_root)?
+        } else {
+            Vec::new()
+        };
+
+        // Installed packages
+        let vendor = project_root.join("vendor");
+        if vendor.is_dir() {
+            let lockfile = project_root.join("composer.lock");
+            let mut metadata = ArtifactMetadata::restorable("composer install");
+            if lockfile.is_file() {
+                metadata = metadata.with_lockfile(lockfile);
+            }
+            metadata.extra.insert("vendored".into(), "true".into());
+
+            artifacts.push(Artifact {
+                path: vendor,
+                kind: ArtifactKind::Dependencies,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata,
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn cleanable_dirs(&self) -> &[&'static str] {
+        &["vendor"]
+    }
+
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("composer install".into())
+    }
+
+    fn priority(&self) -> u8 {
+        // Above Node: front-end tooling shouldn't hide vendor/
+        55
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_php() {
+        let temp = TempDir::new().unwrap();
+        let plugin = PhpPlugin;
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::write(temp.path().join("composer.json"), "{}").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::PhpComposer));
+
+        std::fs::write(temp.path().join("artisan"), "#!/usr/bin/env php").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::PhpLaravel));
+    }
+
+    #[test]
+    fn test_find_vendor() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("composer.json"), "{}").unwrap();
+        std::fs::write(temp.path().join("composer.lock"), "{}").unwrap();
+        std::fs::create_dir(temp.path().join("vendor")).unwrap();
+
+        let artifacts = PhpPlugin.find_artifacts(temp.path()).unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].kind, ArtifactKind::Dependencies);
+        assert!(artifacts[0].is_vendored());
+        assert!(artifacts[0].metadata.lockfile.is_some());
+
+        // A Laravel app with Vite: vendor and node_modules, claimed over Node
+        std::fs::write(temp.path().join("package.json"), "{}").unwrap();
+        std::fs::create_dir(temp.path().join("node_modules")).unwrap();
+        let artifacts = PhpPlugin.find_artifacts(temp.path()).unwrap();
+        let names: Vec<_> = artifacts.iter().map(|a| a.name()).collect();
+        assert!(names.contains(&"vendor") && names.contains(&"node_modules"));
+
+        let registry = crate::plugins::PluginRegistry::with_builtins();
+        let (_, plugin) = registry.detect_project(temp.path()).unwrap();
+        assert_eq!(plugin.id(), "php");
+    }
+}
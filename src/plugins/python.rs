@@ -109,19 +109,27 @@ impl Plugin for PythonPlugin {
         for venv_name in &[".venv", "venv", "env", ".env"] {
             let venv_path = project_root.join(venv_name);
             if venv_path.exists() && is_venv(&venv_path) {
+                let mut metadata = ArtifactMetadata {
+                    restorable: true,
+                    restore_command: Some(self.restore_command(project_root)),
+                    lockfile: self.find_lockfile(project_root),
+                    restore_time_estimate: Some(30),
+                    ..Default::default()
+                };
+                if let Some(interpreter) = missing_base_interpreter(&venv_path) {
+                    metadata.extra.insert(
+                        "broken".into(),
+                        format!("base interpreter {} no longer exists", interpreter.display()),
+                    );
+                }
+
                 artifacts.push(Artifact {
                     path: venv_path,
                     kind: ArtifactKind::VirtualEnv,
                     size: 0,
                     file_count: 0,
                     age: None,
-                    metadata: ArtifactMetadata {
-                        restorable: true,
-                        restore_command: Some(self.restore_command(project_root)),
-                        lockfile: self.find_lockfile(project_root),
-                        restore_time_estimate: Some(30),
-                        ..Default::default()
-                    },
+                    metadata,
                 });
                 break; // Usually only one venv
             }
@@ -232,7 +240,7 @@ impl Plugin for PythonPlugin {
                 size: 0,
                 file_count: 0,
                 age: None,
-                metadata: ArtifactMetadata::default(),
+                metadata: ArtifactMetadata::restorable("coverage html"),
             });
         }
 
@@ -266,6 +274,10 @@ impl Plugin for PythonPlugin {
         ]
     }
 
+    fn rebuild_command(&self, project_root: &Path) -> Option<String> {
+        Some(self.restore_command(project_root))
+    }
+
     fn priority(&self) -> u8 {
         50
     }
@@ -334,7 +346,7 @@ impl PythonPlugin {
                                 size: 0,
                                 file_count: 0,
                                 age: None,
-                                metadata: ArtifactMetadata::default(),
+                                metadata: ArtifactMetadata::restorable("pip install -e ."),
                             });
                         }
                     }
@@ -347,8 +359,8 @@ impl PythonPlugin {
 
 /// Check if a directory is a Python virtual environment
 fn is_venv(path: &Path) -> bool {
-    // Check for pyvenv.cfg (standard venv marker)
-    if path.join("pyvenv.cfg").exists() {
+    // Check for pyvenv.cfg (standard venv marker) or conda-meta (conda env)
+    if path.join("pyvenv.cfg").exists() || path.join("conda-meta").is_dir() {
         return true;
     }
 
@@ -360,6 +372,45 @@ fn is_venv(path: &Path) -> bool {
     has_python
 }
 
+/// Interpreter a venv or conda env runs on, if it no longer resolves
+///
+/// Checks the env's own `bin/python` (`Scripts\python.exe` on Windows), which
+/// a venv links to its base install, and reports where a dangling link
+/// pointed. Without one, falls back to the `home` directory in `pyvenv.cfg`.
+/// A conda env carries its own interpreter, so only a missing one counts.
+/// Returns `None` when the interpreter resolves or the env doesn't say.
+fn missing_base_interpreter(env: &Path) -> Option<PathBuf> {
+    let candidates: &[&str] = if cfg!(windows) {
+        &["Scripts/python.exe"]
+    } else {
+        &["bin/python", "bin/python3"]
+    };
+    // symlink_metadata still sees a link whose target is gone
+    if let Some(own) = candidates.iter().map(|c| env.join(c)).find(|p| p.symlink_metadata().is_ok()) {
+        if own.exists() {
+            return None;
+        }
+        let mut target = own;
+        for _ in 0..8 {
+            let Ok(next) = std::fs::read_link(&target) else {
+                break;
+            };
+            target = target.parent().map_or_else(|| next.clone(), |dir| dir.join(&next));
+        }
+        return Some(target);
+    }
+    if env.join("conda-meta").is_dir() {
+        return Some(env.join(candidates[0]));
+    }
+
+    let cfg = std::fs::read_to_string(env.join("pyvenv.cfg")).ok()?;
+    let home = cfg.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        (k.trim() == "home").then(|| PathBuf::from(v.trim()))
+    })?;
+    (!home.exists()).then_some(home)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -391,6 +442,68 @@ mod tests {
         assert!(is_venv(temp.path()));
     }
 
+    #[test]
+    fn test_broken_venv_is_flagged() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("requirements.txt"), "").unwrap();
+        let venv = temp.path().join(".venv");
+        std::fs::create_dir(&venv).unwrap();
+
+        // No interpreter of its own: the recorded home decides
+        let home = temp.path().join("python/bin");
+        std::fs::create_dir_all(&home).unwrap();
+        std::fs::write(venv.join("pyvenv.cfg"), format!("home = {}\n", home.display())).unwrap();
+        assert_eq!(missing_base_interpreter(&venv), None);
+        std::fs::remove_dir(&home).unwrap();
+        assert_eq!(missing_base_interpreter(&venv), Some(home.clone()));
+
+        let artifacts = PythonPlugin.find_artifacts(temp.path()).unwrap();
+        let venv = artifacts.iter().find(|a| a.name() == ".venv").unwrap();
+        assert!(venv.broken_reason().is_some());
+        assert!(venv.is_safe_to_clean());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_venv_interpreter_link() {
+        let temp = TempDir::new().unwrap();
+        let venv = temp.path().join(".venv");
+        std::fs::create_dir_all(venv.join("bin")).unwrap();
+        let base = temp.path().join("Cellar/python@3.11/bin/python3.11");
+        std::fs::create_dir_all(base.parent().unwrap()).unwrap();
+        std::fs::write(&base, "").unwrap();
+        std::os::unix::fs::symlink(&base, venv.join("bin/python3.11")).unwrap();
+        std::os::unix::fs::symlink("python3.11", venv.join("bin/python")).unwrap();
+
+        // The venv's own link wins over a stale `executable` or `home`
+        std::fs::write(
+            venv.join("pyvenv.cfg"),
+            "home = /nonexistent/bin\nexecutable = /nonexistent/bin/python3.11\n",
+        )
+        .unwrap();
+        assert_eq!(missing_base_interpreter(&venv), None);
+
+        // Removed by an upgrade: report where the link chain ended
+        std::fs::remove_file(&base).unwrap();
+        assert_eq!(missing_base_interpreter(&venv), Some(base));
+    }
+
+    #[test]
+    fn test_conda_env_interpreter() {
+        let temp = TempDir::new().unwrap();
+        let env = temp.path().join("env");
+        std::fs::create_dir_all(env.join("conda-meta")).unwrap();
+        assert!(is_venv(&env));
+
+        let python = if cfg!(windows) { "Scripts/python.exe" } else { "bin/python" };
+        std::fs::create_dir_all(env.join(python).parent().unwrap()).unwrap();
+        std::fs::write(env.join(python), "").unwrap();
+        assert_eq!(missing_base_interpreter(&env), None);
+
+        std::fs::remove_file(env.join(python)).unwrap();
+        assert_eq!(missing_base_interpreter(&env), Some(env.join(python)));
+    }
+
     #[test]
     fn test_find_artifacts() {
         let temp = TempDir::new().unwrap();
@@ -1,7 +1,7 @@
 //! Plugin registry - central management for all plugins
 
 use super::Plugin;
-use crate::core::{ProjectKind, ProjectMarker};
+use crate::core::{Artifact, ProjectKind, ProjectMarker};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::path::Path;
@@ -32,9 +32,28 @@ impl PluginRegistry {
 
     /// Create registry with all built-in plugins
     pub fn with_builtins() -> Self {
+        Self::from_plugins(super::builtin_plugins())
+    }
+
+    /// Create a registry holding `plugins`, registered in order
+    ///
+    /// Combine a subset of [`builtin_plugins`](super::builtin_plugins) with
+    /// your own [`Plugin`] implementations:
+    ///
+    /// ```
+    /// use null_e::plugins::{builtin_plugins, PluginRegistry};
+    ///
+    /// let plugins = builtin_plugins()
+    ///     .into_iter()
+    ///     .filter(|p| matches!(p.id(), "node" | "rust"))
+    ///     .collect();
+    /// let registry = PluginRegistry::from_plugins(plugins);
+    /// assert_eq!(registry.len(), 2);
+    /// ```
+    pub fn from_plugins(plugins: Vec<Box<dyn Plugin>>) -> Self {
         let registry = Self::new();
 
-        for plugin in super::builtin_plugins() {
+        for plugin in plugins {
             registry.register(Arc::from(plugin));
         }
 
@@ -42,6 +61,12 @@ impl PluginRegistry {
     }
 
     /// Register a new plugin
+    ///
+    /// A `Box<dyn Plugin>` converts with `.into()`. When several plugins
+    /// detect the same directory, the highest [`Plugin::priority`] wins and
+    /// ties go to the plugin registered first; orphan detection and
+    /// cleanable-directory lookups also follow registration order. To
+    /// override a builtin, register before it or use a higher priority.
     pub fn register(&self, plugin: Arc<dyn Plugin>) {
         // Add markers for fast lookup
         for marker in plugin.markers() {
@@ -99,6 +124,8 @@ impl PluginRegistry {
     }
 
     /// Detect project type at path
+    ///
+    /// Highest priority first, then registration order.
     pub fn detect_project(&self, path: &Path) -> Option<(ProjectKind, Arc<dyn Plugin>)> {
         let plugins = self.plugins.read();
 
@@ -114,6 +141,20 @@ impl PluginRegistry {
         candidates.into_iter().next().map(|(k, p, _)| (k, p))
     }
 
+    /// Find orphaned artifacts in a directory that isn't a project
+    ///
+    /// Returns the first plugin with findings, along with the project kind
+    /// the leftovers are reported under.
+    pub fn find_orphans(&self, path: &Path) -> Option<(ProjectKind, Arc<dyn Plugin>, Vec<Artifact>)> {
+        let plugins = self.plugins.read();
+
+        plugins.iter().find_map(|p| {
+            let kind = *p.supported_kinds().first()?;
+            let orphans = p.find_orphans(path);
+            (!orphans.is_empty()).then(|| (kind, Arc::clone(p), orphans))
+        })
+    }
+
     /// Get all unique cleanable directory names
     pub fn all_cleanable_dir_names(&self) -> Vec<&'static str> {
         self.cleanable_dirs.read().keys().copied().collect()
@@ -124,6 +165,16 @@ impl PluginRegistry {
         self.plugins.read().iter().find(|p| p.id() == id).cloned()
     }
 
+    /// The marker of plugin `id` that identifies a project at `path`, if any
+    pub fn matching_marker(&self, id: &str, path: &Path) -> Option<ProjectMarker> {
+        self.markers
+            .read()
+            .iter()
+            .filter(|(marker, plugin)| plugin.id() == id && marker.indicator.matches(path))
+            .max_by_key(|(marker, _)| marker.priority)
+            .map(|(marker, _)| marker.clone())
+    }
+
     /// Get number of registered plugins
     pub fn len(&self) -> usize {
         self.plugins.read().len()
@@ -161,6 +212,85 @@ mod tests {
         assert!(!registry.is_cleanable_dir("src"));
     }
 
+    /// Plugin claiming every directory, to pit priorities against each other
+    struct Greedy(&'static str, u8);
+
+    impl Plugin for Greedy {
+        fn id(&self) -> &'static str {
+            self.0
+        }
+        fn name(&self) -> &'static str {
+            self.0
+        }
+        fn supported_kinds(&self) -> &[ProjectKind] {
+            &[ProjectKind::Custom(0)]
+        }
+        fn markers(&self) -> Vec<ProjectMarker> {
+            vec![]
+        }
+        fn detect(&self, _path: &Path) -> Option<ProjectKind> {
+            Some(ProjectKind::Custom(0))
+        }
+        fn find_artifacts(&self, _project_root: &Path) -> crate::error::Result<Vec<Artifact>> {
+            Ok(vec![])
+        }
+        fn priority(&self) -> u8 {
+            self.1
+        }
+    }
+
+    #[test]
+    fn test_detect_project_prefers_priority() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        // Rust (60) outranks Node (50) on a napi-rs style crate
+        std::fs::write(temp.path().join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(temp.path().join("package.json"), "{}").unwrap();
+        let registry = PluginRegistry::with_builtins();
+        let (kind, plugin) = registry.detect_project(temp.path()).unwrap();
+        assert_eq!((kind, plugin.id()), (ProjectKind::Rust, "rust"));
+
+        // Higher priority wins regardless of registration order; ties keep the first
+        let registry = PluginRegistry::new();
+        registry.register(Arc::new(Greedy("low", 10)));
+        registry.register(Arc::new(Greedy("first", 70)));
+        registry.register(Arc::new(Greedy("second", 70)));
+        let (_, plugin) = registry.detect_project(temp.path()).unwrap();
+        assert_eq!(plugin.id(), "first");
+    }
+
+    #[test]
+    fn test_from_plugins() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("package.json"), "{}").unwrap();
+
+        let mut plugins: Vec<Box<dyn Plugin>> = vec![Box::new(Greedy("custom", 50))];
+        plugins.extend(super::super::builtin_plugins().into_iter().filter(|p| p.id() == "node"));
+        let registry = PluginRegistry::from_plugins(plugins);
+        assert_eq!(registry.len(), 2);
+        assert!(registry.get_by_id("rust").is_none());
+
+        // Same priority as Node: registered first, so it wins
+        let (_, plugin) = registry.detect_project(temp.path()).unwrap();
+        assert_eq!(plugin.id(), "custom");
+
+        let boxed: Box<dyn Plugin> = Box::new(Greedy("late", 90));
+        registry.register(boxed.into());
+        let (_, plugin) = registry.detect_project(temp.path()).unwrap();
+        assert_eq!(plugin.id(), "late");
+    }
+
+    #[test]
+    fn test_matching_marker() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("Cargo.toml"), "[package]").unwrap();
+
+        let registry = PluginRegistry::with_builtins();
+        let marker = registry.matching_marker("rust", temp.path()).unwrap();
+        assert_eq!(marker.indicator.to_string(), "Cargo.toml");
+        assert!(registry.matching_marker("node", temp.path()).is_none());
+    }
+
     #[test]
     fn test_get_by_id() {
         let registry = PluginRegistry::with_builtins();
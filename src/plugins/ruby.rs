@@ -0,0 +1,135 @@
+//! Ruby plugin
+//!
+//! `bundle install --path vendor/bundle` (or `BUNDLE_PATH`) installs gems
+//! inside the project, pinned by `Gemfile.lock`. Rails apps are Bundler
+//! projects with `config/application.rb`. Elsewhere a Gemfile next to
+//! `package.json` is usually just tooling, so the JS project wins.
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::{NodePlugin, Plugin};
+use std::path::Path;
+
+/// Plugin for Ruby (Bundler) projects
+pub struct RubyPlugin;
+
+impl Plugin for RubyPlugin {
+    fn id(&self) -> &'static str {
+        "ruby"
+    }
+
+    fn name(&self) -> &'static str {
+        "Ruby"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::RubyBundler, ProjectKind::RubyRails]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![
+            ProjectMarker {
+                indicator: MarkerKind::File("Gemfile"),
+                kind: ProjectKind::RubyBundler,
+                priority: 50,
+            },
+            ProjectMarker {
+                indicator: MarkerKind::File("Gemfile.lock"),
+                kind: ProjectKind::RubyBundler,
+                priority: 45,
+            },
+        ]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        if !path.join("Gemfile").is_file() {
+            return None;
+        }
+        if path.join("config/application.rb").is_file() {
+            Some(ProjectKind::RubyRails)
+        } else if path.join("package.json").is_file() {
+            // A Gemfile for fastlane, CocoaPods or Jekyll in a JS project
+            None
+        } else {
+            Some(ProjectKind::RubyBundler)
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        // Front-end assets built with npm/Vite live in the same project
+        let mut artifacts = if project_root.join("package.json").is_file() {
+            NodePlugin.find_artifacts(project_root)?
+        } else {
+            Vec::new()
+        };
+
+        // Gems installed into the project
+        let bundle = project_root.join("vendor/bundle");
+        if bundle.is_dir() {
+            let lockfile = project_root.join("Gemfile.lock");
+            let mut metadata = ArtifactMetadata::restorable("bundle install");
+            if lockfile.is_file() {
+                metadata = metadata.with_lockfile(lockfile);
+            }
+            metadata.extra.insert("vendored".into(), "true".into());
+
+            artifacts.push(Artifact {
+                path: bundle,
+                kind: ArtifactKind::Dependencies,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata,
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("bundle install".into())
+    }
+
+    fn priority(&self) -> u8 {
+        // Above Node: front-end tooling shouldn't hide vendor/
+        55
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_ruby() {
+        let temp = TempDir::new().unwrap();
+        let plugin = RubyPlugin;
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::write(temp.path().join("Gemfile"), "source 'https://rubygems.org'").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::RubyBundler));
+
+        std::fs::write(temp.path().join("package.json"), "{}").unwrap();
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::create_dir(temp.path().join("config")).unwrap();
+        std::fs::write(temp.path().join("config/application.rb"), "").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::RubyRails));
+    }
+
+    #[test]
+    fn test_find_vendor_bundle() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("Gemfile"), "").unwrap();
+        std::fs::create_dir_all(temp.path().join("vendor/bundle/ruby")).unwrap();
+        std::fs::create_dir(temp.path().join("vendor/assets")).unwrap();
+
+        let artifacts = RubyPlugin.find_artifacts(temp.path()).unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert!(artifacts[0].path.ends_with("vendor/bundle"));
+        assert!(artifacts[0].is_vendored());
+        // No Gemfile.lock: reinstalling may resolve different versions
+        assert!(artifacts[0].metadata.lockfile.is_none());
+    }
+}
@@ -1,9 +1,9 @@
 //! Rust/Cargo plugin
 
 use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
-use crate::error::Result;
-use crate::plugins::Plugin;
-use std::path::Path;
+use crate::error::{DevSweepError, Result};
+use crate::plugins::{activity, Plugin};
+use std::path::{Path, PathBuf};
 
 /// Plugin for Rust/Cargo projects
 pub struct RustPlugin;
@@ -40,42 +40,225 @@ impl Plugin for RustPlugin {
     }
 
     fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
-        let mut artifacts = Vec::new();
-
-        // target directory - the BIG one for Rust
-        let target = project_root.join("target");
-        if target.exists() {
-            artifacts.push(Artifact {
-                path: target,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata {
-                    restorable: true,
-                    restore_command: Some("cargo build".into()),
-                    lockfile: Some(project_root.join("Cargo.lock")),
-                    restore_time_estimate: Some(60), // Rust builds can be slow
-                    ..Default::default()
-                },
+        find_artifacts_with(project_root, target_dir_env(), cargo_home())
+    }
+
+    fn pre_clean(&self, artifact: &Artifact) -> Result<()> {
+        if let Some(lock) = held_cargo_lock(&artifact.path) {
+            return Err(DevSweepError::InUse {
+                path: artifact.path.clone(),
+                reason: format!("a cargo build holds {}", lock.display()),
             });
         }
-
-        // debug artifacts in target/debug (if we want to be more granular)
-        // For now, we just clean the whole target directory
-
-        Ok(artifacts)
+        let project_root = artifact.path.parent().unwrap_or(&artifact.path);
+        activity::ensure_not_in_use(&artifact.path, project_root, CARGO_TOOLS)
     }
 
     fn cleanable_dirs(&self) -> &[&'static str] {
         &["target"]
     }
 
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("cargo build".into())
+    }
+
     fn priority(&self) -> u8 {
         60
     }
 }
 
+/// Processes that build into a project's target directory
+const CARGO_TOOLS: &[&str] = &["cargo", "rustc", "cargo-watch", "bacon"];
+
+/// The `.cargo-lock` of a build currently running in `target`, if any
+///
+/// Cargo holds a file lock on `<target>/<profile>/.cargo-lock` (or
+/// `<target>/<triple>/<profile>/.cargo-lock`) for the whole build.
+fn held_cargo_lock(target: &Path) -> Option<PathBuf> {
+    let subdirs = |dir: &Path| -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.is_dir())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    subdirs(target)
+        .into_iter()
+        .flat_map(|dir| {
+            let nested = subdirs(&dir);
+            std::iter::once(dir).chain(nested)
+        })
+        .map(|dir| dir.join(".cargo-lock"))
+        .find(|lock| {
+            std::fs::File::open(lock).is_ok_and(|file| {
+                matches!(file.try_lock(), Err(std::fs::TryLockError::WouldBlock))
+            })
+        })
+}
+
+impl RustPlugin {
+    /// Locate the target directory Cargo would use for this project
+    ///
+    /// Follows Cargo's precedence: `CARGO_TARGET_DIR` / `CARGO_BUILD_TARGET_DIR`,
+    /// then `build.target-dir` from the nearest `.cargo/config.toml` (or legacy
+    /// `.cargo/config`) walking up from the project, then `$CARGO_HOME/config.toml`,
+    /// and finally `<project>/target`.
+    pub fn resolve_target_dir(&self, project_root: &Path) -> PathBuf {
+        resolve_target_dir_with(project_root, target_dir_env(), cargo_home())
+    }
+}
+
+/// `CARGO_TARGET_DIR`, or its `CARGO_BUILD_TARGET_DIR` alias
+///
+/// Cargo resolves a relative override against where it was run from.
+fn target_dir_env() -> Option<PathBuf> {
+    let dir = std::env::var_os("CARGO_TARGET_DIR")
+        .or_else(|| std::env::var_os("CARGO_BUILD_TARGET_DIR"))
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)?;
+    if dir.is_absolute() {
+        return Some(dir);
+    }
+    Some(std::env::current_dir().map(|cwd| cwd.join(&dir)).unwrap_or(dir))
+}
+
+/// Find the root of the Cargo workspace a member crate belongs to
+///
+/// Walks up from the crate looking for a `Cargo.toml` with a `[workspace]`
+/// table that doesn't list the crate under `exclude`. Returns `None` for
+/// standalone crates and for the workspace root itself.
+pub fn workspace_root(project_root: &Path) -> Option<PathBuf> {
+    project_root.ancestors().skip(1).find_map(|dir| {
+        let content = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+        let value: toml::Value = toml::from_str(&content).ok()?;
+        let workspace = value.get("workspace")?;
+
+        let relative = project_root.strip_prefix(dir).ok()?;
+        let excluded = workspace
+            .get("exclude")
+            .and_then(|e| e.as_array())
+            .is_some_and(|list| {
+                list.iter()
+                    .filter_map(|e| e.as_str())
+                    .any(|e| relative.starts_with(e))
+            });
+
+        (!excluded).then(|| dir.to_path_buf())
+    })
+}
+
+fn find_artifacts_with(
+    project_root: &Path,
+    env_override: Option<PathBuf>,
+    cargo_home: Option<PathBuf>,
+) -> Result<Vec<Artifact>> {
+    let mut artifacts = Vec::new();
+
+    // Workspace members share the target directory of the workspace root
+    let workspace = workspace_root(project_root);
+    let owner = workspace.as_deref().unwrap_or(project_root);
+
+    // target directory - the BIG one for Rust
+    // (may be redirected out-of-tree by CARGO_TARGET_DIR or .cargo/config.toml)
+    let from_env = env_override.is_some();
+    let mut target = resolve_target_dir_with(owner, env_override, cargo_home);
+    if target.exists() {
+        let mut metadata = ArtifactMetadata {
+            restorable: true,
+            restore_command: Some("cargo build".into()),
+            lockfile: Some(owner.join("Cargo.lock")),
+            restore_time_estimate: Some(60), // Rust builds can be slow
+            ..Default::default()
+        };
+        if let Some(root) = &workspace {
+            metadata
+                .extra
+                .insert("workspace_root".into(), root.display().to_string());
+        }
+        let canonical_root = owner
+            .canonicalize()
+            .unwrap_or_else(|_| owner.to_path_buf());
+        let mut out_of_tree = false;
+        if let Ok(canonical_target) = target.canonicalize() {
+            if !canonical_target.starts_with(&canonical_root) {
+                metadata.extra.insert("out_of_tree".into(), "true".into());
+                target = canonical_target;
+                out_of_tree = true;
+            }
+        }
+        // Other projects may build into the same directory
+        if from_env || out_of_tree {
+            metadata.extra.insert("shared_target".into(), "true".into());
+        }
+
+        artifacts.push(Artifact {
+            path: target,
+            kind: ArtifactKind::BuildOutput,
+            size: 0,
+            file_count: 0,
+            age: None,
+            metadata,
+        });
+    }
+
+    // debug artifacts in target/debug (if we want to be more granular)
+    // For now, we just clean the whole target directory
+
+    Ok(artifacts)
+}
+
+fn resolve_target_dir_with(
+    project_root: &Path,
+    env_override: Option<PathBuf>,
+    cargo_home: Option<PathBuf>,
+) -> PathBuf {
+    if let Some(dir) = env_override {
+        return dir;
+    }
+
+    let ancestor_configs = project_root.ancestors().map(|dir| dir.join(".cargo"));
+    for cargo_dir in ancestor_configs.chain(cargo_home) {
+        if let Some(dir) = config_target_dir(&cargo_dir) {
+            return dir;
+        }
+    }
+
+    project_root.join("target")
+}
+
+/// Read `build.target-dir` from `<cargo_dir>/config.toml` or `<cargo_dir>/config`
+///
+/// Relative paths are resolved against the parent of the `.cargo` directory,
+/// matching Cargo's behaviour.
+fn config_target_dir(cargo_dir: &Path) -> Option<PathBuf> {
+    let config = ["config.toml", "config"]
+        .iter()
+        .map(|name| cargo_dir.join(name))
+        .find(|p| p.is_file())?;
+
+    let content = std::fs::read_to_string(&config).ok()?;
+    let value: toml::Value = toml::from_str(&content).ok()?;
+    let target_dir = value.get("build")?.get("target-dir")?.as_str()?;
+
+    let target_dir = PathBuf::from(target_dir);
+    if target_dir.is_absolute() {
+        Some(target_dir)
+    } else {
+        Some(cargo_dir.parent().unwrap_or(cargo_dir).join(target_dir))
+    }
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,11 +294,120 @@ version = "0.1.0"
         std::fs::write(temp.path().join("Cargo.toml"), "[package]").unwrap();
         std::fs::create_dir(temp.path().join("target")).unwrap();
 
-        let plugin = RustPlugin;
-        let artifacts = plugin.find_artifacts(temp.path()).unwrap();
+        let artifacts = find_artifacts_with(temp.path(), None, None).unwrap();
 
         assert_eq!(artifacts.len(), 1);
         assert_eq!(artifacts[0].name(), "target");
         assert_eq!(artifacts[0].kind, ArtifactKind::BuildOutput);
     }
+
+    #[test]
+    fn test_find_artifacts_redirected_target_dir() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("crate");
+        std::fs::create_dir_all(project.join(".cargo")).unwrap();
+        std::fs::write(project.join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(
+            project.join(".cargo/config.toml"),
+            "[build]\ntarget-dir = \"../shared-target\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir(temp.path().join("shared-target")).unwrap();
+
+        let target = resolve_target_dir_with(&project, None, None);
+        assert_eq!(target, project.join("../shared-target"));
+
+        let artifacts = find_artifacts_with(&project, None, None).unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].name(), "shared-target");
+        assert!(!artifacts[0].path.starts_with(&project));
+        assert_eq!(
+            artifacts[0].metadata.extra.get("out_of_tree").map(String::as_str),
+            Some("true")
+        );
+        assert_eq!(
+            artifacts[0].metadata.extra.get("shared_target").map(String::as_str),
+            Some("true")
+        );
+
+        // An in-tree target isn't shared
+        std::fs::remove_file(project.join(".cargo/config.toml")).unwrap();
+        std::fs::create_dir(project.join("target")).unwrap();
+        let artifacts = find_artifacts_with(&project, None, None).unwrap();
+        assert_eq!(artifacts[0].path, project.join("target"));
+        assert!(!artifacts[0].metadata.extra.contains_key("shared_target"));
+    }
+
+    #[test]
+    fn test_workspace_member_uses_root_target() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"vendor\"]\n",
+        )
+        .unwrap();
+        std::fs::create_dir(root.join("target")).unwrap();
+        for member in ["crates/core", "crates/cli", "vendor/forked"] {
+            std::fs::create_dir_all(root.join(member)).unwrap();
+            std::fs::write(root.join(member).join("Cargo.toml"), "[package]").unwrap();
+        }
+
+        assert_eq!(workspace_root(root), None);
+        assert_eq!(workspace_root(&root.join("vendor/forked")), None);
+        assert_eq!(
+            workspace_root(&root.join("crates/core")),
+            Some(root.to_path_buf())
+        );
+
+        let artifacts = find_artifacts_with(&root.join("crates/cli"), None, None).unwrap();
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].path, root.join("target"));
+        assert_eq!(artifacts[0].metadata.lockfile, Some(root.join("Cargo.lock")));
+        assert_eq!(
+            artifacts[0].metadata.extra.get("workspace_root"),
+            Some(&root.display().to_string())
+        );
+
+        // Excluded crates build into their own target directory
+        let artifacts = find_artifacts_with(&root.join("vendor/forked"), None, None).unwrap();
+        assert!(artifacts.is_empty());
+    }
+
+    #[test]
+    fn test_target_dir_env_overrides_config() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".cargo")).unwrap();
+        std::fs::write(
+            temp.path().join(".cargo/config"),
+            "[build]\ntarget-dir = \"from-config\"\n",
+        )
+        .unwrap();
+
+        let shared = temp.path().join("shared");
+        let target = resolve_target_dir_with(temp.path(), Some(shared.clone()), None);
+        assert_eq!(target, shared);
+
+        let target = resolve_target_dir_with(temp.path(), None, None);
+        assert_eq!(target, temp.path().join("from-config"));
+    }
+
+    #[test]
+    fn test_pre_clean_blocks_running_build() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("target");
+        std::fs::create_dir_all(target.join("debug")).unwrap();
+        let lock = target.join("debug/.cargo-lock");
+        std::fs::write(&lock, "").unwrap();
+
+        let mut artifact = Artifact::new(target.clone(), ArtifactKind::BuildOutput);
+        artifact.metadata = ArtifactMetadata::restorable("cargo build");
+        assert!(RustPlugin.pre_clean(&artifact).is_ok());
+
+        // Cargo keeps the lock for the whole build
+        let build = std::fs::File::open(&lock).unwrap();
+        build.lock().unwrap();
+        let err = RustPlugin.pre_clean(&artifact).unwrap_err();
+        assert!(matches!(err, DevSweepError::InUse { path, .. } if path == target));
+    }
 }
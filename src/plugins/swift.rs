@@ -48,37 +48,41 @@ impl Plugin for SwiftPlugin {
         }
 
         // Check for Xcode project
-        if let Ok(entries) = std::fs::read_dir(path) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                if let Some(name) = entry.file_name().to_str() {
-                    if name.ends_with(".xcodeproj") || name.ends_with(".xcworkspace") {
-                        return Some(ProjectKind::SwiftXcode);
-                    }
-                }
-            }
-        }
-
-        None
+        has_xcode_project(path).then_some(ProjectKind::SwiftXcode)
     }
 
     fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
         let mut artifacts = Vec::new();
 
-        // .build directory (Swift Package Manager)
+        // .build directory (Swift Package Manager): checkouts plus builds,
+        // so getting it back costs a fetch and a full rebuild
         let build = project_root.join(".build");
         if build.exists() {
+            let resolved = project_root.join("Package.resolved");
             artifacts.push(Artifact {
                 path: build,
                 kind: ArtifactKind::BuildOutput,
                 size: 0,
                 file_count: 0,
                 age: None,
-                metadata: ArtifactMetadata::restorable("swift build"),
+                metadata: ArtifactMetadata {
+                    restorable: true,
+                    restore_command: Some("swift build".into()),
+                    lockfile: resolved.is_file().then_some(resolved),
+                    restore_time_estimate: Some(120),
+                    ..Default::default()
+                },
             });
         }
 
-        // .swiftpm directory
+        // .swiftpm directory - the generated Xcode workspace, unless it also
+        // holds mirror/registry settings, which may be committed
         let swiftpm = project_root.join(".swiftpm");
+        let swiftpm = if swiftpm.join("configuration").exists() {
+            swiftpm.join("xcode")
+        } else {
+            swiftpm
+        };
         if swiftpm.exists() {
             artifacts.push(Artifact {
                 path: swiftpm,
@@ -86,7 +90,11 @@ impl Plugin for SwiftPlugin {
                 size: 0,
                 file_count: 0,
                 age: None,
-                metadata: ArtifactMetadata::default(),
+                metadata: ArtifactMetadata {
+                    restorable: true,
+                    ..Default::default()
+                }
+                .with_restore_time(10),
             });
         }
 
@@ -108,30 +116,34 @@ impl Plugin for SwiftPlugin {
             });
         }
 
-        // DerivedData (if in project - usually in ~/Library)
-        let derived_data = project_root.join("DerivedData");
-        if derived_data.exists() {
-            artifacts.push(Artifact {
-                path: derived_data,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::restorable("xcodebuild"),
-            });
-        }
+        // Xcode's own output; a plain package has no business with these and
+        // Xcode builds of one go to ~/Library DerivedData (see XcodeCleaner)
+        if has_xcode_project(project_root) {
+            // DerivedData (if in project - usually in ~/Library)
+            let derived_data = project_root.join("DerivedData");
+            if derived_data.exists() {
+                artifacts.push(Artifact {
+                    path: derived_data,
+                    kind: ArtifactKind::BuildOutput,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata: ArtifactMetadata::restorable("xcodebuild"),
+                });
+            }
 
-        // build directory
-        let build_dir = project_root.join("build");
-        if build_dir.exists() {
-            artifacts.push(Artifact {
-                path: build_dir,
-                kind: ArtifactKind::BuildOutput,
-                size: 0,
-                file_count: 0,
-                age: None,
-                metadata: ArtifactMetadata::restorable("xcodebuild"),
-            });
+            // build directory
+            let build_dir = project_root.join("build");
+            if build_dir.exists() {
+                artifacts.push(Artifact {
+                    path: build_dir,
+                    kind: ArtifactKind::BuildOutput,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata: ArtifactMetadata::restorable("xcodebuild"),
+                });
+            }
         }
 
         Ok(artifacts)
@@ -141,11 +153,27 @@ impl Plugin for SwiftPlugin {
         &[".build", ".swiftpm", "Pods", "DerivedData", "build"]
     }
 
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("swift build".into())
+    }
+
     fn priority(&self) -> u8 {
         55
     }
 }
 
+/// Whether `path` holds an `.xcodeproj` or `.xcworkspace`
+fn has_xcode_project(path: &Path) -> bool {
+    std::fs::read_dir(path).is_ok_and(|entries| {
+        entries.filter_map(|e| e.ok()).any(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.ends_with(".xcodeproj") || name.ends_with(".xcworkspace"))
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,4 +213,43 @@ mod tests {
 
         assert_eq!(artifacts.len(), 2);
     }
+
+    #[test]
+    fn test_swiftpm_package_fixture() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("Package.swift"),
+            "// swift-tools-version:5.9\nimport PackageDescription\n\nlet package = Package(name: \"Server\")\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("Package.resolved"), "{}").unwrap();
+        std::fs::create_dir_all(root.join(".build/checkouts")).unwrap();
+        std::fs::create_dir_all(root.join(".swiftpm/xcode")).unwrap();
+        std::fs::create_dir_all(root.join(".swiftpm/configuration")).unwrap();
+        std::fs::create_dir(root.join("build")).unwrap();
+
+        let plugin = SwiftPlugin;
+        assert_eq!(plugin.detect(root), Some(ProjectKind::SwiftSpm));
+        let artifacts = plugin.find_artifacts(root).unwrap();
+        let paths: Vec<_> = artifacts.iter().map(|a| a.path.clone()).collect();
+        // build/ isn't Xcode output without an Xcode project; the committed
+        // .swiftpm/configuration stays
+        assert_eq!(paths, [root.join(".build"), root.join(".swiftpm/xcode")]);
+
+        let build = &artifacts[0];
+        assert_eq!(build.metadata.restore_command.as_deref(), Some("swift build"));
+        assert_eq!(build.metadata.lockfile, Some(root.join("Package.resolved")));
+        assert!(build.metadata.restore_time_estimate.is_some());
+
+        // Both SwiftPM and Xcode: each keeps its own build output
+        std::fs::create_dir(root.join("Server.xcodeproj")).unwrap();
+        let artifacts = plugin.find_artifacts(root).unwrap();
+        let restore = |name: &str| {
+            let artifact = artifacts.iter().find(|a| a.path == root.join(name)).unwrap();
+            artifact.metadata.restore_command.clone()
+        };
+        assert_eq!(restore(".build").as_deref(), Some("swift build"));
+        assert_eq!(restore("build").as_deref(), Some("xcodebuild"));
+    }
 }
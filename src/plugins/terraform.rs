@@ -0,0 +1,143 @@
+//! Terraform plugin
+//!
+//! Each initialized Terraform working directory keeps its own copy of the
+//! provider binaries and remote modules under `.terraform/`. Only those two
+//! caches are reported - `.terraform.lock.hcl`, state files and the backend
+//! config in `.terraform/terraform.tfstate` are left alone.
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::Plugin;
+use std::path::Path;
+
+/// Plugin for Terraform working directories
+pub struct TerraformPlugin;
+
+impl Plugin for TerraformPlugin {
+    fn id(&self) -> &'static str {
+        "terraform"
+    }
+
+    fn name(&self) -> &'static str {
+        "Terraform"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::Terraform]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![
+            ProjectMarker {
+                indicator: MarkerKind::Directory(".terraform"),
+                kind: ProjectKind::Terraform,
+                priority: 45,
+            },
+            ProjectMarker {
+                indicator: MarkerKind::File(".terraform.lock.hcl"),
+                kind: ProjectKind::Terraform,
+                priority: 45,
+            },
+        ]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        if path.join(".terraform").is_dir() && has_tf_files(path) {
+            Some(ProjectKind::Terraform)
+        } else {
+            None
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+        let dot_terraform = project_root.join(".terraform");
+        let lockfile = project_root.join(".terraform.lock.hcl");
+
+        // Provider binaries (usually the bulk of the size) and downloaded modules
+        for cache in ["providers", "modules"] {
+            let path = dot_terraform.join(cache);
+            if path.is_dir() {
+                let mut metadata = ArtifactMetadata::restorable("terraform init");
+                if lockfile.is_file() {
+                    metadata = metadata.with_lockfile(lockfile.clone());
+                }
+
+                artifacts.push(Artifact {
+                    path,
+                    kind: ArtifactKind::Dependencies,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata,
+                });
+            }
+        }
+
+        Ok(artifacts)
+    }
+
+    fn cleanable_dirs(&self) -> &[&'static str] {
+        &[".terraform"]
+    }
+
+    fn priority(&self) -> u8 {
+        45
+    }
+}
+
+/// Check whether a directory holds any Terraform configuration
+fn has_tf_files(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries.filter_map(|e| e.ok()).any(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                (name.ends_with(".tf") || name.ends_with(".tf.json")) && e.path().is_file()
+            })
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_terraform() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("main.tf"), "terraform {}\n").unwrap();
+
+        let plugin = TerraformPlugin;
+        // Not initialized yet, nothing to clean
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::create_dir(temp.path().join(".terraform")).unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::Terraform));
+    }
+
+    #[test]
+    fn test_find_artifacts_skips_state() {
+        let temp = TempDir::new().unwrap();
+        let dot_terraform = temp.path().join(".terraform");
+        std::fs::write(temp.path().join("main.tf"), "terraform {}\n").unwrap();
+        std::fs::write(temp.path().join(".terraform.lock.hcl"), "").unwrap();
+        std::fs::create_dir_all(dot_terraform.join("providers")).unwrap();
+        std::fs::create_dir_all(dot_terraform.join("modules")).unwrap();
+        std::fs::write(dot_terraform.join("terraform.tfstate"), "{}").unwrap();
+
+        let plugin = TerraformPlugin;
+        let artifacts = plugin.find_artifacts(temp.path()).unwrap();
+
+        let names: Vec<_> = artifacts.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec!["providers", "modules"]);
+        assert_eq!(
+            artifacts[0].metadata.lockfile,
+            Some(temp.path().join(".terraform.lock.hcl"))
+        );
+        assert!(artifacts
+            .iter()
+            .all(|a| a.metadata.restore_command.as_deref() == Some("terraform init")));
+    }
+}
@@ -0,0 +1,120 @@
+//! Zig plugin
+//!
+//! `zig build` keeps its compilation cache next to the project (`.zig-cache`,
+//! or `zig-cache` before Zig 0.13) and installs results into `zig-out`.
+
+use crate::core::{Artifact, ArtifactKind, ArtifactMetadata, MarkerKind, ProjectKind, ProjectMarker};
+use crate::error::Result;
+use crate::plugins::Plugin;
+use std::path::Path;
+
+/// Plugin for Zig projects
+pub struct ZigPlugin;
+
+impl Plugin for ZigPlugin {
+    fn id(&self) -> &'static str {
+        "zig"
+    }
+
+    fn name(&self) -> &'static str {
+        "Zig"
+    }
+
+    fn supported_kinds(&self) -> &[ProjectKind] {
+        &[ProjectKind::Zig]
+    }
+
+    fn markers(&self) -> Vec<ProjectMarker> {
+        vec![ProjectMarker {
+            indicator: MarkerKind::File("build.zig"),
+            kind: ProjectKind::Zig,
+            priority: 60,
+        }]
+    }
+
+    fn detect(&self, path: &Path) -> Option<ProjectKind> {
+        if path.join("build.zig").is_file() {
+            Some(ProjectKind::Zig)
+        } else {
+            None
+        }
+    }
+
+    fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+        let mut artifacts = Vec::new();
+
+        // Compilation cache (old and new name)
+        for name in [".zig-cache", "zig-cache"] {
+            let cache = project_root.join(name);
+            if cache.is_dir() {
+                artifacts.push(Artifact {
+                    path: cache,
+                    kind: ArtifactKind::Cache,
+                    size: 0,
+                    file_count: 0,
+                    age: None,
+                    metadata: ArtifactMetadata::restorable("zig build"),
+                });
+            }
+        }
+
+        // Installed build results
+        let zig_out = project_root.join("zig-out");
+        if zig_out.is_dir() {
+            artifacts.push(Artifact {
+                path: zig_out,
+                kind: ArtifactKind::BuildOutput,
+                size: 0,
+                file_count: 0,
+                age: None,
+                metadata: ArtifactMetadata::restorable("zig build"),
+            });
+        }
+
+        Ok(artifacts)
+    }
+
+    fn cleanable_dirs(&self) -> &[&'static str] {
+        &[".zig-cache", "zig-cache", "zig-out"]
+    }
+
+    fn rebuild_command(&self, _project_root: &Path) -> Option<String> {
+        Some("zig build".into())
+    }
+
+    fn priority(&self) -> u8 {
+        60
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_zig() {
+        let temp = TempDir::new().unwrap();
+        let plugin = ZigPlugin;
+        assert_eq!(plugin.detect(temp.path()), None);
+
+        std::fs::write(temp.path().join("build.zig"), "").unwrap();
+        assert_eq!(plugin.detect(temp.path()), Some(ProjectKind::Zig));
+    }
+
+    #[test]
+    fn test_find_artifacts() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("build.zig"), "").unwrap();
+        std::fs::create_dir(temp.path().join(".zig-cache")).unwrap();
+        std::fs::create_dir(temp.path().join("zig-out")).unwrap();
+        std::fs::create_dir(temp.path().join("src")).unwrap();
+
+        let artifacts = ZigPlugin.find_artifacts(temp.path()).unwrap();
+        let found: Vec<_> = artifacts.iter().map(|a| (a.name(), a.kind)).collect();
+        assert_eq!(
+            found,
+            vec![(".zig-cache", ArtifactKind::Cache), ("zig-out", ArtifactKind::BuildOutput)]
+        );
+    }
+}
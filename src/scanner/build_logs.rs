@@ -0,0 +1,111 @@
+//! Build and test logs left inside projects
+//!
+//! Tools drop `*.log` files, `npm-debug.log`, `yarn-error.log`, JVM crash
+//! reports (`hs_err_pid*.log`) and core dumps next to the code. Each is
+//! small, so they are collected into one `Logs` artifact per project.
+
+use crate::core::{Artifact, ArtifactKind, SizeMode};
+use std::path::Path;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// Whether a file name looks like a log or a core dump
+pub(crate) fn is_build_log(name: &str) -> bool {
+    if name.ends_with(".log") || name.starts_with("npm-debug.log") {
+        return true;
+    }
+    // core.1234, but not core.js
+    name.strip_prefix("core.")
+        .is_some_and(|pid| !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Log files under `project`, grouped into one artifact
+///
+/// Hidden directories and any directory `skip` rejects (other artifacts,
+/// nested projects) are not searched. `None` when nothing was found.
+pub(crate) fn find_build_logs(
+    project: &Path,
+    size_mode: SizeMode,
+    skip: impl Fn(&Path) -> bool,
+) -> Option<Artifact> {
+    let mut artifact = Artifact::new(project.join("*.log"), ArtifactKind::Logs);
+    let mut newest: Option<SystemTime> = None;
+
+    let walker = WalkDir::new(project)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            if e.depth() == 0 || !e.file_type().is_dir() {
+                return true;
+            }
+            let hidden = e.file_name().to_string_lossy().starts_with('.');
+            !hidden && !skip(e.path())
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() || !is_build_log(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        artifact.size += size_mode.of(&metadata);
+        if let Ok(modified) = metadata.modified() {
+            newest = newest.max(Some(modified));
+        }
+        artifact.metadata.files.push(entry.into_path());
+    }
+
+    if artifact.metadata.files.is_empty() {
+        return None;
+    }
+
+    artifact.file_count = artifact.metadata.files.len() as u64;
+    artifact.age = newest.and_then(|t| t.elapsed().ok());
+    Some(artifact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_build_log() {
+        assert!(is_build_log("build.log"));
+        assert!(is_build_log("npm-debug.log"));
+        assert!(is_build_log("npm-debug.log.1234"));
+        assert!(is_build_log("hs_err_pid4242.log"));
+        assert!(is_build_log("core.4242"));
+        assert!(!is_build_log("core.js"));
+        assert!(!is_build_log("core"));
+        assert!(!is_build_log("logger.rs"));
+    }
+
+    #[test]
+    fn test_find_build_logs() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("tests/output")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("yarn-error.log"), vec![0u8; 100]).unwrap();
+        std::fs::write(root.join("tests/output/run.log"), vec![0u8; 50]).unwrap();
+        std::fs::write(root.join("core.991"), vec![0u8; 10]).unwrap();
+        std::fs::write(root.join("src.rs"), vec![0u8; 1000]).unwrap();
+        std::fs::write(root.join(".git/index.log"), vec![0u8; 1000]).unwrap();
+        std::fs::write(root.join("node_modules/pkg/install.log"), vec![0u8; 1000]).unwrap();
+
+        let logs = find_build_logs(root, SizeMode::Apparent, |dir| dir.ends_with("node_modules"))
+            .unwrap();
+        assert_eq!(logs.kind, ArtifactKind::Logs);
+        assert_eq!(logs.size, 160);
+        assert_eq!(logs.file_count, 3);
+        assert!(!logs.path.exists());
+        assert_eq!(logs.paths().len(), 3);
+
+        let empty = TempDir::new().unwrap();
+        assert!(find_build_logs(empty.path(), SizeMode::Apparent, |_| false).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! Per-directory `.null-eignore` files
+//!
+//! Teams can commit cleanup policy into a repository: a `.null-eignore` file
+//! uses gitignore syntax and applies to the directory it lives in and
+//! everything below. Files are layered like git layers `.gitignore` - a
+//! deeper file can re-include (`!pattern`) what a parent excluded.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::path::{Path, PathBuf};
+
+/// Name of the per-directory ignore file
+pub const IGNORE_FILE_NAME: &str = ".null-eignore";
+
+/// Stack of ignore files for the directory currently being walked
+///
+/// Walks are depth-first, so entering a directory only needs to drop the
+/// layers of directories that are no longer ancestors.
+#[derive(Default)]
+pub(crate) struct IgnoreStack {
+    layers: Vec<(PathBuf, Gitignore)>,
+}
+
+impl IgnoreStack {
+    /// Create a stack preloaded with the ignore files of a scan root and its parents
+    ///
+    /// The root itself is never ignored - it was asked for explicitly.
+    pub(crate) fn for_root(root: &Path) -> Self {
+        let mut stack = Self::default();
+        let mut ancestors: Vec<_> = root.ancestors().collect();
+        ancestors.reverse();
+        for dir in ancestors {
+            stack.push(dir);
+        }
+        stack
+    }
+
+    /// Enter a directory: pop layers that don't cover it, then load its own file
+    ///
+    /// Returns false (without loading anything) when the directory itself
+    /// is ignored and should not be walked.
+    pub(crate) fn enter(&mut self, dir: &Path) -> bool {
+        while self
+            .layers
+            .last()
+            .is_some_and(|(root, _)| !dir.starts_with(root) || dir == root)
+        {
+            self.layers.pop();
+        }
+
+        if self.is_ignored(dir, true) {
+            return false;
+        }
+
+        self.push(dir);
+        true
+    }
+
+    /// Check a path against the loaded layers, deepest first
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for (root, gitignore) in self.layers.iter().rev() {
+            if !path.starts_with(root) || path == root {
+                continue;
+            }
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn push(&mut self, dir: &Path) {
+        let file = dir.join(IGNORE_FILE_NAME);
+        if !file.is_file() {
+            return;
+        }
+
+        let mut builder = GitignoreBuilder::new(dir);
+        // Invalid lines are skipped, the rest of the file still applies
+        let _ = builder.add(&file);
+        if let Ok(gitignore) = builder.build() {
+            self.layers.push((dir.to_path_buf(), gitignore));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_layered_ignore_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let web = root.join("web");
+        std::fs::create_dir_all(web.join("node_modules")).unwrap();
+        std::fs::create_dir_all(root.join("legacy")).unwrap();
+        std::fs::write(root.join(IGNORE_FILE_NAME), "legacy/\nnode_modules\n").unwrap();
+        std::fs::write(web.join(IGNORE_FILE_NAME), "!node_modules\n").unwrap();
+
+        let mut stack = IgnoreStack::for_root(root);
+        assert!(!stack.enter(&root.join("legacy")));
+        assert!(stack.is_ignored(&root.join("node_modules"), true));
+
+        assert!(stack.enter(&web));
+        assert!(!stack.is_ignored(&web.join("node_modules"), true));
+
+        // Leaving web/ drops its layer again
+        assert!(stack.enter(&root.join("other")));
+        assert!(stack.is_ignored(&root.join("other/node_modules"), true));
+    }
+}
@@ -2,6 +2,11 @@
 //!
 //! High-performance scanner that finds development projects and their artifacts.
 
+mod build_logs;
+mod ignore_files;
 mod parallel;
+mod remote;
 
-pub use parallel::ParallelScanner;
+pub use ignore_files::IGNORE_FILE_NAME;
+pub use parallel::{stream_channel, ParallelScanner};
+pub use remote::{RemoteScanner, RemoteTarget};
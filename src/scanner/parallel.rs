@@ -2,23 +2,262 @@
 //!
 //! Uses jwalk for parallel directory traversal and rayon for parallel processing.
 
+use super::build_logs::find_build_logs;
+use super::ignore_files::IgnoreStack;
 use crate::core::{
-    ArtifactStats, Project, ProjectId, ScanConfig, ScanError, ScanProgress, ScanResult, Scanner,
+    Artifact, ArtifactStats, IoPriority, PhaseTimings, Project, ProjectId, ScanConfig, ScanError,
+    ScanProgress, ScanResult, Scanner, SizeMode,
 };
 use crate::error::{DevSweepError, Result};
 use crate::plugins::PluginRegistry;
-use dashmap::DashMap;
+use crossbeam_channel::{Receiver, Sender};
+use dashmap::{DashMap, DashSet};
 use rayon::prelude::*;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::Ordering;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
+/// Artifact directories the walk never enters (they contain nested packages)
+const SKIP_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    ".venv",
+    "venv",
+    "__pycache__",
+    "vendor",
+    "build",
+    ".gradle",
+    "bin",
+    "obj",
+    ".build",
+    "Pods",
+    "DerivedData",
+    ".next",
+    ".nuxt",
+    "dist",
+    ".cache",
+    ".turbo",
+    "coverage",
+    ".terraform",
+    ".dart_tool",
+    ".cxx",
+];
+
+/// Cooperative throttle that paces a walker to a directory rate
+///
+/// Sleeps in small batches (about ten per second) rather than after every
+/// directory, so the pauses stay cheap while still leaving the disk idle
+/// between bursts.
+struct Throttle {
+    batch: u32,
+    batch_duration: Duration,
+    seen: u32,
+    window_start: Instant,
+}
+
+impl Throttle {
+    fn new(dirs_per_sec: u32) -> Self {
+        let batch = (dirs_per_sec / 10).max(1);
+        Self {
+            batch,
+            batch_duration: Duration::from_secs_f64(f64::from(batch) / f64::from(dirs_per_sec)),
+            seen: 0,
+            window_start: Instant::now(),
+        }
+    }
+
+    /// Record a visited directory, sleeping if the batch finished too fast
+    fn tick(&mut self) {
+        self.seen += 1;
+        if self.seen < self.batch {
+            return;
+        }
+
+        if let Some(remaining) = self.batch_duration.checked_sub(self.window_start.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+        self.seen = 0;
+        self.window_start = Instant::now();
+    }
+}
+
+/// Move the calling thread into the idle I/O scheduling class
+///
+/// Best effort: only Linux exposes this per thread, elsewhere the
+/// cooperative throttle is all we have.
+fn lower_io_priority() {
+    #[cfg(target_os = "linux")]
+    {
+        const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+        const IOPRIO_CLASS_IDLE: libc::c_long = 3;
+        const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+        // SAFETY: ioprio_set takes plain integers; who = 0 means the calling thread
+        unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                0,
+                IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+            );
+        }
+    }
+}
+
+/// Nanoseconds spent per scan phase, summed over walker threads
+#[derive(Default)]
+struct PhaseClock {
+    /// Everything a walker did, detection and sizing included
+    walking: AtomicU64,
+    detection: AtomicU64,
+    sizing: AtomicU64,
+}
+
+impl PhaseClock {
+    fn add(counter: &AtomicU64, elapsed: Duration) {
+        counter.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    fn reset(&self) {
+        for counter in [&self.walking, &self.detection, &self.sizing] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn timings(&self) -> PhaseTimings {
+        let read = |counter: &AtomicU64| Duration::from_nanos(counter.load(Ordering::Relaxed));
+        let (detection, sizing) = (read(&self.detection), read(&self.sizing));
+        PhaseTimings {
+            traversal: read(&self.walking).saturating_sub(detection + sizing),
+            detection,
+            sizing,
+            git: None,
+        }
+    }
+}
+
+/// Projects buffered per scan thread by [`stream_channel`]
+const STREAM_BUFFER_PER_THREAD: usize = 16;
+
+/// Threads a scan with `config` runs on
+fn thread_count(config: &ScanConfig) -> usize {
+    config.parallelism.unwrap_or(num_cpus::get()).max(1)
+}
+
+/// Whether `artifact` is a target directory other projects may build into too
+fn is_shared_target(artifact: &Artifact) -> bool {
+    artifact.metadata.extra.contains_key("shared_target")
+}
+
+/// Bounded channel for [`ParallelScanner::with_project_stream`], sized for
+/// the threads `config` scans with
+///
+/// Holds a few projects per thread, enough to keep the walkers busy while
+/// the consumer catches up; once it is full they wait for the consumer.
+pub fn stream_channel(config: &ScanConfig) -> (Sender<Project>, Receiver<Project>) {
+    crossbeam_channel::bounded(thread_count(config) * STREAM_BUFFER_PER_THREAD)
+}
+
+/// Projects found so far, or only their ids and running totals when streaming
+#[derive(Default)]
+struct Collector {
+    seen: DashSet<ProjectId>,
+    kept: DashMap<ProjectId, Project>,
+    /// Canonical paths of artifacts already reported by some project
+    claimed: DashSet<PathBuf>,
+    /// Canonical shared target directories, and the smallest project root claiming each
+    shared: DashMap<PathBuf, PathBuf>,
+    /// Projects holding a shared target, reported once the walk settles who owns it
+    deferred: parking_lot::Mutex<Vec<(ProjectId, Project)>>,
+    streamed: parking_lot::Mutex<(ArtifactStats, u64, u64)>,
+}
+
+impl Collector {
+    fn contains(&self, id: &ProjectId) -> bool {
+        self.seen.contains(id)
+    }
+
+    fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether `artifact` is reported for the first time; a target directory
+    /// shared by several projects is only counted once
+    fn claim(&self, artifact: &Path) -> bool {
+        let canonical = artifact.canonicalize().unwrap_or_else(|_| artifact.to_path_buf());
+        self.claimed.insert(canonical)
+    }
+
+    /// Whether `root` may report the shared target `artifact`
+    ///
+    /// The project with the smallest root wins whatever order the walk finds
+    /// them in; an earlier, larger claimant loses it in [`Self::settle_shared`].
+    fn claim_shared(&self, artifact: &Path, root: &Path) -> bool {
+        let canonical = artifact.canonicalize().unwrap_or_else(|_| artifact.to_path_buf());
+        let mut owner = self.shared.entry(canonical).or_insert_with(|| root.to_path_buf());
+        if root < owner.as_path() {
+            *owner = root.to_path_buf();
+        }
+        owner.as_path() == root
+    }
+
+    /// Hold `project` back until the walk is over
+    fn defer(&self, id: ProjectId, project: Project) {
+        self.seen.insert(id);
+        self.deferred.lock().push((id, project));
+    }
+
+    /// Deferred projects with each shared target left only on its owner,
+    /// dropping projects that end up empty
+    fn settle_shared(&self) -> Vec<(ProjectId, Project)> {
+        let mut deferred = std::mem::take(&mut *self.deferred.lock());
+        deferred.sort_by(|a, b| a.1.root.cmp(&b.1.root));
+        deferred
+            .into_iter()
+            .filter_map(|(id, mut project)| {
+                project.artifacts.retain(|a| {
+                    !is_shared_target(a) || {
+                        let canonical = a.path.canonicalize().unwrap_or_else(|_| a.path.clone());
+                        self.shared.get(&canonical).is_some_and(|owner| *owner == project.root)
+                    }
+                });
+                project.calculate_totals();
+                (!project.artifacts.is_empty()).then_some((id, project))
+            })
+            .collect()
+    }
+
+    /// Keep `project` for the result
+    fn keep(&self, id: ProjectId, project: Project) {
+        self.seen.insert(id);
+        self.kept.insert(id, project);
+    }
+
+    /// Only count `project` in the totals, for a stream-only consumer
+    fn count(&self, id: ProjectId, project: &Project) {
+        self.seen.insert(id);
+        let mut streamed = self.streamed.lock();
+        for artifact in &project.artifacts {
+            streamed.0.add(artifact);
+        }
+        streamed.1 += project.total_size;
+        streamed.2 += project.cleanable_size;
+    }
+}
+
 /// High-performance parallel scanner
 pub struct ParallelScanner {
     registry: Arc<PluginRegistry>,
     progress: Arc<ScanProgress>,
+    found: Option<Sender<Project>>,
+    /// Projects only go to `found`, the result keeps totals alone
+    stream_only: bool,
+    /// Time spent and projects found per plugin, for the `scan finished` log
+    plugin_timings: DashMap<&'static str, (Duration, usize)>,
+    phases: PhaseClock,
 }
 
 impl ParallelScanner {
@@ -27,39 +266,69 @@ impl ParallelScanner {
         Self {
             registry,
             progress: ScanProgress::new(),
+            found: None,
+            stream_only: false,
+            plugin_timings: DashMap::new(),
+            phases: PhaseClock::default(),
         }
     }
 
+    /// Stream each project through `sender` as soon as it is complete
+    ///
+    /// Projects arrive in discovery order, before `limit` is applied; the
+    /// final [`ScanResult`] is unaffected. The channel is
+    /// dropped with the scanner, which ends the stream.
+    pub fn with_project_sender(mut self, sender: Sender<Project>) -> Self {
+        self.found = Some(sender);
+        self
+    }
+
+    /// Hand every project to `sender` instead of keeping it, so memory stays
+    /// flat however large the tree is
+    ///
+    /// The [`ScanResult`] then has totals, stats and errors but no projects,
+    /// and `limit` doesn't apply. Sending blocks while the channel is full,
+    /// so a channel from [`stream_channel`] holds the scan back to the pace
+    /// of its consumer, which must run on another thread. The stream ends
+    /// when the scanner is dropped.
+    pub fn with_project_stream(mut self, sender: Sender<Project>) -> Self {
+        self.found = Some(sender);
+        self.stream_only = true;
+        self
+    }
+
     /// Scan a single root directory
-    fn scan_root(&self, root: &Path, projects: &DashMap<ProjectId, Project>, config: &ScanConfig) -> Result<()> {
+    fn scan_root(
+        &self,
+        root: &Path,
+        projects: &Collector,
+        config: &ScanConfig,
+        deadline: Option<Instant>,
+        visited: &DashSet<PathBuf>,
+        pool: &rayon::ThreadPool,
+    ) -> Result<()> {
+        let follow_symlinks = config.follow_symlinks;
+        let mut throttle = config.effective_throttle().map(Throttle::new);
         let walker = WalkDir::new(root)
             .max_depth(config.max_depth.unwrap_or(usize::MAX))
-            .follow_links(false);
+            .follow_links(follow_symlinks);
+
+        // Symlinked directories crossed so far; anything below them lives
+        // on the other side of a link boundary
+        let mut link_boundaries: Vec<PathBuf> = Vec::new();
+        if std::fs::symlink_metadata(root).is_ok_and(|m| m.file_type().is_symlink()) {
+            link_boundaries.push(root.to_path_buf());
+        }
 
         let skip_hidden = config.skip_hidden;
 
         // Directories to skip (artifact directories that contain nested packages)
-        let skip_dirs: std::collections::HashSet<&str> = [
-            "node_modules",
-            "target",
-            ".venv",
-            "venv",
-            "__pycache__",
-            "vendor",
-            "build",
-            ".gradle",
-            "bin",
-            "obj",
-            ".build",
-            "Pods",
-            "DerivedData",
-            ".next",
-            ".nuxt",
-            "dist",
-            ".cache",
-            ".turbo",
-            "coverage",
-        ].into_iter().collect();
+        let skip_dirs: std::collections::HashSet<&str> = SKIP_DIRS.iter().copied().collect();
+
+        // .null-eignore files seen on the way down; shared with the loop below
+        // so artifacts can be checked against the same layers
+        let ignore_stack = Rc::new(RefCell::new(IgnoreStack::for_root(root)));
+        let walk_ignore_stack = Rc::clone(&ignore_stack);
 
         let entries = walker.into_iter().filter_entry(move |e| {
             let name = e.file_name().to_str().unwrap_or("");
@@ -78,6 +347,22 @@ impl ParallelScanner {
                 }
             }
 
+            // Skip directories excluded by a .null-eignore file
+            if e.depth() > 0
+                && e.file_type().is_dir()
+                && !walk_ignore_stack.borrow_mut().enter(e.path())
+            {
+                return false;
+            }
+
+            // When following links, never walk the same real directory twice
+            // (cycles, or several links pointing at one place)
+            if follow_symlinks && e.file_type().is_dir() {
+                if let Ok(canonical) = e.path().canonicalize() {
+                    return visited.insert(canonical);
+                }
+            }
+
             true
         });
 
@@ -87,13 +372,25 @@ impl ParallelScanner {
                 return Err(DevSweepError::ScanInterrupted);
             }
 
+            // Stop traversal once the deadline passes, keeping what we found
+            if self.past_deadline(root, config, deadline) {
+                return Ok(());
+            }
+
             let entry = match entry {
                 Ok(e) => e,
+                // A link back to an ancestor is expected when following links
+                Err(e) if e.loop_ancestor().is_some() => continue,
                 Err(e) => {
-                    self.progress.add_error(ScanError::new(
-                        PathBuf::new(),
-                        format!("Walk error: {}", e),
-                    ));
+                    let path = e.path().map(Path::to_path_buf).unwrap_or_default();
+                    let denied = e
+                        .io_error()
+                        .is_some_and(|io| io.kind() == std::io::ErrorKind::PermissionDenied);
+                    self.progress.add_error(if denied {
+                        ScanError::unreadable(path, "Permission denied")
+                    } else {
+                        ScanError::new(path, format!("Walk error: {}", e))
+                    });
                     continue;
                 }
             };
@@ -104,61 +401,187 @@ impl ParallelScanner {
             }
 
             let path = entry.path();
+            if entry.path_is_symlink() && entry.depth() > 0 {
+                link_boundaries.push(path.to_path_buf());
+            }
             self.progress.inc_directories();
             self.progress.set_current_path(path.to_path_buf());
+            if let Some(throttle) = &mut throttle {
+                throttle.tick();
+            }
 
             // Skip if already found as a project or inside a project
             let project_id = ProjectId::from_path(path);
-            if projects.contains_key(&project_id) {
+            if projects.contains(&project_id) {
                 continue;
             }
 
-            // Try to detect project type
-            if let Some((kind, plugin)) = self.registry.detect_project(path) {
-                // Found a project!
-                let mut project = Project::new(kind, path.to_path_buf());
+            // Try to detect project type, falling back to leftovers of deleted projects
+            let detect_start = Instant::now();
+            let found = match self.registry.detect_project(path) {
+                Some((kind, plugin)) => Some((kind, pool.install(|| plugin.find_artifacts(path)), plugin)),
+                None => self
+                    .registry
+                    .find_orphans(path)
+                    .map(|(kind, plugin, orphans)| (kind, Ok(orphans), plugin)),
+            };
+
+            PhaseClock::add(&self.phases.detection, detect_start.elapsed());
 
+            if let Some((kind, found, plugin)) = found {
+                let _span = tracing::debug_span!("project", plugin = plugin.id(), path = %path.display())
+                    .entered();
                 // Find artifacts
-                match plugin.find_artifacts(path) {
+                match found {
                     Ok(mut artifacts) => {
-                        // Calculate sizes in parallel
-                        artifacts.par_iter_mut().for_each(|artifact| {
-                            if let Ok(size) = plugin.calculate_size(artifact) {
+                        // Artifacts shared by a workspace are attributed to its root, once
+                        let (project_root, project_id) = match artifacts
+                            .iter()
+                            .find_map(|a| a.metadata.extra.get("workspace_root"))
+                        {
+                            Some(root) => {
+                                let root = PathBuf::from(root);
+                                let id = ProjectId::from_path(&root);
+                                (root, id)
+                            }
+                            None => (path.to_path_buf(), project_id),
+                        };
+                        if projects.contains(&project_id) {
+                            continue;
+                        }
+
+                        // Found a project!
+                        let mut project = Project::new(kind, project_root.clone());
+                        project.detected_by = Some(plugin.id().to_string());
+
+                        // Drop artifacts excluded by a .null-eignore file
+                        {
+                            let ignore_stack = ignore_stack.borrow();
+                            artifacts.retain(|a| !ignore_stack.is_ignored(&a.path, a.path.is_dir()));
+                        }
+                        artifacts.retain(|a| {
+                            if is_shared_target(a) {
+                                projects.claim_shared(&a.path, &project_root)
+                            } else {
+                                projects.claim(&a.path)
+                            }
+                        });
+
+                        let sizing_start = Instant::now();
+                        // A huge artifact mustn't hold the scan past --timeout or Ctrl-C
+                        let stop = || self.progress.is_cancelled() || self.past_deadline(root, config, deadline);
+
+                        // Calculate sizes in parallel; sizing a single artifact
+                        // may fan out further, which stays within the pool
+                        pool.install(|| artifacts.par_iter_mut().for_each(|artifact| {
+                            artifact.age = std::fs::metadata(&artifact.path)
+                                .and_then(|m| m.modified())
+                                .ok()
+                                .and_then(|t| t.elapsed().ok());
+                            if config.fast {
+                                if let Ok((size, entries)) =
+                                    crate::plugins::estimate_size(&artifact.path, config.size_mode)
+                                {
+                                    artifact.size = size;
+                                    artifact.file_count = entries;
+                                }
+                                return;
+                            }
+
+                            // Remember whether a walk was actually cut short
+                            let cut_off = AtomicBool::new(false);
+                            let stop = || {
+                                let stopped = stop();
+                                if stopped {
+                                    cut_off.store(true, Ordering::Relaxed);
+                                }
+                                stopped
+                            };
+                            let size = match config.size_mode {
+                                SizeMode::Apparent => plugin.calculate_size_until(artifact, SizeMode::Apparent, &stop),
+                                SizeMode::Disk => crate::plugins::disk_usage_until(&artifact.path, &stop).map(
+                                    |(size, shared)| {
+                                        artifact.metadata.shared_size = (shared > 0).then_some(shared);
+                                        size
+                                    },
+                                ),
+                            };
+                            if let Ok(size) = size {
                                 artifact.size = size;
                             }
-                            if let Ok(count) = crate::plugins::count_files(&artifact.path) {
+                            if let Ok(count) = crate::plugins::count_files_until(&artifact.path, &stop) {
                                 artifact.file_count = count;
                             }
-                        });
+                            if cut_off.into_inner() {
+                                artifact.metadata.extra.insert("partial_size".into(), "true".into());
+                            }
+                        }));
+
+                        // Detection and sizing are where a plugin's time goes
+                        let mut timing = self.plugin_timings.entry(plugin.id()).or_default();
+                        timing.0 += detect_start.elapsed();
+                        timing.1 += 1;
+                        drop(timing);
+
+                        // Logs scattered through the project, as one artifact
+                        if config.build_logs {
+                            let ignore_stack = ignore_stack.borrow();
+                            let skip = |dir: &Path| {
+                                dir.file_name()
+                                    .and_then(|n| n.to_str())
+                                    .is_some_and(|n| SKIP_DIRS.contains(&n))
+                                    || artifacts.iter().any(|a| a.path == dir)
+                                    || ignore_stack.is_ignored(dir, true)
+                                    || self.registry.detect_project(dir).is_some()
+                            };
+                            let logs = find_build_logs(&project_root, config.size_mode, skip);
+                            artifacts.extend(logs);
+                        }
+                        PhaseClock::add(&self.phases.sizing, sizing_start.elapsed());
 
-                        // Filter by minimum size if specified
+                        // Filter by minimum size and file count if specified
                         if let Some(min_size) = config.min_size {
                             artifacts.retain(|a| a.size >= min_size);
                         }
+                        if let Some(min_files) = config.min_files {
+                            artifacts.retain(|a| a.file_count >= min_files);
+                        }
 
                         // Skip if no meaningful artifacts
                         if artifacts.is_empty() {
                             continue;
                         }
 
+                        // Mark artifacts reached through a symlink so clean can warn
+                        if let Some(link) =
+                            link_boundaries.iter().find(|link| path.starts_with(link))
+                        {
+                            for artifact in &mut artifacts {
+                                artifact
+                                    .metadata
+                                    .extra
+                                    .insert("via_symlink".into(), link.display().to_string());
+                            }
+                        }
+
                         project.artifacts = artifacts;
                         project.calculate_totals();
 
                         // Get last modified time
-                        if let Ok(meta) = std::fs::metadata(path) {
+                        if let Ok(meta) = std::fs::metadata(&project_root) {
                             project.last_modified = meta.modified().ok();
                         }
 
-                        self.progress.inc_projects();
-                        self.progress.add_size(project.cleanable_size);
-
-                        projects.insert(project_id, project);
+                        // Who owns a shared target is only known once every project is found
+                        if project.artifacts.iter().any(is_shared_target) {
+                            projects.defer(project_id, project);
+                        } else {
+                            self.report(projects, project_id, project);
+                        }
                     }
                     Err(e) => {
-                        self.progress.add_error(ScanError::new(
-                            path.to_path_buf(),
-                            format!("Failed to find artifacts: {}", e),
-                        ));
+                        let e = e.with_context(format!("while finding {} artifacts", plugin.id()));
+                        self.progress.add_error(ScanError::new(path.to_path_buf(), e.to_string()));
                     }
                 }
             }
@@ -166,11 +589,52 @@ impl ParallelScanner {
 
         Ok(())
     }
+
+    /// Hand a finished project to the stream and the result
+    fn report(&self, projects: &Collector, project_id: ProjectId, project: Project) {
+        self.progress.inc_projects();
+        self.progress.add_size(project.cleanable_size);
+        // Nobody listening any more is fine
+        match &self.found {
+            Some(found) if self.stream_only => {
+                projects.count(project_id, &project);
+                let _ = found.send(project);
+            }
+            Some(found) => {
+                let _ = found.send(project.clone());
+                projects.keep(project_id, project);
+            }
+            None => projects.keep(project_id, project),
+        }
+    }
+
+    /// Whether the `--timeout` deadline has passed, recording the timeout
+    /// the first time it's noticed
+    fn past_deadline(&self, root: &Path, config: &ScanConfig, deadline: Option<Instant>) -> bool {
+        if self.progress.is_timed_out() {
+            return true;
+        }
+        if deadline.is_none_or(|d| Instant::now() < d) {
+            return false;
+        }
+        self.progress.mark_timed_out();
+        let secs = config.timeout.map(|t| t.as_secs()).unwrap_or_default();
+        self.progress.add_error(ScanError::new(
+            root.to_path_buf(),
+            DevSweepError::ScanTimeout(secs).to_string(),
+        ));
+        true
+    }
 }
 
 impl Scanner for ParallelScanner {
     fn scan(&self, config: &ScanConfig) -> Result<ScanResult> {
         let start = Instant::now();
+        let deadline = config.timeout.map(|t| start + t);
+        let span = tracing::info_span!("scan", roots = config.roots.len());
+        let _entered = span.enter();
+        self.plugin_timings.clear();
+        self.phases.reset();
 
         // Validate roots
         if config.roots.is_empty() {
@@ -187,38 +651,82 @@ impl Scanner for ParallelScanner {
         }
 
         // Configure thread pool
-        let pool = rayon::ThreadPoolBuilder::new()
-            .num_threads(config.parallelism.unwrap_or(num_cpus::get()))
+        let threads = thread_count(config);
+        let mut builder = rayon::ThreadPoolBuilder::new().num_threads(threads);
+        if config.io_priority == IoPriority::Low {
+            builder = builder.start_handler(|_| lower_io_priority());
+        }
+        let pool = builder
             .build()
             .map_err(|e| DevSweepError::Scanner(format!("Thread pool error: {}", e)))?;
 
         // Concurrent project map
-        let projects: DashMap<ProjectId, Project> = DashMap::new();
-
-        // Scan each root
-        pool.install(|| {
-            config.roots.par_iter().for_each(|root| {
-                if let Err(e) = self.scan_root(root, &projects, config) {
-                    if !matches!(e, DevSweepError::ScanInterrupted) {
-                        self.progress.add_error(ScanError::new(
-                            root.clone(),
-                            e.to_string(),
-                        ));
+        let projects = Collector::default();
+        // Canonical directories already walked (only used when following links)
+        let visited: DashSet<PathBuf> = DashSet::new();
+
+        // Walk roots on plain threads, at most `threads` at once, and hand
+        // detection and sizing to the pool. A walker waiting on the pool
+        // just blocks; a pool worker waiting on nested work would steal
+        // another root's whole walk and stall its own project meanwhile.
+        let next_root = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..threads.min(config.roots.len()) {
+                scope.spawn(|| {
+                    if config.io_priority == IoPriority::Low {
+                        lower_io_priority();
                     }
-                }
-            });
+                    let walk_start = Instant::now();
+                    while let Some(root) = config.roots.get(next_root.fetch_add(1, Ordering::Relaxed)) {
+                        let _span =
+                            tracing::info_span!(parent: &span, "scan_root", root = %root.display()).entered();
+                        if let Err(e) = self.scan_root(root, &projects, config, deadline, &visited, &pool) {
+                            if !e.is_user_interrupt() {
+                                let e = e.with_context(format!("while scanning {}", root.display()));
+                                self.progress.add_error(ScanError::new(root.clone(), e.to_string()));
+                            }
+                        }
+                    }
+                    PhaseClock::add(&self.phases.walking, walk_start.elapsed());
+                });
+            }
         });
 
+        for (id, project) in projects.settle_shared() {
+            self.report(&projects, id, project);
+        }
+
         self.progress.mark_complete();
 
+        for entry in self.plugin_timings.iter() {
+            let (elapsed, projects) = *entry.value();
+            tracing::info!(
+                plugin = *entry.key(),
+                projects,
+                elapsed_ms = elapsed.as_secs_f64() * 1000.0,
+                "plugin timing"
+            );
+        }
+        let phases = self.phases.timings();
+        tracing::info!(
+            directories = self.progress.directories_scanned.load(Ordering::Relaxed),
+            projects = projects.len(),
+            elapsed_ms = start.elapsed().as_secs_f64() * 1000.0,
+            traversal_ms = phases.traversal.as_secs_f64() * 1000.0,
+            detection_ms = phases.detection.as_secs_f64() * 1000.0,
+            sizing_ms = phases.sizing.as_secs_f64() * 1000.0,
+            "scan finished"
+        );
+
         // Check if scan was cancelled
         if self.progress.is_cancelled() {
             return Err(DevSweepError::ScanInterrupted);
         }
 
         // Collect and sort results
-        let mut results: Vec<Project> = projects.into_iter().map(|(_, p)| p).collect();
-        results.sort_by(|a, b| b.cleanable_size.cmp(&a.cleanable_size));
+        let (streamed_stats, streamed_size, streamed_cleanable) = projects.streamed.into_inner();
+        let mut results: Vec<Project> = projects.kept.into_iter().map(|(_, p)| p).collect();
+        results.sort_by(Project::cmp_by_size);
 
         // Apply limit if specified
         if let Some(limit) = config.limit {
@@ -226,15 +734,14 @@ impl Scanner for ParallelScanner {
         }
 
         // Calculate statistics
-        let mut stats = ArtifactStats::default();
-        for project in &results {
-            for artifact in &project.artifacts {
-                stats.add(artifact);
-            }
-        }
+        let stats = if self.stream_only {
+            streamed_stats
+        } else {
+            ArtifactStats::from_artifacts(results.iter().flat_map(|p| &p.artifacts))
+        };
 
-        let total_size: u64 = results.iter().map(|p| p.total_size).sum();
-        let total_cleanable: u64 = results.iter().map(|p| p.cleanable_size).sum();
+        let total_size: u64 = streamed_size + results.iter().map(|p| p.total_size).sum::<u64>();
+        let total_cleanable: u64 = streamed_cleanable + results.iter().map(|p| p.cleanable_size).sum::<u64>();
 
         Ok(ScanResult {
             projects: results,
@@ -244,6 +751,8 @@ impl Scanner for ParallelScanner {
             directories_scanned: self.progress.directories_scanned.load(Ordering::Relaxed),
             errors: std::mem::take(&mut *self.progress.errors.lock()),
             stats,
+            is_partial: self.progress.is_timed_out(),
+            phase_timings: phases,
         })
     }
 
@@ -255,6 +764,9 @@ impl Scanner for ParallelScanner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{Artifact, ArtifactKind, ProjectKind, ProjectMarker};
+    use crate::plugins::Plugin;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     fn setup_node_project(path: &Path) {
@@ -316,6 +828,137 @@ mod tests {
         assert!(result.projects[0].artifacts.iter().any(|a| a.name() == "target"));
     }
 
+    #[test]
+    fn test_fast_scan_estimates_from_top_level() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+        let nested = temp.path().join("node_modules/left-pad");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("index.js"), vec![b'x'; 64 * 1024]).unwrap();
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+        let exact = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+        let fast = scanner.scan(&ScanConfig::new(temp.path()).with_fast()).unwrap();
+
+        let exact = &exact.projects[0].artifacts[0];
+        let fast = &fast.projects[0].artifacts[0];
+        assert_eq!(fast.path, exact.path);
+        // The nested file isn't walked, only the two top-level entries are stat'd
+        assert_eq!(fast.file_count, 2);
+        assert!(fast.size < exact.size);
+        assert!(exact.size >= 64 * 1024);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_mode_excludes_hard_linked_files() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("app");
+        std::fs::create_dir(&project).unwrap();
+        setup_node_project(&project);
+        // Like a pnpm store: the package's file is a hard link into a shared store
+        let store = temp.path().join("store.js");
+        std::fs::write(&store, vec![b'x'; 64 * 1024]).unwrap();
+        std::fs::hard_link(&store, project.join("node_modules/index.js")).unwrap();
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+        let config = ScanConfig::new(&project).with_size_mode(SizeMode::Disk);
+        let result = scanner.scan(&config).unwrap();
+
+        let project = &result.projects[0];
+        let shared = project.artifacts[0].metadata.shared_size.unwrap();
+        assert!(shared >= 64 * 1024);
+        assert_eq!(project.cleanable_size, project.total_size - shared);
+    }
+
+    #[test]
+    fn test_build_logs_only_when_asked() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+        std::fs::write(temp.path().join("npm-debug.log"), vec![0u8; 100]).unwrap();
+        std::fs::write(temp.path().join("node_modules/install.log"), vec![0u8; 100]).unwrap();
+        // A nested project keeps its own logs
+        let nested = temp.path().join("packages/app");
+        std::fs::create_dir_all(&nested).unwrap();
+        setup_node_project(&nested);
+        std::fs::write(nested.join("yarn-error.log"), vec![0u8; 40]).unwrap();
+
+        let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins()));
+        let logs_of = |config: &ScanConfig, root: &Path| -> Option<Artifact> {
+            let result = scanner.scan(config).unwrap();
+            let project = result.projects.into_iter().find(|p| p.root == root)?;
+            project.artifacts.into_iter().find(|a| a.kind == ArtifactKind::Logs)
+        };
+
+        let plain = ScanConfig::new(temp.path());
+        assert!(logs_of(&plain, temp.path()).is_none());
+
+        let config = ScanConfig::new(temp.path()).with_build_logs();
+        let logs = logs_of(&config, temp.path()).unwrap();
+        assert_eq!(logs.metadata.files, vec![temp.path().join("npm-debug.log")]);
+        assert_eq!(logs.size, 100);
+        assert_eq!(logs_of(&config, &nested).unwrap().size, 40);
+    }
+
+    #[test]
+    fn test_sizing_deep_wide_trees_with_bounded_pool() {
+        // node_modules six wide and four deep, in several roots
+        fn fill(dir: &Path, depth: usize) -> u64 {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("index.js"), [b'x'; 64]).unwrap();
+            if depth == 0 {
+                return 64;
+            }
+            64 + (0..6).map(|i| fill(&dir.join(format!("pkg{}", i)), depth - 1)).sum::<u64>()
+        }
+
+        let temp = TempDir::new().unwrap();
+        let mut roots = Vec::new();
+        let mut expected = 0;
+        for name in ["a", "b", "c"] {
+            let root = temp.path().join(name);
+            std::fs::create_dir(&root).unwrap();
+            std::fs::write(root.join("package.json"), r#"{"name": "test"}"#).unwrap();
+            expected = fill(&root.join("node_modules"), 4);
+            roots.push(root);
+        }
+
+        for threads in [1, 2] {
+            let config = ScanConfig::default().with_roots(roots.clone()).with_parallelism(threads);
+            let (tx, rx) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins()));
+                let _ = tx.send(scanner.scan(&config).unwrap());
+            });
+
+            let result = rx
+                .recv_timeout(Duration::from_secs(120))
+                .unwrap_or_else(|_| panic!("scan with {} threads did not finish", threads));
+            assert_eq!(result.projects.len(), 3);
+            for project in &result.projects {
+                assert_eq!(project.artifacts[0].size, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_phase_timings() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+
+        let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins()));
+        let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+        let phases = result.phase_timings;
+
+        assert!(phases.detection > Duration::ZERO);
+        assert!(phases.sizing > Duration::ZERO);
+        assert_eq!(phases.git, None);
+        // A single walker: the phases add up to no more than the scan
+        assert!(phases.traversal + phases.detection + phases.sizing <= result.duration);
+    }
+
     #[test]
     fn test_scan_multiple_projects() {
         let temp = TempDir::new().unwrap();
@@ -339,6 +982,39 @@ mod tests {
         assert_eq!(result.projects.len(), 2);
     }
 
+    #[test]
+    fn test_shared_target_counted_once() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("shared-target/debug")).unwrap();
+        std::fs::write(temp.path().join("shared-target/debug/app"), vec![0u8; 4096]).unwrap();
+        for name in ["one", "two"] {
+            let project = temp.path().join("crates").join(name);
+            std::fs::create_dir_all(project.join(".cargo")).unwrap();
+            std::fs::write(project.join("Cargo.toml"), "[package]").unwrap();
+            std::fs::write(
+                project.join(".cargo/config.toml"),
+                "[build]\ntarget-dir = \"../../shared-target\"\n",
+            )
+            .unwrap();
+        }
+
+        // Whichever project the walk reaches first, the smallest root owns it
+        for _ in 0..5 {
+            let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins()));
+            let config = ScanConfig::default().with_roots([
+                temp.path().join("crates/two"),
+                temp.path().join("crates/one"),
+            ]);
+            let result = scanner.scan(&config).unwrap();
+
+            let artifacts: Vec<_> = result.projects.iter().flat_map(|p| &p.artifacts).collect();
+            assert_eq!(artifacts.len(), 1);
+            assert_eq!(artifacts[0].name(), "shared-target");
+            assert_eq!(result.total_cleanable, artifacts[0].size);
+            assert_eq!(result.projects[0].root, temp.path().join("crates/one"));
+        }
+    }
+
     #[test]
     fn test_scan_with_min_size_filter() {
         let temp = TempDir::new().unwrap();
@@ -355,6 +1031,25 @@ mod tests {
         assert_eq!(result.projects.len(), 0);
     }
 
+    #[test]
+    fn test_scan_with_min_files_filter() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+
+        let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+        let files = result.projects[0].file_count();
+        assert!(files > 0);
+
+        let config = ScanConfig::new(temp.path()).with_min_files(files);
+        assert_eq!(scanner.scan(&config).unwrap().projects.len(), 1);
+
+        let config = ScanConfig::new(temp.path()).with_min_files(files + 1);
+        assert_eq!(scanner.scan(&config).unwrap().projects.len(), 0);
+    }
+
     #[test]
     fn test_scan_with_max_depth() {
         let temp = TempDir::new().unwrap();
@@ -396,6 +1091,277 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_scan_timeout_returns_partial() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+
+        // A zero timeout expires before the first entry is processed
+        let config = ScanConfig::new(temp.path()).with_timeout(Duration::ZERO);
+        let result = scanner.scan(&config).unwrap();
+
+        assert!(result.is_partial);
+        assert!(result.projects.is_empty());
+        assert!(result.summary().contains("partial"));
+    }
+
+    /// Node plugin that takes `delay` to find artifacts and sizes each at 42 bytes
+    struct SlowFixedSize {
+        delay: Duration,
+    }
+
+    impl Plugin for SlowFixedSize {
+        fn id(&self) -> &'static str {
+            "fixed"
+        }
+        fn name(&self) -> &'static str {
+            "Fixed"
+        }
+        fn supported_kinds(&self) -> &[ProjectKind] {
+            crate::plugins::NodePlugin.supported_kinds()
+        }
+        fn markers(&self) -> Vec<ProjectMarker> {
+            crate::plugins::NodePlugin.markers()
+        }
+        fn detect(&self, path: &Path) -> Option<ProjectKind> {
+            crate::plugins::NodePlugin.detect(path)
+        }
+        fn find_artifacts(&self, project_root: &Path) -> Result<Vec<Artifact>> {
+            std::thread::sleep(self.delay);
+            crate::plugins::NodePlugin.find_artifacts(project_root)
+        }
+        fn calculate_size_until(
+            &self,
+            _artifact: &Artifact,
+            _mode: SizeMode,
+            stop: &dyn Fn() -> bool,
+        ) -> Result<u64> {
+            Ok(if stop() { 0 } else { 42 })
+        }
+    }
+
+    fn scan_with(plugin: SlowFixedSize, config: &ScanConfig) -> ScanResult {
+        let plugins: Vec<Box<dyn Plugin>> = vec![Box::new(plugin)];
+        ParallelScanner::new(Arc::new(PluginRegistry::from_plugins(plugins))).scan(config).unwrap()
+    }
+
+    #[test]
+    fn test_sizing_uses_plugin_hook() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+
+        let plugin = SlowFixedSize { delay: Duration::ZERO };
+        let result = scan_with(plugin, &ScanConfig::new(temp.path()));
+
+        let artifact = &result.projects[0].artifacts[0];
+        assert_eq!(artifact.size, 42);
+        assert!(!artifact.is_partial_size());
+    }
+
+    #[test]
+    fn test_sizing_cut_off_is_flagged_partial() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+
+        // The deadline passes between finding the artifact and sizing it
+        let plugin = SlowFixedSize { delay: Duration::from_secs(1) };
+        let config = ScanConfig::new(temp.path()).with_timeout(Duration::from_millis(500));
+        let result = scan_with(plugin, &config);
+
+        assert!(result.is_partial);
+        let artifact = &result.projects[0].artifacts[0];
+        assert_eq!(artifact.size, 0);
+        assert!(artifact.is_partial_size());
+    }
+
+    #[test]
+    fn test_sizing_stops_when_asked() {
+        let temp = TempDir::new().unwrap();
+        let artifact = temp.path().join("node_modules");
+        for i in 0..50 {
+            let dir = artifact.join(format!("pkg{}", i));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("index.js"), "x").unwrap();
+        }
+
+        // Stop a few entries in, as a passed deadline would
+        let stop_after = |n: usize| {
+            let seen = std::sync::atomic::AtomicUsize::new(0);
+            move || seen.fetch_add(1, Ordering::Relaxed) >= n
+        };
+        let size = crate::plugins::calculate_size_until(&artifact, SizeMode::Apparent, &stop_after(10)).unwrap();
+        assert!(size < 50);
+        let count = crate::plugins::count_files_until(&artifact, &stop_after(10)).unwrap();
+        assert!(count < 50);
+        let (size, _) = crate::plugins::disk_usage_until(&artifact, &stop_after(0)).unwrap();
+        assert_eq!(size, 0);
+
+        assert_eq!(crate::plugins::count_files(&artifact).unwrap(), 50);
+    }
+
+    #[test]
+    fn test_scan_without_timeout_is_complete() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+
+        let config = ScanConfig::new(temp.path()).with_timeout(Duration::from_secs(60));
+        let result = scanner.scan(&config).unwrap();
+
+        assert!(!result.is_partial);
+        assert_eq!(result.projects.len(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_null_eignore_files() {
+        let temp = TempDir::new().unwrap();
+        for app in ["web", "legacy/app"] {
+            let dir = temp.path().join(app);
+            std::fs::create_dir_all(&dir).unwrap();
+            setup_node_project(&dir);
+        }
+        std::fs::create_dir(temp.path().join("web/.next")).unwrap();
+        std::fs::write(temp.path().join(".null-eignore"), "legacy/\n").unwrap();
+        std::fs::write(temp.path().join("web/.null-eignore"), "node_modules\n").unwrap();
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+        let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+
+        assert_eq!(result.projects.len(), 1);
+        let names: Vec<_> = result.projects[0].artifacts.iter().map(|a| a.name()).collect();
+        assert_eq!(names, vec![".next"]);
+    }
+
+    #[test]
+    fn test_follow_symlinks_marks_artifacts() {
+        let external = TempDir::new().unwrap();
+        let project = external.path().join("app");
+        std::fs::create_dir(&project).unwrap();
+        setup_node_project(&project);
+
+        let temp = TempDir::new().unwrap();
+        std::os::unix::fs::symlink(external.path(), temp.path().join("external")).unwrap();
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+
+        // Links are not followed by default
+        let scanner = ParallelScanner::new(Arc::clone(&registry));
+        let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+        assert!(result.projects.is_empty());
+
+        let scanner = ParallelScanner::new(registry);
+        let config = ScanConfig::new(temp.path()).with_follow_symlinks();
+        let result = scanner.scan(&config).unwrap();
+
+        assert_eq!(result.projects.len(), 1);
+        let artifact = &result.projects[0].artifacts[0];
+        assert_eq!(
+            artifact.metadata.extra.get("via_symlink"),
+            Some(&temp.path().join("external").display().to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_symlinks_survives_cycles() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("app");
+        std::fs::create_dir(&project).unwrap();
+        setup_node_project(&project);
+
+        std::os::unix::fs::symlink(temp.path(), project.join("loop")).unwrap();
+        std::os::unix::fs::symlink(&project, temp.path().join("app-link")).unwrap();
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+        let config = ScanConfig::new(temp.path()).with_follow_symlinks();
+        let result = scanner.scan(&config).unwrap();
+
+        assert_eq!(result.projects.len(), 1);
+        assert!(result.errors.is_empty());
+    }
+
+    #[test]
+    fn test_throttled_low_priority_scan() {
+        let temp = TempDir::new().unwrap();
+        setup_node_project(temp.path());
+        for i in 0..30 {
+            std::fs::create_dir(temp.path().join(format!("dir{}", i))).unwrap();
+        }
+
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry);
+        let config = ScanConfig::new(temp.path())
+            .with_io_priority(IoPriority::Low)
+            .with_throttle(100);
+
+        let start = Instant::now();
+        let result = scanner.scan(&config).unwrap();
+
+        // 31 directories at 100/s in batches of 10 means at least three pauses
+        assert!(start.elapsed() >= Duration::from_millis(250));
+        assert_eq!(result.projects.len(), 1);
+    }
+
+    #[test]
+    fn test_project_stream() {
+        let temp = TempDir::new().unwrap();
+        for name in ["a", "b"] {
+            std::fs::create_dir(temp.path().join(name)).unwrap();
+            setup_node_project(&temp.path().join(name));
+        }
+
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry).with_project_sender(tx);
+        let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+        drop(scanner);
+
+        let mut streamed: Vec<Project> = rx.iter().collect();
+        streamed.sort_by(Project::cmp_by_size);
+        let roots = |projects: &[Project]| projects.iter().map(|p| p.root.clone()).collect::<Vec<_>>();
+        assert_eq!(roots(&streamed), roots(&result.projects));
+    }
+
+    #[test]
+    fn test_project_stream_only() {
+        let temp = TempDir::new().unwrap();
+        for name in ["a", "b", "c", "d"] {
+            std::fs::create_dir(temp.path().join(name)).unwrap();
+            setup_node_project(&temp.path().join(name));
+        }
+
+        // A one-slot channel and a slow consumer: the scan has to wait for it
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        let consumer = std::thread::spawn(move || {
+            rx.iter()
+                .inspect(|_| std::thread::sleep(Duration::from_millis(20)))
+                .map(|p: Project| p.cleanable_size)
+                .collect::<Vec<_>>()
+        });
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let scanner = ParallelScanner::new(registry).with_project_stream(tx);
+        let config = ScanConfig::new(temp.path()).with_parallelism(2);
+        let result = scanner.scan(&config).unwrap();
+        drop(scanner);
+
+        let sizes = consumer.join().unwrap();
+        assert_eq!(sizes.len(), 4);
+        assert!(result.projects.is_empty());
+        assert_eq!(result.total_cleanable, sizes.iter().sum::<u64>());
+        assert_eq!(result.stats.count_of(ArtifactKind::Dependencies), 4);
+
+        let (tx, _rx) = stream_channel(&config);
+        assert_eq!(tx.capacity(), Some(2 * STREAM_BUFFER_PER_THREAD));
+    }
+
     #[test]
     fn test_progress_tracking() {
         let temp = TempDir::new().unwrap();
@@ -412,4 +1378,29 @@ mod tests {
         assert!(snapshot.is_complete);
         assert!(snapshot.directories_scanned > 0);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unreadable_directories_reported() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let locked = temp.path().join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        setup_node_project(&locked);
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        // Root ignores permission bits, so nothing would be skipped
+        let readable = std::fs::read_dir(&locked).is_ok();
+        let scanner = ParallelScanner::new(Arc::new(PluginRegistry::with_builtins()));
+        let result = scanner.scan(&ScanConfig::new(temp.path()));
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+        if readable {
+            return;
+        }
+
+        let result = result.unwrap();
+        assert!(result.projects.is_empty());
+        assert_eq!(result.unreadable_paths(), vec![locked.as_path()]);
+    }
 }
@@ -0,0 +1,403 @@
+//! Remote scanner over SSH
+//!
+//! Runs `find`/`du` on a remote host to locate known artifact directories
+//! without installing null-e there. Detection only - nothing is deleted remotely.
+
+use crate::core::{
+    Artifact, ArtifactKind, ArtifactStats, MarkerKind, PhaseTimings, Project, ProjectKind, ScanConfig,
+    ScanError, ScanProgress, ScanResult, Scanner,
+};
+use crate::error::{DevSweepError, Result};
+use crate::plugins::PluginRegistry;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Artifact names common enough outside projects (`~/bin`, `~/.cache`, a
+/// docs `build/`) that they only count next to a project marker
+const GENERIC_DIR_NAMES: &[&str] = &[
+    ".cache", "bin", "build", "coverage", "deps", "dist", "obj", "out", "packages", "target", "vendor",
+];
+
+/// A remote scan target parsed from `ssh://[user@]host[:port]/path`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTarget {
+    /// Host (with optional `user@` prefix) passed to ssh
+    pub host: String,
+    /// SSH port, if not the default
+    pub port: Option<u16>,
+    /// Absolute path on the remote host
+    pub path: String,
+}
+
+impl RemoteTarget {
+    /// Parse an `ssh://` URL, returning None for anything else
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix("ssh://")?;
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port.parse().ok()?)),
+            None => (authority, None),
+        };
+
+        // A leading dash would be taken by ssh as an option
+        if host.is_empty() || host.starts_with('-') {
+            return None;
+        }
+
+        Some(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    /// Check whether a scan path refers to a remote target
+    pub fn is_remote(path: &Path) -> bool {
+        path.to_str().is_some_and(|p| p.starts_with("ssh://"))
+    }
+}
+
+impl std::fmt::Display for RemoteTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "ssh://{}:{}{}", self.host, port, self.path),
+            None => write!(f, "ssh://{}{}", self.host, self.path),
+        }
+    }
+}
+
+/// Scanner that discovers artifacts on a remote host over SSH
+pub struct RemoteScanner {
+    registry: Arc<PluginRegistry>,
+    target: RemoteTarget,
+    progress: Arc<ScanProgress>,
+}
+
+impl RemoteScanner {
+    /// Create a new remote scanner for a target
+    pub fn new(registry: Arc<PluginRegistry>, target: RemoteTarget) -> Self {
+        Self {
+            registry,
+            target,
+            progress: ScanProgress::new(),
+        }
+    }
+
+    /// Build the shell command run on the remote host.
+    ///
+    /// Matching directories are pruned so nested artifacts (node_modules inside
+    /// node_modules) are not reported twice, and `du -sk` works on GNU and BSD.
+    /// Generic names like `build` also need a marker of their project next to them.
+    pub fn remote_command(&self, config: &ScanConfig) -> String {
+        let mut names = self.registry.all_cleanable_dir_names();
+        names.sort_unstable();
+
+        let name_tests = names
+            .iter()
+            .filter_map(|name| {
+                let test = format!("-name {}", shell_quote(name));
+                if !GENERIC_DIR_NAMES.contains(name) {
+                    return Some(test);
+                }
+                self.sibling_marker_test(name)
+                    .map(|marker| format!("\\( {} {} \\)", test, marker))
+            })
+            .collect::<Vec<_>>()
+            .join(" -o ");
+
+        let depth = config
+            .max_depth
+            .map(|d| format!(" -maxdepth {}", d))
+            .unwrap_or_default();
+
+        format!(
+            "find {}{} -type d \\( {} \\) -prune -exec du -sk {{}} + 2>/dev/null",
+            shell_quote(&self.target.path),
+            depth,
+            name_tests
+        )
+    }
+
+    /// `find` test passing when a marker of a plugin owning `name` sits next to
+    /// the directory, e.g. `Cargo.toml` beside `target`
+    fn sibling_marker_test(&self, name: &str) -> Option<String> {
+        let mut patterns: Vec<String> = self
+            .registry
+            .plugins_for_cleanable_dir(name)
+            .iter()
+            .flat_map(|plugin| plugin.markers())
+            .flat_map(|marker| marker_patterns(&marker.indicator))
+            .collect();
+        patterns.sort_unstable();
+        patterns.dedup();
+        if patterns.is_empty() {
+            return None;
+        }
+
+        let candidates = patterns
+            .iter()
+            .map(|pattern| format!("\"$1\"/../{}", pattern))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let script = format!("for f in {}; do test -e \"$f\" && exit 0; done; exit 1", candidates);
+        Some(format!("-exec sh -c {} sh {{}} \\;", shell_quote(&script)))
+    }
+
+    /// Turn `du -sk` output into projects keyed by the artifact's parent directory
+    fn parse_output(&self, output: &str, config: &ScanConfig) -> Vec<Project> {
+        let mut projects: BTreeMap<PathBuf, Project> = BTreeMap::new();
+
+        for line in output.lines() {
+            let Some((kib, path)) = line.split_once('\t') else {
+                continue;
+            };
+            let Ok(kib) = kib.trim().parse::<u64>() else {
+                continue;
+            };
+
+            let path = PathBuf::from(path);
+            let (Some(name), Some(root)) = (path.file_name(), path.parent()) else {
+                continue;
+            };
+            let name = name.to_string_lossy().to_string();
+
+            let size = kib * 1024;
+            if config.min_size.is_some_and(|min| size < min) {
+                continue;
+            }
+
+            self.progress.inc_directories();
+
+            let mut artifact = Artifact::new(path.clone(), remote_artifact_kind(&name));
+            artifact.size = size;
+            artifact
+                .metadata
+                .extra
+                .insert("remote".into(), self.target.host.clone());
+
+            projects
+                .entry(root.to_path_buf())
+                .or_insert_with(|| self.new_project(&name, root))
+                .artifacts
+                .push(artifact);
+        }
+
+        projects
+            .into_values()
+            .map(|mut project| {
+                project.calculate_totals();
+                self.progress.inc_projects();
+                self.progress.add_size(project.cleanable_size);
+                project
+            })
+            .collect()
+    }
+
+    /// Best-effort project from the highest-priority plugin owning the artifact directory
+    fn new_project(&self, dir_name: &str, root: &Path) -> Project {
+        let plugin = self
+            .registry
+            .plugins_for_cleanable_dir(dir_name)
+            .into_iter()
+            .max_by_key(|p| p.priority());
+        let kind = plugin
+            .as_ref()
+            .and_then(|p| p.supported_kinds().first().copied())
+            .unwrap_or(ProjectKind::Custom(0));
+
+        let mut project = Project::new(kind, root.to_path_buf());
+        project.detected_by = plugin.map(|p| p.id().to_string());
+        project
+    }
+}
+
+impl Scanner for RemoteScanner {
+    fn scan(&self, config: &ScanConfig) -> Result<ScanResult> {
+        let start = Instant::now();
+
+        let mut ssh = Command::new("ssh");
+        ssh.args(["-o", "BatchMode=yes"]);
+        if let Some(timeout) = config.timeout {
+            ssh.arg("-o")
+                .arg(format!("ConnectTimeout={}", timeout.as_secs().max(1)));
+        }
+        if let Some(port) = self.target.port {
+            ssh.arg("-p").arg(port.to_string());
+        }
+        ssh.arg("--").arg(&self.target.host).arg(self.remote_command(config));
+
+        let output = ssh
+            .output()
+            .map_err(|e| DevSweepError::Scanner(format!("Failed to run ssh: {}", e)))?;
+
+        // find exits non-zero on unreadable directories, so only fail on empty output
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() && stdout.trim().is_empty() {
+            self.progress.mark_complete();
+            return Err(DevSweepError::Scanner(format!(
+                "Remote scan of {} failed: {}",
+                self.target,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let mut projects = self.parse_output(&stdout, config);
+        self.progress.mark_complete();
+
+        projects.sort_by(Project::cmp_by_size);
+        if let Some(limit) = config.limit {
+            projects.truncate(limit);
+        }
+
+        let stats = ArtifactStats::from_artifacts(projects.iter().flat_map(|p| &p.artifacts));
+
+        let mut errors = std::mem::take(&mut *self.progress.errors.lock());
+        if !output.status.success() {
+            errors.push(ScanError::new(
+                PathBuf::from(&self.target.path),
+                "Some remote directories could not be read",
+            ));
+        }
+
+        Ok(ScanResult {
+            total_size: projects.iter().map(|p| p.total_size).sum(),
+            total_cleanable: projects.iter().map(|p| p.cleanable_size).sum(),
+            projects,
+            duration: start.elapsed(),
+            directories_scanned: self.progress.snapshot().directories_scanned,
+            errors,
+            stats,
+            is_partial: false,
+            phase_timings: PhaseTimings::default(),
+        })
+    }
+
+    fn progress(&self) -> Arc<ScanProgress> {
+        Arc::clone(&self.progress)
+    }
+}
+
+/// Classify a remote artifact directory by name, since plugins can't inspect it
+fn remote_artifact_kind(name: &str) -> ArtifactKind {
+    match name {
+        "node_modules" | "vendor" | "packages" | ".gradle" | ".terraform" => ArtifactKind::Dependencies,
+        ".venv" | "venv" | ".tox" | ".nox" => ArtifactKind::VirtualEnv,
+        "coverage" | ".nyc_output" | "htmlcov" | "TestResults" => ArtifactKind::TestOutput,
+        "__pycache__" => ArtifactKind::Bytecode,
+        ".dart_tool" => ArtifactKind::Cache,
+        n if n.contains("cache") => ArtifactKind::Cache,
+        _ => ArtifactKind::BuildOutput,
+    }
+}
+
+/// Quote a string for a POSIX shell
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Shell words matching a marker's files, globs left unquoted
+fn marker_patterns(marker: &MarkerKind) -> Vec<String> {
+    let glob = |name: &str| name.split('*').map(shell_quote).collect::<Vec<_>>().join("*");
+    match marker {
+        MarkerKind::File(name) | MarkerKind::Directory(name) => vec![glob(name)],
+        MarkerKind::Extension(ext) => vec![format!("*.{}", shell_quote(ext))],
+        MarkerKind::AllOf(names) | MarkerKind::AnyOf(names) => names.iter().map(|n| glob(n)).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_target() {
+        let target = RemoteTarget::parse("ssh://builder@ci-01:2222/srv/builds").unwrap();
+        assert_eq!(target.host, "builder@ci-01");
+        assert_eq!(target.port, Some(2222));
+        assert_eq!(target.path, "/srv/builds");
+        assert_eq!(target.to_string(), "ssh://builder@ci-01:2222/srv/builds");
+
+        let target = RemoteTarget::parse("ssh://ci-01").unwrap();
+        assert_eq!(target.port, None);
+        assert_eq!(target.path, "/");
+
+        assert!(RemoteTarget::parse("/home/user").is_none());
+        assert!(RemoteTarget::parse("ssh:///srv").is_none());
+        // ssh would read these as options
+        assert!(RemoteTarget::parse("ssh://-oProxyCommand=touch%20pwned/srv").is_none());
+    }
+
+    #[test]
+    fn test_remote_command_uses_plugin_dirs() {
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let target = RemoteTarget::parse("ssh://ci/srv/it's here").unwrap();
+        let scanner = RemoteScanner::new(registry, target);
+
+        let command = scanner.remote_command(&ScanConfig::default().with_max_depth(6));
+        assert!(command.starts_with(r"find '/srv/it'\''s here' -maxdepth 6 -type d"));
+        assert!(command.contains("-name 'node_modules'"));
+        assert!(command.contains("-name 'target'"));
+        assert!(command.contains("-prune -exec du -sk {} +"));
+
+        // A generic name only counts next to its project's marker
+        assert!(command.contains("-name 'node_modules' -o"));
+        assert!(command.contains(r"\( -name 'target' -exec sh -c "));
+        assert!(command.contains("Cargo.toml"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_remote_command_needs_project_markers() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let dirs = ["app/target/debug", "docs/build/html", "home/bin", "home/.cache/pip", "web/node_modules/x", "net/bin"];
+        for dir in dirs {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::write(root.join("app/Cargo.toml"), "[package]").unwrap();
+        std::fs::write(root.join("net/App.csproj"), "<Project />").unwrap();
+
+        // Run the command as the remote host would
+        let target = RemoteTarget::parse(&format!("ssh://ci{}", root.display())).unwrap();
+        let scanner = RemoteScanner::new(Arc::new(PluginRegistry::with_builtins()), target);
+        let command = scanner.remote_command(&ScanConfig::default());
+        let output = Command::new("sh").arg("-c").arg(&command).output().unwrap();
+        let mut found: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(_, path)| path.strip_prefix(&*root.to_string_lossy()).unwrap().to_string())
+            .collect();
+        found.sort();
+
+        assert_eq!(found, ["/app/target", "/net/bin", "/web/node_modules"]);
+    }
+
+    #[test]
+    fn test_parse_du_output() {
+        let registry = Arc::new(PluginRegistry::with_builtins());
+        let target = RemoteTarget::parse("ssh://ci/srv").unwrap();
+        let scanner = RemoteScanner::new(registry, target);
+
+        let output = "2048\t/srv/web/node_modules\n512\t/srv/web/.next\n4\t/srv/api/target\nnoise\n";
+        let config = ScanConfig::default().with_min_size(10_000);
+        let projects = scanner.parse_output(output, &config);
+
+        assert_eq!(projects.len(), 1);
+        let web = &projects[0];
+        assert_eq!(web.root, PathBuf::from("/srv/web"));
+        assert_eq!(web.kind, ProjectKind::NodeNpm);
+        assert_eq!(web.artifacts.len(), 2);
+        assert_eq!(web.total_size, 2560 * 1024);
+        assert_eq!(
+            web.artifacts[0].metadata.extra.get("remote"),
+            Some(&"ci".to_string())
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Local usage statistics
+//!
+//! With `[stats] enabled = true`, every clean that freed something appends a
+//! line to `stats.jsonl` in the data directory; `null-e stats` adds them up.
+//! Nothing here ever leaves the machine.
+
+use crate::error::{DevSweepError, Result, ResultExt};
+use crate::notify::CompletionSummary;
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// One finished clean
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsEntry {
+    /// When the clean finished
+    pub at: DateTime<Utc>,
+    /// Command that cleaned, e.g. `clean` or `caches`
+    pub command: String,
+    /// Bytes freed
+    pub bytes_freed: u64,
+    /// Items cleaned
+    pub items: usize,
+}
+
+impl StatsEntry {
+    /// Entry for a real clean that freed something; `None` for dry runs and no-ops
+    pub fn from_summary(summary: &CompletionSummary) -> Option<Self> {
+        if summary.dry_run || summary.cleaned == 0 {
+            return None;
+        }
+        Some(Self {
+            at: summary.finished_at,
+            command: summary.command.clone(),
+            bytes_freed: summary.bytes_freed,
+            items: summary.cleaned,
+        })
+    }
+}
+
+/// Running totals over a set of entries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsTotals {
+    pub bytes_freed: u64,
+    pub items: usize,
+    /// Number of cleans
+    pub runs: usize,
+}
+
+impl StatsTotals {
+    fn add(&mut self, entry: &StatsEntry) {
+        self.bytes_freed += entry.bytes_freed;
+        self.items += entry.items;
+        self.runs += 1;
+    }
+
+    /// Totals over every entry
+    pub fn of<'a>(entries: impl IntoIterator<Item = &'a StatsEntry>) -> Self {
+        let mut totals = Self::default();
+        for entry in entries {
+            totals.add(entry);
+        }
+        totals
+    }
+}
+
+/// Totals per calendar month (UTC), oldest first
+pub fn monthly(entries: &[StatsEntry]) -> Vec<((i32, u32), StatsTotals)> {
+    let mut months: std::collections::BTreeMap<(i32, u32), StatsTotals> = std::collections::BTreeMap::new();
+    for entry in entries {
+        months.entry((entry.at.year(), entry.at.month())).or_default().add(entry);
+    }
+    months.into_iter().collect()
+}
+
+/// Append-only log of finished cleans
+pub struct StatsLog {
+    path: PathBuf,
+}
+
+impl StatsLog {
+    /// The log in the default data directory
+    pub fn new() -> Result<Self> {
+        let path = dirs::data_dir()
+            .ok_or_else(|| DevSweepError::Config("Cannot find data directory".into()))?
+            .join("devsweep")
+            .join("stats.jsonl");
+        Ok(Self { path })
+    }
+
+    /// Use a log at `path` instead of the default location
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Where the log is kept
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Add one entry at the end of the log
+    pub fn append(&self, entry: &StatsEntry) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).with_path(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_path(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?).with_path(&self.path)?;
+        Ok(())
+    }
+
+    /// All entries, oldest first; lines that don't parse are skipped
+    pub fn load(&self) -> Result<Vec<StatsEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&self.path).with_path(&self.path)?;
+        Ok(content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(year: i32, month: u32, bytes_freed: u64, items: usize) -> StatsEntry {
+        StatsEntry {
+            at: Utc.with_ymd_and_hms(year, month, 15, 12, 0, 0).unwrap(),
+            command: "clean".into(),
+            bytes_freed,
+            items,
+        }
+    }
+
+    #[test]
+    fn test_stats_log_roundtrip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let log = StatsLog::at(temp.path().join("nested/stats.jsonl"));
+        assert!(log.load().unwrap().is_empty());
+
+        log.append(&entry(2026, 1, 1_000, 2)).unwrap();
+        log.append(&entry(2026, 3, 500, 1)).unwrap();
+        // A torn write shouldn't lose the rest of the history
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap()
+            .write_all(b"{\"at\":\n")
+            .unwrap();
+        log.append(&entry(2026, 3, 250, 4)).unwrap();
+
+        let entries = log.load().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            StatsTotals::of(&entries),
+            StatsTotals { bytes_freed: 1_750, items: 7, runs: 3 }
+        );
+
+        let months = monthly(&entries);
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0], ((2026, 1), StatsTotals { bytes_freed: 1_000, items: 2, runs: 1 }));
+        assert_eq!(months[1], ((2026, 3), StatsTotals { bytes_freed: 750, items: 5, runs: 2 }));
+    }
+
+    #[test]
+    fn test_entry_from_summary() {
+        let summary = CompletionSummary {
+            bytes_freed: 4_096,
+            cleaned: 3,
+            ..CompletionSummary::new("sweep", false)
+        };
+        let entry = StatsEntry::from_summary(&summary).unwrap();
+        assert_eq!(entry.command, "sweep");
+        assert_eq!(entry.bytes_freed, 4_096);
+        assert_eq!(entry.items, 3);
+
+        // Dry runs and cleans that removed nothing aren't recorded
+        assert!(StatsEntry::from_summary(&CompletionSummary { cleaned: 3, ..CompletionSummary::new("clean", true) }).is_none());
+        assert!(StatsEntry::from_summary(&CompletionSummary::new("clean", false)).is_none());
+    }
+}
@@ -9,8 +9,8 @@ mod record;
 pub use platform::*;
 pub use record::*;
 
-use crate::core::{Artifact, CleanResult};
-use crate::error::{DevSweepError, Result};
+use crate::core::{extended_path, Artifact, CleanResult};
+use crate::error::{DevSweepError, Result, ResultExt};
 use std::path::Path;
 
 /// Delete method for cleanup operations
@@ -35,44 +35,242 @@ impl DeleteMethod {
             _ => None,
         }
     }
+
+    /// Name used in reports, the same spelling `from_str` accepts
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Trash => "trash",
+            Self::Permanent => "permanent",
+            Self::DryRun => "dry-run",
+        }
+    }
 }
 
 /// Delete a path using the specified method
+///
+/// Returns the bytes that actually disappeared. The size is measured before
+/// deleting and then reconciled with whatever is still left at the path, so
+/// a partially failed trash move doesn't report the full amount. Moves to
+/// the trash are recorded (see [`delete_path_as`]), labeled with the parent
+/// directory's name.
 pub fn delete_path(path: &Path, method: DeleteMethod) -> Result<u64> {
-    if !path.exists() {
+    let origin = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let kind = if path.is_dir() { "directory" } else { "file" };
+    delete_path_as(path, method, &origin, kind)
+}
+
+/// Delete a path like [`delete_path`], recording a trash move in the
+/// [`TrashRecordStore`] so it can be restored later
+///
+/// `origin` names what the path belonged to (a project, cache or cleaner)
+/// and `kind` what it was. Failing to write the record doesn't fail the
+/// delete; the item is in the trash either way.
+pub fn delete_path_as(path: &Path, method: DeleteMethod, origin: &str, kind: &str) -> Result<u64> {
+    let existed = std::fs::symlink_metadata(path).is_ok();
+    let freed = remove_path(path, method)?;
+
+    if method == DeleteMethod::Trash && existed {
+        let record = TrashRecord::new(path.to_path_buf(), freed, origin, kind);
+        if let Err(e) = TrashRecordStore::new().and_then(|store| store.add(record)) {
+            tracing::warn!(path = %path.display(), error = %e, "could not record trashed item");
+        }
+    }
+
+    Ok(freed)
+}
+
+fn remove_path(path: &Path, method: DeleteMethod) -> Result<u64> {
+    let _span = tracing::debug_span!("delete", path = %path.display(), method = ?method).entered();
+    // Filesystem calls go through the long-path form; the trash gets the original
+    let fs_path = extended_path(path);
+    if !fs_path.exists() {
         return Ok(0);
     }
 
     match method {
         DeleteMethod::DryRun => {
             // Just calculate size, don't delete
-            let size = calculate_size(path)?;
+            let size = calculate_size(&fs_path)?;
             Ok(size)
         }
         DeleteMethod::Trash => {
-            let size = calculate_size(path)?;
+            let size = calculate_size(&fs_path)?;
             trash::delete(path).map_err(|e| {
-                DevSweepError::Trash(format!("Failed to move to trash: {}", e))
+                // Prefer a reason we can name over the platform's error
+                check_trashable(path)
+                    .err()
+                    .unwrap_or_else(|| DevSweepError::Trash(format!("Failed to move to trash: {}", e)))
             })?;
-            Ok(size)
+            let freed = verify_removed(&fs_path, size);
+            if freed == 0 && size > 0 {
+                return Err(DevSweepError::Trash(format!(
+                    "{} still exists after moving to trash",
+                    path.display()
+                )));
+            }
+            Ok(freed)
         }
         DeleteMethod::Permanent => {
-            let size = calculate_size(path)?;
-            if path.is_dir() {
-                std::fs::remove_dir_all(path)?;
+            let size = calculate_size(&fs_path)?;
+            if fs_path.is_dir() {
+                std::fs::remove_dir_all(&fs_path)
             } else {
-                std::fs::remove_file(path)?;
+                std::fs::remove_file(&fs_path)
             }
-            Ok(size)
+            .context(format!("while deleting {}", path.display()))?;
+            Ok(verify_removed(&fs_path, size))
         }
     }
 }
 
 /// Delete an artifact
+///
+/// If one of its paths fails, the result is a failure that still counts
+/// the bytes freed by the paths deleted before it.
 pub fn delete_artifact(artifact: &Artifact, method: DeleteMethod) -> CleanResult {
-    match delete_path(&artifact.path, method) {
-        Ok(_bytes) => CleanResult::success(artifact.clone(), method == DeleteMethod::Trash),
-        Err(e) => CleanResult::failure(artifact.clone(), e.to_string()),
+    // Blocks shared with other files stay allocated, so they aren't freed
+    let shared = artifact.metadata.shared_size.unwrap_or(0);
+    let origin = artifact
+        .path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut bytes = 0;
+    for path in artifact.paths() {
+        match delete_path_as(path, method, &origin, artifact.kind.description()) {
+            Ok(freed) => bytes += freed,
+            // Paths removed before the failure stay removed
+            Err(e) => {
+                return CleanResult::failure(artifact.clone(), e.to_string())
+                    .with_bytes_freed(bytes.saturating_sub(shared))
+            }
+        }
+    }
+    CleanResult::success(artifact.clone(), method == DeleteMethod::Trash)
+        .with_bytes_freed(bytes.saturating_sub(shared))
+}
+
+/// Rehearse deleting an artifact with `method`: every check the real delete
+/// would run, without removing anything
+///
+/// Succeeds with the bytes that would be freed, or fails with the reason the
+/// real delete would fail. This is what `--dry-run` reports.
+pub fn rehearse_artifact(artifact: &Artifact, method: DeleteMethod) -> CleanResult {
+    let shared = artifact.metadata.shared_size.unwrap_or(0);
+    let mut bytes = 0;
+    for path in artifact.paths() {
+        match rehearse_path(path, method) {
+            Ok(size) => bytes += size,
+            Err(e) => return CleanResult::failure(artifact.clone(), e.to_string()),
+        }
+    }
+    CleanResult::success(artifact.clone(), false).with_bytes_freed(bytes.saturating_sub(shared))
+}
+
+/// Rehearse deleting `path` with `method`, returning the bytes it would free
+pub fn rehearse_path(path: &Path, method: DeleteMethod) -> Result<u64> {
+    let fs_path = extended_path(path);
+    if std::fs::symlink_metadata(&fs_path).is_err() {
+        return Ok(0);
+    }
+    check_deletable(path, method)?;
+    calculate_size(&fs_path)
+}
+
+/// Reconcile a pre-deletion size with what is still on disk at `path`
+///
+/// Returns `size_before` when the path is gone, otherwise only the part
+/// that no longer exists.
+pub fn verify_removed(path: &Path, size_before: u64) -> u64 {
+    if std::fs::symlink_metadata(path).is_err() {
+        return size_before;
+    }
+    let remaining = calculate_size(path).unwrap_or(size_before);
+    size_before.saturating_sub(remaining)
+}
+
+/// Check, without deleting anything, whether `path` could be removed with `method`
+///
+/// Removing an entry needs write access to its parent directory. Moving to
+/// the trash also needs a trash that can take it (see [`check_trashable`])
+/// and, for a directory, write access to the directory itself; a permanent
+/// delete needs it on every directory in the tree. The first
+/// blocking path is reported with the same error mapping as
+/// [`ResultExt::with_path`], e.g. [`DevSweepError::PermissionDenied`].
+pub fn check_deletable(path: &Path, method: DeleteMethod) -> Result<()> {
+    let fs_path = extended_path(path);
+    let metadata = match std::fs::symlink_metadata(&fs_path) {
+        Ok(m) => m,
+        // Nothing to delete, so nothing can fail
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e).with_path(path),
+    };
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        check_writable_dir(parent)?;
+    }
+
+    if method == DeleteMethod::Trash {
+        check_trashable(path)?;
+    }
+
+    if !metadata.is_dir() {
+        return Ok(());
+    }
+
+    match method {
+        DeleteMethod::Trash => check_writable_dir(path),
+        DeleteMethod::Permanent | DeleteMethod::DryRun => {
+            for entry in walkdir::WalkDir::new(&fs_path) {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        let blocked = e.path().unwrap_or(path).to_path_buf();
+                        return match e.into_io_error() {
+                            Some(io_err) => Err(io_err).with_path(blocked),
+                            None => Ok(()),
+                        };
+                    }
+                };
+                if entry.file_type().is_dir() {
+                    check_writable_dir(entry.path())?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check that entries can be added to and removed from a directory
+#[cfg(unix)]
+fn check_writable_dir(dir: &Path) -> Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(dir.as_os_str().as_bytes())
+        .map_err(|e| DevSweepError::Other(e.to_string()))?;
+
+    // SAFETY: c_path is a valid NUL-terminated string that outlives the call
+    let rc = unsafe { libc::access(c_path.as_ptr(), libc::W_OK | libc::X_OK) };
+    if rc == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error()).with_path(dir)
+    }
+}
+
+/// Check that entries can be added to and removed from a directory
+#[cfg(not(unix))]
+fn check_writable_dir(dir: &Path) -> Result<()> {
+    let metadata = std::fs::metadata(dir).with_path(dir)?;
+    if metadata.permissions().readonly() {
+        Err(DevSweepError::PermissionDenied(dir.to_path_buf()))
+    } else {
+        Ok(())
     }
 }
 
@@ -98,6 +296,7 @@ fn calculate_size(path: &Path) -> Result<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::ArtifactKind;
     use tempfile::TempDir;
 
     #[test]
@@ -108,6 +307,56 @@ mod tests {
         assert_eq!(DeleteMethod::from_str("invalid"), None);
     }
 
+    #[test]
+    fn test_delete_grouped_artifact_removes_only_its_files() {
+        let temp = TempDir::new().unwrap();
+        let logs = [temp.path().join("a.log"), temp.path().join("b.log")];
+        for log in &logs {
+            std::fs::write(log, "log line").unwrap();
+        }
+        std::fs::write(temp.path().join("main.rs"), "fn main() {}").unwrap();
+
+        let mut artifact = Artifact::new(temp.path().join("*.log"), ArtifactKind::Logs);
+        artifact.metadata.files = logs.to_vec();
+        let result = delete_artifact(&artifact, DeleteMethod::Permanent);
+
+        assert!(result.success);
+        assert_eq!(result.bytes_freed, 16);
+        assert!(logs.iter().all(|log| !log.exists()));
+        assert!(temp.path().join("main.rs").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_delete_grouped_artifact_reports_partial_progress() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let first = temp.path().join("a.log");
+        std::fs::write(&first, "log line").unwrap();
+        let locked = temp.path().join("locked");
+        std::fs::create_dir(&locked).unwrap();
+        let second = locked.join("b.log");
+        std::fs::write(&second, "log line").unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores permission bits, so nothing would fail
+        let writable = check_writable_dir(&locked).is_ok();
+        let mut artifact = Artifact::new(temp.path().join("*.log"), ArtifactKind::Logs);
+        artifact.metadata.files = vec![first.clone(), second.clone()];
+        let result = delete_artifact(&artifact, DeleteMethod::Permanent);
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+        if writable {
+            return;
+        }
+
+        assert!(!result.success);
+        assert_eq!(result.bytes_freed, 8);
+        assert_eq!(result.record("permanent").bytes_freed, 8);
+        assert!(!first.exists());
+        assert!(second.exists());
+    }
+
     #[test]
     fn test_dry_run_doesnt_delete() {
         let temp = TempDir::new().unwrap();
@@ -150,6 +399,100 @@ mod tests {
         assert!(!dir.exists());
     }
 
+    #[test]
+    fn test_verify_removed() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("subdir");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("kept.txt"), "12345").unwrap();
+
+        // Only part of the tree went away
+        assert_eq!(verify_removed(&dir, 15), 10);
+        // Everything that was there is still there
+        assert_eq!(verify_removed(&dir, 5), 0);
+        // Path is gone entirely
+        assert_eq!(verify_removed(&temp.path().join("missing"), 15), 15);
+    }
+
+    #[test]
+    fn test_check_deletable() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("subdir");
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("nested/file.txt"), "content").unwrap();
+
+        assert!(check_deletable(&dir, DeleteMethod::Permanent).is_ok());
+        assert!(check_deletable(&temp.path().join("missing"), DeleteMethod::Trash).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_deletable_reports_readonly_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("subdir");
+        let nested = dir.join("nested");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("file.txt"), "content").unwrap();
+        std::fs::set_permissions(&nested, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores permission bits, so there is nothing to check
+        let writable = check_writable_dir(&nested).is_ok();
+        let result = check_deletable(&dir, DeleteMethod::Permanent);
+        std::fs::set_permissions(&nested, std::fs::Permissions::from_mode(0o755)).unwrap();
+        if writable {
+            return;
+        }
+
+        assert!(matches!(result, Err(DevSweepError::PermissionDenied(p)) if p == nested));
+        // Trash only moves the top-level directory, so the nested one doesn't matter
+        assert!(check_deletable(&dir, DeleteMethod::Trash).is_ok());
+    }
+
+    #[test]
+    fn test_rehearse_artifact() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("target");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("out.bin"), "0123456789").unwrap();
+
+        let artifact = Artifact::new(dir.clone(), ArtifactKind::BuildOutput);
+        for method in [DeleteMethod::Trash, DeleteMethod::Permanent] {
+            let result = rehearse_artifact(&artifact, method);
+            assert!(result.success, "{:?}: {:?}", method, result.error);
+            assert_eq!(result.bytes_freed, 10);
+            assert!(!result.trashed);
+        }
+        assert!(dir.join("out.bin").exists());
+        assert!(check_trashable(&dir).is_ok());
+        assert_eq!(rehearse_path(&temp.path().join("missing"), DeleteMethod::Trash).unwrap(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rehearse_reports_would_be_failure() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let parent = temp.path().join("locked");
+        std::fs::create_dir_all(parent.join("node_modules")).unwrap();
+        std::fs::set_permissions(&parent, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores permission bits, so nothing would fail
+        let writable = check_writable_dir(&parent).is_ok();
+        let artifact = Artifact::new(parent.join("node_modules"), ArtifactKind::Dependencies);
+        let result = rehearse_artifact(&artifact, DeleteMethod::Permanent);
+        std::fs::set_permissions(&parent, std::fs::Permissions::from_mode(0o755)).unwrap();
+        if writable {
+            return;
+        }
+
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("locked"));
+        assert!(parent.join("node_modules").exists());
+    }
+
     #[test]
     fn test_calculate_size() {
         let temp = TempDir::new().unwrap();
@@ -1,7 +1,7 @@
 //! Platform-specific trash implementation
 
 use crate::error::{DevSweepError, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Get the trash directory for the current platform
 pub fn get_trash_dir() -> Result<PathBuf> {
@@ -69,6 +69,86 @@ pub fn get_trash_size() -> Result<u64> {
     Ok(size)
 }
 
+/// Check, without moving anything, that the system trash can take `path`
+///
+/// On Linux an item on a different filesystem than the home trash goes to a
+/// trash directory at the top of its own filesystem (`.Trash/<uid>` or
+/// `.Trash-<uid>`), which has to exist or be creatable there; read-only
+/// mounts, network shares and bind mounts often don't allow that. The
+/// Windows Recycle Bin goes through the shell, which still refuses paths over
+/// `MAX_PATH` and reserved names like `nul`. macOS hands the move to the OS,
+/// so there is nothing to check ahead.
+pub fn check_trashable(path: &Path) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        use crate::error::ResultExt;
+        use std::os::unix::fs::MetadataExt;
+
+        let device = |p: &Path| std::fs::metadata(p).map(|m| m.dev()).ok();
+        let item_device = std::fs::symlink_metadata(path).with_path(path)?.dev();
+
+        // The home trash is created on first use, under its nearest existing ancestor
+        let home_trash = get_trash_dir()?;
+        let (home_device, existing) = home_trash
+            .ancestors()
+            .find_map(|p| Some((device(p)?, p)))
+            .ok_or_else(|| DevSweepError::Trash(format!("Cannot find the trash at {}", home_trash.display())))?;
+        if item_device == home_device {
+            return super::check_writable_dir(existing).map_err(|e| {
+                DevSweepError::Trash(format!("Cannot move to trash at {}: {}", home_trash.display(), e))
+            });
+        }
+
+        let top = path
+            .ancestors()
+            .skip(1)
+            .take_while(|p| device(p) == Some(item_device))
+            .last()
+            .unwrap_or(path);
+        // SAFETY: getuid has no preconditions and cannot fail
+        let uid = unsafe { libc::getuid() };
+        let trash_dirs = [top.join(".Trash").join(uid.to_string()), top.join(format!(".Trash-{}", uid))];
+        let usable = trash_dirs
+            .iter()
+            .any(|dir| dir.is_dir() && super::check_writable_dir(dir).is_ok())
+            || super::check_writable_dir(top).is_ok();
+        if !usable {
+            return Err(DevSweepError::Trash(format!(
+                "{} is on another filesystem than the trash, and {} has no writable trash directory",
+                path.display(),
+                top.display()
+            )));
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        use crate::core::{is_reserved_name, MAX_PATH};
+        use std::os::windows::ffi::OsStrExt;
+
+        let length = std::path::absolute(path).map_or(0, |p| p.as_os_str().encode_wide().count());
+        let reason = if length >= MAX_PATH {
+            Some(format!("its path is {} characters long", length))
+        } else {
+            path.file_name()
+                .filter(|name| is_reserved_name(name))
+                .map(|name| format!("{} is a reserved name", name.to_string_lossy()))
+        };
+        if let Some(reason) = reason {
+            return Err(DevSweepError::Trash(format!(
+                "The Recycle Bin can't take {}: {}; use --method permanent",
+                path.display(),
+                reason
+            )));
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", windows)))]
+    let _ = path;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
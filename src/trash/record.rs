@@ -1,6 +1,6 @@
 //! Trash records - tracking deleted items for recovery
 
-use crate::error::{DevSweepError, Result};
+use crate::error::{DevSweepError, Result, ResultExt};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -59,19 +59,24 @@ impl TrashRecordStore {
 
         // Ensure directory exists
         if let Some(parent) = records_path.parent() {
-            std::fs::create_dir_all(parent)?;
+            std::fs::create_dir_all(parent).with_path(parent)?;
         }
 
         Ok(Self { records_path })
     }
 
+    /// Use a records file at `records_path` instead of the default location
+    pub fn at(records_path: PathBuf) -> Self {
+        Self { records_path }
+    }
+
     /// Load all records
     pub fn load(&self) -> Result<Vec<TrashRecord>> {
         if !self.records_path.exists() {
             return Ok(Vec::new());
         }
 
-        let content = std::fs::read_to_string(&self.records_path)?;
+        let content = std::fs::read_to_string(&self.records_path).with_path(&self.records_path)?;
         let records: Vec<TrashRecord> = serde_json::from_str(&content)?;
         Ok(records)
     }
@@ -79,7 +84,7 @@ impl TrashRecordStore {
     /// Save all records
     pub fn save(&self, records: &[TrashRecord]) -> Result<()> {
         let content = serde_json::to_string_pretty(records)?;
-        std::fs::write(&self.records_path, content)?;
+        std::fs::write(&self.records_path, content).with_path(&self.records_path)?;
         Ok(())
     }
 
@@ -153,5 +158,20 @@ mod tests {
         assert_eq!(record.project_name, "my-project");
     }
 
-    // Note: More comprehensive tests would require mocking the filesystem
+    #[test]
+    fn test_store_add_and_remove() {
+        let temp = TempDir::new().unwrap();
+        let store = TrashRecordStore::at(temp.path().join("trash_records.json"));
+        assert!(store.load().unwrap().is_empty());
+
+        let record = TrashRecord::new(PathBuf::from("/home/u/.cache/pip"), 500, "pip", "cache");
+        let id = record.id.clone();
+        store.add(record).unwrap();
+        store.add(TrashRecord::new(PathBuf::from("/home/u/Downloads/x.iso"), 700, "Downloads", "file")).unwrap();
+        assert_eq!(store.total_size().unwrap(), 1200);
+
+        let removed = store.remove(&id).unwrap().unwrap();
+        assert_eq!(removed.original_path, PathBuf::from("/home/u/.cache/pip"));
+        assert_eq!(store.load().unwrap().len(), 1);
+    }
 }
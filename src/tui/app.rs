@@ -5,7 +5,8 @@ use crate::plugins::PluginRegistry;
 use crate::scanner::ParallelScanner;
 use std::collections::HashSet;
 use std::path::PathBuf;
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::Arc;
 use std::thread;
 
@@ -14,7 +15,7 @@ pub enum ScanMessage {
     /// Progress update
     Progress { dirs_scanned: usize, message: String },
     /// Scan completed for projects
-    CompleteProjects(ScanResult),
+    CompleteProjects(Box<ScanResult>),
     /// Scan completed for caches
     CompleteCaches(Vec<CacheEntry>),
     /// Scan completed for cleaners
@@ -23,6 +24,37 @@ pub enum ScanMessage {
     Error(String),
 }
 
+/// Message sent from the deletion thread
+pub enum CleanMessage {
+    /// Started working on an item
+    Started { path: PathBuf },
+    /// Finished an item (`freed` is 0 when it failed)
+    Finished { path: PathBuf, success: bool, freed: u64 },
+    /// All items processed, or the rest skipped after cancellation
+    Complete,
+}
+
+/// Progress of a running deletion
+#[derive(Debug, Clone, Default)]
+pub struct CleanProgress {
+    /// Number of items queued for deletion
+    pub total: usize,
+    /// Number of items processed so far
+    pub done: usize,
+    /// Item currently being deleted
+    pub current: Option<PathBuf>,
+    /// Bytes freed so far
+    pub freed: u64,
+    /// Items deleted successfully
+    pub succeeded: usize,
+    /// Items that failed to delete
+    pub failed: usize,
+    /// Whether the user asked to stop
+    pub cancelled: bool,
+    /// Paths that were actually removed
+    deleted: HashSet<PathBuf>,
+}
+
 /// Scan mode - what type of scan to perform
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScanMode {
@@ -240,6 +272,12 @@ pub struct App {
     pub permanent_delete: bool,
     /// Items pending deletion (path, optional clean_command)
     pub pending_delete_items: Vec<(PathBuf, Option<String>)>,
+    /// Deletion progress receiver (for async cleaning)
+    clean_receiver: Option<Receiver<CleanMessage>>,
+    /// Set to skip the remaining deletions
+    clean_cancel: Arc<AtomicBool>,
+    /// Progress of the current deletion
+    pub clean_progress: CleanProgress,
 }
 
 /// Application state/screen
@@ -310,6 +348,9 @@ impl App {
             anim_frame: 0,
             permanent_delete: false,
             pending_delete_items: Vec::new(),
+            clean_receiver: None,
+            clean_cancel: Arc::new(AtomicBool::new(false)),
+            clean_progress: CleanProgress::default(),
         }
     }
 
@@ -766,7 +807,6 @@ impl App {
         });
         let registry = Arc::new(PluginRegistry::with_builtins());
         let scanner = ParallelScanner::new(registry);
-        let mut config = ScanConfig::default();
 
         // Use home directory if paths is just current dir (for better project discovery)
         let project_paths = if paths.len() == 1 && paths[0] == std::env::current_dir().unwrap_or_default() {
@@ -777,9 +817,10 @@ impl App {
         } else {
             paths
         };
-        config.roots = project_paths;
+        let config = ScanConfig::default().with_roots(project_paths);
 
-        if let Ok(result) = scanner.scan(&config) {
+        if let Ok(mut result) = scanner.scan(&config) {
+            crate::config::IgnoreList::load_default().unwrap_or_default().apply(&mut result.projects, false);
             for p in result.projects {
                 for artifact in &p.artifacts {
                     all_cleaners.push(CleanerEntry {
@@ -818,12 +859,16 @@ impl App {
             paths
         };
 
-        let mut config = ScanConfig::default();
-        config.roots = project_paths;
+        let config = ScanConfig::default().with_roots(project_paths);
 
         match scanner.scan(&config) {
-            Ok(result) => {
-                let _ = tx.send(ScanMessage::CompleteProjects(result));
+            Ok(mut result) => {
+                // Leave out what the user asked to keep, as `scan` and `clean` do
+                let ignore = crate::config::IgnoreList::load_default().unwrap_or_default();
+                if ignore.apply(&mut result.projects, false) > 0 {
+                    result.total_cleanable = result.projects.iter().map(|p| p.cleanable_size).sum();
+                }
+                let _ = tx.send(ScanMessage::CompleteProjects(Box::new(result)));
             }
             Err(e) => {
                 let _ = tx.send(ScanMessage::Error(e.to_string()));
@@ -1329,7 +1374,7 @@ impl App {
                         self.scan_message = message;
                     }
                     ScanMessage::CompleteProjects(result) => {
-                        self.handle_project_scan_complete(result);
+                        self.handle_project_scan_complete(*result);
                         self.scan_receiver = None;
                         return;
                     }
@@ -1368,7 +1413,7 @@ impl App {
 
         // Sort by size (largest first)
         self.projects
-            .sort_by(|a, b| b.project.cleanable_size.cmp(&a.project.cleanable_size));
+            .sort_by(|a, b| a.project.cmp_by_size(&b.project));
 
         self.total_size = scan_result.total_cleanable;
         self.dirs_scanned = scan_result.directories_scanned;
@@ -1385,7 +1430,8 @@ impl App {
     /// Handle cache scan completion
     fn handle_cache_scan_complete(&mut self, caches: Vec<CacheEntry>) {
         self.caches = caches;
-        self.caches.sort_by(|a, b| b.size.cmp(&a.size));
+        self.caches
+            .sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
 
         self.total_size = self.caches.iter().map(|c| c.size).sum();
         self.state = AppState::CacheResults;
@@ -1401,7 +1447,8 @@ impl App {
     /// Handle cleaner scan completion
     fn handle_cleaner_scan_complete(&mut self, cleaners: Vec<CleanerEntry>) {
         self.cleaners = cleaners;
-        self.cleaners.sort_by(|a, b| b.size.cmp(&a.size));
+        self.cleaners
+            .sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
 
         self.total_size = self.cleaners.iter().map(|c| c.size).sum();
         self.state = AppState::CleanerResults;
@@ -1872,7 +1919,8 @@ impl App {
             .projects
             .iter()
             .filter(|p| p.selected)
-            .flat_map(|p| p.project.artifacts.iter().map(|a| (a.path.clone(), None)))
+            .flat_map(|p| p.project.artifacts.iter().flat_map(|a| a.paths()))
+            .map(|path| (path.to_path_buf(), None))
             .collect();
 
         // Add cache items (no clean_command)
@@ -1902,12 +1950,90 @@ impl App {
         std::mem::take(&mut self.pending_delete_items)
     }
 
-    /// Mark deletion complete
-    pub fn deletion_complete(&mut self, success_count: usize, fail_count: usize, freed: u64) {
-        // Remove deleted items
-        self.projects.retain(|p| !p.selected);
-        self.caches.retain(|c| !c.selected);
-        self.cleaners.retain(|c| !c.selected);
+    /// Start tracking a deletion of `total` items.
+    ///
+    /// Returns the channel and cancel flag the deletion thread reports through.
+    pub fn track_deletion(&mut self, total: usize) -> (Sender<CleanMessage>, Arc<AtomicBool>) {
+        let (tx, rx): (Sender<CleanMessage>, Receiver<CleanMessage>) = mpsc::channel();
+        self.clean_receiver = Some(rx);
+        self.clean_cancel = Arc::new(AtomicBool::new(false));
+        self.clean_progress = CleanProgress {
+            total,
+            ..Default::default()
+        };
+
+        (tx, Arc::clone(&self.clean_cancel))
+    }
+
+    /// Skip the remaining deletions once the current item finishes
+    pub fn cancel_cleaning(&mut self) {
+        self.clean_cancel.store(true, Ordering::Relaxed);
+        self.clean_progress.cancelled = true;
+        self.status_message = Some("Cancelling - finishing current item...".to_string());
+    }
+
+    /// Check for deletion updates (call this on tick)
+    pub fn check_clean_progress(&mut self) {
+        let Some(ref rx) = self.clean_receiver else {
+            return;
+        };
+
+        loop {
+            match rx.try_recv() {
+                Ok(CleanMessage::Started { path }) => {
+                    self.clean_progress.current = Some(path);
+                }
+                Ok(CleanMessage::Finished { path, success, freed }) => {
+                    let progress = &mut self.clean_progress;
+                    progress.done += 1;
+                    if success {
+                        progress.succeeded += 1;
+                        progress.freed += freed;
+                        progress.deleted.insert(path);
+                    } else {
+                        progress.failed += 1;
+                    }
+                }
+                Ok(CleanMessage::Complete) => {
+                    self.clean_receiver = None;
+                    self.deletion_complete();
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    // Deletion thread died without finishing
+                    self.clean_receiver = None;
+                    self.deletion_complete();
+                    self.status_message = Some("Error during deletion!".to_string());
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Mark deletion complete, removing only the entries that were deleted
+    pub fn deletion_complete(&mut self) {
+        let progress = std::mem::take(&mut self.clean_progress);
+        let deleted = &progress.deleted;
+
+        // Remove deleted items; anything skipped or failed stays listed
+        for entry in self.projects.iter_mut().filter(|p| p.selected) {
+            entry
+                .project
+                .artifacts
+                .retain(|a| !a.paths().iter().all(|path| deleted.contains(*path)));
+            entry.project.calculate_totals();
+            entry.selected = false;
+        }
+        self.projects.retain(|p| !p.project.artifacts.is_empty());
+        self.caches.retain(|c| !deleted.contains(&c.path));
+        self.cleaners.retain(|c| !deleted.contains(&c.path));
+        for cache in &mut self.caches {
+            cache.selected = false;
+        }
+        for cleaner in &mut self.cleaners {
+            cleaner.selected = false;
+        }
 
         // Return to appropriate state
         if !self.projects.is_empty() {
@@ -1920,12 +2046,23 @@ impl App {
             self.state = AppState::Ready;
         }
 
-        self.status_message = Some(format!(
-            "Deleted {} items, freed {} ({} failed)",
-            success_count,
-            format_size(freed),
-            fail_count
-        ));
+        let skipped = progress.total - progress.done;
+        self.status_message = Some(if progress.cancelled && skipped > 0 {
+            format!(
+                "Cancelled: deleted {} items, freed {} ({} skipped, {} failed)",
+                progress.succeeded,
+                format_size(progress.freed),
+                skipped,
+                progress.failed
+            )
+        } else {
+            format!(
+                "Deleted {} items, freed {} ({} failed)",
+                progress.succeeded,
+                format_size(progress.freed),
+                progress.failed
+            )
+        });
 
         // Recalculate total
         self.total_size = self.projects.iter().map(|p| p.project.cleanable_size).sum::<u64>()
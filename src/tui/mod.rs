@@ -6,9 +6,10 @@
 
 pub mod app;
 pub mod event;
+pub mod picker;
 pub mod ui;
 
-pub use app::{App, AppState, ProjectEntry};
+pub use app::{App, AppState, CleanMessage, ProjectEntry};
 pub use event::{Action, Event, EventHandler};
 
 use crate::error::Result;
@@ -21,6 +22,9 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::thread;
 use std::time::Duration;
 
 /// Run the TUI application
@@ -164,11 +168,9 @@ fn run_app(
                         _ => {}
                     },
                     AppState::Cleaning => {
-                        // Allow quit during cleaning
+                        // Stop after the item currently being deleted
                         if matches!(action, Action::Quit | Action::Cancel) {
-                            app.pending_delete_items.clear();
-                            app.state = AppState::Results;
-                            app.status_message = Some("Cleaning cancelled".to_string());
+                            app.cancel_cleaning();
                         }
                     }
                 }
@@ -182,26 +184,18 @@ fn run_app(
                     app.check_scan_progress();
                 }
 
-                // Process pending deletions (runs after UI has rendered Cleaning state)
+                // Start pending deletions (runs after UI has rendered Cleaning state)
                 if app.has_pending_delete() {
                     let items = app.take_pending_delete_items();
                     let permanent = app.permanent_delete;
+                    let (tx, cancel) = app.track_deletion(items.len());
 
-                    // Wrap in catch_unwind to handle panics gracefully
-                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                        delete_items(&items, permanent)
-                    }));
+                    thread::spawn(move || delete_items(&items, permanent, &tx, &cancel));
+                }
 
-                    match result {
-                        Ok((success, failed, freed)) => {
-                            app.deletion_complete(success, failed, freed);
-                        }
-                        Err(_) => {
-                            // Panic during deletion - recover gracefully
-                            app.deletion_complete(0, items.len(), 0);
-                            app.status_message = Some("Error during deletion!".to_string());
-                        }
-                    }
+                // Check for deletion progress on every tick
+                if app.state == AppState::Cleaning {
+                    app.check_clean_progress();
                 }
             }
             Event::Resize(_, _) => {
@@ -235,14 +229,16 @@ fn run_app(
     Ok(())
 }
 
-/// Delete items and return (success_count, fail_count, bytes_freed)
+/// Delete items, reporting progress for each one over `tx`
 /// Items are tuples of (path, optional clean_command)
 /// If clean_command is Some, run that command instead of deleting the path
-fn delete_items(items: &[(PathBuf, Option<String>)], permanent: bool) -> (usize, usize, u64) {
-    let mut success = 0;
-    let mut failed = 0;
-    let mut freed = 0u64;
-
+/// Remaining items are skipped once `cancel` is set
+fn delete_items(
+    items: &[(PathBuf, Option<String>)],
+    permanent: bool,
+    tx: &Sender<CleanMessage>,
+    cancel: &AtomicBool,
+) {
     let method = if permanent {
         DeleteMethod::Permanent
     } else {
@@ -250,53 +246,58 @@ fn delete_items(items: &[(PathBuf, Option<String>)], permanent: bool) -> (usize,
     };
 
     for (path, clean_command) in items {
-        // Get size before deletion
-        let size = if path.is_dir() {
-            dir_size(path)
-        } else {
-            std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
-        };
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
 
-        // If there's a clean_command, run it instead of deleting the path
-        let result = if let Some(cmd) = clean_command {
-            // Run the clean command (e.g., "docker rmi abc123")
-            run_clean_command(cmd)
-        } else {
-            // Delete using selected method
-            delete_path(path, method).map(|_| ())
-        };
+        let _ = tx.send(CleanMessage::Started { path: path.clone() });
 
-        match result {
-            Ok(_) => {
-                success += 1;
-                freed += size;
-            }
-            Err(_) => {
-                failed += 1;
+        // Wrap in catch_unwind so one bad item doesn't abort the rest
+        let result = std::panic::catch_unwind(|| {
+            // If there's a clean_command, run it instead of deleting the path
+            if let Some(cmd) = clean_command {
+                // A command doesn't say what it freed, so go by the size beforehand
+                let size = if path.is_dir() {
+                    dir_size(path)
+                } else {
+                    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+                };
+                // Run the clean command (e.g., "docker rmi abc123")
+                run_clean_command(cmd).map(|()| size)
+            } else {
+                // Delete using selected method, which reports what was actually freed
+                delete_path(path, method)
             }
-        }
+        });
+
+        let freed = match result {
+            Ok(Ok(bytes)) => Some(bytes),
+            _ => None,
+        };
+        let _ = tx.send(CleanMessage::Finished {
+            path: path.clone(),
+            success: freed.is_some(),
+            freed: freed.unwrap_or(0),
+        });
     }
 
-    (success, failed, freed)
+    let _ = tx.send(CleanMessage::Complete);
 }
 
-/// Run a shell command for cleaning (Docker, etc.)
+/// Run a shell command for cleaning (Docker, etc.), killed on timeout
 fn run_clean_command(cmd: &str) -> crate::error::Result<()> {
     use std::process::Command;
 
-    let output = if cfg!(target_os = "windows") {
-        Command::new("cmd").args(["/C", cmd]).output()
+    let command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", cmd]);
+        command
     } else {
-        Command::new("sh").args(["-c", cmd]).output()
+        let mut command = Command::new("sh");
+        command.args(["-c", cmd]);
+        command
     };
-
-    match output {
-        Ok(out) if out.status.success() => Ok(()),
-        Ok(out) => Err(crate::error::DevSweepError::Other(
-            String::from_utf8_lossy(&out.stderr).to_string(),
-        )),
-        Err(e) => Err(crate::error::DevSweepError::Other(e.to_string())),
-    }
+    crate::cleaners::wait_for_command(command, cmd)
 }
 
 /// Calculate directory size
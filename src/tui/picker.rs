@@ -0,0 +1,196 @@
+//! Arrow-key multi-select for CLI prompts
+//!
+//! A lighter alternative to the full TUI: the list is drawn inline below the
+//! prompt, and the terminal is left as it was once a choice is made.
+
+use crate::error::Result;
+use crossterm::{
+    cursor::{Hide, MoveToColumn, MoveToPreviousLine, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::Print,
+    terminal::{self, disable_raw_mode, enable_raw_mode, Clear, ClearType},
+};
+use std::io::{self, Write};
+
+/// Rows kept free for the prompt and the key help
+const CHROME_ROWS: usize = 3;
+
+/// What a key press did to the picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Continue,
+    Done,
+    Cancel,
+}
+
+/// Cursor, scroll position and checked items of a list
+#[derive(Debug)]
+struct Picker {
+    cursor: usize,
+    offset: usize,
+    height: usize,
+    checked: Vec<bool>,
+}
+
+impl Picker {
+    fn new(len: usize, height: usize) -> Self {
+        Self {
+            cursor: 0,
+            offset: 0,
+            height: height.max(1),
+            checked: vec![false; len],
+        }
+    }
+
+    fn handle(&mut self, key: KeyEvent) -> Step {
+        let last = self.checked.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Step::Cancel,
+            KeyCode::Esc | KeyCode::Char('q') => return Step::Cancel,
+            KeyCode::Enter => return Step::Done,
+            KeyCode::Up | KeyCode::Char('k') => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::PageUp => self.cursor = self.cursor.saturating_sub(self.height),
+            KeyCode::PageDown => self.cursor = (self.cursor + self.height).min(last),
+            KeyCode::Home | KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::End | KeyCode::Char('G') => self.cursor = last,
+            KeyCode::Char(' ') => {
+                if let Some(checked) = self.checked.get_mut(self.cursor) {
+                    *checked = !*checked;
+                }
+            }
+            KeyCode::Char('a') => {
+                let all = self.checked.iter().all(|c| *c);
+                self.checked.iter_mut().for_each(|c| *c = !all);
+            }
+            _ => {}
+        }
+
+        // Keep the cursor inside the visible window
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + self.height {
+            self.offset = self.cursor + 1 - self.height;
+        }
+        Step::Continue
+    }
+
+    fn selected(&self) -> Vec<usize> {
+        self.checked
+            .iter()
+            .enumerate()
+            .filter_map(|(i, checked)| checked.then_some(i))
+            .collect()
+    }
+}
+
+/// Puts the terminal back however the picker exits
+struct RawModeGuard;
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = queue!(io::stdout(), Clear(ClearType::FromCursorDown), Show);
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Let the user check items with the arrow keys and Space
+///
+/// Returns the checked indices in list order, or `None` when cancelled with
+/// Esc, `q` or Ctrl-C. Needs an interactive terminal.
+pub fn multi_select(prompt: &str, items: &[String]) -> Result<Option<Vec<usize>>> {
+    if items.is_empty() {
+        return Ok(Some(vec![]));
+    }
+    let (width, rows) = terminal::size().unwrap_or((80, 24));
+    let height = (rows as usize).saturating_sub(CHROME_ROWS).min(items.len());
+    let mut picker = Picker::new(items.len(), height);
+
+    enable_raw_mode()?;
+    let _guard = RawModeGuard;
+    let mut out = io::stdout();
+    queue!(out, Hide)?;
+
+    loop {
+        render(&mut out, prompt, items, &picker, width as usize)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match picker.handle(key) {
+            Step::Continue => {}
+            Step::Done => return Ok(Some(picker.selected())),
+            Step::Cancel => return Ok(None),
+        }
+    }
+}
+
+/// Draw the prompt, the visible window of the list and the key help,
+/// leaving the cursor where the prompt starts
+fn render(out: &mut impl Write, prompt: &str, items: &[String], picker: &Picker, width: usize) -> Result<()> {
+    let label_width = width.saturating_sub(6);
+    let checked = picker.checked.iter().filter(|c| **c).count();
+
+    queue!(out, MoveToColumn(0), Clear(ClearType::FromCursorDown))?;
+    queue!(out, Print(format!("{} ({} selected)\r\n", prompt, checked)))?;
+
+    let visible: Vec<_> = items.iter().enumerate().skip(picker.offset).take(picker.height).collect();
+    for &(i, item) in &visible {
+        let pointer = if i == picker.cursor { '>' } else { ' ' };
+        let mark = if picker.checked[i] { 'x' } else { ' ' };
+        let label: String = item.chars().take(label_width).collect();
+        queue!(out, Print(format!("{} [{}] {}\r\n", pointer, mark, label)))?;
+    }
+
+    queue!(
+        out,
+        Print("↑/↓ move · Space select · a all · Enter confirm · Esc cancel"),
+        MoveToPreviousLine(visible.len() as u16 + 1),
+    )?;
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(picker: &mut Picker, code: KeyCode) -> Step {
+        picker.handle(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_picker_keys() {
+        let mut picker = Picker::new(10, 3);
+
+        press(&mut picker, KeyCode::Char(' '));
+        for _ in 0..4 {
+            press(&mut picker, KeyCode::Down);
+        }
+        press(&mut picker, KeyCode::Char(' '));
+        assert_eq!(picker.selected(), vec![0, 4]);
+        // The window follows the cursor
+        assert_eq!((picker.cursor, picker.offset), (4, 2));
+
+        press(&mut picker, KeyCode::End);
+        assert_eq!((picker.cursor, picker.offset), (9, 7));
+        press(&mut picker, KeyCode::Up);
+        press(&mut picker, KeyCode::PageUp);
+        assert_eq!((picker.cursor, picker.offset), (5, 5));
+
+        press(&mut picker, KeyCode::Char('a'));
+        assert_eq!(picker.selected().len(), 10);
+        press(&mut picker, KeyCode::Char('a'));
+        assert!(picker.selected().is_empty());
+
+        assert_eq!(press(&mut picker, KeyCode::Enter), Step::Done);
+        assert_eq!(press(&mut picker, KeyCode::Esc), Step::Cancel);
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(picker.handle(ctrl_c), Step::Cancel);
+    }
+}
@@ -625,14 +625,14 @@ fn render_cleaning_screen(app: &App, frame: &mut Frame, area: Rect) {
         (
             "🔥",
             " PERMANENTLY DELETING ",
-            "Removing files permanently (cannot be recovered)...",
+            "Permanently deleting",
             Color::Red,
         )
     } else {
         (
             "🗑️",
             " Cleaning ",
-            "Moving items to trash...",
+            "Moving to trash",
             Color::Yellow,
         )
     };
@@ -646,7 +646,7 @@ fn render_cleaning_screen(app: &App, frame: &mut Frame, area: Rect) {
             Constraint::Length(2),  // Spacer
             Constraint::Length(3),  // Progress bar
             Constraint::Length(2),  // Spacer
-            Constraint::Length(2),  // Item count
+            Constraint::Length(3),  // Item count
             Constraint::Min(0),     // Rest
         ])
         .split(area);
@@ -665,23 +665,48 @@ fn render_cleaning_screen(app: &App, frame: &mut Frame, area: Rect) {
     .alignment(Alignment::Center);
     frame.render_widget(title_text, chunks[0]);
 
-    // Animated progress bar (pulses since we don't track individual file progress)
-    let progress = ((app.anim_frame % 20) as f64 / 20.0 * 0.3) + 0.7; // Pulse between 70-100%
+    // Progress bar across items, labelled with the item being deleted
+    let progress = &app.clean_progress;
+    let percent = (progress.done * 100)
+        .checked_div(progress.total)
+        .unwrap_or(0) as u16;
+    let label = match &progress.current {
+        Some(path) if !progress.cancelled => format!(
+            "{}: {}",
+            message,
+            path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string())
+        ),
+        Some(_) => "Cancelling - finishing current item...".to_string(),
+        None => format!("{}...", message),
+    };
     let gauge = Gauge::default()
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(color)))
         .gauge_style(Style::default().fg(color).add_modifier(Modifier::BOLD))
-        .percent((progress * 100.0) as u16)
+        .percent(percent.min(100))
         .label(Span::styled(
-            message,
+            label,
             Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
         ));
     frame.render_widget(gauge, chunks[2]);
 
-    // Item count
-    let item_text = Paragraph::new(Line::from(Span::styled(
-        format!("Processing {} items...", app.pending_delete_items.len()),
-        Style::default().fg(Color::Cyan),
-    )))
+    // Item count and running freed total
+    let item_text = Paragraph::new(vec![
+        Line::from(Span::styled(
+            format!(
+                "{}/{} items - freed {} so far",
+                progress.done,
+                progress.total,
+                format_size(progress.freed)
+            ),
+            Style::default().fg(Color::Cyan),
+        )),
+        Line::from(Span::styled(
+            "[q] Cancel remaining",
+            Style::default().fg(Color::Gray),
+        )),
+    ])
     .alignment(Alignment::Center);
     frame.render_widget(item_text, chunks[4]);
 
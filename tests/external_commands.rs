@@ -0,0 +1,42 @@
+//! Tests for the `--no-external-commands` / `--no-docker` switches
+//!
+//! The switches are process-wide, so they live in their own test binary
+//! where flipping them can't race with detection in other tests.
+
+use null_e::cleaners::{self, binaries::BinaryAnalyzer, docker::DockerCleaner, runtimes::RuntimesCleaner};
+
+/// Turns external commands and docker back on when dropped, even if an
+/// assertion failed
+struct RestoreFlags;
+
+impl Drop for RestoreFlags {
+    fn drop(&mut self) {
+        cleaners::set_external_commands_enabled(true);
+        cleaners::set_docker_enabled(true);
+    }
+}
+
+#[test]
+fn test_external_commands_toggle_short_circuits_detectors() {
+    let _restore = RestoreFlags;
+
+    cleaners::set_external_commands_enabled(false);
+    assert!(!cleaners::docker_enabled());
+    assert!(!DockerCleaner::new().is_available());
+    assert!(DockerCleaner::new().detect().unwrap().is_empty());
+    if let Some(analyzer) = BinaryAnalyzer::new() {
+        assert!(analyzer.analyze().unwrap().binaries.is_empty());
+    }
+    // The active toolchain is only known from `rustup default`
+    if let Some(runtimes) = RuntimesCleaner::new() {
+        assert!(!runtimes.detect().unwrap().iter().any(|i| i.subcategory == "rustup"));
+    }
+
+    cleaners::set_external_commands_enabled(true);
+    cleaners::set_docker_enabled(false);
+    assert!(cleaners::external_commands_enabled());
+    assert!(!cleaners::docker_enabled());
+
+    cleaners::set_docker_enabled(true);
+    assert!(cleaners::docker_enabled());
+}
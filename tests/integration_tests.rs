@@ -143,6 +143,56 @@ fn test_scanner_finds_rust_project() {
         .any(|a| a.name() == "target"));
 }
 
+#[test]
+fn test_scanner_attributes_workspace_target_to_root() {
+    let temp = TempDir::new().unwrap();
+    create_rust_project(temp.path());
+    std::fs::write(
+        temp.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"crates/*\"]\n",
+    )
+    .unwrap();
+    for member in ["crates/core", "crates/cli"] {
+        let dir = temp.path().join(member);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"member\"\n").unwrap();
+    }
+
+    let registry = Arc::new(PluginRegistry::with_builtins());
+    let scanner = ParallelScanner::new(registry);
+
+    // Scanning a single member still surfaces the shared target directory
+    let result = scanner
+        .scan(&ScanConfig::new(temp.path().join("crates/core")))
+        .unwrap();
+    assert_eq!(result.projects.len(), 1);
+    assert_eq!(result.projects[0].root, temp.path());
+    assert!(result.projects[0].cleanable_size > 0);
+
+    // Scanning the whole workspace reports it once
+    let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+    assert_eq!(result.projects.len(), 1);
+    assert_eq!(result.projects[0].artifacts.len(), 1);
+    assert_eq!(result.projects[0].artifacts[0].path, temp.path().join("target"));
+}
+
+#[test]
+fn test_scanner_reports_orphaned_node_modules() {
+    let temp = TempDir::new().unwrap();
+    let leftover = temp.path().join("deleted-app");
+    std::fs::create_dir_all(leftover.join("node_modules/lodash")).unwrap();
+    std::fs::write(leftover.join("node_modules/lodash/index.js"), "module.exports = {};").unwrap();
+
+    let registry = Arc::new(PluginRegistry::with_builtins());
+    let scanner = ParallelScanner::new(registry);
+    let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+
+    assert_eq!(result.projects.len(), 1);
+    assert_eq!(result.projects[0].root, leftover);
+    assert!(result.projects[0].artifacts[0].is_orphaned());
+    assert!(result.projects[0].artifacts[0].is_safe_to_clean());
+}
+
 #[test]
 fn test_scanner_finds_python_project() {
     let temp = TempDir::new().unwrap();
@@ -457,6 +507,149 @@ fn test_config_serialization_roundtrip() {
     );
 }
 
+/// Run the `null-e` binary with its config directory and home inside `home`
+#[cfg(target_os = "linux")]
+fn null_e(home: &std::path::Path, env: &[(&str, &str)], args: &[&str]) -> std::process::Output {
+    let mut cmd = std::process::Command::new(env!("CARGO_BIN_EXE_null-e"));
+    cmd.env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_CACHE_HOME", home.join(".cache"))
+        .env_remove("NULL_E_MIN_SIZE")
+        .env_remove("NULL_E_DELETE_METHOD")
+        .env_remove("NULL_E_PROTECTION")
+        .env_remove("NULL_E_NO_CACHE")
+        .env_remove("NULL_E_SAFE_MODE")
+        .envs(env.iter().copied())
+        .args(args);
+    cmd.output().unwrap()
+}
+
+#[cfg(target_os = "linux")]
+fn write_config(home: &std::path::Path, content: &str) {
+    let dir = home.join(".config/devsweep");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("config.toml"), content).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_min_size_precedence_file_env_flag() {
+    let home = TempDir::new().unwrap();
+    let projects = TempDir::new().unwrap();
+    create_node_project(projects.path());
+    let root = projects.path().to_str().unwrap();
+
+    // The node_modules is a few dozen bytes: visible at 1 byte, hidden at 1GB
+    let found = |env: &[(&str, &str)], flags: &[&str]| {
+        let base = ["--output", "ndjson", "--no-cache", "scan", root];
+        let args: Vec<&str> = base.iter().chain(flags).copied().collect();
+        let output = null_e(home.path(), env, &args);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).lines().count()
+    };
+
+    write_config(home.path(), "[scan]\nmin_size = 1\n");
+    assert_eq!(found(&[], &[]), 1);
+
+    write_config(home.path(), "[scan]\nmin_size = 1000000000\n");
+    assert_eq!(found(&[], &[]), 0);
+
+    // The environment beats the file
+    assert_eq!(found(&[("NULL_E_MIN_SIZE", "1")], &[]), 1);
+
+    // A flag beats both
+    write_config(home.path(), "[scan]\nmin_size = 1\n");
+    assert_eq!(found(&[("NULL_E_MIN_SIZE", "1GB")], &[]), 0);
+    assert_eq!(found(&[("NULL_E_MIN_SIZE", "1GB")], &["--min-size", "1"]), 1);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_malformed_config_only_blocks_commands_that_use_it() {
+    let home = TempDir::new().unwrap();
+    write_config(home.path(), "[scan\nmin_size = ");
+
+    let path = null_e(home.path(), &[], &["config", "--path"]);
+    assert!(path.status.success());
+    assert!(String::from_utf8_lossy(&path.stdout).contains("devsweep"));
+    assert!(null_e(home.path(), &[], &["list"]).status.success());
+
+    let scan = null_e(home.path(), &[], &["scan", home.path().to_str().unwrap()]);
+    assert!(!scan.status.success());
+}
+
+#[test]
+fn test_scanner_finds_zig_elixir_haskell_projects() {
+    let temp = TempDir::new().unwrap();
+
+    let fixtures: [(&str, &str, &[&str]); 3] = [
+        ("zig-app", "build.zig", &[".zig-cache", "zig-out"]),
+        ("mix-app", "mix.exs", &["_build", "deps"]),
+        ("cabal-app", "app.cabal", &["dist-newstyle"]),
+    ];
+    for (name, marker, dirs) in fixtures {
+        let root = temp.path().join(name);
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(root.join(marker), "").unwrap();
+        for dir in dirs {
+            std::fs::create_dir(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("blob"), vec![0u8; 1024]).unwrap();
+        }
+    }
+
+    let registry = Arc::new(PluginRegistry::with_builtins());
+    let scanner = ParallelScanner::new(registry);
+    let result = scanner.scan(&ScanConfig::new(temp.path())).unwrap();
+
+    let mut found: Vec<_> = result
+        .projects
+        .iter()
+        .map(|p| (p.kind, p.artifacts.len()))
+        .collect();
+    found.sort_by_key(|(kind, _)| kind.display_name());
+    assert_eq!(
+        found,
+        vec![(ProjectKind::Elixir, 2), (ProjectKind::Haskell, 1), (ProjectKind::Zig, 2)]
+    );
+}
+
+#[test]
+fn test_android_gradle_artifacts_do_not_overlap() {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path().join("android-app");
+    let app = root.join("app");
+    std::fs::create_dir_all(&app).unwrap();
+    std::fs::write(root.join("settings.gradle.kts"), "").unwrap();
+    std::fs::write(app.join("build.gradle.kts"), "").unwrap();
+    for dir in [root.join(".gradle"), root.join("build"), app.join("build"), app.join(".cxx")] {
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("blob"), vec![0u8; 1024]).unwrap();
+    }
+
+    let registry = Arc::new(PluginRegistry::with_builtins());
+    let scanner = ParallelScanner::new(registry);
+    let config = ScanConfig::new(temp.path()).with_hidden();
+    let result = scanner.scan(&config).unwrap();
+
+    let mut paths: Vec<PathBuf> = result
+        .projects
+        .iter()
+        .flat_map(|p| p.artifacts.iter().map(|a| a.path.clone()))
+        .collect();
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![root.join(".gradle"), root.join("app/.cxx"), root.join("app/build"), root.join("build")]
+    );
+
+    // Each byte is counted once: no artifact contains another, or a global cache
+    let caches = null_e::caches::detect_caches().unwrap();
+    for path in &paths {
+        assert_eq!(paths.iter().filter(|other| other.starts_with(path)).count(), 1);
+        assert!(caches.iter().all(|c| !c.path.starts_with(path) && !path.starts_with(&c.path)));
+    }
+}
+
 // ============================================================================
 // Individual Plugin Tests
 // ============================================================================
@@ -584,3 +777,35 @@ fn test_artifact_stats() {
     assert!(largest.is_some());
     assert_eq!(largest.unwrap().0, ArtifactKind::Dependencies);
 }
+
+#[cfg(unix)]
+#[test]
+fn test_clean_command_timeout_kills_child() {
+    use null_e::cleaners::{self, run_clean_command};
+    use std::time::{Duration, Instant};
+
+    assert!(run_clean_command("true").is_ok());
+    assert!(matches!(
+        run_clean_command("false"),
+        Err(DevSweepError::CleanFailed { .. })
+    ));
+    assert!(matches!(run_clean_command("  "), Err(DevSweepError::Config(_))));
+
+    cleaners::set_command_timeout(Some(Duration::from_secs(1)));
+    let started = Instant::now();
+    let result = run_clean_command("sleep 30");
+    cleaners::set_command_timeout(Some(Duration::from_secs(cleaners::DEFAULT_COMMAND_TIMEOUT_SECS)));
+
+    assert!(started.elapsed() < Duration::from_secs(10));
+    match result {
+        Err(DevSweepError::CommandTimeout { command, secs }) => {
+            assert_eq!(command, "sleep 30");
+            assert_eq!(secs, 1);
+        }
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+
+    cleaners::set_command_timeout(None);
+    assert_eq!(cleaners::command_timeout(), None);
+    cleaners::set_command_timeout(Some(Duration::from_secs(cleaners::DEFAULT_COMMAND_TIMEOUT_SECS)));
+}